@@ -14,11 +14,20 @@ pub enum CliRequest {
         paths: Vec<String>,
         urls: Vec<String>,
         diff_paths: Vec<[String; 2]>,
+        merge_paths: Vec<[String; 4]>,
+        /// Whether the text piped to the CLI follows, in `StdinChunk`
+        /// requests ending with `StdinEnd`.
+        stdin: bool,
+        language: Option<String>,
         wait: bool,
         open_new_workspace: Option<bool>,
         env: Option<HashMap<String, String>>,
         user_data_dir: Option<String>,
     },
+    StdinChunk {
+        text: String,
+    },
+    StdinEnd,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -26,6 +35,7 @@ pub enum CliResponse {
     Ping,
     Stdout { message: String },
     Stderr { message: String },
+    Output { text: String },
     Exit { status: i32 },
 }
 
@@ -50,7 +50,8 @@ Examples:
           Open your project in Zed
     `zed -n path-to-file `
           Open file/folder in a new window",
-    after_help = "To read from stdin, append '-', e.g. 'ps axf | zed -'"
+    after_help = "To read from stdin, append '-', e.g. 'ps axf | zed -'.
+With '--wait', the edited text is written to stdout once its tab is closed, e.g. 'sort data | zed --wait - | uniq'"
 )]
 struct Args {
     /// Wait for all of the given paths to be opened/closed before exiting.
@@ -92,6 +93,15 @@ struct Args {
     /// Pairs of file paths to diff. Can be specified multiple times.
     #[arg(long, action = clap::ArgAction::Append, num_args = 2, value_names = ["OLD_PATH", "NEW_PATH"])]
     diff: Vec<String>,
+    /// Paths for a three-way merge of LOCAL and REMOTE, which were both changed
+    /// from BASE, into MERGED. Waits until the merge view is closed, so that
+    /// Zed can be used as a `git mergetool`. Can be specified multiple times.
+    #[arg(long, action = clap::ArgAction::Append, num_args = 4, value_names = ["BASE", "LOCAL", "REMOTE", "MERGED"])]
+    merge: Vec<String>,
+    /// The language of the text read from stdin, by name or file extension.
+    /// Detected from the text if not specified.
+    #[arg(long, value_name = "LANGUAGE")]
+    language: Option<String>,
     /// Uninstall Zed from user system
     #[cfg(all(
         any(target_os = "linux", target_os = "macos"),
@@ -236,7 +246,8 @@ fn main() -> Result<()> {
     let mut paths = vec![];
     let mut urls = vec![];
     let mut diff_paths = vec![];
-    let mut stdin_tmp_file: Option<fs::File> = None;
+    let mut merge_paths = vec![];
+    let mut read_stdin = false;
     let mut anonymous_fd_tmp_files = vec![];
 
     for path in args.diff.chunks(2) {
@@ -246,6 +257,15 @@ fn main() -> Result<()> {
         ]);
     }
 
+    for path in args.merge.chunks(4) {
+        merge_paths.push([
+            parse_path_with_position(&path[0])?,
+            parse_path_with_position(&path[1])?,
+            parse_path_with_position(&path[2])?,
+            parse_path_with_position(&path[3])?,
+        ]);
+    }
+
     for path in args.paths_with_position.iter() {
         if path.starts_with("zed://")
             || path.starts_with("http://")
@@ -255,10 +275,7 @@ fn main() -> Result<()> {
         {
             urls.push(path.to_string());
         } else if path == "-" && args.paths_with_position.len() == 1 {
-            let file = NamedTempFile::new()?;
-            paths.push(file.path().to_string_lossy().to_string());
-            let (file, _) = file.keep()?;
-            stdin_tmp_file = Some(file);
+            read_stdin = true;
         } else if let Some(file) = anonymous_fd(path) {
             let tmp_file = NamedTempFile::new()?;
             paths.push(tmp_file.path().to_string_lossy().to_string());
@@ -281,43 +298,53 @@ fn main() -> Result<()> {
             let (_, handshake) = server.accept().context("Handshake after Zed spawn")?;
             let (tx, rx) = (handshake.requests, handshake.responses);
 
+            let wait = args.wait || !merge_paths.is_empty();
             tx.send(CliRequest::Open {
                 paths,
                 urls,
+                wait,
                 diff_paths,
-                wait: args.wait,
+                merge_paths,
+                stdin: read_stdin,
+                language: args.language,
                 open_new_workspace,
                 env,
                 user_data_dir: user_data_dir_for_thread,
             })?;
 
+            // Stream stdin while it's being written, so that the output of
+            // long running commands shows up as it is produced.
+            let stdin_pipe_handle = read_stdin.then(|| {
+                let tx = tx.clone();
+                thread::spawn(move || pipe_stdin(tx))
+            });
+
             while let Ok(response) = rx.recv() {
                 match response {
                     CliResponse::Ping => {}
                     CliResponse::Stdout { message } => println!("{message}"),
                     CliResponse::Stderr { message } => eprintln!("{message}"),
+                    CliResponse::Output { text } => {
+                        let mut stdout = io::stdout().lock();
+                        io::Write::write_all(&mut stdout, text.as_bytes())?;
+                        io::Write::flush(&mut stdout)?;
+                    }
                     CliResponse::Exit { status } => {
                         exit_status.lock().replace(status);
-                        return Ok(());
+                        break;
                     }
                 }
             }
 
+            // When waiting, the edited text has already been written back, so
+            // there's no point in reading the rest of stdin.
+            if let Some(handle) = stdin_pipe_handle.filter(|_| !wait) {
+                handle.join().unwrap()?;
+            }
             Ok(())
         }
     });
 
-    let stdin_pipe_handle: Option<JoinHandle<anyhow::Result<()>>> =
-        stdin_tmp_file.map(|tmp_file| {
-            thread::spawn(move || {
-                let stdin = std::io::stdin().lock();
-                if io::IsTerminal::is_terminal(&stdin) {
-                    return Ok(());
-                }
-                return pipe_to_tmp(stdin, tmp_file);
-            })
-        });
-
     let anonymous_fd_pipe_handles: Vec<JoinHandle<anyhow::Result<()>>> = anonymous_fd_tmp_files
         .into_iter()
         .map(|(file, tmp_file)| thread::spawn(move || pipe_to_tmp(file, tmp_file)))
@@ -328,9 +355,6 @@ fn main() -> Result<()> {
     } else {
         app.launch(url)?;
         sender.join().unwrap()?;
-        if let Some(handle) = stdin_pipe_handle {
-            handle.join().unwrap()?;
-        }
         for handle in anonymous_fd_pipe_handles {
             handle.join().unwrap()?;
         }
@@ -342,6 +366,59 @@ fn main() -> Result<()> {
     Ok(())
 }
 
+/// Sends stdin to Zed in chunks as it is read. Text that isn't valid UTF-8 is
+/// decoded lossily.
+fn pipe_stdin(tx: cli::ipc::IpcSender<CliRequest>) -> Result<()> {
+    let mut stdin = io::stdin().lock();
+    let mut buffer = [0; 8 * 1024];
+    let mut pending = Vec::new();
+    if !io::IsTerminal::is_terminal(&stdin) {
+        loop {
+            let bytes_read = match io::Read::read(&mut stdin, &mut buffer) {
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                res => res.context("reading stdin")?,
+            };
+            if bytes_read == 0 {
+                break;
+            }
+            pending.extend_from_slice(&buffer[..bytes_read]);
+
+            // Keep a character that was split across reads until the rest of
+            // it is read.
+            let complete_len = pending.len() - incomplete_char_len(&pending);
+            let text = String::from_utf8_lossy(&pending[..complete_len]).into_owned();
+            pending.drain(..complete_len);
+            tx.send(CliRequest::StdinChunk { text })?;
+        }
+    }
+    if !pending.is_empty() {
+        let text = String::from_utf8_lossy(&pending).into_owned();
+        tx.send(CliRequest::StdinChunk { text })?;
+    }
+    tx.send(CliRequest::StdinEnd)?;
+    Ok(())
+}
+
+/// Returns the length of the UTF-8 encoded character at the end of `bytes`
+/// that is missing some of its bytes, if any.
+fn incomplete_char_len(bytes: &[u8]) -> usize {
+    for len in 1..=bytes.len().min(3) {
+        let byte = bytes[bytes.len() - len];
+        // Skip continuation bytes until the start of the character.
+        if byte & 0b1100_0000 == 0b1000_0000 {
+            continue;
+        }
+        let char_len = match byte {
+            0b1100_0000..=0b1101_1111 => 2,
+            0b1110_0000..=0b1110_1111 => 3,
+            0b1111_0000..=0b1111_0111 => 4,
+            _ => 1,
+        };
+        return if char_len > len { len } else { 0 };
+    }
+    0
+}
+
 fn pipe_to_tmp(mut src: impl io::Read, mut dest: fs::File) -> Result<()> {
     let mut buffer = [0; 8 * 1024];
     loop {
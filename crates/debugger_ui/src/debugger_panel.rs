@@ -5,8 +5,9 @@ use crate::session::running::breakpoint_list::BreakpointList;
 use crate::{
     ClearAllBreakpoints, Continue, CopyDebugAdapterArguments, Detach, FocusBreakpointList,
     FocusConsole, FocusFrames, FocusLoadedSources, FocusModules, FocusTerminal, FocusVariables,
-    NewProcessModal, NewProcessMode, Pause, Restart, StepInto, StepOut, StepOver, Stop,
-    ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence, spawn_task_or_modal,
+    NewProcessModal, NewProcessMode, Pause, Restart, ReverseContinue, StepBack, StepInto, StepOut,
+    StepOver, Stop, ToggleExpandItem, ToggleSessionPicker, ToggleThreadPicker, persistence,
+    spawn_task_or_modal,
 };
 use anyhow::{Context as _, Result, anyhow};
 use dap::adapters::DebugAdapterName;
@@ -155,6 +156,7 @@ impl DebugPanel {
                         cx,
                         |breakpoint_store, cx| {
                             breakpoint_store.clear_breakpoints(cx);
+                            breakpoint_store.clear_data_breakpoints(cx);
                         },
                     )
                 });
@@ -615,6 +617,8 @@ impl DebugPanel {
                                     let capabilities = running_state.read(cx).capabilities(cx);
                                     let supports_detach =
                                         running_state.read(cx).session().read(cx).is_attached();
+                                    let supports_step_back =
+                                        capabilities.supports_step_back.unwrap_or_default();
                                     this.map(|this| {
                                         if thread_status == ThreadStatus::Running {
                                             this.child(
@@ -746,6 +750,62 @@ impl DebugPanel {
                                                 }
                                             }),
                                     )
+                                    .when(supports_step_back, |this| {
+                                        this.child(
+                                            IconButton::new(
+                                                "debug-step-back",
+                                                IconName::DebugStepBack,
+                                            )
+                                            .icon_size(IconSize::XSmall)
+                                            .shape(ui::IconButtonShape::Square)
+                                            .on_click(window.listener_for(
+                                                &running_state,
+                                                |this, _, _window, cx| {
+                                                    this.step_back(cx);
+                                                },
+                                            ))
+                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .tooltip({
+                                                let focus_handle = focus_handle.clone();
+                                                move |window, cx| {
+                                                    Tooltip::for_action_in(
+                                                        "Step back",
+                                                        &StepBack,
+                                                        &focus_handle,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                }
+                                            }),
+                                        )
+                                        .child(
+                                            IconButton::new(
+                                                "debug-reverse-continue",
+                                                IconName::RotateCcw,
+                                            )
+                                            .icon_size(IconSize::XSmall)
+                                            .shape(ui::IconButtonShape::Square)
+                                            .on_click(window.listener_for(
+                                                &running_state,
+                                                |this, _, _window, cx| {
+                                                    this.reverse_continue(cx);
+                                                },
+                                            ))
+                                            .disabled(thread_status != ThreadStatus::Stopped)
+                                            .tooltip({
+                                                let focus_handle = focus_handle.clone();
+                                                move |window, cx| {
+                                                    Tooltip::for_action_in(
+                                                        "Reverse continue",
+                                                        &ReverseContinue,
+                                                        &focus_handle,
+                                                        window,
+                                                        cx,
+                                                    )
+                                                }
+                                            }),
+                                        )
+                                    })
                                     .child(Divider::vertical())
                                     .child(
                                         IconButton::new("debug-restart", IconName::DebugRestart)
@@ -41,6 +41,9 @@ actions!(
         StepOver,
         StepOut,
         StepBack,
+        ReverseContinue,
+        StepOverInstruction,
+        StepIntoInstruction,
         Stop,
         ToggleIgnoreBreakpoints,
         ClearAllBreakpoints,
@@ -115,6 +118,8 @@ pub fn init(cx: &mut App) {
 
                 let caps = running_state.capabilities(cx);
                 let supports_step_back = caps.supports_step_back.unwrap_or_default();
+                let supports_instruction_stepping =
+                    caps.supports_stepping_granularity.unwrap_or_default();
                 let supports_detach = running_state.session().read(cx).is_attached();
                 let status = running_state.thread_status(cx);
 
@@ -147,9 +152,37 @@ pub fn init(cx: &mut App) {
                         }
                     })
                     .when(supports_step_back, |div| {
-                        let active_item = active_item.clone();
-                        div.on_action(move |_: &StepBack, _, cx| {
-                            active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepBack, _, cx| {
+                                active_item.update(cx, |item, cx| item.step_back(cx)).ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &ReverseContinue, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.reverse_continue(cx))
+                                    .ok();
+                            }
+                        })
+                    })
+                    .when(supports_instruction_stepping, |div| {
+                        div.on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepOverInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_over_instruction(cx))
+                                    .ok();
+                            }
+                        })
+                        .on_action({
+                            let active_item = active_item.clone();
+                            move |_: &StepIntoInstruction, _, cx| {
+                                active_item
+                                    .update(cx, |item, cx| item.step_in_instruction(cx))
+                                    .ok();
+                            }
                         })
                     })
                     .on_action({
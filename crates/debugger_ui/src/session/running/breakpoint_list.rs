@@ -9,13 +9,13 @@ use dap::ExceptionBreakpointsFilter;
 use editor::Editor;
 use gpui::{
     Action, AppContext, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful,
-    Task, UniformListScrollHandle, WeakEntity, uniform_list,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
 };
 use language::Point;
 use project::{
     Project,
     debugger::{
-        breakpoint_store::{BreakpointEditAction, BreakpointStore, SourceBreakpoint},
+        breakpoint_store::{self, BreakpointEditAction, BreakpointStore, SourceBreakpoint},
         session::Session,
     },
     worktree_store::WorktreeStore,
@@ -35,6 +35,7 @@ use zed_actions::{ToggleEnableBreakpoint, UnsetBreakpoint};
 pub(crate) enum SelectedBreakpointKind {
     Source,
     Exception,
+    Data,
 }
 pub(crate) struct BreakpointList {
     workspace: WeakEntity<Workspace>,
@@ -48,6 +49,7 @@ pub(crate) struct BreakpointList {
     focus_handle: FocusHandle,
     scroll_handle: UniformListScrollHandle,
     selected_ix: Option<usize>,
+    _subscription: Subscription,
 }
 
 impl Focusable for BreakpointList {
@@ -70,7 +72,8 @@ impl BreakpointList {
         let scroll_handle = UniformListScrollHandle::new();
         let scrollbar_state = ScrollbarState::new(scroll_handle.clone());
 
-        cx.new(|_| Self {
+        cx.new(|cx| Self {
+            _subscription: cx.observe(&breakpoint_store, |_, _, cx| cx.notify()),
             breakpoint_store,
             worktree_store,
             scrollbar_state,
@@ -101,6 +104,17 @@ impl BreakpointList {
         })
     }
 
+    fn edit_data_breakpoint(
+        &mut self,
+        data_id: &str,
+        action: BreakpointEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        self.breakpoint_store.update(cx, |breakpoint_store, cx| {
+            breakpoint_store.edit_data_breakpoint(data_id, action, cx);
+        })
+    }
+
     fn go_to_line_breakpoint(
         &mut self,
         path: Arc<Path>,
@@ -144,6 +158,9 @@ impl BreakpointList {
                 BreakpointEntryKind::ExceptionBreakpoint(bp) => {
                     (SelectedBreakpointKind::Exception, bp.is_enabled)
                 }
+                BreakpointEntryKind::DataBreakpoint(bp) => {
+                    (SelectedBreakpointKind::Data, bp.breakpoint.is_enabled())
+                }
             })
         })
     }
@@ -226,7 +243,8 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.go_to_line_breakpoint(path, row, window, cx);
             }
-            BreakpointEntryKind::ExceptionBreakpoint(_) => {}
+            BreakpointEntryKind::ExceptionBreakpoint(_)
+            | BreakpointEntryKind::DataBreakpoint(_) => {}
         }
     }
 
@@ -254,6 +272,10 @@ impl BreakpointList {
                     });
                 }
             }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                let data_id = data_breakpoint.breakpoint.data_id.clone();
+                self.edit_data_breakpoint(&data_id, BreakpointEditAction::InvertState, cx);
+            }
         }
         cx.notify();
     }
@@ -274,6 +296,10 @@ impl BreakpointList {
                 let row = line_breakpoint.breakpoint.row;
                 self.edit_line_breakpoint(path, row, BreakpointEditAction::Toggle, cx);
             }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                let data_id = data_breakpoint.breakpoint.data_id.clone();
+                self.edit_data_breakpoint(&data_id, BreakpointEditAction::Toggle, cx);
+            }
             BreakpointEntryKind::ExceptionBreakpoint(_) => {}
         }
         cx.notify();
@@ -358,7 +384,9 @@ impl BreakpointList {
         let selection_kind = self.selection_kind();
         let focus_handle = self.focus_handle.clone();
         let remove_breakpoint_tooltip = selection_kind.map(|(kind, _)| match kind {
-            SelectedBreakpointKind::Source => "Remove breakpoint from a breakpoint list",
+            SelectedBreakpointKind::Source | SelectedBreakpointKind::Data => {
+                "Remove breakpoint from a breakpoint list"
+            }
             SelectedBreakpointKind::Exception => {
                 "Exception Breakpoints cannot be removed from the breakpoint list"
             }
@@ -426,7 +454,8 @@ impl BreakpointList {
                         })
                     })
                     .disabled(
-                        selection_kind.map(|kind| kind.0) != Some(SelectedBreakpointKind::Source),
+                        selection_kind
+                            .is_none_or(|(kind, _)| kind == SelectedBreakpointKind::Exception),
                     )
                     .on_click({
                         let focus_handle = focus_handle.clone();
@@ -504,8 +533,23 @@ impl Render for BreakpointList {
                     weak: weak.clone(),
                 })
         });
-        self.breakpoints
-            .extend(breakpoints.chain(exception_breakpoints));
+        let data_breakpoints = self
+            .breakpoint_store
+            .read(cx)
+            .data_breakpoints()
+            .iter()
+            .map(|breakpoint| BreakpointEntry {
+                kind: BreakpointEntryKind::DataBreakpoint(DataBreakpoint {
+                    breakpoint: breakpoint.clone(),
+                }),
+                weak: weak.clone(),
+            })
+            .collect::<Vec<_>>();
+        self.breakpoints.extend(
+            breakpoints
+                .chain(data_breakpoints)
+                .chain(exception_breakpoints),
+        );
         v_flex()
             .id("breakpoint-list")
             .key_context("BreakpointList")
@@ -739,10 +783,116 @@ impl ExceptionBreakpoint {
         )
     }
 }
+#[derive(Clone, Debug)]
+struct DataBreakpoint {
+    breakpoint: breakpoint_store::DataBreakpoint,
+}
+
+impl DataBreakpoint {
+    fn render(
+        &mut self,
+        ix: usize,
+        focus_handle: FocusHandle,
+        list: WeakEntity<BreakpointList>,
+    ) -> ListItem {
+        let is_enabled = self.breakpoint.is_enabled();
+        let icon_name = if is_enabled {
+            IconName::DebugBreakpoint
+        } else {
+            IconName::DebugDisabledBreakpoint
+        };
+        let data_id = self.breakpoint.data_id.clone();
+        let access = self
+            .breakpoint
+            .access_type
+            .map(|access_type| format!("on {}", access_type.label()));
+        let details = access
+            .into_iter()
+            .chain(
+                self.breakpoint
+                    .condition
+                    .as_ref()
+                    .map(|condition| format!("if {condition}")),
+            )
+            .chain(
+                self.breakpoint
+                    .hit_condition
+                    .as_ref()
+                    .map(|hit_condition| format!("hit {hit_condition}")),
+            )
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        ListItem::new(SharedString::from(format!(
+            "data-breakpoint-ui-item-{}",
+            self.breakpoint.data_id
+        )))
+        .on_click({
+            let list = list.clone();
+            move |_, _, cx| {
+                list.update(cx, |list, cx| list.select_ix(Some(ix), cx))
+                    .ok();
+            }
+        })
+        .rounded()
+        .on_secondary_mouse_down(|_, _, cx| {
+            cx.stop_propagation();
+        })
+        .start_slot(
+            div()
+                .id(SharedString::from(format!(
+                    "data-breakpoint-ui-item-{}-click-handler",
+                    self.breakpoint.data_id
+                )))
+                .tooltip(move |window, cx| {
+                    Tooltip::for_action_in(
+                        if is_enabled {
+                            "Disable Data Breakpoint"
+                        } else {
+                            "Enable Data Breakpoint"
+                        },
+                        &ToggleEnableBreakpoint,
+                        &focus_handle,
+                        window,
+                        cx,
+                    )
+                })
+                .on_click(move |_, _, cx| {
+                    list.update(cx, |list, cx| {
+                        list.edit_data_breakpoint(&data_id, BreakpointEditAction::InvertState, cx);
+                    })
+                    .ok();
+                })
+                .cursor_pointer()
+                .child(Indicator::icon(Icon::new(icon_name)).color(Color::Debugger)),
+        )
+        .child(
+            h_flex()
+                .py_1()
+                .gap_1()
+                .min_h(px(26.))
+                .child(
+                    Label::new(self.breakpoint.description.clone())
+                        .size(LabelSize::Small)
+                        .line_height_style(ui::LineHeightStyle::UiLabel),
+                )
+                .when(!details.is_empty(), |this| {
+                    this.child(
+                        Label::new(details)
+                            .color(Color::Muted)
+                            .size(LabelSize::Small)
+                            .line_height_style(ui::LineHeightStyle::UiLabel),
+                    )
+                }),
+        )
+    }
+}
+
 #[derive(Clone, Debug)]
 enum BreakpointEntryKind {
     LineBreakpoint(LineBreakpoint),
     ExceptionBreakpoint(ExceptionBreakpoint),
+    DataBreakpoint(DataBreakpoint),
 }
 
 #[derive(Clone, Debug)]
@@ -766,6 +916,9 @@ impl BreakpointEntry {
             BreakpointEntryKind::ExceptionBreakpoint(exception_breakpoint) => {
                 exception_breakpoint.render(ix, focus_handle, self.weak.clone())
             }
+            BreakpointEntryKind::DataBreakpoint(data_breakpoint) => {
+                data_breakpoint.render(ix, focus_handle, self.weak.clone())
+            }
         }
     }
 }
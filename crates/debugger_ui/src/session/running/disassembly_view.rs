@@ -0,0 +1,516 @@
+use std::{ops::Range, path::Path, sync::Arc};
+
+use collections::{HashMap, HashSet};
+use dap::StackFrameId;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful,
+    Subscription, Task, UniformListScrollHandle, WeakEntity, uniform_list,
+};
+use language::{Buffer, Point};
+use project::{
+    ProjectPath,
+    debugger::session::{Session, SessionEvent, ThreadStatus, parse_memory_address},
+};
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use util::ResultExt;
+use workspace::Workspace;
+
+use super::{
+    RunningState,
+    stack_frame_list::{StackFrameList, StackFrameListEvent},
+};
+use crate::{StepIntoInstruction, StepOverInstruction};
+
+/// The number of instructions shown before the instruction pointer.
+const INSTRUCTIONS_BEFORE: i64 = 32;
+const INSTRUCTION_COUNT: u64 = 96;
+
+#[derive(Debug, Clone)]
+pub(crate) enum DisassemblyEntry {
+    /// A line of source code that the following instructions were generated from.
+    Source { path: Arc<Path>, line: u64 },
+    Instruction {
+        address: Option<u64>,
+        instruction: dap::DisassembledInstruction,
+    },
+}
+
+pub struct DisassemblyView {
+    session: Entity<Session>,
+    state: WeakEntity<RunningState>,
+    stack_frame_list: Entity<StackFrameList>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    /// The memory reference of the selected stack frame's instruction pointer.
+    instruction_pointer: Option<String>,
+    loaded_instruction_pointer: Option<String>,
+    entries: Vec<DisassemblyEntry>,
+    source_buffers: HashMap<Arc<Path>, Entity<Buffer>>,
+    requested_sources: HashSet<Arc<Path>>,
+    error: Option<SharedString>,
+    _load_task: Option<Task<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl DisassemblyView {
+    pub fn new(
+        session: Entity<Session>,
+        state: WeakEntity<RunningState>,
+        stack_frame_list: Entity<StackFrameList>,
+        workspace: WeakEntity<Workspace>,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        let _subscriptions = vec![
+            cx.subscribe(&stack_frame_list, |this, _, event, cx| match event {
+                StackFrameListEvent::SelectedStackFrameChanged(stack_frame_id) => {
+                    this.select_stack_frame(*stack_frame_id, cx);
+                }
+                StackFrameListEvent::BuiltEntries => {}
+            }),
+            cx.subscribe(&session, |this, _, event, _| {
+                // The code at the instruction pointer may have changed, so reload it even if the
+                // selected frame stays at the same address.
+                if let SessionEvent::Stopped(_) = event {
+                    this.loaded_instruction_pointer = None;
+                }
+            }),
+        ];
+
+        let scroll_handle = UniformListScrollHandle::new();
+
+        let mut this = Self {
+            scrollbar_state: ScrollbarState::new(scroll_handle.clone()),
+            scroll_handle,
+            session,
+            state,
+            stack_frame_list: stack_frame_list.clone(),
+            workspace,
+            focus_handle,
+            instruction_pointer: None,
+            loaded_instruction_pointer: None,
+            entries: Vec::new(),
+            source_buffers: HashMap::default(),
+            requested_sources: HashSet::default(),
+            error: None,
+            _load_task: None,
+            _subscriptions,
+        };
+        if let Some(stack_frame_id) = stack_frame_list.read(cx).opened_stack_frame_id() {
+            this.select_stack_frame(stack_frame_id, cx);
+        }
+        this
+    }
+
+    #[cfg(test)]
+    pub(crate) fn entries(&self) -> &[DisassemblyEntry] {
+        &self.entries
+    }
+
+    fn select_stack_frame(&mut self, stack_frame_id: StackFrameId, cx: &mut Context<Self>) {
+        self.instruction_pointer = self
+            .stack_frame_list
+            .read(cx)
+            .flatten_entries(true, true)
+            .into_iter()
+            .find(|frame| frame.id == stack_frame_id)
+            .and_then(|frame| frame.instruction_pointer_reference);
+        cx.notify();
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        self.loaded_instruction_pointer = self.instruction_pointer.clone();
+        let Some(memory_reference) = self.instruction_pointer.clone() else {
+            self.entries.clear();
+            self.error = None;
+            self._load_task = None;
+            return;
+        };
+
+        let task = self.session.read(cx).disassemble(
+            memory_reference,
+            -INSTRUCTIONS_BEFORE,
+            INSTRUCTION_COUNT,
+        );
+        self._load_task = Some(cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(instructions) => {
+                        this.error = None;
+                        this.entries = interleave_source(instructions);
+                        this.open_source_buffers(cx);
+                        if let Some(ix) = this.current_instruction_ix() {
+                            this.scroll_handle
+                                .scroll_to_item(ix, ScrollStrategy::Center);
+                        }
+                    }
+                    Err(error) => {
+                        this.entries.clear();
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        }));
+    }
+
+    fn current_instruction_ix(&self) -> Option<usize> {
+        let current_address = self
+            .instruction_pointer
+            .as_deref()
+            .and_then(parse_memory_address)?;
+        self.entries.iter().position(|entry| match entry {
+            DisassemblyEntry::Instruction { address, .. } => *address == Some(current_address),
+            DisassemblyEntry::Source { .. } => false,
+        })
+    }
+
+    fn open_source_buffers(&mut self, cx: &mut Context<Self>) {
+        let Some(project) = self
+            .workspace
+            .read_with(cx, |workspace, _| workspace.project().clone())
+            .log_err()
+        else {
+            return;
+        };
+
+        let paths = self
+            .entries
+            .iter()
+            .filter_map(|entry| match entry {
+                DisassemblyEntry::Source { path, .. } => Some(path.clone()),
+                DisassemblyEntry::Instruction { .. } => None,
+            })
+            .filter(|path| self.requested_sources.insert(path.clone()))
+            .collect::<Vec<_>>();
+
+        // The sources are opened through worktrees, so that they can be read
+        // from the remote host when debugging remotely.
+        for path in paths {
+            let find_worktree = project.update(cx, |project, cx| {
+                project.find_or_create_worktree(&path, false, cx)
+            });
+            let project = project.downgrade();
+            cx.spawn(async move |this, cx| {
+                let (worktree, relative_path) = find_worktree.await?;
+                let open_buffer = project.update(cx, |project, cx| {
+                    let worktree_id = worktree.read(cx).id();
+                    project.open_buffer(
+                        ProjectPath {
+                            worktree_id,
+                            path: relative_path.into(),
+                        },
+                        cx,
+                    )
+                })?;
+                let buffer = open_buffer.await?;
+                this.update(cx, |this, cx| {
+                    this.source_buffers.insert(path, buffer);
+                    cx.notify();
+                })
+            })
+            .detach_and_log_err(cx);
+        }
+    }
+
+    fn step(&mut self, step_in: bool, cx: &mut Context<Self>) {
+        self.state
+            .update(cx, |state, cx| {
+                if step_in {
+                    state.step_in_instruction(cx);
+                } else {
+                    state.step_over_instruction(cx);
+                }
+            })
+            .ok();
+    }
+
+    fn source_line_text(&self, path: &Arc<Path>, line: u64, cx: &App) -> Option<String> {
+        let buffer = self.source_buffers.get(path)?.read(cx);
+        let row = u32::try_from(line.checked_sub(1)?).ok()?;
+        if row > buffer.max_point().row {
+            return None;
+        }
+        let text = buffer
+            .text_for_range(Point::new(row, 0)..Point::new(row, buffer.line_len(row)))
+            .collect::<String>();
+        Some(text.trim().to_string())
+    }
+
+    fn render_entry(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        match &self.entries[ix] {
+            DisassemblyEntry::Source { path, line } => {
+                let file_name = path
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| path.to_string_lossy().to_string());
+                let text = self.source_line_text(path, *line, cx);
+
+                h_flex()
+                    .id(("disassembly-source", ix))
+                    .w_full()
+                    .gap_2()
+                    .px_1()
+                    .pt_1()
+                    .font_buffer(cx)
+                    .text_ui_sm(cx)
+                    .child(
+                        div()
+                            .text_color(cx.theme().colors().text_muted)
+                            .child(format!("{file_name}:{line}")),
+                    )
+                    .when_some(text, |this, text| this.child(text))
+                    .into_any()
+            }
+            DisassemblyEntry::Instruction {
+                address,
+                instruction,
+            } => {
+                let is_current = self
+                    .instruction_pointer
+                    .as_deref()
+                    .and_then(parse_memory_address)
+                    == *address
+                    && address.is_some();
+
+                h_flex()
+                    .id(("disassembly-instruction", ix))
+                    .w_full()
+                    .gap_2()
+                    .px_1()
+                    .font_buffer(cx)
+                    .text_ui_sm(cx)
+                    .when(is_current, |this| {
+                        this.bg(cx.theme().colors().editor_debugger_active_line_background)
+                    })
+                    .child(div().w_3().when(is_current, |this| {
+                        this.child(
+                            Icon::new(IconName::ArrowRight)
+                                .size(IconSize::XSmall)
+                                .color(Color::Warning),
+                        )
+                    }))
+                    .child(
+                        div()
+                            .text_color(cx.theme().colors().text_muted)
+                            .child(instruction.address.clone()),
+                    )
+                    .when_some(instruction.instruction_bytes.clone(), |this, bytes| {
+                        this.child(
+                            div()
+                                .min_w_32()
+                                .text_color(cx.theme().colors().text_muted)
+                                .child(bytes),
+                        )
+                    })
+                    .child(instruction.instruction.clone())
+                    .when_some(instruction.symbol.clone(), |this, symbol| {
+                        this.child(
+                            div()
+                                .text_color(cx.theme().colors().text_muted)
+                                .child(format!("<{symbol}>")),
+                        )
+                    })
+                    .into_any()
+            }
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let is_stopped = self
+            .state
+            .read_with(cx, |state, cx| state.thread_status(cx))
+            .ok()
+            .flatten()
+            == Some(ThreadStatus::Stopped);
+        let can_step = is_stopped
+            && self
+                .session
+                .read(cx)
+                .capabilities()
+                .supports_stepping_granularity
+                .unwrap_or_default();
+        let focus_handle = self.focus_handle.clone();
+
+        h_flex()
+            .w_full()
+            .gap_1()
+            .p_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                IconButton::new("disassembly-step-over", IconName::ArrowRight)
+                    .icon_size(IconSize::XSmall)
+                    .shape(ui::IconButtonShape::Square)
+                    .disabled(!can_step)
+                    .on_click(cx.listener(|this, _, _, cx| this.step(false, cx)))
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Step Over Instruction",
+                                &StepOverInstruction,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("disassembly-step-into", IconName::ArrowDownRight)
+                    .icon_size(IconSize::XSmall)
+                    .shape(ui::IconButtonShape::Square)
+                    .disabled(!can_step)
+                    .on_click(cx.listener(|this, _, _, cx| this.step(true, cx)))
+                    .tooltip(move |window, cx| {
+                        Tooltip::for_action_in(
+                            "Step Into Instruction",
+                            &StepIntoInstruction,
+                            &focus_handle,
+                            window,
+                            cx,
+                        )
+                    }),
+            )
+            .when_some(self.instruction_pointer.clone(), |this, address| {
+                this.child(
+                    Label::new(address)
+                        .size(LabelSize::Small)
+                        .color(Color::Muted)
+                        .buffer_font(cx),
+                )
+            })
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("disassembly-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+/// Inserts a source entry before each run of instructions that belongs to a new source line.
+///
+/// Debug adapters may omit an instruction's `location` when it is the same as the one of the
+/// previous instruction, so the last seen location is carried forward.
+pub(crate) fn interleave_source(
+    instructions: Vec<dap::DisassembledInstruction>,
+) -> Vec<DisassemblyEntry> {
+    let mut entries = Vec::with_capacity(instructions.len());
+    let mut current_path: Option<Arc<Path>> = None;
+    let mut last_source: Option<(Arc<Path>, u64)> = None;
+
+    for instruction in instructions {
+        if let Some(path) = instruction
+            .location
+            .as_ref()
+            .and_then(|source| source.path.as_deref())
+        {
+            current_path = Some(Arc::from(Path::new(path)));
+        }
+
+        if let (Some(path), Some(line)) = (current_path.clone(), instruction.line) {
+            let source = (path, line);
+            if last_source.as_ref() != Some(&source) {
+                entries.push(DisassemblyEntry::Source {
+                    path: source.0.clone(),
+                    line,
+                });
+                last_source = Some(source);
+            }
+        }
+
+        entries.push(DisassemblyEntry::Instruction {
+            address: parse_memory_address(&instruction.address),
+            instruction,
+        });
+    }
+
+    entries
+}
+
+impl Focusable for DisassemblyView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for DisassemblyView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.loaded_instruction_pointer != self.instruction_pointer {
+            self.load(cx);
+        }
+
+        let content = if let Some(error) = self.error.clone() {
+            div()
+                .p_2()
+                .child(Label::new(error).color(Color::Error))
+                .into_any_element()
+        } else if self.instruction_pointer.is_none() {
+            div()
+                .p_2()
+                .child(
+                    Label::new("The selected stack frame has no instruction pointer")
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            div()
+                .relative()
+                .flex_1()
+                .size_full()
+                .child(
+                    uniform_list(
+                        "disassembly-view",
+                        self.entries.len(),
+                        cx.processor(|this, range: Range<usize>, _window, cx| {
+                            range.map(|ix| this.render_entry(ix, cx)).collect()
+                        }),
+                    )
+                    .track_scroll(self.scroll_handle.clone())
+                    .size_full(),
+                )
+                .child(self.render_vertical_scrollbar(cx))
+                .into_any_element()
+        };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_header(cx))
+            .child(content)
+    }
+}
@@ -0,0 +1,394 @@
+use std::ops::Range;
+
+use anyhow::anyhow;
+use editor::Editor;
+use gpui::{
+    AnyElement, Entity, FocusHandle, Focusable, MouseButton, ScrollStrategy, Stateful,
+    Subscription, Task, UniformListScrollHandle, uniform_list,
+};
+use menu::Confirm;
+use project::debugger::session::{MemoryBlock, Session, SessionEvent};
+use ui::{Scrollbar, ScrollbarState, Tooltip, prelude::*};
+use workspace::notifications::DetachAndPromptErr;
+
+/// The number of bytes read from the debuggee at once.
+const PAGE_SIZE: u64 = 1024;
+const BYTES_PER_ROW: usize = 16;
+
+pub struct MemoryView {
+    session: Entity<Session>,
+    focus_handle: FocusHandle,
+    address_editor: Entity<Editor>,
+    write_editor: Entity<Editor>,
+    scroll_handle: UniformListScrollHandle,
+    scrollbar_state: ScrollbarState,
+    memory_reference: Option<String>,
+    /// Offset of the displayed page, relative to `memory_reference`.
+    offset: i64,
+    block: Option<MemoryBlock>,
+    selected_address: Option<u64>,
+    error: Option<SharedString>,
+    _load_task: Task<()>,
+    _subscription: Subscription,
+}
+
+impl MemoryView {
+    pub fn new(session: Entity<Session>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let focus_handle = cx.focus_handle();
+
+        let address_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Memory reference or address", cx);
+            editor
+        });
+        let write_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Bytes to write at the selection, e.g. de ad be ef", cx);
+            editor
+        });
+
+        let _subscription = cx.subscribe(&session, |this, _, event, cx| {
+            if let SessionEvent::Stopped(_) = event {
+                this.load(cx);
+            }
+        });
+
+        let scroll_handle = UniformListScrollHandle::new();
+
+        Self {
+            scrollbar_state: ScrollbarState::new(scroll_handle.clone()),
+            scroll_handle,
+            session,
+            focus_handle,
+            address_editor,
+            write_editor,
+            memory_reference: None,
+            offset: 0,
+            block: None,
+            selected_address: None,
+            error: None,
+            _load_task: Task::ready(()),
+            _subscription,
+        }
+    }
+
+    /// Shows the memory at the given memory reference, such as the one of a variable.
+    pub(crate) fn open(
+        &mut self,
+        memory_reference: String,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.address_editor.update(cx, |editor, cx| {
+            editor.set_text(memory_reference.as_str(), window, cx);
+        });
+        self.memory_reference = Some(memory_reference);
+        self.offset = 0;
+        self.selected_address = None;
+        self.load(cx);
+    }
+
+    #[cfg(test)]
+    pub(crate) fn block(&self) -> Option<&MemoryBlock> {
+        self.block.as_ref()
+    }
+
+    fn load(&mut self, cx: &mut Context<Self>) {
+        let Some(memory_reference) = self.memory_reference.clone() else {
+            return;
+        };
+
+        let task = self
+            .session
+            .read(cx)
+            .read_memory(memory_reference, self.offset, PAGE_SIZE, cx);
+
+        self._load_task = cx.spawn(async move |this, cx| {
+            let result = task.await;
+            this.update(cx, |this, cx| {
+                match result {
+                    Ok(block) => {
+                        this.block = Some(block);
+                        this.error = None;
+                    }
+                    Err(error) => {
+                        this.block = None;
+                        this.error = Some(error.to_string().into());
+                    }
+                }
+                cx.notify();
+            })
+            .ok();
+        });
+    }
+
+    fn go_to_address(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let memory_reference = self.address_editor.read(cx).text(cx).trim().to_string();
+        if memory_reference.is_empty() {
+            return;
+        }
+        self.open(memory_reference, window, cx);
+    }
+
+    fn go_to_page(&mut self, delta: i64, cx: &mut Context<Self>) {
+        self.offset = self.offset.saturating_add(delta * PAGE_SIZE as i64);
+        self.selected_address = None;
+        self.scroll_handle.scroll_to_item(0, ScrollStrategy::Top);
+        self.load(cx);
+    }
+
+    fn write_bytes(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(memory_reference), Some(block)) = (self.memory_reference.clone(), &self.block)
+        else {
+            return;
+        };
+        let address = self.selected_address.unwrap_or(block.address);
+        let offset = self.offset + address.wrapping_sub(block.address) as i64;
+        let task = match parse_hex_bytes(&self.write_editor.read(cx).text(cx)) {
+            Some(data) => self.session.update(cx, |session, cx| {
+                session.write_memory(memory_reference, offset, &data, cx)
+            }),
+            None => Task::ready(Err(anyhow!(
+                "Enter the bytes to write in hexadecimal, e.g. de ad be ef"
+            ))),
+        };
+
+        cx.spawn_in(window, async move |this, cx| {
+            task.await?;
+            this.update_in(cx, |this, window, cx| {
+                this.write_editor.update(cx, |editor, cx| {
+                    editor.clear(window, cx);
+                });
+                this.load(cx);
+            })
+        })
+        .detach_and_prompt_err("Failed to write memory", window, cx, |_, _, _| None);
+    }
+
+    fn row_count(&self) -> usize {
+        self.block
+            .as_ref()
+            .map_or(0, |block| block_len(block).div_ceil(BYTES_PER_ROW))
+    }
+
+    fn render_row(&self, ix: usize, cx: &mut Context<Self>) -> AnyElement {
+        let Some(block) = self.block.as_ref() else {
+            return div().into_any();
+        };
+
+        let len = block_len(block);
+        let range = ix * BYTES_PER_ROW..((ix + 1) * BYTES_PER_ROW).min(len);
+        let row_address = block.address.wrapping_add(range.start as u64);
+
+        let mut hex = h_flex().gap_1();
+        let mut ascii = String::with_capacity(BYTES_PER_ROW);
+        for byte_ix in range {
+            let address = block.address.wrapping_add(byte_ix as u64);
+            let byte = block.data.get(byte_ix).copied();
+            let (text, ascii_char) = match byte {
+                Some(byte) if byte.is_ascii_graphic() || byte == b' ' => {
+                    (format!("{byte:02x}"), byte as char)
+                }
+                Some(byte) => (format!("{byte:02x}"), '.'),
+                None => ("??".to_string(), '?'),
+            };
+            ascii.push(ascii_char);
+
+            hex = hex.child(
+                div()
+                    .id(("memory-byte", byte_ix))
+                    .px_0p5()
+                    .rounded_sm()
+                    .when(byte.is_none(), |this| {
+                        this.text_color(cx.theme().colors().text_muted)
+                    })
+                    .when(self.selected_address == Some(address), |this| {
+                        this.bg(cx.theme().colors().element_selected)
+                    })
+                    .hover(|this| this.bg(cx.theme().colors().element_hover))
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.selected_address = Some(address);
+                        cx.notify();
+                    }))
+                    .child(text),
+            );
+        }
+
+        h_flex()
+            .id(("memory-row", ix))
+            .w_full()
+            .gap_3()
+            .px_1()
+            .font_buffer(cx)
+            .text_ui_sm(cx)
+            .child(
+                div()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(format!("{row_address:016x}")),
+            )
+            .child(hex)
+            .child(
+                div()
+                    .text_color(cx.theme().colors().text_muted)
+                    .child(ascii),
+            )
+            .into_any()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_block = self.memory_reference.is_some();
+        let supports_write = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_write_memory_request
+            .unwrap_or_default();
+
+        v_flex()
+            .w_full()
+            .gap_1()
+            .p_1()
+            .border_b_1()
+            .border_color(cx.theme().colors().border_variant)
+            .child(
+                h_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .flex_1()
+                            .on_action(cx.listener(Self::go_to_address))
+                            .child(self.address_editor.clone()),
+                    )
+                    .child(
+                        IconButton::new("memory-previous-page", IconName::ChevronLeft)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!has_block)
+                            .tooltip(Tooltip::text("Previous Page"))
+                            .on_click(cx.listener(|this, _, _, cx| this.go_to_page(-1, cx))),
+                    )
+                    .child(
+                        IconButton::new("memory-next-page", IconName::ChevronRight)
+                            .icon_size(IconSize::XSmall)
+                            .disabled(!has_block)
+                            .tooltip(Tooltip::text("Next Page"))
+                            .on_click(cx.listener(|this, _, _, cx| this.go_to_page(1, cx))),
+                    ),
+            )
+            .when(supports_write && self.block.is_some(), |this| {
+                this.child(
+                    div()
+                        .on_action(cx.listener(Self::write_bytes))
+                        .child(self.write_editor.clone()),
+                )
+            })
+    }
+
+    fn render_vertical_scrollbar(&self, cx: &mut Context<Self>) -> Stateful<Div> {
+        div()
+            .occlude()
+            .id("memory-view-vertical-scrollbar")
+            .on_mouse_move(cx.listener(|_, _, _, cx| {
+                cx.notify();
+                cx.stop_propagation()
+            }))
+            .on_hover(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_any_mouse_down(|_, _, cx| {
+                cx.stop_propagation();
+            })
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|_, _, _, cx| {
+                    cx.stop_propagation();
+                }),
+            )
+            .on_scroll_wheel(cx.listener(|_, _, _, cx| {
+                cx.notify();
+            }))
+            .h_full()
+            .absolute()
+            .right_1()
+            .top_1()
+            .bottom_0()
+            .w(px(12.))
+            .cursor_default()
+            .children(Scrollbar::vertical(self.scrollbar_state.clone()))
+    }
+}
+
+/// Parses whitespace separated hexadecimal bytes, such as `de ad be ef` or `deadbeef`.
+pub(crate) fn parse_hex_bytes(text: &str) -> Option<Vec<u8>> {
+    let digits = text
+        .split_whitespace()
+        .flat_map(|word| word.strip_prefix("0x").unwrap_or(word).chars())
+        .collect::<Vec<_>>();
+    if digits.is_empty() || digits.len() % 2 != 0 {
+        return None;
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let high = pair[0].to_digit(16)?;
+            let low = pair[1].to_digit(16)?;
+            Some((high * 16 + low) as u8)
+        })
+        .collect()
+}
+
+/// Returns the number of bytes shown for a block, which is never more than was
+/// requested, whatever the debug adapter reported.
+fn block_len(block: &MemoryBlock) -> usize {
+    (block.data.len() as u64)
+        .saturating_add(block.unreadable_bytes)
+        .min(PAGE_SIZE) as usize
+}
+
+impl Focusable for MemoryView {
+    fn focus_handle(&self, _: &gpui::App) -> gpui::FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Render for MemoryView {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let content = if let Some(error) = self.error.clone() {
+            div()
+                .p_2()
+                .child(Label::new(error).color(Color::Error))
+                .into_any_element()
+        } else if self.memory_reference.is_none() {
+            div()
+                .p_2()
+                .child(
+                    Label::new("Enter an address, or choose View Memory on a variable")
+                        .color(Color::Muted),
+                )
+                .into_any_element()
+        } else {
+            div()
+                .relative()
+                .flex_1()
+                .size_full()
+                .child(
+                    uniform_list(
+                        "memory-view",
+                        self.row_count(),
+                        cx.processor(|this, range: Range<usize>, _window, cx| {
+                            range.map(|ix| this.render_row(ix, cx)).collect()
+                        }),
+                    )
+                    .track_scroll(self.scroll_handle.clone())
+                    .size_full(),
+                )
+                .child(self.render_vertical_scrollbar(cx))
+                .into_any_element()
+        };
+
+        v_flex()
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .child(self.render_header(cx))
+            .child(content)
+    }
+}
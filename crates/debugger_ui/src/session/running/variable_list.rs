@@ -6,14 +6,16 @@ use dap::{
 use editor::Editor;
 use gpui::{
     Action, AnyElement, ClickEvent, ClipboardItem, Context, DismissEvent, Empty, Entity,
-    FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful, Subscription,
-    TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred, uniform_list,
+    EventEmitter, FocusHandle, Focusable, Hsla, MouseButton, MouseDownEvent, Point, Stateful,
+    Subscription, TextStyleRefinement, UniformListScrollHandle, actions, anchored, deferred,
+    uniform_list,
 };
 use menu::{SelectFirst, SelectLast, SelectNext, SelectPrevious};
 use project::debugger::session::{Session, SessionEvent, Watcher};
 use std::{collections::HashMap, ops::Range, sync::Arc};
 use ui::{ContextMenu, ListItem, ScrollableHandle, Scrollbar, ScrollbarState, Tooltip, prelude::*};
 use util::debug_panic;
+use workspace::notifications::DetachAndPromptErr;
 
 actions!(
     variable_list,
@@ -25,9 +27,16 @@ actions!(
         EditVariable,
         AddWatch,
         RemoveWatch,
+        AddDataBreakpoint,
+        ViewMemory,
     ]
 );
 
+pub(crate) enum VariableListEvent {
+    /// The memory at the given memory reference should be shown.
+    ViewMemory(String),
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq)]
 pub(crate) struct EntryState {
     depth: usize,
@@ -568,6 +577,22 @@ impl VariableList {
             .capabilities()
             .supports_set_variable
             .unwrap_or_default();
+        let supports_data_breakpoints = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_data_breakpoints
+            .unwrap_or_default();
+
+        let supports_read_memory = self
+            .session
+            .read(cx)
+            .capabilities()
+            .supports_read_memory_request
+            .unwrap_or_default();
+        let has_memory_reference = entry
+            .as_variable()
+            .is_some_and(|variable| variable.memory_reference.is_some());
 
         let context_menu = ContextMenu::build(window, cx, |menu, _, _| {
             menu.when(entry.as_variable().is_some(), |menu| {
@@ -577,6 +602,12 @@ impl VariableList {
                         menu.action("Edit Value", EditVariable.boxed_clone())
                     })
                     .action("Watch Variable", AddWatch.boxed_clone())
+                    .when(supports_data_breakpoints, |menu| {
+                        menu.action("Break on Value Change", AddDataBreakpoint.boxed_clone())
+                    })
+                    .when(supports_read_memory && has_memory_reference, |menu| {
+                        menu.action("View Memory", ViewMemory.boxed_clone())
+                    })
             })
             .when(entry.as_watcher().is_some(), |menu| {
                 menu.action("Copy Name", CopyVariableName.boxed_clone())
@@ -710,6 +741,61 @@ impl VariableList {
         .detach_and_log_err(cx);
     }
 
+    fn add_data_breakpoint(
+        &mut self,
+        _: &AddDataBreakpoint,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+
+        let Some(entry) = self.entries.iter().find(|entry| &entry.path == selection) else {
+            return;
+        };
+
+        let Some(variable) = entry.as_variable() else {
+            return;
+        };
+
+        let Some(state) = self.entry_states.get(&entry.path) else {
+            debug_panic!("Trying to set a data breakpoint on a variable that doesn't have a state");
+            return;
+        };
+
+        let frame_id = self.selected_stack_frame_id;
+        self.session
+            .update(cx, |session, cx| {
+                session.add_data_breakpoint(
+                    Some(state.parent_reference),
+                    variable.name.clone(),
+                    frame_id,
+                    cx,
+                )
+            })
+            .detach_and_prompt_err("Failed to set data breakpoint", window, cx, |_, _, _| None);
+    }
+
+    fn view_memory(&mut self, _: &ViewMemory, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(selection) = self.selection.as_ref() else {
+            return;
+        };
+
+        let Some(entry) = self.entries.iter().find(|entry| &entry.path == selection) else {
+            return;
+        };
+
+        let Some(memory_reference) = entry
+            .as_variable()
+            .and_then(|variable| variable.memory_reference.clone())
+        else {
+            return;
+        };
+
+        cx.emit(VariableListEvent::ViewMemory(memory_reference));
+    }
+
     fn remove_watcher(&mut self, _: &RemoveWatch, _: &mut Window, cx: &mut Context<Self>) {
         let Some(selection) = self.selection.as_ref() else {
             return;
@@ -1323,6 +1409,8 @@ impl VariableList {
     }
 }
 
+impl EventEmitter<VariableListEvent> for VariableList {}
+
 impl Focusable for VariableList {
     fn focus_handle(&self, _: &App) -> gpui::FocusHandle {
         self.focus_handle.clone()
@@ -1351,6 +1439,8 @@ impl Render for VariableList {
             .on_action(cx.listener(Self::edit_variable))
             .on_action(cx.listener(Self::add_watcher))
             .on_action(cx.listener(Self::remove_watcher))
+            .on_action(cx.listener(Self::add_data_breakpoint))
+            .on_action(cx.listener(Self::view_memory))
             .child(
                 uniform_list(
                     "variable-list",
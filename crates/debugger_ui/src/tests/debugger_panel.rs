@@ -9,8 +9,8 @@ use dap::{
     adapters::DebugTaskDefinition,
     client::SessionId,
     requests::{
-        Continue, Disconnect, Launch, Next, RunInTerminal, SetBreakpoints, StackTrace,
-        StartDebugging, StepBack, StepIn, StepOut, Threads,
+        Continue, Disconnect, Launch, Next, ReverseContinue, RunInTerminal, SetBreakpoints,
+        StackTrace, StartDebugging, StepBack, StepIn, StepOut, Threads,
     },
 };
 use editor::{
@@ -27,7 +27,7 @@ use std::{
     path::Path,
     sync::{
         Arc,
-        atomic::{AtomicBool, Ordering},
+        atomic::{AtomicBool, AtomicU64, Ordering},
     },
 };
 use terminal_view::terminal_panel::TerminalPanel;
@@ -1008,6 +1008,20 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
         })
     });
 
+    client.on_request::<ReverseContinue, _>(move |_, _| {
+        Err(ErrorResponse {
+            error: Some(dap::Message {
+                id: 1,
+                format: "error".into(),
+                variables: None,
+                send_telemetry: None,
+                show_user: None,
+                url: None,
+                url_label: None,
+            }),
+        })
+    });
+
     client
         .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
             reason: dap::StoppedEventReason::Pause,
@@ -1032,6 +1046,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
         "step_over",
         "continue_thread",
         "step_back",
+        "reverse_continue",
         "step_in",
         "step_out",
     ] {
@@ -1039,6 +1054,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
             "step_over" => running_state.step_over(cx),
             "continue_thread" => running_state.continue_thread(cx),
             "step_back" => running_state.step_back(cx),
+            "reverse_continue" => running_state.reverse_continue(cx),
             "step_in" => running_state.step_in(cx),
             "step_out" => running_state.step_out(cx),
             _ => unreachable!(),
@@ -1051,7 +1067,7 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
                     .thread_status(cx)
                     .expect("There should be an active thread selected"),
                 match *operation {
-                    "continue_thread" => ThreadStatus::Running,
+                    "continue_thread" | "reverse_continue" => ThreadStatus::Running,
                     _ => ThreadStatus::Stepping,
                 },
                 "Thread status was not set to correct intermediate state after {} request",
@@ -1079,6 +1095,163 @@ async fn test_debug_panel_item_thread_status_reset_on_failure(
     }
 }
 
+#[gpui::test]
+async fn test_reverse_debugging(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.rs": "First line\nSecond line\nThird line\nFourth line",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<dap::requests::Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_step_back: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    // The fake adapter replays a recording of the program, which is currently stopped on the
+    // third line. Stepping moves through the recording in either direction.
+    let line = Arc::new(AtomicU64::new(3));
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>({
+        let line = line.clone();
+        move |_, _| {
+            Ok(dap::StackTraceResponse {
+                stack_frames: vec![dap::StackFrame {
+                    id: 1,
+                    name: "main".into(),
+                    source: Some(dap::Source {
+                        name: Some("main.rs".into()),
+                        path: Some(path!("/project/main.rs").into()),
+                        source_reference: None,
+                        presentation_hint: None,
+                        origin: None,
+                        sources: None,
+                        adapter_data: None,
+                        checksums: None,
+                    }),
+                    line: line.load(Ordering::SeqCst),
+                    column: 1,
+                    end_line: None,
+                    end_column: None,
+                    can_restart: None,
+                    instruction_pointer_reference: None,
+                    module_id: None,
+                    presentation_hint: None,
+                }],
+                total_frames: None,
+            })
+        }
+    });
+
+    client.on_request::<Next, _>({
+        let line = line.clone();
+        move |_, _| {
+            line.fetch_add(1, Ordering::SeqCst);
+            Ok(())
+        }
+    });
+
+    client.on_request::<StepBack, _>({
+        let line = line.clone();
+        move |_, args| {
+            assert_eq!(1, args.thread_id);
+            line.fetch_sub(1, Ordering::SeqCst);
+            Ok(())
+        }
+    });
+
+    client.on_request::<ReverseContinue, _>({
+        let line = line.clone();
+        move |_, args| {
+            assert_eq!(1, args.thread_id);
+            // There are no breakpoints, so execution runs back to the start of the recording.
+            line.store(1, Ordering::SeqCst);
+            Ok(())
+        }
+    });
+
+    let stopped_event = || {
+        dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Step,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        })
+    };
+
+    client.fake_event(stopped_event()).await;
+    cx.run_until_parked();
+
+    let running_state = active_debug_session_panel(workspace, cx)
+        .read_with(cx, |item, _| item.running_state().clone());
+    cx.run_until_parked();
+
+    let current_line = |cx: &mut VisualTestContext| {
+        running_state.update(cx, |running_state, cx| {
+            running_state
+                .stack_frame_list()
+                .update(cx, |list, cx| list.dap_stack_frames(cx))
+                .first()
+                .map(|frame| frame.line)
+        })
+    };
+    assert_eq!(current_line(cx), Some(3));
+
+    for (operation, expected_line) in [
+        ("step_back", 2),
+        ("step_over", 3),
+        ("step_back", 2),
+        ("reverse_continue", 1),
+        ("step_over", 2),
+    ] {
+        running_state.update(cx, |running_state, cx| match operation {
+            "step_back" => running_state.step_back(cx),
+            "step_over" => running_state.step_over(cx),
+            "reverse_continue" => running_state.reverse_continue(cx),
+            _ => unreachable!(),
+        });
+        cx.run_until_parked();
+
+        client.fake_event(stopped_event()).await;
+        cx.run_until_parked();
+
+        assert_eq!(
+            current_line(cx),
+            Some(expected_line),
+            "Unexpected position in the recording after {operation}"
+        );
+    }
+}
+
 #[gpui::test]
 async fn test_send_breakpoints_when_editor_has_been_saved(
     executor: BackgroundExecutor,
@@ -0,0 +1,239 @@
+use crate::{
+    debugger_panel::DebugPanel,
+    persistence::DebuggerPaneItem,
+    session::running::{disassembly_view::DisassemblyEntry, memory_view::parse_hex_bytes},
+    tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
+};
+use dap::{
+    StackFrame, StoppedEvent,
+    requests::{Disassemble, Initialize, ReadMemory, Scopes, StackTrace, Threads},
+};
+use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
+use project::{FakeFs, Project, debugger::session::MemoryBlock};
+use serde_json::json;
+use std::sync::{
+    Arc,
+    atomic::{AtomicBool, Ordering},
+};
+use util::path;
+
+fn instruction(
+    address: &str,
+    line: Option<u64>,
+    path: Option<&str>,
+) -> dap::DisassembledInstruction {
+    dap::DisassembledInstruction {
+        address: address.into(),
+        instruction_bytes: None,
+        instruction: format!("nop ; {address}"),
+        symbol: None,
+        location: path.map(|path| dap::Source {
+            name: None,
+            path: Some(path.into()),
+            source_reference: None,
+            presentation_hint: None,
+            origin: None,
+            sources: None,
+            adapter_data: None,
+            checksums: None,
+        }),
+        line,
+        column: None,
+        end_line: None,
+        end_column: None,
+        presentation_hint: None,
+    }
+}
+
+#[gpui::test]
+async fn test_memory_and_disassembly_views(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+            "main.c": "int main() {\n    return 0;\n}\n",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let session = start_debug_session(&workspace, cx, |client| {
+        client.on_request::<Initialize, _>(move |_, _| {
+            Ok(dap::Capabilities {
+                supports_read_memory_request: Some(true),
+                supports_disassemble_request: Some(true),
+                ..Default::default()
+            })
+        });
+    })
+    .unwrap();
+
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<Scopes, _>(move |_, _| Ok(dap::ScopesResponse { scopes: vec![] }));
+
+    client.on_request::<Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "main".into(),
+                source: None,
+                line: 2,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: Some("0x1000".into()),
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    let called_disassemble = Arc::new(AtomicBool::new(false));
+    client.on_request::<Disassemble, _>({
+        let called_disassemble = called_disassemble.clone();
+        move |_, args| {
+            called_disassemble.store(true, Ordering::SeqCst);
+            assert_eq!("0x1000", args.memory_reference);
+            assert!(args.instruction_offset.is_some_and(|offset| offset < 0));
+
+            let main_c = path!("/project/main.c");
+            Ok(dap::DisassembleResponse {
+                instructions: vec![
+                    instruction("0x0ffc", Some(1), Some(main_c)),
+                    instruction("0x1000", Some(2), None),
+                    instruction("0x1004", Some(2), None),
+                    instruction("0x1008", None, None),
+                ],
+            })
+        }
+    });
+
+    client.on_request::<ReadMemory, _>(move |_, args| {
+        assert_eq!("0x2000", args.memory_reference);
+        assert_eq!(Some(0), args.offset);
+
+        Ok(dap::ReadMemoryResponse {
+            address: "0x2000".into(),
+            unreadable_bytes: Some(4),
+            data: Some("AQIDBA==".into()),
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            item.running_state().clone()
+        });
+
+    cx.run_until_parked();
+
+    assert!(
+        !called_disassemble.load(Ordering::SeqCst),
+        "Disassemble should only be requested once the disassembly view is shown"
+    );
+
+    running_state.update_in(cx, |this, window, cx| {
+        this.ensure_pane_item(DebuggerPaneItem::Disassembly, window, cx);
+        this.activate_item(DebuggerPaneItem::Disassembly, window, cx);
+        cx.refresh_windows();
+    });
+
+    cx.run_until_parked();
+
+    assert!(called_disassemble.load(Ordering::SeqCst));
+
+    running_state.update(cx, |state, cx| {
+        let entries = state
+            .disassembly_view()
+            .read(cx)
+            .entries()
+            .iter()
+            .map(|entry| match entry {
+                DisassemblyEntry::Source { path, line } => {
+                    format!("{}:{line}", path.file_name().unwrap().to_string_lossy())
+                }
+                DisassemblyEntry::Instruction { address, .. } => {
+                    format!("{:#x}", address.unwrap())
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            entries,
+            vec![
+                "main.c:1", "0xffc", "main.c:2", "0x1000", "0x1004", "0x1008"
+            ]
+        );
+    });
+
+    running_state.update_in(cx, |state, window, cx| {
+        state.memory_view().update(cx, |view, cx| {
+            view.open("0x2000".into(), window, cx);
+        });
+    });
+
+    cx.run_until_parked();
+
+    running_state.update(cx, |state, cx| {
+        assert_eq!(
+            state.memory_view().read(cx).block(),
+            Some(&MemoryBlock {
+                address: 0x2000,
+                data: vec![1, 2, 3, 4],
+                unreadable_bytes: 4,
+            })
+        );
+    });
+}
+
+#[test]
+fn test_parse_hex_bytes() {
+    assert_eq!(
+        parse_hex_bytes("de ad be ef"),
+        Some(vec![0xde, 0xad, 0xbe, 0xef])
+    );
+    assert_eq!(
+        parse_hex_bytes("0xdead BEEF"),
+        Some(vec![0xde, 0xad, 0xbe, 0xef])
+    );
+    assert_eq!(parse_hex_bytes("abc"), None);
+    assert_eq!(parse_hex_bytes("zz"), None);
+    assert_eq!(parse_hex_bytes(""), None);
+}
@@ -1,5 +1,5 @@
 use std::sync::{
-    Arc,
+    Arc, Mutex,
     atomic::{AtomicBool, Ordering},
 };
 
@@ -7,18 +7,24 @@ use crate::{
     DebugPanel,
     persistence::DebuggerPaneItem,
     session::running::variable_list::{
-        AddWatch, CollapseSelectedEntry, ExpandSelectedEntry, RemoveWatch,
+        AddDataBreakpoint, AddWatch, CollapseSelectedEntry, ExpandSelectedEntry, RemoveWatch,
     },
     tests::{active_debug_session_panel, init_test, init_test_workspace, start_debug_session},
 };
 use collections::HashMap;
 use dap::{
     Scope, StackFrame, Variable,
-    requests::{Evaluate, Initialize, Launch, Scopes, StackTrace, Variables},
+    requests::{
+        DataBreakpointInfo, Evaluate, Initialize, Launch, Scopes, SetDataBreakpoints, StackTrace,
+        Variables,
+    },
 };
 use gpui::{BackgroundExecutor, TestAppContext, VisualTestContext};
 use menu::{SelectFirst, SelectNext, SelectPrevious};
-use project::{FakeFs, Project};
+use project::{
+    FakeFs, Project,
+    debugger::breakpoint_store::{BreakpointEditAction, DataBreakpointAccessType},
+};
 use serde_json::json;
 use ui::SharedString;
 use unindent::Unindent as _;
@@ -2343,3 +2349,252 @@ async fn test_refresh_watchers(executor: BackgroundExecutor, cx: &mut TestAppCon
         assert_eq!(3, watcher.variables_reference);
     });
 }
+
+#[gpui::test]
+async fn test_add_data_breakpoint(executor: BackgroundExecutor, cx: &mut TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(executor.clone());
+
+    let test_file_content = r#"
+        let variable1 = "Value 1";
+    "#
+    .unindent();
+
+    fs.insert_tree(
+        path!("/project"),
+        json!({
+           "src": {
+               "test.js": test_file_content,
+           }
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/project").as_ref()], cx).await;
+    let workspace = init_test_workspace(&project, cx).await;
+    workspace
+        .update(cx, |workspace, window, cx| {
+            workspace.focus_panel::<DebugPanel>(window, cx);
+        })
+        .unwrap();
+    let cx = &mut VisualTestContext::from_window(*workspace, cx);
+
+    let sent_data_breakpoints = Arc::new(Mutex::new(Vec::new()));
+    let session = start_debug_session(&workspace, cx, {
+        let sent_data_breakpoints = sent_data_breakpoints.clone();
+        move |client| {
+            client.on_request::<Initialize, _>(move |_, _| {
+                Ok(dap::Capabilities {
+                    supports_data_breakpoints: Some(true),
+                    ..Default::default()
+                })
+            });
+
+            let sent_data_breakpoints = sent_data_breakpoints.clone();
+            client.on_request::<SetDataBreakpoints, _>(move |_, args| {
+                sent_data_breakpoints
+                    .lock()
+                    .unwrap()
+                    .push(args.breakpoints.clone());
+                Ok(dap::SetDataBreakpointsResponse {
+                    breakpoints: Vec::default(),
+                })
+            });
+        }
+    })
+    .unwrap();
+    let client = session.update(cx, |session, _| session.adapter_client().unwrap());
+
+    client.on_request::<dap::requests::Threads, _>(move |_, _| {
+        Ok(dap::ThreadsResponse {
+            threads: vec![dap::Thread {
+                id: 1,
+                name: "Thread 1".into(),
+            }],
+        })
+    });
+
+    client.on_request::<StackTrace, _>(move |_, _| {
+        Ok(dap::StackTraceResponse {
+            stack_frames: vec![StackFrame {
+                id: 1,
+                name: "Stack Frame 1".into(),
+                source: Some(dap::Source {
+                    name: Some("test.js".into()),
+                    path: Some(path!("/project/src/test.js").into()),
+                    source_reference: None,
+                    presentation_hint: None,
+                    origin: None,
+                    sources: None,
+                    adapter_data: None,
+                    checksums: None,
+                }),
+                line: 1,
+                column: 1,
+                end_line: None,
+                end_column: None,
+                can_restart: None,
+                instruction_pointer_reference: None,
+                module_id: None,
+                presentation_hint: None,
+            }],
+            total_frames: None,
+        })
+    });
+
+    client.on_request::<Scopes, _>(move |_, _| {
+        Ok(dap::ScopesResponse {
+            scopes: vec![Scope {
+                name: "Scope 1".into(),
+                presentation_hint: None,
+                variables_reference: 2,
+                named_variables: None,
+                indexed_variables: None,
+                expensive: false,
+                source: None,
+                line: None,
+                column: None,
+                end_line: None,
+                end_column: None,
+            }],
+        })
+    });
+
+    client.on_request::<Variables, _>(move |_, _| {
+        Ok(dap::VariablesResponse {
+            variables: vec![Variable {
+                name: "variable1".into(),
+                value: "value 1".into(),
+                type_: None,
+                presentation_hint: None,
+                evaluate_name: None,
+                variables_reference: 0,
+                named_variables: None,
+                indexed_variables: None,
+                memory_reference: None,
+                declaration_location_reference: None,
+                value_location_reference: None,
+            }],
+        })
+    });
+
+    client.on_request::<DataBreakpointInfo, _>(move |_, args| {
+        assert_eq!("variable1", args.name);
+        assert_eq!(Some(2), args.variables_reference);
+
+        Ok(dap::DataBreakpointInfoResponse {
+            data_id: Some("variable1-id".into()),
+            description: "variable1".into(),
+            access_types: Some(vec![
+                dap::DataBreakpointAccessType::Read,
+                dap::DataBreakpointAccessType::Write,
+            ]),
+            can_persist: Some(true),
+        })
+    });
+
+    client
+        .fake_event(dap::messages::Events::Stopped(dap::StoppedEvent {
+            reason: dap::StoppedEventReason::Pause,
+            description: None,
+            thread_id: Some(1),
+            preserve_focus_hint: None,
+            text: None,
+            all_threads_stopped: None,
+            hit_breakpoint_ids: None,
+        }))
+        .await;
+
+    cx.run_until_parked();
+
+    // The data breakpoints are sent as part of the configuration sequence.
+    assert_eq!(
+        std::mem::take(&mut *sent_data_breakpoints.lock().unwrap()),
+        vec![Vec::<dap::DataBreakpoint>::new()]
+    );
+
+    let running_state =
+        active_debug_session_panel(workspace, cx).update_in(cx, |item, window, cx| {
+            cx.focus_self(window);
+            let running = item.running_state().clone();
+
+            let variable_list = running.update(cx, |state, cx| {
+                state.activate_item(DebuggerPaneItem::Variables, window, cx);
+                state.variable_list().clone()
+            });
+            variable_list.update(cx, |_, cx| cx.focus_self(window));
+            running
+        });
+    cx.run_until_parked();
+
+    running_state.update(cx, |running_state, cx| {
+        running_state.variable_list().update(cx, |_, cx| {
+            cx.dispatch_action(&SelectFirst);
+            cx.dispatch_action(&SelectNext);
+        });
+    });
+    cx.run_until_parked();
+
+    running_state.update(cx, |running_state, cx| {
+        running_state.variable_list().update(cx, |_, cx| {
+            cx.dispatch_action(&AddDataBreakpoint);
+        });
+    });
+    cx.run_until_parked();
+
+    let breakpoint_store = project.read_with(cx, |project, _| project.breakpoint_store());
+    breakpoint_store.read_with(cx, |breakpoint_store, _| {
+        let data_breakpoints = breakpoint_store.data_breakpoints();
+        assert_eq!(data_breakpoints.len(), 1);
+        assert_eq!(data_breakpoints[0].data_id.as_ref(), "variable1-id");
+        assert_eq!(data_breakpoints[0].description.as_ref(), "variable1");
+        assert_eq!(
+            data_breakpoints[0].access_type,
+            Some(DataBreakpointAccessType::Write)
+        );
+        assert!(data_breakpoints[0].can_persist);
+    });
+
+    let watchpoint = dap::DataBreakpoint {
+        data_id: "variable1-id".into(),
+        access_type: Some(dap::DataBreakpointAccessType::Write),
+        condition: None,
+        hit_condition: None,
+    };
+    assert_eq!(
+        std::mem::take(&mut *sent_data_breakpoints.lock().unwrap()),
+        vec![vec![watchpoint.clone()]]
+    );
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.edit_data_breakpoint(
+            "variable1-id",
+            BreakpointEditAction::EditHitCondition("3".into()),
+            cx,
+        );
+        breakpoint_store.edit_data_breakpoint(
+            "variable1-id",
+            BreakpointEditAction::InvertState,
+            cx,
+        );
+    });
+    cx.run_until_parked();
+
+    // Disabled data breakpoints are not sent to the adapter.
+    assert_eq!(
+        std::mem::take(&mut *sent_data_breakpoints.lock().unwrap()),
+        vec![
+            vec![dap::DataBreakpoint {
+                hit_condition: Some("3".into()),
+                ..watchpoint
+            }],
+            vec![],
+        ]
+    );
+
+    breakpoint_store.update(cx, |breakpoint_store, cx| {
+        breakpoint_store.remove_data_breakpoint("variable1-id", cx);
+        assert!(breakpoint_store.data_breakpoints().is_empty());
+    });
+}
@@ -324,7 +324,9 @@ actions!(
         GoToPreviousHunk,
         GoToImplementation,
         GoToImplementationSplit,
+        GoToNewerUndoState,
         GoToNextChange,
+        GoToOlderUndoState,
         GoToParentModule,
         GoToPreviousChange,
         GoToPreviousDiagnostic,
@@ -455,6 +457,7 @@ actions!(
         ToggleSelectedDiffHunks,
         ToggleSelectionMenu,
         ToggleSoftWrap,
+        ToggleStickyScroll,
         ToggleTabBar,
         Transpose,
         Undo,
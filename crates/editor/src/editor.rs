@@ -34,6 +34,7 @@ mod lsp_ext;
 mod mouse_context_menu;
 pub mod movement;
 mod persistence;
+mod persistent_undo;
 mod proposed_changes_editor;
 mod rust_analyzer_ext;
 pub mod scroll;
@@ -47,6 +48,8 @@ mod editor_tests;
 #[cfg(test)]
 mod inline_completion_tests;
 mod signature_help;
+mod snippet_variables;
+mod sticky_scroll;
 #[cfg(any(test, feature = "test-support"))]
 pub mod test;
 
@@ -163,9 +166,9 @@ use multi_buffer::{
 };
 use parking_lot::Mutex;
 use project::{
-    CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, InlayHint,
-    Location, LocationLink, PrepareRenameResponse, Project, ProjectItem, ProjectTransaction,
-    TaskSourceKind,
+    CodeAction, Completion, CompletionIntent, CompletionSource, DocumentHighlight, DocumentLink,
+    InlayHint, Location, LocationLink, PrepareRenameResponse, Project, ProjectItem,
+    ProjectTransaction, TaskSourceKind,
     debugger::breakpoint_store::Breakpoint,
     lsp_store::{CompletionDocumentation, FormatTrigger, LspFormatTarget, OpenLspBufferHandle},
     project_settings::{GitGutterSetting, ProjectSettings},
@@ -180,6 +183,7 @@ use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsLocation, SettingsStore, update_settings_file};
 use smallvec::{SmallVec, smallvec};
 use snippet::Snippet;
+use snippet_variables::SnippetVariables;
 use std::sync::Arc;
 use std::{
     any::TypeId,
@@ -193,6 +197,7 @@ use std::{
     rc::Rc,
     time::{Duration, Instant},
 };
+use sticky_scroll::StickyScopesCache;
 pub use sum_tree::Bias;
 use sum_tree::TreeMap;
 use text::{BufferId, FromAnchor, OffsetUtf16, Rope};
@@ -982,6 +987,8 @@ pub struct Editor {
     select_next_state: Option<SelectNextState>,
     select_prev_state: Option<SelectNextState>,
     selection_history: SelectionHistory,
+    /// The version of the buffer whose undo history was last persisted.
+    persisted_undo_version: Option<clock::Global>,
     defer_selection_effects: bool,
     deferred_selection_effects_state: Option<DeferredSelectionEffectsState>,
     autoclose_regions: Vec<AutocloseRegion>,
@@ -1022,6 +1029,8 @@ pub struct Editor {
     show_breakpoints: Option<bool>,
     show_wrap_guides: Option<bool>,
     show_indent_guides: Option<bool>,
+    show_sticky_scroll: Option<bool>,
+    sticky_scopes_cache: StickyScopesCache,
     placeholder_text: Option<Arc<str>>,
     highlight_order: usize,
     highlighted_rows: HashMap<TypeId, Vec<RowHighlight>>,
@@ -1454,6 +1463,16 @@ struct SnippetState {
     ranges: Vec<Vec<Range<Anchor>>>,
     active_index: usize,
     choices: Vec<Option<Vec<String>>>,
+    transforms: Vec<SnippetTransform>,
+}
+
+/// A range of a snippet whose text is derived from the text of one of its
+/// tabstops, and is updated as that tabstop is edited.
+#[derive(Debug)]
+struct SnippetTransform {
+    source: Range<Anchor>,
+    target: Range<Anchor>,
+    transform: snippet::Transform,
 }
 
 #[doc(hidden)]
@@ -1974,6 +1993,7 @@ impl Editor {
             select_next_state: None,
             select_prev_state: None,
             selection_history: SelectionHistory::default(),
+            persisted_undo_version: None,
             defer_selection_effects: false,
             deferred_selection_effects_state: None,
             autoclose_regions: Vec::new(),
@@ -2010,6 +2030,8 @@ impl Editor {
             show_breakpoints: None,
             show_wrap_guides: None,
             show_indent_guides,
+            show_sticky_scroll: None,
+            sticky_scopes_cache: StickyScopesCache::default(),
             placeholder_text: None,
             highlight_order: 0,
             highlighted_rows: HashMap::default(),
@@ -2271,6 +2293,7 @@ impl Editor {
                 editor.create_minimap(EditorSettings::get_global(cx).minimap, window, cx);
             editor.colors = Some(LspColorData::new(cx));
             editor.update_lsp_data(None, None, window, cx);
+            editor.restore_persistent_undo_history(window, cx);
         }
 
         editor.report_editor_event("Editor Opened", None, cx);
@@ -5701,6 +5724,12 @@ impl Editor {
         cx.stop_propagation();
 
         let buffer_handle = completions_menu.buffer.clone();
+        let selected_text = self
+            .buffer
+            .read(cx)
+            .snapshot(cx)
+            .text_for_range(self.selections.newest::<usize>(cx).range())
+            .collect::<String>();
 
         let CompletionEdit {
             new_text,
@@ -5711,6 +5740,7 @@ impl Editor {
             intent,
             &buffer_handle,
             &completions_menu.initial_position.text_anchor,
+            &selected_text,
             cx,
         );
 
@@ -7909,6 +7939,24 @@ impl Editor {
 
             (color, icon)
         };
+        let is_conditional = breakpoint.condition.is_some() || breakpoint.hit_condition.is_some();
+        let details = [
+            breakpoint
+                .message
+                .as_ref()
+                .map(|message| format!("Log message: {message}")),
+            breakpoint
+                .condition
+                .as_ref()
+                .map(|condition| format!("Condition: {condition}")),
+            breakpoint
+                .hit_condition
+                .as_ref()
+                .map(|hit_condition| format!("Hit condition: {hit_condition}")),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
 
         let breakpoint = Arc::from(breakpoint.clone());
 
@@ -7934,12 +7982,20 @@ impl Editor {
         } else {
             SharedString::from("Right-click for more options.")
         };
+        let meta = if details.is_empty() || is_phantom {
+            meta
+        } else {
+            SharedString::from(format!("{}\n{meta}", details.join("\n")))
+        };
         IconButton::new(("breakpoint_indicator", row.0 as usize), icon)
             .icon_size(IconSize::XSmall)
             .size(ui::ButtonSize::None)
             .when(is_rejected, |this| {
                 this.indicator(Indicator::icon(Icon::new(IconName::Warning)).color(Color::Warning))
             })
+            .when(is_conditional && !is_rejected && !is_phantom, |this| {
+                this.indicator(Indicator::dot().color(Color::Info))
+            })
             .icon_color(color)
             .style(ButtonStyle::Transparent)
             .on_click(cx.listener({
@@ -9267,7 +9323,7 @@ impl Editor {
             choices: Option<Vec<String>>,
         }
 
-        let tabstops = self.buffer.update(cx, |buffer, cx| {
+        let (tabstops, transforms) = self.buffer.update(cx, |buffer, cx| {
             let snippet_text: Arc<str> = snippet.text.clone().into();
             let edits = insertion_ranges
                 .iter()
@@ -9313,7 +9369,42 @@ impl Editor {
                         choices: tabstop.choices.clone(),
                     }
                 })
-                .collect::<Vec<_>>()
+                .collect::<Vec<_>>();
+
+            let mut delta = 0_isize;
+            let insertion_starts = &insertion_ranges
+                .iter()
+                .map(|insertion_range| {
+                    let insertion_start = insertion_range.start as isize + delta;
+                    delta += snippet.text.len() as isize - insertion_range.len() as isize;
+                    insertion_start
+                })
+                .collect::<Vec<_>>();
+            let to_offsets = |insertion_start: isize, range: &Range<isize>| {
+                ((insertion_start + range.start) as usize).min(snapshot.len())
+                    ..((insertion_start + range.end) as usize).min(snapshot.len())
+            };
+            let transforms = snippet
+                .tabstops
+                .iter()
+                .filter_map(|tabstop| Some((tabstop.ranges.first()?, &tabstop.transforms)))
+                .flat_map(|(source, transforms)| {
+                    transforms.iter().flat_map(move |(target, transform)| {
+                        insertion_starts.iter().map(move |insertion_start| {
+                            let source = to_offsets(*insertion_start, source);
+                            let target = to_offsets(*insertion_start, target);
+                            SnippetTransform {
+                                source: snapshot.anchor_before(source.start)
+                                    ..snapshot.anchor_after(source.end),
+                                target: snippet_transform_target(snapshot, target),
+                                transform: transform.clone(),
+                            }
+                        })
+                    })
+                })
+                .collect::<Vec<_>>();
+
+            (tabstops, transforms)
         });
         if let Some(tabstop) = tabstops.first() {
             self.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
@@ -9345,6 +9436,7 @@ impl Editor {
                     active_index: 0,
                     ranges,
                     choices,
+                    transforms,
                 });
             }
 
@@ -9457,6 +9549,124 @@ impl Editor {
         false
     }
 
+    /// Updates the text of the active snippet's transforms, such as
+    /// `${1/(.*)/${1:/upcase}/}`, to match the current text of their tabstops.
+    fn refresh_snippet_transforms(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(snippet) = self.snippet_stack.last() else {
+            return;
+        };
+        if snippet.transforms.is_empty() {
+            return;
+        }
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let to_offsets = |range: &Range<Anchor>| {
+            let start = range.start.to_offset(&snapshot);
+            start..range.end.to_offset(&snapshot).max(start)
+        };
+        let mut edits = snippet
+            .transforms
+            .iter()
+            .enumerate()
+            .filter_map(|(ix, transform)| {
+                let source_text = snapshot
+                    .text_for_range(to_offsets(&transform.source))
+                    .collect::<String>();
+                let new_text = transform.transform.apply(&source_text);
+                let target = to_offsets(&transform.target);
+                let old_text = snapshot.text_for_range(target.clone()).collect::<String>();
+                (old_text != new_text).then_some((ix, target, new_text))
+            })
+            .collect::<Vec<_>>();
+        if edits.is_empty() {
+            return;
+        }
+        edits.sort_by_key(|(_, target, _)| target.start);
+
+        // Anchors at the boundaries of the replaced text may end up on either side
+        // of it, so compute where every range ends up and anchor it again.
+        let map_offset = |offset: usize, bias: Bias| {
+            let mut delta = 0_isize;
+            for (_, range, new_text) in &edits {
+                if offset < range.start
+                    || (offset == range.start && (bias == Bias::Left || !range.is_empty()))
+                {
+                    break;
+                } else if offset >= range.end {
+                    delta += new_text.len() as isize - range.len() as isize;
+                } else {
+                    return (range.start as isize + delta) as usize;
+                }
+            }
+            (offset as isize + delta) as usize
+        };
+        let map_range = |range: &Range<Anchor>| {
+            let range = to_offsets(range);
+            map_offset(range.start, Bias::Right)..map_offset(range.end, Bias::Left)
+        };
+        let tabstop_ranges = snippet
+            .ranges
+            .iter()
+            .map(|ranges| ranges.iter().map(map_range).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        let transform_ranges = snippet
+            .transforms
+            .iter()
+            .enumerate()
+            .map(|(ix, transform)| {
+                let target = to_offsets(&transform.target);
+                let target_start = map_offset(target.start, Bias::Left);
+                let target_len = edits
+                    .iter()
+                    .find(|(edit_ix, _, _)| *edit_ix == ix)
+                    .map_or(target.len(), |(_, _, new_text)| new_text.len());
+                (
+                    map_range(&transform.source),
+                    target_start..target_start + target_len,
+                )
+            })
+            .collect::<Vec<_>>();
+        let selections = self
+            .selections
+            .all::<usize>(cx)
+            .into_iter()
+            .map(|mut selection| {
+                selection.start = map_offset(selection.start, Bias::Left);
+                selection.end = map_offset(selection.end, Bias::Left);
+                selection
+            })
+            .collect::<Vec<_>>();
+
+        self.buffer.update(cx, |buffer, cx| {
+            let edits = edits
+                .into_iter()
+                .map(|(_, target, new_text)| (target, new_text));
+            buffer.edit(edits, None, cx);
+        });
+        self.change_selections(None, window, cx, |s| s.select(selections));
+
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let Some(snippet) = self.snippet_stack.last_mut() else {
+            return;
+        };
+        snippet.ranges = tabstop_ranges
+            .into_iter()
+            .map(|ranges| {
+                ranges
+                    .into_iter()
+                    .map(|range| {
+                        snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end)
+                    })
+                    .collect()
+            })
+            .collect();
+        for (transform, (source, target)) in snippet.transforms.iter_mut().zip(transform_ranges) {
+            transform.source =
+                snapshot.anchor_before(source.start)..snapshot.anchor_after(source.end);
+            transform.target = snippet_transform_target(&snapshot, target);
+        }
+    }
+
     pub fn clear(&mut self, window: &mut Window, cx: &mut Context<Self>) {
         self.transact(window, cx, |this, window, cx| {
             this.select_all(&SelectAll, window, cx);
@@ -11971,6 +12181,89 @@ impl Editor {
         }
     }
 
+    pub fn go_to_older_undo_state(
+        &mut self,
+        _: &GoToOlderUndoState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.travel_undo_history(-1, window, cx);
+    }
+
+    pub fn go_to_newer_undo_state(
+        &mut self,
+        _: &GoToNewerUndoState,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.travel_undo_history(1, window, cx);
+    }
+
+    /// Moves through the states of the buffer's undo tree in the order they
+    /// were created, including the branches abandoned by editing after undoing.
+    pub fn travel_undo_history(
+        &mut self,
+        delta: isize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.travel_undo_tree(window, cx, |buffer, cx| {
+            buffer.travel_undo_history(delta, cx)
+        });
+    }
+
+    /// Brings the buffer to the state right after the given transaction of its
+    /// undo tree was applied, or to its initial state if `target` is `None`.
+    pub fn travel_to_undo_state(
+        &mut self,
+        target: Option<TransactionId>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.travel_undo_tree(window, cx, |buffer, cx| {
+            buffer.travel_to_undo_state(target, cx)
+        });
+    }
+
+    fn travel_undo_tree(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+        travel: impl FnOnce(&mut Buffer, &mut Context<Buffer>) -> bool,
+    ) {
+        if self.read_only(cx) {
+            return;
+        }
+        let Some(buffer) = self.buffer.read(cx).as_singleton() else {
+            return;
+        };
+
+        self.hide_mouse_cursor(HideMouseCursorOrigin::TypingAction, cx);
+
+        let old_version = buffer.read(cx).version();
+        if !buffer.update(cx, travel) {
+            return;
+        }
+        let buffer = buffer.read(cx);
+        let first_change = buffer.edits_since::<usize>(&old_version).next();
+        let transaction_id = buffer
+            .peek_undo_stack()
+            .or_else(|| buffer.peek_redo_stack())
+            .map(|entry| entry.transaction_id());
+
+        // Like undoing, put the cursor at the start of the first change.
+        if let Some(change) = first_change {
+            self.change_selections(Some(Autoscroll::fit()), window, cx, |s| {
+                s.select_ranges([change.new.start..change.new.start])
+            });
+        }
+        self.unmark_text(window, cx);
+        self.refresh_inline_completion(true, false, window, cx);
+        if let Some(transaction_id) = transaction_id {
+            cx.emit(EditorEvent::Edited { transaction_id });
+        }
+    }
+
     pub fn finalize_last_transaction(&mut self, cx: &mut Context<Self>) {
         self.buffer
             .update(cx, |buffer, cx| buffer.finalize_last_transaction(cx));
@@ -16585,6 +16878,7 @@ impl Editor {
         self.with_selection_effects_deferred(window, cx, |this, window, cx| {
             this.start_transaction_at(Instant::now(), window, cx);
             update(this, window, cx);
+            this.refresh_snippet_transforms(window, cx);
             this.end_transaction_at(Instant::now(), cx)
         })
     }
@@ -18044,6 +18338,11 @@ impl Editor {
         cx.notify();
     }
 
+    pub fn set_show_sticky_scroll(&mut self, show_sticky_scroll: bool, cx: &mut Context<Self>) {
+        self.show_sticky_scroll = Some(show_sticky_scroll);
+        cx.notify();
+    }
+
     pub fn working_directory(&self, cx: &App) -> Option<PathBuf> {
         if let Some(buffer) = self.buffer().read(cx).as_singleton() {
             if let Some(file) = buffer.read(cx).file().and_then(|f| f.as_local()) {
@@ -19549,7 +19848,10 @@ impl Editor {
                 cx.notify();
             }
             multi_buffer::Event::DirtyChanged => cx.emit(EditorEvent::DirtyChanged),
-            multi_buffer::Event::Saved => cx.emit(EditorEvent::Saved),
+            multi_buffer::Event::Saved => {
+                self.persist_undo_history(cx);
+                cx.emit(EditorEvent::Saved);
+            }
             multi_buffer::Event::FileHandleChanged
             | multi_buffer::Event::Reloaded
             | multi_buffer::Event::BufferDiffChanged => cx.emit(EditorEvent::TitleChanged),
@@ -20588,11 +20890,23 @@ fn vim_enabled(cx: &App) -> bool {
         == Some(&serde_json::Value::Bool(true))
 }
 
+/// Anchors the range of a snippet transform, so that text inserted at either of
+/// its boundaries, such as at the end of an adjacent tabstop, stays outside of it.
+fn snippet_transform_target(snapshot: &MultiBufferSnapshot, range: Range<usize>) -> Range<Anchor> {
+    let start = snapshot.anchor_after(range.start);
+    if range.is_empty() {
+        start..start
+    } else {
+        start..snapshot.anchor_before(range.end)
+    }
+}
+
 fn process_completion_for_edit(
     completion: &Completion,
     intent: CompletionIntent,
     buffer: &Entity<Buffer>,
     cursor_position: &text::Anchor,
+    selected_text: &str,
     cx: &mut Context<Editor>,
 ) -> CompletionEdit {
     let buffer = buffer.read(cx);
@@ -20616,7 +20930,14 @@ fn process_completion_for_edit(
                 }
             }
         }
-        match Snippet::parse(&snippet_source).log_err() {
+        let variables = SnippetVariables {
+            buffer: &buffer_snapshot,
+            position: text::ToOffset::to_offset(cursor_position, &buffer_snapshot),
+            selected_text,
+        };
+        match Snippet::parse_with_variables(&snippet_source, |name| variables.resolve(name, cx))
+            .log_err()
+        {
             Some(parsed_snippet) => (Some(parsed_snippet.clone()), parsed_snippet.text),
             None => (None, completion.new_text.clone()),
         }
@@ -21251,6 +21572,20 @@ pub trait SemanticsProvider {
         cx: &mut App,
     ) -> Option<Task<Result<Vec<LocationLink>>>>;
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<DocumentLink>>>>;
+
+    fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>>;
+
     fn range_for_rename(
         &self,
         buffer: &Entity<Buffer>,
@@ -21687,6 +22022,26 @@ impl SemanticsProvider for Entity<Project> {
         }))
     }
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<Result<Vec<DocumentLink>>>> {
+        Some(self.update(cx, |project, cx| project.document_links(buffer, cx)))
+    }
+
+    fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: &mut App,
+    ) -> Option<Task<Result<DocumentLink>>> {
+        Some(self.update(cx, |project, cx| {
+            project.resolve_document_link(link, buffer, server_id, cx)
+        }))
+    }
+
     fn supports_inlay_hints(&self, buffer: &Entity<Buffer>, cx: &mut App) -> bool {
         // TODO: make this work for remote projects
         self.update(cx, |project, cx| {
@@ -23,6 +23,7 @@ pub struct EditorSettings {
     pub toolbar: Toolbar,
     pub scrollbar: Scrollbar,
     pub minimap: Minimap,
+    pub sticky_scroll: StickyScroll,
     pub gutter: Gutter,
     pub scroll_beyond_last_line: ScrollBeyondLastLine,
     pub vertical_scroll_margin: f32,
@@ -54,6 +55,7 @@ pub struct EditorSettings {
     pub inline_code_actions: bool,
     pub drag_and_drop_selection: bool,
     pub lsp_document_colors: DocumentColorsRenderMode,
+    pub persistent_undo: PersistentUndo,
 }
 
 /// How to render LSP `textDocument/documentColor` colors in the editor.
@@ -174,6 +176,19 @@ impl Minimap {
     }
 }
 
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct StickyScroll {
+    pub enabled: bool,
+    pub max_lines: usize,
+}
+
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct PersistentUndo {
+    pub enabled: bool,
+    pub max_age_days: u64,
+    pub max_size_kb: u64,
+}
+
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
 pub struct Gutter {
     pub min_line_number_digits: usize,
@@ -425,6 +440,8 @@ pub struct EditorSettingsContent {
     pub scrollbar: Option<ScrollbarContent>,
     /// Minimap related settings
     pub minimap: Option<MinimapContent>,
+    /// Sticky scroll related settings
+    pub sticky_scroll: Option<StickyScrollContent>,
     /// Gutter related settings
     pub gutter: Option<GutterContent>,
     /// Whether the editor will scroll beyond the last line.
@@ -546,6 +563,9 @@ pub struct EditorSettingsContent {
     ///
     /// Default: [`DocumentColorsRenderMode::Inlay`]
     pub lsp_document_colors: Option<DocumentColorsRenderMode>,
+
+    /// Persistent undo history related settings
+    pub persistent_undo: Option<PersistentUndoContent>,
 }
 
 // Toolbar related settings
@@ -643,6 +663,38 @@ pub struct MinimapContent {
     pub max_width_columns: Option<num::NonZeroU32>,
 }
 
+/// Sticky scroll related settings
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct StickyScrollContent {
+    /// Whether to pin the headers of the scopes enclosing the top of the viewport.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Maximum number of headers to pin at the top of the editor.
+    ///
+    /// Default: 5
+    pub max_lines: Option<usize>,
+}
+
+/// Persistent undo history related settings
+#[derive(Copy, Clone, Debug, Default, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct PersistentUndoContent {
+    /// Whether to keep the undo history of files across restarts. The history
+    /// is only restored if the file hasn't changed on disk since it was saved.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// Number of days after which the undo history of a file that hasn't been
+    /// saved again is discarded.
+    ///
+    /// Default: 30
+    pub max_age_days: Option<u64>,
+    /// Maximum size in kilobytes of the undo history stored for all files.
+    ///
+    /// Default: 10240
+    pub max_size_kb: Option<u64>,
+}
+
 /// Forcefully enable or disable the scrollbar for each axis
 #[derive(Copy, Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq, Default)]
 pub struct ScrollbarAxesContent {
@@ -891,5 +943,15 @@ impl Settings for EditorSettings {
         if minimap != MinimapContent::default() {
             current.minimap = Some(minimap)
         }
+
+        let mut sticky_scroll = StickyScrollContent::default();
+        vscode.bool_setting("editor.stickyScroll.enabled", &mut sticky_scroll.enabled);
+        vscode.usize_setting(
+            "editor.stickyScroll.maxLineCount",
+            &mut sticky_scroll.max_lines,
+        );
+        if sticky_scroll != StickyScrollContent::default() {
+            current.sticky_scroll = Some(sticky_scroll)
+        }
     }
 }
@@ -261,6 +261,42 @@ fn test_undo_redo_with_selection_restoration(cx: &mut TestAppContext) {
     });
 }
 
+#[gpui::test]
+fn test_undo_tree_navigation(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let buffer = cx.update(|cx| MultiBuffer::build_simple("", cx));
+    let editor = cx.add_window(|window, cx| build_editor(buffer.clone(), window, cx));
+
+    _ = editor.update(cx, |editor, window, cx| {
+        editor.insert("a", window, cx);
+        editor.insert("b", window, cx);
+        editor.undo(&Undo, window, cx);
+        editor.insert("c", window, cx);
+        assert_eq!(editor.text(cx), "ac");
+
+        // Going back in time visits the branch abandoned by typing "c".
+        editor.go_to_older_undo_state(&GoToOlderUndoState, window, cx);
+        assert_eq!(editor.text(cx), "ab");
+        assert_eq!(editor.selections.ranges(cx), vec![1..1]);
+        editor.go_to_older_undo_state(&GoToOlderUndoState, window, cx);
+        assert_eq!(editor.text(cx), "a");
+
+        editor.go_to_newer_undo_state(&GoToNewerUndoState, window, cx);
+        assert_eq!(editor.text(cx), "ab");
+        editor.go_to_newer_undo_state(&GoToNewerUndoState, window, cx);
+        assert_eq!(editor.text(cx), "ac");
+        editor.go_to_newer_undo_state(&GoToNewerUndoState, window, cx);
+        assert_eq!(editor.text(cx), "ac");
+
+        editor.travel_to_undo_state(None, window, cx);
+        assert_eq!(editor.text(cx), "");
+        editor.redo(&Redo, window, cx);
+        editor.redo(&Redo, window, cx);
+        assert_eq!(editor.text(cx), "ac");
+    });
+}
+
 #[gpui::test]
 fn test_ime_composition(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -9165,6 +9201,64 @@ async fn test_snippets(cx: &mut TestAppContext) {
     "});
 }
 
+#[gpui::test]
+async fn test_snippet_transforms(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let mut cx = EditorTestContext::new(cx).await;
+
+    cx.set_state(indoc! {"
+        a.ˇ b
+        a.ˇ b
+    "});
+
+    cx.update_editor(|editor, window, cx| {
+        let snippet =
+            Snippet::parse("${1:foo_bar}: ${1/(.*)/${1:/upcase}/} = $2${2/(.*)/$1$1/}$0").unwrap();
+        let insertion_ranges = editor
+            .selections
+            .all(cx)
+            .iter()
+            .map(|s| s.range().clone())
+            .collect::<Vec<_>>();
+        editor
+            .insert_snippet(&insertion_ranges, snippet, window, cx)
+            .unwrap();
+    });
+
+    cx.assert_editor_state(indoc! {"
+        a.«foo_barˇ»: FOO_BAR =  b
+        a.«foo_barˇ»: FOO_BAR =  b
+    "});
+
+    // Transforms are updated as the tabstop is edited.
+    cx.update_editor(|editor, window, cx| editor.handle_input("baz", window, cx));
+    cx.assert_editor_state(indoc! {"
+        a.bazˇ: BAZ =  b
+        a.bazˇ: BAZ =  b
+    "});
+
+    cx.update_editor(|editor, window, cx| editor.backspace(&Backspace, window, cx));
+    cx.assert_editor_state(indoc! {"
+        a.baˇ: BA =  b
+        a.baˇ: BA =  b
+    "});
+
+    // Text typed right before a transform stays outside of it.
+    cx.update_editor(|editor, window, cx| assert!(editor.move_to_next_snippet_tabstop(window, cx)));
+    cx.update_editor(|editor, window, cx| editor.handle_input("x", window, cx));
+    cx.assert_editor_state(indoc! {"
+        a.ba: BA = xˇxx b
+        a.ba: BA = xˇxx b
+    "});
+
+    cx.update_editor(|editor, window, cx| editor.handle_input("y", window, cx));
+    cx.assert_editor_state(indoc! {"
+        a.ba: BA = xyˇxyxy b
+        a.ba: BA = xyˇxyxy b
+    "});
+}
+
 #[gpui::test]
 async fn test_snippet_indentation(cx: &mut TestAppContext) {
     init_test(cx, |_| {});
@@ -17720,6 +17814,71 @@ async fn test_stage_and_unstage_added_file_hunk(
     cx.assert_index_text(None);
 }
 
+#[gpui::test]
+async fn test_sticky_scroll_headers(cx: &mut TestAppContext) {
+    init_test(cx, |_| {});
+
+    let language = Arc::new(
+        Language::new(
+            LanguageConfig::default(),
+            Some(tree_sitter_rust::LANGUAGE.into()),
+        )
+        .with_outline_query(indoc! {r#"
+            (impl_item "impl" @context type: (_) @name) @item
+            (function_item "fn" @context name: (_) @name) @item
+        "#})
+        .unwrap(),
+    );
+
+    let mut cx = EditorTestContext::new(cx).await;
+    cx.update_buffer(|buffer, cx| buffer.set_language(Some(language), cx));
+    cx.set_state(indoc! {"
+        ˇimpl Foo {
+            fn one() {
+                let a = 1;
+                let b = 2;
+                let c = 3;
+            }
+
+            fn two() {
+                let d = 4;
+            }
+        }
+    "});
+    cx.run_until_parked();
+
+    let mut sticky_rows = |scroll_top: u32, max_lines: usize| {
+        cx.update_editor(|editor, window, cx| {
+            editor
+                .snapshot(window, cx)
+                .sticky_headers(
+                    DisplayRow(scroll_top),
+                    max_lines,
+                    &mut editor.sticky_scopes_cache,
+                )
+                .into_iter()
+                .map(|header| header.row.0)
+                .collect::<Vec<_>>()
+        })
+    };
+
+    assert_eq!(sticky_rows(0, 5), Vec::<u32>::new());
+    assert_eq!(sticky_rows(1, 5), vec![0, 1]);
+    assert_eq!(sticky_rows(3, 5), vec![0, 1]);
+    // The function ends right below the pinned `impl` header.
+    assert_eq!(sticky_rows(4, 5), vec![0]);
+    assert_eq!(sticky_rows(7, 5), vec![0, 7]);
+    // Only the innermost scopes are kept when the limit is reached.
+    assert_eq!(sticky_rows(2, 1), vec![1]);
+    assert_eq!(sticky_rows(2, 0), Vec::<u32>::new());
+
+    cx.update_editor(|editor, window, cx| {
+        assert!(!editor.sticky_scroll_enabled(cx));
+        editor.toggle_sticky_scroll(&ToggleStickyScroll, window, cx);
+        assert!(editor.sticky_scroll_enabled(cx));
+    });
+}
+
 async fn setup_indent_guides_editor(
     text: &str,
     cx: &mut TestAppContext,
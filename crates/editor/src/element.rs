@@ -29,6 +29,7 @@ use crate::{
     items::BufferSearchHighlights,
     mouse_context_menu::{self, MenuPosition},
     scroll::{ActiveScrollbarState, ScrollbarThumbState, scroll_amount::ScrollAmount},
+    sticky_scroll::StickyHeader,
 };
 use buffer_diff::{DiffHunkStatus, DiffHunkStatusKind};
 use collections::{BTreeMap, HashMap};
@@ -264,6 +265,8 @@ impl EditorElement {
         register_action(editor, window, Editor::paste);
         register_action(editor, window, Editor::undo);
         register_action(editor, window, Editor::redo);
+        register_action(editor, window, Editor::go_to_older_undo_state);
+        register_action(editor, window, Editor::go_to_newer_undo_state);
         register_action(editor, window, Editor::move_page_up);
         register_action(editor, window, Editor::move_page_down);
         register_action(editor, window, Editor::next_screen);
@@ -433,6 +436,7 @@ impl EditorElement {
         register_action(editor, window, Editor::toggle_line_numbers);
         register_action(editor, window, Editor::toggle_relative_line_numbers);
         register_action(editor, window, Editor::toggle_indent_guides);
+        register_action(editor, window, Editor::toggle_sticky_scroll);
         register_action(editor, window, Editor::toggle_inlay_hints);
         register_action(editor, window, Editor::toggle_edit_predictions);
         if editor.read(cx).diagnostics_enabled() {
@@ -3996,6 +4000,81 @@ impl EditorElement {
         header
     }
 
+    fn layout_sticky_scroll(
+        &self,
+        snapshot: &EditorSnapshot,
+        start_row: DisplayRow,
+        line_height: Pixels,
+        right_margin: Pixels,
+        editor_width: Pixels,
+        hitbox: &Hitbox,
+        gutter_hitbox: &Hitbox,
+        gutter_dimensions: GutterDimensions,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Option<StickyScrollLayout> {
+        if !self.editor.read(cx).sticky_scroll_enabled(cx) {
+            return None;
+        }
+
+        let max_lines = EditorSettings::get_global(cx).sticky_scroll.max_lines;
+        let headers = self.editor.update(cx, |editor, _| {
+            snapshot.sticky_headers(start_row, max_lines, &mut editor.sticky_scopes_cache)
+        });
+        if headers.is_empty() {
+            return None;
+        }
+
+        let settings = EditorSettings::get_global(cx);
+
+        let show_line_numbers = snapshot
+            .show_line_numbers
+            .unwrap_or(settings.gutter.line_numbers)
+            && gutter_hitbox.size.width > Pixels::ZERO;
+        let lines = headers
+            .into_iter()
+            .map(|header| {
+                let line = layout_line(
+                    header.display_row,
+                    snapshot,
+                    &self.style,
+                    editor_width,
+                    |_| false,
+                    window,
+                    cx,
+                );
+                let line_number = show_line_numbers.then(|| {
+                    self.shape_line_number(
+                        SharedString::from((header.row.0 + 1).to_string()),
+                        cx.theme().colors().editor_line_number,
+                        window,
+                    )
+                });
+                StickyScrollLine {
+                    header,
+                    line,
+                    line_number,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let bounds = Bounds::new(
+            hitbox.origin,
+            size(
+                hitbox.size.width - right_margin,
+                line_height * lines.len() as f32,
+            ),
+        );
+        let hitbox = window.insert_hitbox(bounds, HitboxBehavior::BlockMouse);
+
+        Some(StickyScrollLayout {
+            lines,
+            hitbox,
+            gutter_width: gutter_hitbox.size.width,
+            gutter_right_padding: gutter_dimensions.right_padding,
+        })
+    }
+
     fn layout_cursor_popovers(
         &self,
         line_height: Pixels,
@@ -6416,6 +6495,84 @@ impl EditorElement {
         }
     }
 
+    fn paint_sticky_scroll(&self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
+        let Some(sticky_scroll) = layout.sticky_scroll.take() else {
+            return;
+        };
+
+        let line_height = layout.position_map.line_height;
+        let bounds = sticky_scroll.hitbox.bounds;
+        let text_bounds = Bounds::from_corners(
+            point(layout.content_origin.x, bounds.origin.y),
+            bounds.bottom_right(),
+        );
+
+        window.paint_layer(bounds, |window| {
+            window.paint_quad(fill(bounds, cx.theme().colors().editor_background));
+            for (ix, sticky_line) in sticky_scroll.lines.iter().enumerate() {
+                let line_y = bounds.origin.y + line_height * ix as f32;
+                if let Some(line_number) = &sticky_line.line_number {
+                    let origin = point(
+                        bounds.origin.x + sticky_scroll.gutter_width
+                            - line_number.width
+                            - sticky_scroll.gutter_right_padding,
+                        line_y,
+                    );
+                    line_number.paint(origin, line_height, window, cx).log_err();
+                }
+
+                window.with_content_mask(
+                    Some(ContentMask {
+                        bounds: text_bounds,
+                    }),
+                    |window| {
+                        let origin = point(
+                            layout.content_origin.x - layout.position_map.scroll_pixel_position.x,
+                            line_y,
+                        );
+                        sticky_line.line.draw_at(origin, line_height, window, cx);
+                    },
+                );
+            }
+            window.paint_quad(fill(
+                Bounds::new(
+                    point(bounds.origin.x, bounds.bottom() - px(1.)),
+                    size(bounds.size.width, px(1.)),
+                ),
+                cx.theme().colors().border_variant,
+            ));
+        });
+
+        window.set_cursor_style(CursorStyle::PointingHand, &sticky_scroll.hitbox);
+
+        window.on_mouse_event({
+            let editor = self.editor.clone();
+            let headers = sticky_scroll
+                .lines
+                .iter()
+                .map(|sticky_line| sticky_line.header)
+                .collect::<Vec<_>>();
+            let hitbox = sticky_scroll.hitbox.clone();
+
+            move |event: &MouseDownEvent, phase, window, cx| {
+                if phase == DispatchPhase::Capture
+                    || event.button != MouseButton::Left
+                    || !hitbox.is_hovered(window)
+                {
+                    return;
+                }
+
+                let ix = ((event.position.y - hitbox.origin.y) / line_height) as usize;
+                if let Some(header) = headers.get(ix) {
+                    editor.update(cx, |editor, cx| {
+                        editor.jump_to_sticky_header(*header, ix, window, cx);
+                    });
+                    cx.stop_propagation();
+                }
+            }
+        });
+    }
+
     fn paint_minimap(&self, layout: &mut EditorLayout, window: &mut Window, cx: &mut App) {
         if let Some(mut layout) = layout.minimap.take() {
             let minimap_hitbox = layout.thumb_layout.hitbox.clone();
@@ -7420,6 +7577,30 @@ impl LineWithInvisibles {
         );
     }
 
+    fn draw_at(
+        &self,
+        origin: gpui::Point<Pixels>,
+        line_height: Pixels,
+        window: &mut Window,
+        cx: &mut App,
+    ) {
+        let mut fragment_origin = origin;
+        for fragment in &self.fragments {
+            match fragment {
+                LineFragment::Text(line) => {
+                    line.paint_background(fragment_origin, line_height, window, cx)
+                        .log_err();
+                    line.paint(fragment_origin, line_height, window, cx)
+                        .log_err();
+                    fragment_origin.x += line.width;
+                }
+                LineFragment::Element { size, .. } => {
+                    fragment_origin.x += size.width;
+                }
+            }
+        }
+    }
+
     fn draw_background(
         &self,
         layout: &EditorLayout,
@@ -8854,6 +9035,19 @@ impl Element for EditorElement {
                         )
                     };
 
+                    let sticky_scroll = self.layout_sticky_scroll(
+                        &snapshot,
+                        start_row,
+                        line_height,
+                        right_margin,
+                        editor_width,
+                        &hitbox,
+                        &gutter_hitbox,
+                        gutter_dimensions,
+                        window,
+                        cx,
+                    );
+
                     let position_map = Rc::new(PositionMap {
                         size: bounds.size,
                         visible_row_range,
@@ -8915,6 +9109,7 @@ impl Element for EditorElement {
                         tab_invisible,
                         space_invisible,
                         sticky_buffer_header,
+                        sticky_scroll,
                         expand_toggles,
                     }
                 })
@@ -8983,6 +9178,7 @@ impl Element for EditorElement {
                         }
                     });
 
+                    self.paint_sticky_scroll(layout, window, cx);
                     self.paint_minimap(layout, window, cx);
                     self.paint_scrollbars(layout, window, cx);
                     self.paint_inline_completion_popover(layout, window, cx);
@@ -9100,6 +9296,7 @@ pub struct EditorLayout {
     tab_invisible: ShapedLine,
     space_invisible: ShapedLine,
     sticky_buffer_header: Option<AnyElement>,
+    sticky_scroll: Option<StickyScrollLayout>,
     document_colors: Option<(DocumentColorsRenderMode, Vec<(Range<DisplayPoint>, Hsla)>)>,
 }
 
@@ -9114,6 +9311,19 @@ struct LineNumberLayout {
     hitbox: Option<Hitbox>,
 }
 
+struct StickyScrollLayout {
+    lines: Vec<StickyScrollLine>,
+    hitbox: Hitbox,
+    gutter_width: Pixels,
+    gutter_right_padding: Pixels,
+}
+
+struct StickyScrollLine {
+    header: StickyHeader,
+    line: LineWithInvisibles,
+    line_number: Option<ShapedLine>,
+}
+
 struct ColoredRange<T> {
     start: T,
     end: T,
@@ -1,12 +1,13 @@
 use crate::{
     Anchor, Editor, EditorSettings, EditorSnapshot, FindAllReferences, GoToDefinition,
     GoToTypeDefinition, GotoDefinitionKind, InlayId, Navigated, PointForPosition, SelectPhase,
+    SemanticsProvider,
     editor_settings::GoToDefinitionFallback,
     hover_popover::{self, InlayHover},
     scroll::ScrollAmount,
 };
 use gpui::{App, AsyncWindowContext, Context, Entity, Modifiers, Task, Window, px};
-use language::{Bias, ToOffset};
+use language::{Bias, OffsetRangeExt as _, ToOffset};
 use linkify::{LinkFinder, LinkKind};
 use lsp::LanguageServerId;
 use project::{
@@ -14,7 +15,7 @@ use project::{
     ResolveState, ResolvedPath,
 };
 use settings::Settings;
-use std::ops::Range;
+use std::{ops::Range, rc::Rc};
 use theme::ActiveTheme as _;
 use util::{ResultExt, TryFutureExt as _, maybe};
 
@@ -545,6 +546,22 @@ pub fn show_link_definition(
                             (range, vec![HoverLink::Url(url)])
                         })
                         .ok()
+                    } else if let Some((link_range, link)) = find_document_link(
+                        &buffer,
+                        project.clone(),
+                        provider.clone(),
+                        buffer_position,
+                        cx,
+                    )
+                    .await
+                    {
+                        let range = maybe!({
+                            let start = snapshot.anchor_in_excerpt(excerpt_id, link_range.start)?;
+                            let end = snapshot.anchor_in_excerpt(excerpt_id, link_range.end)?;
+                            Some(RangeInEditor::Text(start..end))
+                        });
+
+                        Some((range, vec![link]))
                     } else if let Some((filename_range, filename)) =
                         find_file(&buffer, project.clone(), buffer_position, cx).await
                     {
@@ -775,6 +792,53 @@ pub(crate) fn find_url_from_range(
     None
 }
 
+pub(crate) async fn find_document_link(
+    buffer: &Entity<language::Buffer>,
+    project: Option<Entity<Project>>,
+    provider: Option<Rc<dyn SemanticsProvider>>,
+    position: text::Anchor,
+    cx: &mut AsyncWindowContext,
+) -> Option<(Range<text::Anchor>, HoverLink)> {
+    let provider = provider?;
+    let links_task = cx
+        .update(|_, cx| provider.document_links(buffer, cx))
+        .ok()??;
+    let links = links_task.await.log_err()?;
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot()).ok()?;
+    let mut link = links
+        .into_iter()
+        .filter(|link| {
+            link.range.start.cmp(&position, &snapshot).is_le()
+                && link.range.end.cmp(&position, &snapshot).is_ge()
+        })
+        .min_by_key(|link| link.range.to_offset(&snapshot).len())?;
+    if let ResolveState::CanResolve(server_id, _) = link.resolve_state {
+        let resolve_task = cx
+            .update(|_, cx| provider.resolve_document_link(link, buffer.clone(), server_id, cx))
+            .ok()??;
+        link = resolve_task.await.log_err()?;
+    }
+
+    let target = link.target?;
+    let hover_link = match lsp::Url::parse(&target) {
+        Ok(url) if url.scheme() == "file" => HoverLink::File(ResolvedPath::AbsPath {
+            path: url.to_file_path().ok()?,
+            is_dir: false,
+        }),
+        Ok(url) => HoverLink::Url(url.to_string()),
+        Err(_) => {
+            let resolved_path = project?
+                .update(cx, |project, cx| {
+                    project.resolve_path_in_buffer(&target, buffer, cx)
+                })
+                .ok()?
+                .await?;
+            HoverLink::File(resolved_path)
+        }
+    };
+    Some((link.range, hover_link))
+}
+
 pub(crate) async fn find_file(
     buffer: &Entity<language::Buffer>,
     project: Option<Entity<Project>>,
@@ -1503,6 +1567,64 @@ mod tests {
         assert_eq!(cx.opened_url(), Some("https://zed.dev/releases".into()));
     }
 
+    #[gpui::test]
+    async fn test_document_links(cx: &mut gpui::TestAppContext) {
+        init_test(cx, |_| {});
+        let mut cx = EditorLspTestContext::new_rust(
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: Some(true),
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            cx,
+        )
+        .await;
+
+        cx.set_state(indoc! {"
+            // See the release notes for ˇdetails.
+        "});
+        let link_range = cx.lsp_range(indoc! {"
+            // See «the release notes» for details.
+        "});
+
+        let mut link_requests = cx.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>(
+            move |_, _, _| async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: link_range,
+                    target: None,
+                    tooltip: None,
+                    data: Some(serde_json::json!({ "id": 1 })),
+                }]))
+            },
+        );
+        let mut resolve_requests = cx
+            .set_request_handler::<lsp::request::DocumentLinkResolve, _, _>(
+                |_, link, _| async move {
+                    assert_eq!(link.data, Some(serde_json::json!({ "id": 1 })));
+                    Ok(lsp::DocumentLink {
+                        target: Some(lsp::Url::parse("https://zed.dev/releases").unwrap()),
+                        ..link
+                    })
+                },
+            );
+
+        let screen_coord = cx.pixel_position(indoc! {"
+            // See the release nˇotes for details.
+        "});
+        cx.simulate_mouse_move(screen_coord, None, Modifiers::secondary_key());
+        link_requests.next().await;
+        resolve_requests.next().await;
+        cx.background_executor.run_until_parked();
+        cx.assert_editor_text_highlights::<HoveredLinkState>(indoc! {"
+            // See «the release notesˇ» for details.
+        "});
+
+        cx.simulate_click(screen_coord, Modifiers::secondary_key());
+        assert_eq!(cx.opened_url(), Some("https://zed.dev/releases".into()));
+    }
+
     #[gpui::test]
     async fn test_surrounding_filename(cx: &mut gpui::TestAppContext) {
         init_test(cx, |_| {});
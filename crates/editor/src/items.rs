@@ -1502,6 +1502,7 @@ impl SearchableItem for Editor {
                 case: true,
                 word: true,
                 regex: true,
+                structural: true,
                 replacement: false,
                 selection: false,
                 find_in_results: true,
@@ -1511,6 +1512,7 @@ impl SearchableItem for Editor {
                 case: true,
                 word: true,
                 regex: true,
+                structural: true,
                 replacement: true,
                 selection: true,
                 find_in_results: false,
@@ -1583,6 +1585,14 @@ impl SearchableItem for Editor {
     ) {
         let text = self.buffer.read(cx);
         let text = text.snapshot(cx);
+        if query.is_structural() {
+            if let Some(replacement) = structural_replacement(&text, identifier, query) {
+                self.transact(window, cx, |this, _, cx| {
+                    this.edit([(identifier.clone(), Arc::from(replacement))], cx);
+                });
+            }
+            return;
+        }
         let text = text.text_for_range(identifier.clone()).collect::<Vec<_>>();
         let text: Cow<_> = if text.len() == 1 {
             text.first().cloned().unwrap().into()
@@ -1610,6 +1620,13 @@ impl SearchableItem for Editor {
         let mut last_point: Option<Point> = None;
 
         for m in matches {
+            if query.is_structural() {
+                if let Some(replacement) = structural_replacement(&text, m, query) {
+                    edits.push((m.clone(), Arc::from(replacement)));
+                }
+                continue;
+            }
+
             let point = m.start.to_point(&text);
             let text = text.text_for_range(m.clone()).collect::<Vec<_>>();
 
@@ -1808,6 +1825,35 @@ pub fn active_match_index(
     }
 }
 
+/// Computes the replacement of a structural search match, which needs the
+/// syntax tree of the buffer containing it.
+fn structural_replacement(
+    snapshot: &MultiBufferSnapshot,
+    range: &Range<Anchor>,
+    query: &SearchQuery,
+) -> Option<String> {
+    match snapshot.range_to_buffer_ranges(range.clone()).as_slice() {
+        [(buffer, range, _)] => query.replacement_in(buffer, range.clone()),
+        _ => None,
+    }
+}
+
+/// Computes the text that replacing the match at `range` with the replacement
+/// of `query` would produce.
+pub fn replacement_for_match(
+    snapshot: &MultiBufferSnapshot,
+    range: &Range<Anchor>,
+    query: &SearchQuery,
+) -> Option<String> {
+    if query.is_structural() {
+        return structural_replacement(snapshot, range, query);
+    }
+    let text = snapshot.text_for_range(range.clone()).collect::<String>();
+    query
+        .replacement_for(&text)
+        .map(|replacement| replacement.into_owned())
+}
+
 pub fn entry_label_color(selected: bool) -> Color {
     if selected {
         Color::Default
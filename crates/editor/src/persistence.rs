@@ -113,6 +113,13 @@ define_connection!(
     //   start: usize,
     //   end: usize,
     // )
+    //
+    // undo_histories(
+    //   path: PathBuf,
+    //   content_hash: String,
+    //   history: String,
+    //   updated_at: i64,
+    // )
     pub static ref DB: EditorDb<WorkspaceDb> = &[
         sql! (
             CREATE TABLE editors(
@@ -188,6 +195,15 @@ define_connection!(
                 ON DELETE CASCADE
             ) STRICT;
         ),
+        sql! (
+            CREATE TABLE undo_histories (
+                path BLOB NOT NULL,
+                content_hash TEXT NOT NULL,
+                history TEXT NOT NULL,
+                updated_at INTEGER NOT NULL,
+                PRIMARY KEY(path)
+            ) STRICT;
+        ),
     ];
 );
 
@@ -373,6 +389,60 @@ VALUES {placeholders};
         }
         Ok(())
     }
+
+    // Returns the content hash of the file the history was recorded for, and the history
+    query! {
+        pub fn get_undo_history(path: PathBuf) -> Result<Option<(String, String)>> {
+            SELECT content_hash, history
+            FROM undo_histories
+            WHERE path = ?
+        }
+    }
+
+    query! {
+        pub async fn save_undo_history(
+            path: PathBuf,
+            content_hash: String,
+            history: String,
+            updated_at: i64
+        ) -> Result<()> {
+            INSERT INTO undo_histories
+                (path, content_hash, history, updated_at)
+            VALUES
+                (?1, ?2, ?3, ?4)
+            ON CONFLICT DO UPDATE SET
+                content_hash = ?2,
+                history = ?3,
+                updated_at = ?4
+        }
+    }
+
+    query! {
+        pub async fn delete_undo_history(path: PathBuf) -> Result<()> {
+            DELETE FROM undo_histories
+            WHERE path = ?
+        }
+    }
+
+    // Deletes the histories saved before `oldest_updated_at`, then the least
+    // recently saved ones until they fit in `max_total_size` bytes
+    query! {
+        pub async fn prune_undo_histories(oldest_updated_at: i64, max_total_size: i64) -> Result<()> {
+            DELETE FROM undo_histories
+            WHERE updated_at < ?1;
+
+            DELETE FROM undo_histories
+            WHERE path IN (
+                SELECT path FROM (
+                    SELECT
+                        path,
+                        SUM(LENGTH(history)) OVER (ORDER BY updated_at DESC, path) AS total_size
+                    FROM undo_histories
+                )
+                WHERE total_size > ?2
+            )
+        }
+    }
 }
 
 #[cfg(test)]
@@ -454,4 +524,41 @@ mod tests {
             .unwrap();
         assert_eq!(have, serialized_editor);
     }
+
+    #[gpui::test]
+    async fn test_save_and_prune_undo_histories() {
+        let path_a = PathBuf::from("/undo/a.txt");
+        let path_b = PathBuf::from("/undo/b.txt");
+        let path_c = PathBuf::from("/undo/c.txt");
+
+        DB.save_undo_history(path_a.clone(), "hash-a".into(), "a".repeat(10), 1)
+            .await
+            .unwrap();
+        DB.save_undo_history(path_b.clone(), "hash-b".into(), "b".repeat(10), 2)
+            .await
+            .unwrap();
+        DB.save_undo_history(path_c.clone(), "hash-c".into(), "c".repeat(10), 3)
+            .await
+            .unwrap();
+        DB.save_undo_history(path_a.clone(), "hash-a2".into(), "a".repeat(5), 4)
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.get_undo_history(path_a.clone()).unwrap(),
+            Some(("hash-a2".into(), "a".repeat(5)))
+        );
+
+        // The history of b is the oldest remaining one after c and a were saved.
+        DB.prune_undo_histories(2, 15).await.unwrap();
+        assert!(DB.get_undo_history(path_a.clone()).unwrap().is_some());
+        assert!(DB.get_undo_history(path_b.clone()).unwrap().is_none());
+        assert!(DB.get_undo_history(path_c.clone()).unwrap().is_some());
+
+        DB.prune_undo_histories(4, 1024).await.unwrap();
+        assert!(DB.get_undo_history(path_a.clone()).unwrap().is_some());
+        assert!(DB.get_undo_history(path_c.clone()).unwrap().is_none());
+
+        DB.delete_undo_history(path_a.clone()).await.unwrap();
+        assert!(DB.get_undo_history(path_a).unwrap().is_none());
+    }
 }
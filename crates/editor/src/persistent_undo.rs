@@ -0,0 +1,255 @@
+use std::{
+    ops::Range,
+    path::PathBuf,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use gpui::{App, Context, Entity, Window};
+use language::{Buffer, Rope, Selection, SelectionGoal, SerializedEdit, SerializedTransaction};
+use serde::{Deserialize, Serialize};
+use settings::Settings as _;
+use sha2::{Digest, Sha256};
+use util::ResultExt as _;
+
+use crate::{Editor, EditorSettings, persistence::DB};
+
+/// The maximum number of transactions persisted for a single file.
+const MAX_PERSISTED_TRANSACTIONS: usize = 1000;
+
+#[derive(Serialize, Deserialize)]
+struct PersistedTransaction {
+    edits: Vec<PersistedEdit>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct PersistedEdit {
+    old_range: Range<usize>,
+    old_text: String,
+    new_text: String,
+}
+
+impl From<SerializedTransaction> for PersistedTransaction {
+    fn from(transaction: SerializedTransaction) -> Self {
+        Self {
+            edits: transaction
+                .edits
+                .into_iter()
+                .map(|edit| PersistedEdit {
+                    old_range: edit.old_range,
+                    old_text: edit.old_text,
+                    new_text: edit.new_text,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<PersistedTransaction> for SerializedTransaction {
+    fn from(transaction: PersistedTransaction) -> Self {
+        Self {
+            edits: transaction
+                .edits
+                .into_iter()
+                .map(|edit| SerializedEdit {
+                    old_range: edit.old_range,
+                    old_text: edit.old_text,
+                    new_text: edit.new_text,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl Editor {
+    /// Returns the buffer whose undo history should be persisted, along with
+    /// the path of its file, if persistent undo is enabled for this editor.
+    fn persistent_undo_buffer(&self, cx: &App) -> Option<(Entity<Buffer>, PathBuf)> {
+        if !self.mode.is_full() || !EditorSettings::get_global(cx).persistent_undo.enabled {
+            return None;
+        }
+        if !self.project.as_ref()?.read(cx).is_local() {
+            return None;
+        }
+        let buffer = self.buffer.read(cx).as_singleton()?;
+        let abs_path = buffer.read(cx).file()?.as_local()?.abs_path(cx);
+        Some((buffer, abs_path))
+    }
+
+    /// Stores the undo history of the buffer, keyed by the hash of the text
+    /// that was just saved.
+    pub(crate) fn persist_undo_history(&mut self, cx: &mut Context<Self>) {
+        let Some((buffer, abs_path)) = self.persistent_undo_buffer(cx) else {
+            return;
+        };
+        let buffer = buffer.read(cx);
+        if buffer.is_dirty() {
+            return;
+        }
+        // Saving without editing leaves the history as it was persisted.
+        let version = buffer.version();
+        if self.persisted_undo_version.as_ref() == Some(&version) {
+            return;
+        }
+        let Some(history) = buffer.undo_history_snapshot() else {
+            return;
+        };
+        self.persisted_undo_version = Some(version);
+
+        let settings = EditorSettings::get_global(cx).persistent_undo;
+        let max_size = settings.max_size_kb.saturating_mul(1024) as usize;
+        let max_age = Duration::from_secs(settings.max_age_days.saturating_mul(24 * 60 * 60));
+        let text = buffer.as_rope().clone();
+        cx.background_spawn(async move {
+            let transactions = history
+                .serialize(MAX_PERSISTED_TRANSACTIONS)
+                .into_iter()
+                .map(|transaction| {
+                    let transaction = PersistedTransaction::from(transaction);
+                    let json = serde_json::to_string(&transaction)?;
+                    anyhow::Ok(json)
+                })
+                .collect::<anyhow::Result<Vec<_>>>()?;
+
+            // Drop the oldest transactions until the history fits in the size budget.
+            let mut size = 2;
+            let first_kept = transactions
+                .iter()
+                .rposition(|transaction| {
+                    size += transaction.len() + 1;
+                    size > max_size
+                })
+                .map_or(0, |ix| ix + 1);
+            let transactions = &transactions[first_kept..];
+
+            if transactions.is_empty() {
+                DB.delete_undo_history(abs_path).await?;
+            } else {
+                let history = format!("[{}]", transactions.join(","));
+                let now = SystemTime::now().duration_since(UNIX_EPOCH)?;
+                DB.save_undo_history(abs_path, content_hash(&text), history, now.as_secs() as i64)
+                    .await?;
+                DB.prune_undo_histories(
+                    now.saturating_sub(max_age).as_secs() as i64,
+                    max_size as i64,
+                )
+                .await?;
+            }
+            anyhow::Ok(())
+        })
+        .detach_and_log_err(cx);
+    }
+
+    /// Restores the undo history stored for the buffer's file, as long as the
+    /// buffer hasn't been edited and its text matches the one it was stored for.
+    pub(crate) fn restore_persistent_undo_history(
+        &mut self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some((buffer, abs_path)) = self.persistent_undo_buffer(cx) else {
+            return;
+        };
+        let (version, text) = {
+            let buffer = buffer.read(cx);
+            if buffer.is_dirty() || buffer.peek_undo_stack().is_some() {
+                return;
+            }
+            (buffer.version(), buffer.as_rope().clone())
+        };
+        let load_history = cx.background_spawn(async move {
+            let Some((hash, history)) = DB.get_undo_history(abs_path)? else {
+                return anyhow::Ok(None);
+            };
+            if hash != content_hash(&text) {
+                return Ok(None);
+            }
+            let transactions = serde_json::from_str::<Vec<PersistedTransaction>>(&history)?;
+            Ok(Some(
+                transactions
+                    .into_iter()
+                    .map(SerializedTransaction::from)
+                    .collect::<Vec<_>>(),
+            ))
+        });
+
+        cx.spawn_in(window, async move |editor, cx| {
+            let Some(transactions) = load_history.await.log_err().flatten() else {
+                return;
+            };
+            editor
+                .update_in(cx, |editor, window, cx| {
+                    let buffer_unchanged = {
+                        let buffer = buffer.read(cx);
+                        buffer.version() == version && buffer.peek_undo_stack().is_none()
+                    };
+                    if buffer_unchanged {
+                        editor.restore_undo_transactions(&buffer, &transactions, window, cx);
+                    }
+                })
+                .ok();
+        })
+        .detach();
+    }
+
+    fn restore_undo_transactions(
+        &mut self,
+        buffer: &Entity<Buffer>,
+        transactions: &[SerializedTransaction],
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let selections = self.selections.all::<usize>(cx);
+        let Some(transaction_ids) = buffer
+            .update(cx, |buffer, cx| {
+                buffer.restore_undo_history(transactions, cx)
+            })
+            .log_err()
+        else {
+            return;
+        };
+        self.change_selections(None, window, cx, |s| s.select(selections));
+
+        // Undoing a transaction moves the cursors to the edits it made, so
+        // record selections for the restored transactions.
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        for transaction_id in transaction_ids {
+            let ranges = buffer
+                .read(cx)
+                .edited_ranges_for_transaction_id::<usize>(transaction_id)
+                .collect::<Vec<_>>();
+            let mut selections_at = |offset: fn(&Range<usize>) -> usize| {
+                ranges
+                    .iter()
+                    .map(|range| {
+                        let anchor = snapshot.anchor_before(offset(range));
+                        Selection {
+                            id: self.selections.new_selection_id(),
+                            start: anchor,
+                            end: anchor,
+                            reversed: false,
+                            goal: SelectionGoal::None,
+                        }
+                    })
+                    .collect::<Arc<[_]>>()
+            };
+            let before = selections_at(|range| range.start);
+            let after = selections_at(|range| range.end);
+            self.selection_history
+                .insert_transaction(transaction_id, before);
+            if let Some((_, selections_after)) =
+                self.selection_history.transaction_mut(transaction_id)
+            {
+                *selections_after = Some(after);
+            }
+        }
+    }
+}
+
+fn content_hash(text: &Rope) -> String {
+    let mut hasher = Sha256::new();
+    for chunk in text.chunks() {
+        hasher.update(chunk.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
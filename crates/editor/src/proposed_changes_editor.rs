@@ -504,6 +504,26 @@ impl SemanticsProvider for BranchBufferSemanticsProvider {
         self.0.definitions(&buffer, position, kind, cx)
     }
 
+    fn document_links(
+        &self,
+        buffer: &Entity<Buffer>,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<Vec<project::DocumentLink>>>> {
+        let buffer = self.to_base(&buffer, &[], cx)?;
+        self.0.document_links(&buffer, cx)
+    }
+
+    fn resolve_document_link(
+        &self,
+        link: project::DocumentLink,
+        buffer: Entity<Buffer>,
+        server_id: lsp::LanguageServerId,
+        cx: &mut App,
+    ) -> Option<Task<anyhow::Result<project::DocumentLink>>> {
+        let buffer = self.to_base(&buffer, &[], cx)?;
+        self.0.resolve_document_link(link, buffer, server_id, cx)
+    }
+
     fn range_for_rename(
         &self,
         _: &Entity<Buffer>,
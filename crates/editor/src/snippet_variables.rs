@@ -0,0 +1,127 @@
+use gpui::App;
+use language::{BufferSnapshot, Point};
+use std::{
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use text::ToPoint as _;
+use time::{OffsetDateTime, UtcOffset};
+use uuid::Uuid;
+
+/// The context in which a snippet is expanded, from which its variables, such
+/// as `$TM_FILENAME` or `$CURRENT_YEAR`, are resolved.
+pub(crate) struct SnippetVariables<'a> {
+    pub buffer: &'a BufferSnapshot,
+    /// The offset in `buffer` at which the snippet is expanded.
+    pub position: usize,
+    pub selected_text: &'a str,
+}
+
+impl SnippetVariables<'_> {
+    /// Returns the value of the variable with the given name, or `None` if the
+    /// variable is unknown.
+    pub fn resolve(&self, name: &str, cx: &App) -> Option<String> {
+        let file = self.buffer.file();
+        let worktree = project::File::from_dyn(file).map(|file| file.worktree.read(cx));
+        let point = self.position.to_point(self.buffer);
+        let now = || {
+            let offset = UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC);
+            OffsetDateTime::now_utc().to_offset(offset)
+        };
+        let comment_delimiters = || {
+            let scope = self.buffer.language_scope_at(self.position)?;
+            scope
+                .block_comment_delimiters()
+                .map(|(start, end)| (start.to_string(), end.to_string()))
+        };
+
+        let value = match name {
+            "TM_SELECTED_TEXT" => self.selected_text.to_string(),
+            "TM_CURRENT_LINE" => {
+                let line_end = Point::new(point.row, self.buffer.line_len(point.row));
+                self.buffer
+                    .text_for_range(Point::new(point.row, 0)..line_end)
+                    .collect()
+            }
+            "TM_CURRENT_WORD" => {
+                let (range, _) = self.buffer.surrounding_word(self.position);
+                self.buffer.text_for_range(range).collect()
+            }
+            "TM_LINE_INDEX" => point.row.to_string(),
+            "TM_LINE_NUMBER" => (point.row + 1).to_string(),
+            "TM_FILENAME" => file
+                .map(|file| file.file_name(cx).to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "TM_FILENAME_BASE" => file
+                .and_then(|file| Path::new(file.file_name(cx)).file_stem())
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "TM_DIRECTORY" => file
+                .and_then(|file| Some(file_path(file, cx).parent()?.to_path_buf()))
+                .map(|directory| directory.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "TM_FILEPATH" => file
+                .map(|file| file_path(file, cx).to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "RELATIVE_FILEPATH" => file
+                .map(|file| file.path().to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "WORKSPACE_NAME" => worktree
+                .map(|worktree| worktree.root_name().to_string())
+                .unwrap_or_default(),
+            "WORKSPACE_FOLDER" => worktree
+                .map(|worktree| worktree.abs_path().to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            "CLIPBOARD" => cx
+                .read_from_clipboard()
+                .and_then(|item| item.text())
+                .unwrap_or_default(),
+            "CURSOR_INDEX" => "0".to_string(),
+            "CURSOR_NUMBER" => "1".to_string(),
+            "CURRENT_YEAR" => now().year().to_string(),
+            "CURRENT_YEAR_SHORT" => format!("{:02}", now().year() % 100),
+            "CURRENT_MONTH" => format!("{:02}", u8::from(now().month())),
+            "CURRENT_MONTH_NAME" => now().month().to_string(),
+            "CURRENT_MONTH_NAME_SHORT" => now().month().to_string()[..3].to_string(),
+            "CURRENT_DATE" => format!("{:02}", now().day()),
+            "CURRENT_DAY_NAME" => now().weekday().to_string(),
+            "CURRENT_DAY_NAME_SHORT" => now().weekday().to_string()[..3].to_string(),
+            "CURRENT_HOUR" => format!("{:02}", now().hour()),
+            "CURRENT_MINUTE" => format!("{:02}", now().minute()),
+            "CURRENT_SECOND" => format!("{:02}", now().second()),
+            "CURRENT_SECONDS_UNIX" => now().unix_timestamp().to_string(),
+            "CURRENT_TIMEZONE_OFFSET" => {
+                let offset = now().offset();
+                let sign = if offset.is_negative() { '-' } else { '+' };
+                let (hours, minutes, _) = offset.as_hms();
+                format!("{sign}{:02}:{:02}", hours.abs(), minutes.abs())
+            }
+            "RANDOM" => format!("{:06}", rand::random::<u32>() % 1_000_000),
+            "RANDOM_HEX" => format!("{:06x}", rand::random::<u32>() & 0xff_ffff),
+            "UUID" => Uuid::new_v4().to_string(),
+            "LINE_COMMENT" => self
+                .buffer
+                .language_scope_at(self.position)
+                .and_then(|scope| {
+                    let prefix = scope.line_comment_prefixes().first()?;
+                    Some(prefix.trim_end().to_string())
+                })
+                .unwrap_or_default(),
+            "BLOCK_COMMENT_START" => comment_delimiters()
+                .map(|(start, _)| start.trim_end().to_string())
+                .unwrap_or_default(),
+            "BLOCK_COMMENT_END" => comment_delimiters()
+                .map(|(_, end)| end.trim_start().to_string())
+                .unwrap_or_default(),
+            _ => return None,
+        };
+        Some(value)
+    }
+}
+
+fn file_path(file: &Arc<dyn language::File>, cx: &App) -> PathBuf {
+    match file.as_local() {
+        Some(file) => file.abs_path(cx),
+        None => file.full_path(cx),
+    }
+}
@@ -0,0 +1,165 @@
+use std::{cmp::Reverse, ops::Range};
+
+use collections::HashMap;
+use gpui::{App, Context, Window};
+use language::{Point, TextObject, TreeSitterOptions};
+use multi_buffer::{MultiBufferRow, MultiBufferSnapshot, ToOffset as _, ToPoint as _};
+use settings::Settings as _;
+
+use crate::{
+    DisplayPoint, DisplayRow, Editor, EditorSettings, EditorSnapshot, ToggleStickyScroll,
+    display_map::ToDisplayPoint as _, scroll::Autoscroll,
+};
+
+/// A line pinned to the top of the editor because the scope it starts
+/// encloses the rows scrolled underneath it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StickyHeader {
+    pub row: MultiBufferRow,
+    pub display_row: DisplayRow,
+}
+
+impl Editor {
+    pub fn sticky_scroll_enabled(&self, cx: &App) -> bool {
+        self.mode.is_full()
+            && self.buffer.read(cx).is_singleton()
+            && self
+                .show_sticky_scroll
+                .unwrap_or_else(|| EditorSettings::get_global(cx).sticky_scroll.enabled)
+    }
+
+    pub fn toggle_sticky_scroll(
+        &mut self,
+        _: &ToggleStickyScroll,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let currently_enabled = self
+            .show_sticky_scroll
+            .unwrap_or_else(|| EditorSettings::get_global(cx).sticky_scroll.enabled);
+        self.set_show_sticky_scroll(!currently_enabled, cx);
+    }
+
+    /// Moves the cursor to the given pinned header, keeping the line at the
+    /// position it was pinned at so that the view doesn't jump.
+    pub(crate) fn jump_to_sticky_header(
+        &mut self,
+        header: StickyHeader,
+        index: usize,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let snapshot = self.buffer.read(cx).snapshot(cx);
+        let indent = snapshot.indent_size_for_line(header.row);
+        let position = Point::new(header.row.0, indent.len);
+        self.change_selections(Some(Autoscroll::top_relative(index)), window, cx, |s| {
+            s.select_ranges([position..position])
+        });
+    }
+}
+
+/// The scopes enclosing the buffer rows that sticky headers were looked up
+/// for, kept until the buffer is edited or reparsed.
+#[derive(Default)]
+pub(crate) struct StickyScopesCache {
+    buffer_version: (usize, usize),
+    enclosing_scopes: HashMap<u32, Vec<Range<u32>>>,
+}
+
+impl StickyScopesCache {
+    fn enclosing_scopes(&mut self, buffer: &MultiBufferSnapshot, row: u32) -> &[Range<u32>] {
+        let buffer_version = (buffer.edit_count(), buffer.non_text_state_update_count());
+        if self.buffer_version != buffer_version {
+            self.buffer_version = buffer_version;
+            self.enclosing_scopes.clear();
+        }
+        self.enclosing_scopes
+            .entry(row)
+            .or_insert_with(|| enclosing_scopes(buffer, row))
+    }
+}
+
+impl EditorSnapshot {
+    /// Returns the headers of the scopes enclosing the rows covered by the
+    /// headers themselves when the viewport starts at `scroll_top`, outermost
+    /// first. At most the `max_lines` innermost scopes are returned.
+    pub(crate) fn sticky_headers(
+        &self,
+        scroll_top: DisplayRow,
+        max_lines: usize,
+        cache: &mut StickyScopesCache,
+    ) -> Vec<StickyHeader> {
+        if max_lines == 0 {
+            return Vec::new();
+        }
+
+        let buffer = &self.buffer_snapshot;
+        let max_row = self.max_point().row();
+        // Every pinned header hides one more row, so keep looking further down
+        // until the scopes enclosing the first uncovered row are all pinned.
+        let mut scopes = Vec::new();
+        loop {
+            let covered_row = DisplayRow(scroll_top.0 + scopes.len() as u32);
+            if covered_row > max_row {
+                break;
+            }
+            let covered_row = DisplayPoint::new(covered_row, 0)
+                .to_point(&self.display_snapshot)
+                .row;
+            let enclosing = cache.enclosing_scopes(buffer, covered_row);
+            let enclosing = &enclosing[enclosing.len().saturating_sub(max_lines)..];
+            if enclosing.len() <= scopes.len() {
+                break;
+            }
+            scopes = enclosing.to_vec();
+        }
+
+        let mut headers = Vec::with_capacity(scopes.len());
+        for scope in scopes {
+            let covered_row = DisplayRow(scroll_top.0 + headers.len() as u32);
+            let display_row = Point::new(scope.start, 0)
+                .to_display_point(&self.display_snapshot)
+                .row();
+            let end_display_row = Point::new(scope.end, 0)
+                .to_display_point(&self.display_snapshot)
+                .row();
+            if display_row < covered_row && end_display_row > covered_row {
+                headers.push(StickyHeader {
+                    row: MultiBufferRow(scope.start),
+                    display_row,
+                });
+            }
+        }
+        headers
+    }
+}
+
+/// Returns the row ranges of the outline items, functions and classes that
+/// strictly enclose the given row, sorted from the outermost to the innermost.
+fn enclosing_scopes(buffer: &MultiBufferSnapshot, row: u32) -> Vec<Range<u32>> {
+    let offset = Point::new(row, 0).to_offset(buffer);
+    let outline_scopes = buffer
+        .symbols_containing(offset, None)
+        .map(|(_, items)| items)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|item| item.range.start.to_point(buffer).row..item.range.end.to_point(buffer).row);
+    let text_object_scopes = buffer
+        .text_object_ranges(offset..offset, TreeSitterOptions::default())
+        .filter(|(_, text_object)| {
+            matches!(
+                text_object,
+                TextObject::AroundFunction | TextObject::AroundClass
+            )
+        })
+        .map(|(range, _)| range.start.to_point(buffer).row..range.end.to_point(buffer).row)
+        .collect::<Vec<_>>();
+
+    let mut scopes = outline_scopes
+        .chain(text_object_scopes)
+        .filter(|scope| scope.start < row && scope.end > row)
+        .collect::<Vec<_>>();
+    scopes.sort_unstable_by_key(|scope| (scope.start, Reverse(scope.end)));
+    scopes.dedup_by_key(|scope| scope.start);
+    scopes
+}
@@ -25,6 +25,7 @@ mod conflict_view;
 pub mod diff_view;
 pub mod git_panel;
 mod git_panel_settings;
+pub mod merge_view;
 pub mod onboarding;
 pub mod picker_prompt;
 pub mod project_diff;
@@ -0,0 +1,658 @@
+//! MergeView provides a UI for resolving a three-way merge, such as when Zed
+//! is used as a `git mergetool`.
+
+use anyhow::Result;
+use editor::{Editor, EditorEvent};
+use futures::channel::oneshot;
+use gpui::{
+    AnyElement, AnyView, App, AppContext as _, Context, Entity, EventEmitter, FocusHandle,
+    Focusable, IntoElement, Render, Task, Window,
+};
+use language::Buffer;
+use project::{ConflictSet, Project};
+use std::{
+    any::{Any, TypeId},
+    ops::Range,
+    path::{Path, PathBuf},
+};
+use ui::{Color, Icon, IconName, Label, LabelCommon as _, SharedString};
+use util::paths::PathExt as _;
+use workspace::{
+    Item, ItemNavHistory, ToolbarItemLocation, Workspace,
+    item::{BreadcrumbText, ItemEvent, SaveOptions, TabContentParams},
+    searchable::SearchableItemHandle,
+};
+
+pub struct MergeView {
+    editor: Entity<Editor>,
+    merged_buffer: Entity<Buffer>,
+    project: Entity<Project>,
+    local_path: PathBuf,
+    remote_path: PathBuf,
+    merge_result_tx: Option<oneshot::Sender<Result<bool>>>,
+    _conflict_set: Entity<ConflictSet>,
+}
+
+impl MergeView {
+    /// Opens a view for merging the changes from `base_path` to `local_path`
+    /// and `remote_path` into `merged_path`.
+    ///
+    /// If `merged_path` doesn't contain conflict markers yet, it is replaced
+    /// with the result of merging the other files.
+    pub fn open(
+        base_path: PathBuf,
+        local_path: PathBuf,
+        remote_path: PathBuf,
+        merged_path: PathBuf,
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut App,
+    ) -> Task<Result<Entity<Self>>> {
+        let workspace = workspace.weak_handle();
+        window.spawn(cx, async move |cx| {
+            let project = workspace.update(cx, |workspace, _| workspace.project().clone())?;
+            let mut texts = Vec::new();
+            for path in [&base_path, &local_path, &remote_path] {
+                let buffer = project
+                    .update(cx, |project, cx| project.open_local_buffer(path, cx))?
+                    .await?;
+                texts.push(buffer.read_with(cx, |buffer, _| buffer.text())?);
+            }
+            let merged_buffer = project
+                .update(cx, |project, cx| {
+                    project.open_local_buffer(&merged_path, cx)
+                })?
+                .await?;
+
+            merged_buffer.update(cx, |buffer, cx| {
+                if ConflictSet::parse(&buffer.text_snapshot())
+                    .conflicts
+                    .is_empty()
+                {
+                    let merged_text = merge_texts(
+                        &texts[0],
+                        &texts[1],
+                        &texts[2],
+                        &file_label(&local_path),
+                        &file_label(&remote_path),
+                    );
+                    if merged_text != buffer.text() {
+                        buffer.set_text(merged_text, cx);
+                    }
+                }
+            })?;
+
+            let conflict_set = project.update(cx, |project, cx| {
+                let git_store = project.git_store().clone();
+                let conflict_set = git_store.update(cx, |git_store, cx| {
+                    git_store.open_conflict_set(merged_buffer.clone(), cx)
+                });
+                // The merged file isn't necessarily unmerged in a repository,
+                // e.g. when resolving conflicts for `jj resolve`.
+                conflict_set.update(cx, |conflict_set, cx| {
+                    conflict_set.set_has_conflict(true, cx);
+                });
+                // Opening the conflict set again parses the conflict markers.
+                git_store.update(cx, |git_store, cx| {
+                    git_store.open_conflict_set(merged_buffer.clone(), cx)
+                })
+            })?;
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let merge_view = cx.new(|cx| {
+                    MergeView::new(
+                        merged_buffer,
+                        conflict_set,
+                        project.clone(),
+                        local_path,
+                        remote_path,
+                        window,
+                        cx,
+                    )
+                });
+
+                let pane = workspace.active_pane();
+                pane.update(cx, |pane, cx| {
+                    pane.add_item(Box::new(merge_view.clone()), true, true, None, window, cx);
+                });
+
+                merge_view
+            })
+        })
+    }
+
+    pub fn new(
+        merged_buffer: Entity<Buffer>,
+        conflict_set: Entity<ConflictSet>,
+        project: Entity<Project>,
+        local_path: PathBuf,
+        remote_path: PathBuf,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let editor = cx.new(|cx| {
+            let mut editor =
+                Editor::for_buffer(merged_buffer.clone(), Some(project.clone()), window, cx);
+            editor.disable_diagnostics(cx);
+            editor
+        });
+
+        // Write the merged file once the view is closed, so that the tool that
+        // invoked the merge can pick up the result.
+        cx.on_release(|this, cx| {
+            let save = this.project.update(cx, |project, cx| {
+                project.save_buffer(this.merged_buffer.clone(), cx)
+            });
+            let merged_buffer = this.merged_buffer.clone();
+            let merge_result_tx = this.merge_result_tx.take();
+            cx.spawn(async move |cx| {
+                let result = async {
+                    save.await?;
+                    merged_buffer.read_with(cx, |buffer, _| {
+                        ConflictSet::parse(&buffer.text_snapshot())
+                            .conflicts
+                            .is_empty()
+                    })
+                }
+                .await;
+                if let Some(merge_result_tx) = merge_result_tx {
+                    merge_result_tx.send(result).ok();
+                }
+            })
+            .detach();
+        })
+        .detach();
+
+        Self {
+            editor,
+            merged_buffer,
+            project,
+            local_path,
+            remote_path,
+            merge_result_tx: None,
+            _conflict_set: conflict_set,
+        }
+    }
+
+    /// Returns a receiver that resolves once the view was closed and the merged
+    /// file was written, with whether all of the conflicts were resolved.
+    pub fn merge_result(&mut self) -> oneshot::Receiver<Result<bool>> {
+        let (tx, rx) = oneshot::channel();
+        self.merge_result_tx = Some(tx);
+        rx
+    }
+
+    fn merged_path(&self, cx: &App) -> Option<PathBuf> {
+        self.merged_buffer
+            .read(cx)
+            .file()
+            .map(|file| file.full_path(cx))
+    }
+}
+
+fn file_label(path: &Path) -> String {
+    path.file_name()
+        .unwrap_or(path.as_os_str())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Merges the changes from `base` to `local` and `remote` line by line, adding
+/// conflict markers where both sides changed the same lines differently.
+fn merge_texts(
+    base: &str,
+    local: &str,
+    remote: &str,
+    local_label: &str,
+    remote_label: &str,
+) -> String {
+    #[derive(Clone, Copy, PartialEq)]
+    enum Side {
+        Local,
+        Remote,
+    }
+
+    let base_lines = base.split_inclusive('\n').collect::<Vec<_>>();
+    let local_lines = local.split_inclusive('\n').collect::<Vec<_>>();
+    let remote_lines = remote.split_inclusive('\n').collect::<Vec<_>>();
+
+    let mut hunks = language::line_diff(base, local)
+        .into_iter()
+        .map(|(base_rows, rows)| (Side::Local, base_rows, rows))
+        .chain(
+            language::line_diff(base, remote)
+                .into_iter()
+                .map(|(base_rows, rows)| (Side::Remote, base_rows, rows)),
+        )
+        .collect::<Vec<_>>();
+    hunks.sort_by_key(|(_, base_rows, _)| (base_rows.start, base_rows.end));
+
+    let mut merged = String::with_capacity(base.len().max(local.len()).max(remote.len()));
+    let push_lines = |merged: &mut String, lines: &[&str]| {
+        for line in lines {
+            merged.push_str(line);
+        }
+    };
+    // Conflict markers start lines of their own, even when they follow the
+    // last line of a side, which may not end with a newline.
+    let push_marker = |merged: &mut String, marker: &str| {
+        if !merged.is_empty() && !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+        merged.push_str(marker);
+        merged.push('\n');
+    };
+
+    // The difference between a row in `base` and the corresponding row of each
+    // side, outside of the hunks of that side.
+    let mut local_delta = 0_i64;
+    let mut remote_delta = 0_i64;
+    let mut base_row = 0;
+    let mut hunks = hunks.into_iter().peekable();
+    while let Some((side, base_rows, rows)) = hunks.next() {
+        // Group the hunks that overlap or touch, as they conflict unless they
+        // make the same change.
+        let mut cluster = base_rows.start..base_rows.end;
+        let mut cluster_hunks = vec![(side, base_rows, rows)];
+        while let Some((_, next_base_rows, _)) = hunks.peek() {
+            if next_base_rows.start > cluster.end {
+                break;
+            }
+            let hunk = hunks.next().unwrap();
+            cluster.end = cluster.end.max(hunk.1.end);
+            cluster_hunks.push(hunk);
+        }
+
+        push_lines(
+            &mut merged,
+            &base_lines[base_row as usize..cluster.start as usize],
+        );
+
+        let side_rows = |side: Side, delta: i64| -> (Range<usize>, i64) {
+            let hunk_delta = cluster_hunks
+                .iter()
+                .filter(|(hunk_side, _, _)| *hunk_side == side)
+                .map(|(_, base_rows, rows)| rows.len() as i64 - base_rows.len() as i64)
+                .sum::<i64>();
+            let start = (cluster.start as i64 + delta) as usize;
+            let end = (cluster.end as i64 + delta + hunk_delta) as usize;
+            (start..end, hunk_delta)
+        };
+        let (local_rows, local_hunk_delta) = side_rows(Side::Local, local_delta);
+        let (remote_rows, remote_hunk_delta) = side_rows(Side::Remote, remote_delta);
+        let local_changed = cluster_hunks.iter().any(|(side, ..)| *side == Side::Local);
+        let remote_changed = cluster_hunks.iter().any(|(side, ..)| *side == Side::Remote);
+        let local_text = &local_lines[local_rows];
+        let remote_text = &remote_lines[remote_rows];
+
+        if !remote_changed || local_text == remote_text {
+            push_lines(&mut merged, local_text);
+        } else if !local_changed {
+            push_lines(&mut merged, remote_text);
+        } else {
+            push_marker(&mut merged, &format!("<<<<<<< {local_label}"));
+            push_lines(&mut merged, local_text);
+            push_marker(&mut merged, "||||||| base");
+            push_lines(
+                &mut merged,
+                &base_lines[cluster.start as usize..cluster.end as usize],
+            );
+            push_marker(&mut merged, "=======");
+            push_lines(&mut merged, remote_text);
+            push_marker(&mut merged, &format!(">>>>>>> {remote_label}"));
+        }
+
+        local_delta += local_hunk_delta;
+        remote_delta += remote_hunk_delta;
+        base_row = cluster.end;
+    }
+
+    push_lines(&mut merged, &base_lines[base_row as usize..]);
+    merged
+}
+
+impl EventEmitter<EditorEvent> for MergeView {}
+
+impl Focusable for MergeView {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.editor.focus_handle(cx)
+    }
+}
+
+impl Item for MergeView {
+    type Event = EditorEvent;
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::GitBranch).color(Color::Muted))
+    }
+
+    fn tab_content(&self, params: TabContentParams, _window: &Window, cx: &App) -> AnyElement {
+        Label::new(self.tab_content_text(params.detail.unwrap_or_default(), cx))
+            .color(if params.selected {
+                Color::Default
+            } else {
+                Color::Muted
+            })
+            .into_any_element()
+    }
+
+    fn tab_content_text(&self, _detail: usize, cx: &App) -> SharedString {
+        let merged_filename = self
+            .merged_path(cx)
+            .map(|path| file_label(&path))
+            .unwrap_or_else(|| "untitled".into());
+        format!("{merged_filename} (Merge)").into()
+    }
+
+    fn tab_tooltip_text(&self, cx: &App) -> Option<ui::SharedString> {
+        let merged_path = self
+            .merged_path(cx)
+            .map(|path| path.compact().to_string_lossy().to_string())
+            .unwrap_or_else(|| "untitled".into());
+        Some(
+            format!(
+                "{} + {} → {merged_path}",
+                self.local_path.compact().to_string_lossy(),
+                self.remote_path.compact().to_string_lossy()
+            )
+            .into(),
+        )
+    }
+
+    fn to_item_events(event: &EditorEvent, f: impl FnMut(ItemEvent)) {
+        Editor::to_item_events(event, f)
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        Some("Merge View Opened")
+    }
+
+    fn deactivated(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor
+            .update(cx, |editor, cx| editor.deactivated(window, cx));
+    }
+
+    fn is_singleton(&self, _: &App) -> bool {
+        false
+    }
+
+    fn act_as_type<'a>(
+        &'a self,
+        type_id: TypeId,
+        self_handle: &'a Entity<Self>,
+        _: &'a App,
+    ) -> Option<AnyView> {
+        if type_id == TypeId::of::<Self>() {
+            Some(self_handle.to_any())
+        } else if type_id == TypeId::of::<Editor>() {
+            Some(self.editor.to_any())
+        } else {
+            None
+        }
+    }
+
+    fn as_searchable(&self, _: &Entity<Self>) -> Option<Box<dyn SearchableItemHandle>> {
+        Some(Box::new(self.editor.clone()))
+    }
+
+    fn for_each_project_item(
+        &self,
+        cx: &App,
+        f: &mut dyn FnMut(gpui::EntityId, &dyn project::ProjectItem),
+    ) {
+        self.editor.for_each_project_item(cx, f)
+    }
+
+    fn set_nav_history(
+        &mut self,
+        nav_history: ItemNavHistory,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, _| {
+            editor.set_nav_history(Some(nav_history));
+        });
+    }
+
+    fn navigate(
+        &mut self,
+        data: Box<dyn Any>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        self.editor
+            .update(cx, |editor, cx| editor.navigate(data, window, cx))
+    }
+
+    fn breadcrumb_location(&self, _: &App) -> ToolbarItemLocation {
+        ToolbarItemLocation::PrimaryLeft
+    }
+
+    fn breadcrumbs(&self, theme: &theme::Theme, cx: &App) -> Option<Vec<BreadcrumbText>> {
+        self.editor.breadcrumbs(theme, cx)
+    }
+
+    fn added_to_workspace(
+        &mut self,
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.editor.update(cx, |editor, cx| {
+            editor.added_to_workspace(workspace, window, cx)
+        });
+    }
+
+    // The merged file is written when the view is closed, so closing it never
+    // prompts to save.
+    fn is_dirty(&self, _: &App) -> bool {
+        false
+    }
+
+    fn can_save(&self, cx: &App) -> bool {
+        self.editor.read(cx).can_save(cx)
+    }
+
+    fn save(
+        &mut self,
+        options: SaveOptions,
+        project: Entity<Project>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        self.editor
+            .update(cx, |editor, cx| editor.save(options, project, window, cx))
+    }
+}
+
+impl Render for MergeView {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        self.editor.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Fs, Project};
+    use settings::{Settings, SettingsStore};
+    use unindent::unindent;
+    use util::path;
+    use workspace::Workspace;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings_store = SettingsStore::test(cx);
+            cx.set_global(settings_store);
+            language::init(cx);
+            Project::init_settings(cx);
+            workspace::init_settings(cx);
+            editor::init_settings(cx);
+            theme::ThemeSettings::register(cx)
+        });
+    }
+
+    #[test]
+    fn test_merge_texts() {
+        let base = "one\ntwo\nthree\nfour\nfive\n";
+
+        // Changes to different lines are merged.
+        assert_eq!(
+            merge_texts(
+                base,
+                "ONE\ntwo\nthree\nfour\nfive\n",
+                "one\ntwo\nthree\nfour\nFIVE\n",
+                "local",
+                "remote"
+            ),
+            "ONE\ntwo\nthree\nfour\nFIVE\n"
+        );
+
+        // Identical changes are merged.
+        assert_eq!(
+            merge_texts(
+                base,
+                "one\nTWO\nthree\nfour\nfive\n",
+                "one\nTWO\nthree\nfour\nfive\n",
+                "local",
+                "remote"
+            ),
+            "one\nTWO\nthree\nfour\nfive\n"
+        );
+
+        // Conflicting changes are marked.
+        assert_eq!(
+            merge_texts(
+                base,
+                "one\ntwo\nTHREE\nfour\nfive\nsix\n",
+                "one\ntwo\n3\nfour\nfive",
+                "local",
+                "remote"
+            ),
+            unindent(
+                "
+                one
+                two
+                <<<<<<< local
+                THREE
+                ||||||| base
+                three
+                =======
+                3
+                >>>>>>> remote
+                four
+                <<<<<<< local
+                five
+                six
+                ||||||| base
+                five
+                =======
+                five
+                >>>>>>> remote
+                "
+            )
+        );
+
+        // A missing newline at the end of both sides is kept.
+        assert_eq!(
+            merge_texts(
+                "one\ntwo\nthree",
+                "ONE\ntwo\nthree",
+                "one\ntwo\nTHREE",
+                "local",
+                "remote"
+            ),
+            "ONE\ntwo\nTHREE"
+        );
+        assert_eq!(
+            merge_texts("one\ntwo", "one\nTWO", "one\n2", "local", "remote"),
+            "one\n<<<<<<< local\nTWO\n||||||| base\ntwo\n=======\n2\n>>>>>>> remote\n"
+        );
+    }
+
+    #[gpui::test]
+    async fn test_merge_view(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            path!("/test"),
+            serde_json::json!({
+                "base.txt": "one\ntwo\nthree\n",
+                "local.txt": "ONE\ntwo\nthree (local)\n",
+                "remote.txt": "one\ntwo\nthree (remote)\n",
+                "merged.txt": "",
+            }),
+        )
+        .await;
+
+        let project = Project::test(fs.clone(), [path!("/test").as_ref()], cx).await;
+
+        let (workspace, cx) =
+            cx.add_window_view(|window, cx| Workspace::test_new(project.clone(), window, cx));
+
+        let merge_view = workspace
+            .update_in(cx, |workspace, window, cx| {
+                MergeView::open(
+                    PathBuf::from(path!("/test/base.txt")),
+                    PathBuf::from(path!("/test/local.txt")),
+                    PathBuf::from(path!("/test/remote.txt")),
+                    PathBuf::from(path!("/test/merged.txt")),
+                    workspace,
+                    window,
+                    cx,
+                )
+            })
+            .await
+            .unwrap();
+        cx.run_until_parked();
+
+        let conflict_set = merge_view.read_with(cx, |merge_view, cx| {
+            assert_eq!(
+                merge_view.merged_buffer.read(cx).text(),
+                unindent(
+                    "
+                    ONE
+                    two
+                    <<<<<<< local.txt
+                    three (local)
+                    ||||||| base
+                    three
+                    =======
+                    three (remote)
+                    >>>>>>> remote.txt
+                    "
+                )
+            );
+            merge_view._conflict_set.clone()
+        });
+        conflict_set.read_with(cx, |conflict_set, _| {
+            assert!(conflict_set.has_conflict);
+            assert_eq!(conflict_set.snapshot().conflicts.len(), 1);
+        });
+        drop(conflict_set);
+
+        let merge_result = merge_view.update_in(cx, |merge_view, window, cx| {
+            merge_view.editor.update(cx, |editor, cx| {
+                editor.set_text("ONE\ntwo\nthree (merged)\n", window, cx);
+            });
+            merge_view.merge_result()
+        });
+        drop(merge_view);
+
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.active_pane().update(cx, |pane, cx| {
+                pane.close_active_item(&Default::default(), window, cx)
+                    .detach_and_log_err(cx);
+            });
+        });
+        cx.run_until_parked();
+
+        assert!(merge_result.await.unwrap().unwrap());
+        assert_eq!(
+            fs.load(path!("/test/merged.txt").as_ref()).await.unwrap(),
+            "ONE\ntwo\nthree (merged)\n"
+        );
+    }
+}
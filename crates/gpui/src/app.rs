@@ -618,6 +618,12 @@ impl App {
         self.platform.active_window()
     }
 
+    /// Returns the ID of the window that is currently being updated, such as a window that is
+    /// being drawn or handling an event, if any.
+    pub fn updating_window_id(&self) -> Option<WindowId> {
+        self.window_update_stack.last().copied()
+    }
+
     /// Opens a new window with the given option and the root view returned by the given function.
     /// The function is invoked with a `Window`, which can be used to interact with window-specific
     /// functionality.
@@ -59,8 +59,9 @@ use text::*;
 pub use text::{
     Anchor, Bias, Buffer as TextBuffer, BufferId, BufferSnapshot as TextBufferSnapshot, Edit,
     LineIndent, OffsetRangeExt, OffsetUtf16, Patch, Point, PointUtf16, Rope, Selection,
-    SelectionGoal, Subscription, TextDimension, TextSummary, ToOffset, ToOffsetUtf16, ToPoint,
-    ToPointUtf16, Transaction, TransactionId, Unclipped,
+    SelectionGoal, SerializedEdit, SerializedTransaction, Subscription, TextDimension, TextSummary,
+    ToOffset, ToOffsetUtf16, ToPoint, ToPointUtf16, Transaction, TransactionId, TransactionSummary,
+    Unclipped, UndoHistorySnapshot, UndoTreeNode,
 };
 use theme::{ActiveTheme as _, SyntaxTheme};
 #[cfg(any(test, feature = "test-support"))]
@@ -1994,6 +1995,14 @@ impl Buffer {
         }
     }
 
+    /// Marks whether the buffer conflicts with its file, regardless of the
+    /// file's modification time, e.g. because it was edited concurrently with
+    /// a reload.
+    pub fn set_conflict(&mut self, has_conflict: bool, cx: &mut Context<Self>) {
+        self.has_conflict = has_conflict;
+        cx.notify();
+    }
+
     /// Gets a [`Subscription`] that tracks all of the changes to the buffer's text.
     pub fn subscribe(&mut self) -> Subscription {
         self.text.subscribe()
@@ -2689,6 +2698,74 @@ impl Buffer {
         undone
     }
 
+    /// Rebuilds the undo stack from transactions serialized while the buffer
+    /// had the same text, e.g. in a previous session.
+    ///
+    /// The buffer's text is left unchanged, so a buffer that was saved remains so.
+    /// Returns the ids of the restored transactions, from the oldest to the newest.
+    pub fn restore_undo_history(
+        &mut self,
+        transactions: &[SerializedTransaction],
+        cx: &mut Context<Self>,
+    ) -> Result<Vec<TransactionId>> {
+        let was_dirty = self.is_dirty();
+        let was_saved = self.saved_version == self.version;
+        let old_version = self.version.clone();
+
+        let (transaction_ids, operations) = self.text.restore_undo_history(transactions)?;
+        for operation in operations {
+            self.send_operation(Operation::Buffer(operation), true, cx);
+        }
+        if was_saved {
+            self.saved_version = self.version.clone();
+            self.has_unsaved_edits.set((self.version.clone(), false));
+        }
+        self.did_edit(&old_version, was_dirty, cx);
+        Ok(transaction_ids)
+    }
+
+    /// Undoes and redoes transactions so that the buffer is in the state right
+    /// after the given transaction of the undo tree was applied, or in its
+    /// initial state if `target` is `None`.
+    pub fn travel_to_undo_state(
+        &mut self,
+        target: Option<TransactionId>,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let was_dirty = self.is_dirty();
+        let old_version = self.version.clone();
+
+        let operations = self.text.travel_to_undo_state(target);
+        self.apply_history_operations(operations, &old_version, was_dirty, cx)
+    }
+
+    /// Moves through the states of the undo tree in the order they were
+    /// created, going back in time when `delta` is negative.
+    pub fn travel_undo_history(&mut self, delta: isize, cx: &mut Context<Self>) -> bool {
+        let was_dirty = self.is_dirty();
+        let old_version = self.version.clone();
+
+        let operations = self.text.travel_undo_history(delta);
+        self.apply_history_operations(operations, &old_version, was_dirty, cx)
+    }
+
+    fn apply_history_operations(
+        &mut self,
+        operations: Vec<text::Operation>,
+        old_version: &clock::Global,
+        was_dirty: bool,
+        cx: &mut Context<Self>,
+    ) -> bool {
+        let changed = !operations.is_empty();
+        for operation in operations {
+            self.send_operation(Operation::Buffer(operation), true, cx);
+        }
+        if changed {
+            self.did_edit(old_version, was_dirty, cx);
+        }
+        changed
+    }
+
     pub fn undo_operations(&mut self, counts: HashMap<Lamport, u32>, cx: &mut Context<Buffer>) {
         let was_dirty = self.is_dirty();
         let operation = self.text.undo_operations(counts);
@@ -4068,6 +4145,44 @@ impl BufferSnapshot {
         })
     }
 
+    /// Returns the ranges and targets of any matches of the links query.
+    /// The target of each link is the text of its `@link` capture, which is either a URL
+    /// or a path relative to the buffer's file.
+    pub fn links_in_range<T: ToOffset>(
+        &self,
+        range: Range<T>,
+    ) -> impl Iterator<Item = (Range<usize>, String)> + '_ {
+        let offset_range = range.start.to_offset(self)..range.end.to_offset(self);
+        let mut syntax_matches = self.syntax.matches(offset_range, self, |grammar| {
+            grammar.links_config.as_ref().map(|config| &config.query)
+        });
+
+        let configs = syntax_matches
+            .grammars()
+            .iter()
+            .map(|grammar| grammar.links_config.as_ref())
+            .collect::<Vec<_>>();
+
+        iter::from_fn(move || {
+            while let Some(mat) = syntax_matches.peek() {
+                let link_range = configs[mat.grammar_index].and_then(|config| {
+                    mat.captures
+                        .iter()
+                        .find(|capture| capture.index == config.link_capture_ix)
+                        .map(|capture| capture.node.byte_range())
+                });
+                syntax_matches.advance();
+                if let Some(link_range) = link_range {
+                    let target = self.text_for_range(link_range.clone()).collect::<String>();
+                    if !target.trim().is_empty() {
+                        return Some((link_range, target));
+                    }
+                }
+            }
+            None
+        })
+    }
+
     pub fn injections_intersecting_range<T: ToOffset>(
         &self,
         range: Range<T>,
@@ -192,6 +192,21 @@ async fn test_first_line_pattern(cx: &mut TestAppContext) {
         .name(),
         "JavaScript".into()
     );
+
+    assert!(
+        languages
+            .language_for_content(&"nothing".into())
+            .await
+            .is_err()
+    );
+    assert_eq!(
+        languages
+            .language_for_content(&"#!/bin/env node".into())
+            .await
+            .unwrap()
+            .name(),
+        "JavaScript".into()
+    );
 }
 
 #[gpui::test]
@@ -2828,6 +2843,39 @@ fn test_undo_after_merge_into_base(cx: &mut TestAppContext) {
     branch.read_with(cx, |branch, _| assert_eq!(branch.text(), "ABCdefgHIjk"));
 }
 
+#[gpui::test]
+fn test_restore_undo_history(cx: &mut TestAppContext) {
+    cx.update(|cx| init_settings(cx, |_| {}));
+
+    let buffer = cx.new(|cx| Buffer::local("abc", cx));
+    let transactions = buffer.update(cx, |buffer, cx| {
+        buffer.edit([(0..0, "1")], None, cx);
+        buffer.finalize_last_transaction();
+        buffer.edit([(4..4, "2")], None, cx);
+        buffer.serialize_undo_history(usize::MAX)
+    });
+    assert_eq!(transactions.len(), 2);
+
+    let restored = cx.new(|cx| Buffer::local("1abc2", cx));
+    restored.update(cx, |buffer, cx| {
+        assert!(!buffer.is_dirty());
+        let transaction_ids = buffer.restore_undo_history(&transactions, cx).unwrap();
+        assert_eq!(transaction_ids.len(), 2);
+        assert_eq!(buffer.text(), "1abc2");
+        assert!(!buffer.is_dirty());
+
+        buffer.undo(cx);
+        assert_eq!(buffer.text(), "1abc");
+        assert!(buffer.is_dirty());
+        buffer.undo(cx);
+        assert_eq!(buffer.text(), "abc");
+        buffer.redo(cx);
+        buffer.redo(cx);
+        assert_eq!(buffer.text(), "1abc2");
+        assert!(!buffer.is_dirty());
+    });
+}
+
 #[gpui::test]
 async fn test_preview_edits(cx: &mut TestAppContext) {
     cx.update(|cx| {
@@ -3462,6 +3510,43 @@ let word=öäpple.bar你 Öäpple word2-öÄpPlE-Pizza-word ÖÄPPLE word
     });
 }
 
+#[gpui::test]
+fn test_links_in_range(cx: &mut App) {
+    let text = r#"
+        {
+            "name": "zed",
+            "main": "./lib/index.js",
+            "homepage": "https://zed.dev"
+        }
+    "#
+    .unindent();
+
+    let language = json_lang()
+        .with_links_query(
+            r#"
+            (pair
+                key: (string (string_content) @_key)
+                value: (string (string_content) @link)
+                (#any-of? @_key "main" "homepage"))
+            "#,
+        )
+        .unwrap();
+
+    cx.new(|cx| {
+        let buffer = Buffer::local(text, cx).with_language(Arc::new(language), cx);
+        let snapshot = buffer.snapshot();
+        let links = snapshot
+            .links_in_range(0..snapshot.len())
+            .map(|(range, target)| {
+                assert_eq!(snapshot.text_for_range(range).collect::<String>(), target);
+                target
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(links, ["./lib/index.js", "https://zed.dev"]);
+        buffer
+    });
+}
+
 fn ruby_lang() -> Language {
     Language::new(
         LanguageConfig {
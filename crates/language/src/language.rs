@@ -1083,6 +1083,7 @@ pub struct Grammar {
     pub(crate) injection_config: Option<InjectionConfig>,
     pub(crate) override_config: Option<OverrideConfig>,
     pub(crate) debug_variables_config: Option<DebugVariablesConfig>,
+    pub(crate) links_config: Option<LinksConfig>,
     pub(crate) highlight_map: Mutex<HighlightMap>,
 }
 
@@ -1181,6 +1182,11 @@ struct RedactionConfig {
     pub redaction_capture_ix: u32,
 }
 
+struct LinksConfig {
+    pub query: Query,
+    pub link_capture_ix: u32,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 enum RunnableCapture {
     Named(SharedString),
@@ -1260,6 +1266,7 @@ impl Language {
                     runnable_config: None,
                     error_query: Query::new(&ts_language, "(ERROR) @error").ok(),
                     debug_variables_config: None,
+                    links_config: None,
                     ts_language,
                     highlight_map: Default::default(),
                 })
@@ -1335,6 +1342,11 @@ impl Language {
                 .with_debug_variables_query(query.as_ref())
                 .context("Error loading debug variables query")?;
         }
+        if let Some(query) = queries.links {
+            self = self
+                .with_links_query(query.as_ref())
+                .context("Error loading links query")?;
+        }
         Ok(self)
     }
 
@@ -1696,6 +1708,23 @@ impl Language {
         Ok(self)
     }
 
+    pub fn with_links_query(mut self, source: &str) -> anyhow::Result<Self> {
+        let grammar = self.grammar_mut().context("cannot mutate grammar")?;
+
+        let query = Query::new(&grammar.ts_language, source)?;
+        let mut link_capture_ix = None;
+        get_capture_indices(&query, &mut [("link", &mut link_capture_ix)]);
+
+        if let Some(link_capture_ix) = link_capture_ix {
+            grammar.links_config = Some(LinksConfig {
+                query,
+                link_capture_ix,
+            });
+        }
+
+        Ok(self)
+    }
+
     fn grammar_mut(&mut self) -> Option<&mut Grammar> {
         Arc::get_mut(self.grammar.as_mut()?)
     }
@@ -231,6 +231,7 @@ pub const QUERY_FILENAME_PREFIXES: &[(
     ("runnables", |q| &mut q.runnables),
     ("debugger", |q| &mut q.debugger),
     ("textobjects", |q| &mut q.text_objects),
+    ("links", |q| &mut q.links),
 ];
 
 /// Tree-sitter language queries for a given language.
@@ -247,6 +248,7 @@ pub struct LanguageQueries {
     pub runnables: Option<Cow<'static, str>>,
     pub text_objects: Option<Cow<'static, str>>,
     pub debugger: Option<Cow<'static, str>>,
+    pub links: Option<Cow<'static, str>>,
 }
 
 #[derive(Clone, Default)]
@@ -730,6 +732,25 @@ impl LanguageRegistry {
         }
     }
 
+    /// Returns the language whose first line pattern matches the given
+    /// content, e.g. for text that doesn't come from a file.
+    pub fn language_for_content(
+        self: &Arc<Self>,
+        content: &Rope,
+    ) -> impl Future<Output = Result<Arc<Language>>> + use<> {
+        let available_language =
+            self.language_for_file_internal(Path::new(""), Some(content), None);
+
+        let this = self.clone();
+        async move {
+            if let Some(language) = available_language {
+                this.load_language(&language).await?
+            } else {
+                Err(anyhow!(LanguageNotFound))
+            }
+        }
+    }
+
     fn language_for_file_internal(
         self: &Arc<Self>,
         path: &Path,
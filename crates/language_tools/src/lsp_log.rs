@@ -1277,6 +1277,7 @@ impl SearchableItem for LspLogView {
             case: true,
             word: true,
             regex: true,
+            structural: false,
             find_in_results: false,
             // LSP log is read-only.
             replacement: false,
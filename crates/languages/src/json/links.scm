@@ -0,0 +1,8 @@
+; Make JSON schema references and `package.json` entry points clickable
+
+(document
+    (object
+        (pair
+            key: (string (string_content) @_key)
+            value: (string (string_content) @link)
+            (#any-of? @_key "$schema" "main" "module" "types" "typings" "browser"))))
@@ -0,0 +1,2 @@
+(inline_link (link_destination) @link)
+(image (link_destination) @link)
@@ -1448,6 +1448,11 @@ impl BufferStore {
         this.update(&mut cx, |this, cx| {
             if let Some(buffer) = this.get_possibly_incomplete(buffer_id) {
                 buffer.update(cx, |buffer, cx| {
+                    // A conflict from editing the buffer offline is resolved
+                    // once it matches the reloaded file.
+                    if buffer.version() == version {
+                        buffer.set_conflict(false, cx);
+                    }
                     buffer.did_reload(version, line_ending, mtime, cx);
                 });
             }
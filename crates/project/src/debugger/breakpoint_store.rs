@@ -156,6 +156,7 @@ pub struct ActiveStackFrame {
 
 pub struct BreakpointStore {
     breakpoints: BTreeMap<Arc<Path>, BreakpointsInFile>,
+    data_breakpoints: Vec<DataBreakpoint>,
     downstream_client: Option<(AnyProtoClient, u64)>,
     active_stack_frame: Option<ActiveStackFrame>,
     // E.g ssh
@@ -170,6 +171,7 @@ impl BreakpointStore {
     pub fn local(worktree_store: Entity<WorktreeStore>, buffer_store: Entity<BufferStore>) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Local(LocalBreakpointStore {
                 worktree_store,
                 buffer_store,
@@ -182,6 +184,7 @@ impl BreakpointStore {
     pub(crate) fn remote(upstream_project_id: u64, upstream_client: AnyProtoClient) -> Self {
         BreakpointStore {
             breakpoints: BTreeMap::new(),
+            data_breakpoints: Vec::new(),
             mode: BreakpointStoreMode::Remote(RemoteBreakpointStore {
                 upstream_client,
                 _upstream_project_id: upstream_project_id,
@@ -856,6 +859,103 @@ impl BreakpointStore {
         }
     }
 
+    pub fn data_breakpoints(&self) -> &[DataBreakpoint] {
+        &self.data_breakpoints
+    }
+
+    /// Adds a data breakpoint, replacing the one with the same data id if there is one.
+    pub fn add_data_breakpoint(&mut self, breakpoint: DataBreakpoint, cx: &mut Context<Self>) {
+        if let Some(existing) = self
+            .data_breakpoints
+            .iter_mut()
+            .find(|existing| existing.data_id == breakpoint.data_id)
+        {
+            *existing = breakpoint;
+        } else {
+            self.data_breakpoints.push(breakpoint);
+        }
+        cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn remove_data_breakpoint(&mut self, data_id: &str, cx: &mut Context<Self>) {
+        let len_before = self.data_breakpoints.len();
+        self.data_breakpoints
+            .retain(|breakpoint| breakpoint.data_id.as_ref() != data_id);
+        if len_before != self.data_breakpoints.len() {
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        }
+    }
+
+    /// Edits a data breakpoint. `BreakpointEditAction::Toggle` removes it, and log messages are
+    /// ignored, as the protocol has no logpoints for data breakpoints.
+    pub fn edit_data_breakpoint(
+        &mut self,
+        data_id: &str,
+        edit_action: BreakpointEditAction,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(position) = self
+            .data_breakpoints
+            .iter()
+            .position(|breakpoint| breakpoint.data_id.as_ref() == data_id)
+        else {
+            return;
+        };
+        let breakpoint = &mut self.data_breakpoints[position];
+        match edit_action {
+            BreakpointEditAction::Toggle => {
+                self.data_breakpoints.remove(position);
+            }
+            BreakpointEditAction::InvertState => {
+                breakpoint.state = if breakpoint.state.is_enabled() {
+                    BreakpointState::Disabled
+                } else {
+                    BreakpointState::Enabled
+                };
+            }
+            BreakpointEditAction::EditCondition(condition) => {
+                breakpoint.condition = (!condition.is_empty()).then_some(condition);
+            }
+            BreakpointEditAction::EditHitCondition(hit_condition) => {
+                breakpoint.hit_condition = (!hit_condition.is_empty()).then_some(hit_condition);
+            }
+            BreakpointEditAction::EditLogMessage(_) => return,
+        }
+        cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+        cx.notify();
+    }
+
+    pub fn clear_data_breakpoints(&mut self, cx: &mut Context<Self>) {
+        if !self.data_breakpoints.is_empty() {
+            self.data_breakpoints.clear();
+            cx.emit(BreakpointStoreEvent::DataBreakpointsUpdated);
+            cx.notify();
+        }
+    }
+
+    /// The data breakpoints that are valid across debug sessions, and hence can be stored with
+    /// the workspace.
+    pub fn persistent_data_breakpoints(&self) -> Vec<DataBreakpoint> {
+        self.data_breakpoints
+            .iter()
+            .filter(|breakpoint| breakpoint.can_persist)
+            .cloned()
+            .collect()
+    }
+
+    pub fn with_serialized_data_breakpoints(
+        &mut self,
+        data_breakpoints: Vec<DataBreakpoint>,
+        cx: &mut Context<Self>,
+    ) {
+        if let BreakpointStoreMode::Local(_) = &self.mode {
+            self.data_breakpoints = data_breakpoints;
+            cx.notify();
+        }
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub(crate) fn breakpoint_paths(&self) -> Vec<Arc<Path>> {
         self.breakpoints.keys().cloned().collect()
@@ -873,6 +973,7 @@ pub enum BreakpointStoreEvent {
     ClearDebugLines,
     BreakpointsUpdated(Arc<Path>, BreakpointUpdatedReason),
     BreakpointsCleared(Vec<Arc<Path>>),
+    DataBreakpointsUpdated,
 }
 
 impl EventEmitter<BreakpointStoreEvent> for BreakpointStore {}
@@ -1018,6 +1119,80 @@ pub struct SourceBreakpoint {
     pub state: BreakpointState,
 }
 
+#[derive(Copy, Clone, Debug, Hash, PartialEq, Eq)]
+pub enum DataBreakpointAccessType {
+    Read,
+    Write,
+    ReadWrite,
+}
+
+impl DataBreakpointAccessType {
+    pub fn label(&self) -> &'static str {
+        match self {
+            DataBreakpointAccessType::Read => "read",
+            DataBreakpointAccessType::Write => "write",
+            DataBreakpointAccessType::ReadWrite => "read/write",
+        }
+    }
+}
+
+impl From<DataBreakpointAccessType> for dap::DataBreakpointAccessType {
+    fn from(access_type: DataBreakpointAccessType) -> Self {
+        match access_type {
+            DataBreakpointAccessType::Read => Self::Read,
+            DataBreakpointAccessType::Write => Self::Write,
+            DataBreakpointAccessType::ReadWrite => Self::ReadWrite,
+        }
+    }
+}
+
+impl From<dap::DataBreakpointAccessType> for DataBreakpointAccessType {
+    fn from(access_type: dap::DataBreakpointAccessType) -> Self {
+        match access_type {
+            dap::DataBreakpointAccessType::Read => Self::Read,
+            dap::DataBreakpointAccessType::Write => Self::Write,
+            dap::DataBreakpointAccessType::ReadWrite => Self::ReadWrite,
+        }
+    }
+}
+
+/// Breakpoint that stops when a variable or a memory location is accessed, also known as a watchpoint.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataBreakpoint {
+    /// The id the debug adapter gave to the data, from a `dataBreakpointInfo` request.
+    pub data_id: Arc<str>,
+    /// Describes the data, such as the name of the variable.
+    pub description: Arc<str>,
+    pub access_type: Option<DataBreakpointAccessType>,
+    pub condition: Option<BreakpointMessage>,
+    pub hit_condition: Option<Arc<str>>,
+    pub state: BreakpointState,
+    /// Whether the data id stays valid in later debug sessions.
+    pub can_persist: bool,
+}
+
+impl DataBreakpoint {
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.state.is_enabled()
+    }
+}
+
+impl From<DataBreakpoint> for dap::DataBreakpoint {
+    fn from(bp: DataBreakpoint) -> Self {
+        Self {
+            data_id: String::from(bp.data_id.as_ref()),
+            access_type: bp.access_type.map(Into::into),
+            condition: bp
+                .condition
+                .map(|condition| String::from(condition.as_ref())),
+            hit_condition: bp
+                .hit_condition
+                .map(|hit_condition| String::from(hit_condition.as_ref())),
+        }
+    }
+}
+
 impl From<SourceBreakpoint> for dap::SourceBreakpoint {
     fn from(bp: SourceBreakpoint) -> Self {
         Self {
@@ -253,6 +253,7 @@ mod tests {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            ..Default::default()
         };
 
         let scenario = locator
@@ -280,6 +281,7 @@ mod tests {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            ..Default::default()
         };
 
         let scenario = locator
@@ -418,6 +420,7 @@ mod tests {
             tags: vec![],
             show_summary: true,
             show_command: true,
+            ..Default::default()
         };
 
         let scenario = locator
@@ -1,18 +1,22 @@
 use crate::debugger::breakpoint_store::BreakpointSessionState;
 
 use super::breakpoint_store::{
-    BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason, SourceBreakpoint,
+    BreakpointState, BreakpointStore, BreakpointStoreEvent, BreakpointUpdatedReason,
+    DataBreakpoint, SourceBreakpoint,
 };
 use super::dap_command::{
-    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DisconnectCommand,
-    EvaluateCommand, Initialize, Launch, LoadedSourcesCommand, LocalDapCommand, LocationsCommand,
-    ModulesCommand, NextCommand, PauseCommand, RestartCommand, RestartStackFrameCommand,
-    ScopesCommand, SetExceptionBreakpoints, SetVariableValueCommand, StackTraceCommand,
-    StepBackCommand, StepCommand, StepInCommand, StepOutCommand, TerminateCommand,
-    TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    self, Attach, ConfigurationDone, ContinueCommand, DapCommand, DataBreakpointInfoCommand,
+    DisassembleCommand, DisconnectCommand, EvaluateCommand, Initialize, Launch,
+    LoadedSourcesCommand, LocalDapCommand, LocationsCommand, ModulesCommand, NextCommand,
+    PauseCommand, ReadMemoryCommand, RestartCommand, RestartStackFrameCommand,
+    ReverseContinueCommand, ScopesCommand, SetDataBreakpoints, SetExceptionBreakpoints,
+    SetVariableValueCommand, StackTraceCommand, StepBackCommand, StepCommand, StepInCommand,
+    StepOutCommand, TerminateCommand, TerminateThreadsCommand, ThreadsCommand, VariablesCommand,
+    WriteMemoryCommand,
 };
 use super::dap_store::DapStore;
 use anyhow::{Context as _, Result, anyhow};
+use base64::Engine as _;
 use collections::{HashMap, HashSet, IndexMap};
 use dap::adapters::{DebugAdapterBinary, DebugAdapterName};
 use dap::messages::Response;
@@ -316,6 +320,27 @@ impl RunningMode {
         self.request(arg)
     }
 
+    fn send_data_breakpoints(
+        &self,
+        ignore_breakpoints: bool,
+        breakpoint_store: &Entity<BreakpointStore>,
+        cx: &App,
+    ) -> Task<Result<Vec<dap::Breakpoint>>> {
+        let breakpoints = if ignore_breakpoints {
+            vec![]
+        } else {
+            breakpoint_store
+                .read(cx)
+                .data_breakpoints()
+                .iter()
+                .filter(|breakpoint| breakpoint.is_enabled())
+                .cloned()
+                .map(Into::into)
+                .collect()
+        };
+        self.request(SetDataBreakpoints { breakpoints })
+    }
+
     fn send_source_breakpoints(
         &self,
         ignore_breakpoints: bool,
@@ -409,6 +434,7 @@ impl RunningMode {
         };
 
         let configuration_done_supported = ConfigurationDone::is_supported(capabilities);
+        let data_breakpoints_supported = SetDataBreakpoints::is_supported(capabilities);
         let exception_filters = capabilities
             .exception_breakpoint_filters
             .as_ref()
@@ -461,6 +487,13 @@ impl RunningMode {
                     }
                 })?;
 
+                if data_breakpoints_supported {
+                    cx.update(|cx| this.send_data_breakpoints(false, &breakpoint_store, cx))?
+                        .await
+                        .context("setting data breakpoints")
+                        .log_err();
+                }
+
                 this.send_exception_breakpoints(exception_filters, supports_exception_filters)
                     .await
                     .ok();
@@ -699,6 +732,29 @@ impl Hash for RequestSlot {
     }
 }
 
+/// Bytes read from the memory of the debuggee.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MemoryBlock {
+    /// The address of the first byte.
+    pub address: u64,
+    pub data: Vec<u8>,
+    /// The number of bytes after `data` that could not be read.
+    pub unreadable_bytes: u64,
+}
+
+/// Parses an address as sent by debug adapters, which is either hexadecimal with a `0x` prefix
+/// or decimal.
+pub fn parse_memory_address(address: &str) -> Option<u64> {
+    let address = address.trim();
+    match address
+        .strip_prefix("0x")
+        .or_else(|| address.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => address.parse().ok(),
+    }
+}
+
 #[derive(Debug, Clone, Hash, PartialEq, Eq)]
 pub struct CompletionsQuery {
     pub query: String,
@@ -787,6 +843,19 @@ impl Session {
                         local.unset_breakpoints_from_paths(paths, cx).detach();
                     }
                 }
+                BreakpointStoreEvent::DataBreakpointsUpdated => {
+                    if !SetDataBreakpoints::is_supported(&this.capabilities) {
+                        return;
+                    }
+                    if let Some(local) = (!this.ignore_breakpoints)
+                        .then(|| this.as_running_mut())
+                        .flatten()
+                    {
+                        local
+                            .send_data_breakpoints(false, &store, cx)
+                            .detach_and_log_err(cx);
+                    }
+                }
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
             })
             .detach();
@@ -1701,6 +1770,11 @@ impl Session {
         self.ignore_breakpoints = ignore;
 
         if let Some(local) = self.as_running() {
+            if SetDataBreakpoints::is_supported(&self.capabilities) {
+                local
+                    .send_data_breakpoints(ignore, &self.breakpoint_store, cx)
+                    .detach_and_log_err(cx);
+            }
             local.send_source_breakpoints(ignore, &self.breakpoint_store, cx)
         } else {
             // todo(debugger): We need to propagate this change to downstream sessions and send a message to upstream sessions
@@ -1741,6 +1815,156 @@ impl Session {
         }
     }
 
+    /// Asks the debug adapter whether a data breakpoint can be set on a variable, and with which
+    /// access types.
+    pub fn data_breakpoint_info(
+        &self,
+        variables_reference: Option<u64>,
+        name: String,
+        frame_id: Option<u64>,
+    ) -> Task<Result<dap::DataBreakpointInfoResponse>> {
+        self.request_local(DataBreakpointInfoCommand {
+            variables_reference,
+            name,
+            frame_id,
+        })
+    }
+
+    /// Reads `count` bytes of memory, starting `offset` bytes after the location of a memory
+    /// reference, such as the one of a variable or a stack frame's instruction pointer.
+    pub fn read_memory(
+        &self,
+        memory_reference: String,
+        offset: i64,
+        count: u64,
+        cx: &App,
+    ) -> Task<Result<MemoryBlock>> {
+        let response = self.request_local(ReadMemoryCommand {
+            memory_reference,
+            offset,
+            count,
+        });
+        cx.background_spawn(async move {
+            let response = response.await?;
+            let address = parse_memory_address(&response.address)
+                .with_context(|| format!("invalid memory address {:?}", response.address))?;
+            let data = match response.data {
+                Some(data) => base64::engine::general_purpose::STANDARD
+                    .decode(data)
+                    .context("decoding memory")?,
+                None => Vec::new(),
+            };
+            Ok(MemoryBlock {
+                address,
+                data,
+                unreadable_bytes: response.unreadable_bytes.unwrap_or_default(),
+            })
+        })
+    }
+
+    /// Writes bytes to memory, starting `offset` bytes after the location of a memory reference.
+    /// Returns the number of bytes written.
+    pub fn write_memory(
+        &mut self,
+        memory_reference: String,
+        offset: i64,
+        data: &[u8],
+        cx: &mut Context<Self>,
+    ) -> Task<Result<u64>> {
+        let len = data.len() as u64;
+        let response = self.request_local(WriteMemoryCommand {
+            memory_reference,
+            offset,
+            data: base64::engine::general_purpose::STANDARD.encode(data),
+        });
+        cx.spawn(async move |this, cx| {
+            let response = response.await?;
+            // Writing memory can change the value of any variable.
+            this.update(cx, |this, cx| {
+                this.invalidate_command_type::<VariablesCommand>();
+                cx.emit(SessionEvent::Variables);
+            })?;
+            Ok(response.bytes_written.unwrap_or(len))
+        })
+    }
+
+    /// Disassembles `instruction_count` instructions, starting `instruction_offset` instructions
+    /// after the location of a memory reference.
+    pub fn disassemble(
+        &self,
+        memory_reference: String,
+        instruction_offset: i64,
+        instruction_count: u64,
+    ) -> Task<Result<Vec<dap::DisassembledInstruction>>> {
+        self.request_local(DisassembleCommand {
+            memory_reference,
+            instruction_offset,
+            instruction_count,
+        })
+    }
+
+    /// Sends a request that is not forwarded to collaborators, if the debug adapter supports it.
+    fn request_local<T: LocalDapCommand>(&self, request: T) -> Task<Result<T::Response>>
+    where
+        <T::DapRequest as dap::requests::Request>::Response: 'static,
+        <T::DapRequest as dap::requests::Request>::Arguments: 'static + Send,
+    {
+        if !T::is_supported(&self.capabilities) {
+            return Task::ready(Err(anyhow!(
+                "{} does not support the {} request",
+                self.adapter,
+                T::DapRequest::COMMAND
+            )));
+        }
+        let Some(local) = self.as_running() else {
+            return Task::ready(Err(anyhow!(
+                "the {} request is not supported in remote sessions",
+                T::DapRequest::COMMAND
+            )));
+        };
+        local.request(request)
+    }
+
+    /// Sets a data breakpoint that stops when the given variable is written to, or accessed if
+    /// the debug adapter cannot watch for writes alone.
+    pub fn add_data_breakpoint(
+        &self,
+        variables_reference: Option<u64>,
+        name: String,
+        frame_id: Option<u64>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let info = self.data_breakpoint_info(variables_reference, name, frame_id);
+        let breakpoint_store = self.breakpoint_store.clone();
+        cx.spawn(async move |_, cx| {
+            let info = info.await?;
+            let data_id = info
+                .data_id
+                .with_context(|| format!("cannot set a data breakpoint: {}", info.description))?;
+            let access_type = info.access_types.as_ref().and_then(|access_types| {
+                access_types
+                    .iter()
+                    .find(|access_type| matches!(access_type, dap::DataBreakpointAccessType::Write))
+                    .or_else(|| access_types.first())
+                    .cloned()
+            });
+            breakpoint_store.update(cx, |breakpoint_store, cx| {
+                breakpoint_store.add_data_breakpoint(
+                    DataBreakpoint {
+                        data_id: data_id.into(),
+                        description: info.description.into(),
+                        access_type: access_type.map(Into::into),
+                        condition: None,
+                        hit_condition: None,
+                        state: BreakpointState::Enabled,
+                        can_persist: info.can_persist.unwrap_or_default(),
+                    },
+                    cx,
+                )
+            })
+        })
+    }
+
     pub fn breakpoints_enabled(&self) -> bool {
         self.ignore_breakpoints
     }
@@ -2051,6 +2275,23 @@ impl Session {
         .detach();
     }
 
+    /// Runs the thread backwards until a breakpoint or the start of the recording is reached.
+    pub fn reverse_continue(&mut self, thread_id: ThreadId, cx: &mut Context<Self>) {
+        let command = ReverseContinueCommand {
+            thread_id: thread_id.0,
+            single_thread: self.capabilities.supports_single_thread_execution_requests,
+        };
+
+        self.thread_states.continue_thread(thread_id);
+
+        self.request(
+            command,
+            Self::on_step_response::<ReverseContinueCommand>(thread_id),
+            cx,
+        )
+        .detach();
+    }
+
     pub fn stack_frames(
         &mut self,
         thread_id: ThreadId,
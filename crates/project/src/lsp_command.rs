@@ -2,7 +2,7 @@ mod signature_help;
 
 use crate::{
     CodeAction, CompletionSource, CoreCompletion, CoreCompletionResponse, DocumentColor,
-    DocumentHighlight, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
+    DocumentHighlight, DocumentLink, DocumentSymbol, Hover, HoverBlock, HoverBlockKind, InlayHint,
     InlayHintLabel, InlayHintLabelPart, InlayHintLabelPartTooltip, InlayHintTooltip, Location,
     LocationLink, LspAction, LspPullDiagnostics, MarkupContent, PrepareRenameResponse,
     ProjectTransaction, PulledDiagnostics, ResolveState,
@@ -247,6 +247,9 @@ pub(crate) struct GetCodeLens;
 #[derive(Debug, Copy, Clone)]
 pub(crate) struct GetDocumentColor;
 
+#[derive(Debug, Copy, Clone)]
+pub(crate) struct GetDocumentLinks;
+
 impl GetCodeLens {
     pub(crate) fn can_resolve_lens(capabilities: &ServerCapabilities) -> bool {
         capabilities
@@ -257,6 +260,120 @@ impl GetCodeLens {
     }
 }
 
+impl GetDocumentLinks {
+    pub(crate) fn can_resolve_links(capabilities: &ServerCapabilities) -> bool {
+        capabilities
+            .document_link_provider
+            .as_ref()
+            .and_then(|document_link_options| document_link_options.resolve_provider)
+            .unwrap_or(false)
+    }
+
+    pub(crate) fn lsp_to_project_link(
+        link: lsp::DocumentLink,
+        snapshot: &BufferSnapshot,
+        resolve_state: ResolveState,
+    ) -> DocumentLink {
+        let link_range = range_from_lsp(link.range);
+        let start = snapshot.clip_point_utf16(link_range.start, Bias::Left);
+        let end = snapshot.clip_point_utf16(link_range.end, Bias::Right);
+        DocumentLink {
+            range: snapshot.anchor_before(start)..snapshot.anchor_after(end),
+            target: link.target.map(|target| target.to_string()),
+            tooltip: link.tooltip,
+            resolve_state,
+        }
+    }
+
+    pub(crate) fn project_to_lsp_link(
+        link: DocumentLink,
+        snapshot: &BufferSnapshot,
+    ) -> Result<lsp::DocumentLink> {
+        let range =
+            link.range.start.to_point_utf16(snapshot)..link.range.end.to_point_utf16(snapshot);
+        Ok(lsp::DocumentLink {
+            range: range_to_lsp(range)?,
+            target: link
+                .target
+                .as_deref()
+                .and_then(|target| lsp::Url::parse(target).ok()),
+            tooltip: link.tooltip,
+            data: match link.resolve_state {
+                ResolveState::CanResolve(_, data) => data,
+                ResolveState::Resolved | ResolveState::Resolving => None,
+            },
+        })
+    }
+
+    pub(crate) fn project_to_proto_link(link: DocumentLink) -> proto::DocumentLink {
+        let (state, lsp_resolve_state) = match link.resolve_state {
+            ResolveState::Resolved => (0, None),
+            ResolveState::CanResolve(server_id, resolve_data) => (
+                1,
+                Some(proto::resolve_state::LspResolveState {
+                    server_id: server_id.to_proto(),
+                    value: resolve_data.map(|json_data| {
+                        serde_json::to_string(&json_data)
+                            .expect("failed to serialize resolve json data")
+                    }),
+                }),
+            ),
+            ResolveState::Resolving => (2, None),
+        };
+        proto::DocumentLink {
+            start: Some(serialize_anchor(&link.range.start)),
+            end: Some(serialize_anchor(&link.range.end)),
+            target: link.target,
+            tooltip: link.tooltip,
+            resolve_state: Some(proto::ResolveState {
+                state,
+                lsp_resolve_state,
+            }),
+        }
+    }
+
+    pub(crate) fn proto_to_project_link(message_link: proto::DocumentLink) -> Result<DocumentLink> {
+        let resolve_state = message_link
+            .resolve_state
+            .context("incorrect proto document link message: no resolve state")?;
+        let resolve_state = match resolve_state.state {
+            0 => ResolveState::Resolved,
+            1 => {
+                let lsp_resolve_state = resolve_state
+                    .lsp_resolve_state
+                    .context("no lsp resolve data for the document link that can be resolved")?;
+                let value = lsp_resolve_state
+                    .value
+                    .as_deref()
+                    .map(serde_json::from_str::<Option<lsp::LSPAny>>)
+                    .transpose()
+                    .context("incorrect proto document link message: non-json resolve state")?
+                    .flatten();
+                ResolveState::CanResolve(
+                    LanguageServerId::from_proto(lsp_resolve_state.server_id),
+                    value,
+                )
+            }
+            2 => ResolveState::Resolving,
+            invalid => anyhow::bail!("Unexpected resolve state {invalid} for document link"),
+        };
+        let start = message_link
+            .start
+            .and_then(deserialize_anchor)
+            .context("invalid document link start")?;
+        let end = message_link
+            .end
+            .and_then(deserialize_anchor)
+            .context("invalid document link end")?;
+        Ok(DocumentLink {
+            range: start..end,
+            target: message_link.target,
+            tooltip: message_link.tooltip,
+            resolve_state,
+        })
+    }
+}
+
 #[derive(Debug)]
 pub(crate) struct LinkedEditingRange {
     pub position: Anchor,
@@ -4285,6 +4402,131 @@ impl LspCommand for GetDocumentColor {
     }
 }
 
+#[async_trait(?Send)]
+impl LspCommand for GetDocumentLinks {
+    type Response = Vec<DocumentLink>;
+    type LspRequest = lsp::request::DocumentLinkRequest;
+    type ProtoRequest = proto::GetDocumentLinks;
+
+    fn display_name(&self) -> &str {
+        "Document links"
+    }
+
+    fn check_capabilities(&self, server_capabilities: AdapterServerCapabilities) -> bool {
+        server_capabilities
+            .server_capabilities
+            .document_link_provider
+            .is_some()
+    }
+
+    fn to_lsp(
+        &self,
+        path: &Path,
+        _: &Buffer,
+        _: &Arc<LanguageServer>,
+        _: &App,
+    ) -> Result<lsp::DocumentLinkParams> {
+        Ok(lsp::DocumentLinkParams {
+            text_document: make_text_document_identifier(path)?,
+            work_done_progress_params: Default::default(),
+            partial_result_params: Default::default(),
+        })
+    }
+
+    async fn response_from_lsp(
+        self,
+        message: Option<Vec<lsp::DocumentLink>>,
+        lsp_store: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        mut cx: AsyncApp,
+    ) -> Result<Self::Response> {
+        let snapshot = buffer.read_with(&mut cx, |buffer, _| buffer.snapshot())?;
+        let can_resolve = cx.update(|cx| {
+            lsp_store
+                .read(cx)
+                .language_server_for_id(server_id)
+                .is_some_and(|server| Self::can_resolve_links(&server.capabilities()))
+        })?;
+        Ok(message
+            .unwrap_or_default()
+            .into_iter()
+            .filter_map(|link| {
+                let resolve_state = if link.target.is_some() {
+                    ResolveState::Resolved
+                } else if can_resolve {
+                    ResolveState::CanResolve(server_id, link.data.clone())
+                } else {
+                    return None;
+                };
+                Some(Self::lsp_to_project_link(link, &snapshot, resolve_state))
+            })
+            .collect())
+    }
+
+    fn to_proto(&self, project_id: u64, buffer: &Buffer) -> Self::ProtoRequest {
+        proto::GetDocumentLinks {
+            project_id,
+            buffer_id: buffer.remote_id().to_proto(),
+            version: serialize_version(&buffer.version()),
+        }
+    }
+
+    async fn from_proto(
+        message: Self::ProtoRequest,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        Ok(Self)
+    }
+
+    fn response_to_proto(
+        response: Self::Response,
+        _: &mut LspStore,
+        _: PeerId,
+        buffer_version: &clock::Global,
+        _: &mut App,
+    ) -> proto::GetDocumentLinksResponse {
+        proto::GetDocumentLinksResponse {
+            links: response
+                .into_iter()
+                .map(Self::project_to_proto_link)
+                .collect(),
+            version: serialize_version(buffer_version),
+        }
+    }
+
+    async fn response_from_proto(
+        self,
+        message: proto::GetDocumentLinksResponse,
+        _: Entity<LspStore>,
+        buffer: Entity<Buffer>,
+        mut cx: AsyncApp,
+    ) -> Result<Self::Response> {
+        buffer
+            .update(&mut cx, |buffer, _| {
+                buffer.wait_for_version(deserialize_version(&message.version))
+            })?
+            .await?;
+        message
+            .links
+            .into_iter()
+            .map(Self::proto_to_project_link)
+            .collect::<Result<Vec<_>>>()
+            .context("deserializing proto document links response")
+    }
+
+    fn buffer_id_from_proto(message: &Self::ProtoRequest) -> Result<BufferId> {
+        BufferId::new(message.buffer_id)
+    }
+}
+
 fn process_related_documents(
     diagnostics: &mut HashMap<lsp::Url, LspPullDiagnostics>,
     server_id: LanguageServerId,
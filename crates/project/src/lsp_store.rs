@@ -4,8 +4,9 @@ pub mod rust_analyzer_ext;
 
 use crate::{
     CodeAction, ColorPresentation, Completion, CompletionResponse, CompletionSource,
-    CoreCompletion, DocumentColor, Hover, InlayHint, LspAction, LspPullDiagnostics, ProjectItem,
-    ProjectPath, ProjectTransaction, PulledDiagnostics, ResolveState, Symbol, ToolchainStore,
+    CoreCompletion, DocumentColor, DocumentLink, Hover, InlayHint, LspAction, LspPullDiagnostics,
+    ProjectItem, ProjectPath, ProjectTransaction, PulledDiagnostics, ResolveState, Symbol,
+    ToolchainStore,
     buffer_store::{BufferStore, BufferStoreEvent},
     environment::ProjectEnvironment,
     lsp_command::{self, *},
@@ -91,6 +92,7 @@ use std::{
     sync::Arc,
     time::{Duration, Instant},
 };
+use task::{Problem, ProblemSeverity, TaskId};
 use text::{Anchor, BufferId, LineEnding, OffsetRangeExt};
 use url::Url;
 use util::{
@@ -171,6 +173,14 @@ pub struct LocalLspStore {
     lsp_tree: Entity<LanguageServerTree>,
     registered_buffers: HashMap<BufferId, usize>,
     buffer_pull_diagnostics_result_ids: HashMap<LanguageServerId, HashMap<PathBuf, Option<String>>>,
+    task_diagnostics: HashMap<TaskId, TaskDiagnostics>,
+}
+
+/// Diagnostics published from the output of a task by its problem matchers.
+struct TaskDiagnostics {
+    /// The id the diagnostics are stored under, in place of a language server's.
+    source_id: LanguageServerId,
+    paths: HashSet<PathBuf>,
 }
 
 impl LocalLspStore {
@@ -3633,6 +3643,7 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_get_project_symbols);
         client.add_entity_request_handler(Self::handle_resolve_inlay_hint);
         client.add_entity_request_handler(Self::handle_get_color_presentation);
+        client.add_entity_request_handler(Self::handle_resolve_document_link);
         client.add_entity_request_handler(Self::handle_open_buffer_for_symbol);
         client.add_entity_request_handler(Self::handle_refresh_inlay_hints);
         client.add_entity_request_handler(Self::handle_refresh_code_lens);
@@ -3654,6 +3665,7 @@ impl LspStore {
         client.add_entity_request_handler(Self::handle_lsp_command::<PrepareRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<PerformRename>);
         client.add_entity_request_handler(Self::handle_lsp_command::<LinkedEditingRange>);
+        client.add_entity_request_handler(Self::handle_lsp_command::<GetDocumentLinks>);
 
         client.add_entity_request_handler(Self::handle_lsp_ext_cancel_flycheck);
         client.add_entity_request_handler(Self::handle_lsp_ext_run_flycheck);
@@ -3781,6 +3793,7 @@ impl LspStore {
                 lsp_tree: LanguageServerTree::new(manifest_tree, languages.clone(), cx),
                 registered_buffers: HashMap::default(),
                 buffer_pull_diagnostics_result_ids: HashMap::default(),
+                task_diagnostics: HashMap::default(),
             }),
             last_formatting_failure: None,
             downstream_client: None,
@@ -5067,6 +5080,62 @@ impl LspStore {
         }
     }
 
+    pub fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        if !matches!(link.resolve_state, ResolveState::CanResolve(..)) {
+            return Task::ready(Ok(link));
+        }
+
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request = proto::ResolveDocumentLink {
+                project_id,
+                buffer_id: buffer.read(cx).remote_id().into(),
+                language_server_id: server_id.to_proto(),
+                link: Some(GetDocumentLinks::project_to_proto_link(link.clone())),
+            };
+            cx.background_spawn(async move {
+                let response = upstream_client
+                    .request(request)
+                    .await
+                    .context("document link proto request")?;
+                match response.link {
+                    Some(resolved_link) => GetDocumentLinks::proto_to_project_link(resolved_link)
+                        .context("document link proto resolve response conversion"),
+                    None => Ok(link),
+                }
+            })
+        } else {
+            let Some(lang_server) = buffer.update(cx, |buffer, cx| {
+                self.language_server_for_local_buffer(buffer, server_id, cx)
+                    .map(|(_, server)| server.clone())
+            }) else {
+                return Task::ready(Ok(link));
+            };
+            if !GetDocumentLinks::can_resolve_links(&lang_server.capabilities()) {
+                return Task::ready(Ok(link));
+            }
+            let buffer_snapshot = buffer.read(cx).snapshot();
+            cx.background_spawn(async move {
+                let lsp_link = GetDocumentLinks::project_to_lsp_link(link, &buffer_snapshot)?;
+                let resolved_link = lang_server
+                    .request::<lsp::request::DocumentLinkResolve>(lsp_link)
+                    .await
+                    .into_response()
+                    .context("document link resolve LSP request")?;
+                Ok(GetDocumentLinks::lsp_to_project_link(
+                    resolved_link,
+                    &buffer_snapshot,
+                    ResolveState::Resolved,
+                ))
+            })
+        }
+    }
+
     pub(crate) fn linked_edit(
         &mut self,
         buffer: &Entity<Buffer>,
@@ -5384,6 +5453,89 @@ impl LspStore {
         }
     }
 
+    pub fn document_links(
+        &mut self,
+        buffer_handle: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        if let Some((upstream_client, project_id)) = self.upstream_client() {
+            let request_task = upstream_client.request(proto::MultiLspQuery {
+                buffer_id: buffer_handle.read(cx).remote_id().into(),
+                version: serialize_version(&buffer_handle.read(cx).version()),
+                project_id,
+                strategy: Some(proto::multi_lsp_query::Strategy::All(
+                    proto::AllLanguageServers {},
+                )),
+                request: Some(proto::multi_lsp_query::Request::GetDocumentLinks(
+                    GetDocumentLinks.to_proto(project_id, buffer_handle.read(cx)),
+                )),
+            });
+            let buffer = buffer_handle.clone();
+            cx.spawn(async move |weak_project, cx| {
+                let Some(project) = weak_project.upgrade() else {
+                    return Ok(Vec::new());
+                };
+                let responses = request_task.await?.responses;
+                let document_links = join_all(
+                    responses
+                        .into_iter()
+                        .filter_map(|lsp_response| match lsp_response.response? {
+                            proto::lsp_response::Response::GetDocumentLinksResponse(response) => {
+                                Some(response)
+                            }
+                            unexpected => {
+                                debug_panic!("Unexpected response: {unexpected:?}");
+                                None
+                            }
+                        })
+                        .map(|document_links_response| {
+                            GetDocumentLinks.response_from_proto(
+                                document_links_response,
+                                project.clone(),
+                                buffer.clone(),
+                                cx.clone(),
+                            )
+                        }),
+                )
+                .await;
+
+                Ok(document_links
+                    .into_iter()
+                    .collect::<Result<Vec<Vec<_>>>>()?
+                    .into_iter()
+                    .flatten()
+                    .collect())
+            })
+        } else {
+            let has_link_providers = self.as_local().is_some_and(|local| {
+                buffer_handle.update(cx, |buffer, cx| {
+                    local
+                        .language_servers_for_buffer(buffer, cx)
+                        .any(|(_, server)| {
+                            GetDocumentLinks
+                                .check_capabilities(server.adapter_server_capabilities())
+                        })
+                })
+            });
+            if !has_link_providers {
+                return Task::ready(Ok(Vec::new()));
+            }
+            let document_links_task = self.request_multiple_lsp_locally(
+                buffer_handle,
+                None::<usize>,
+                GetDocumentLinks,
+                cx,
+            );
+            cx.spawn(async move |_, _| {
+                Ok(document_links_task
+                    .await
+                    .into_iter()
+                    .flat_map(|(_, document_links)| document_links)
+                    .collect())
+            })
+        }
+    }
+
     #[inline(never)]
     pub fn completions(
         &self,
@@ -7286,6 +7438,92 @@ impl LspStore {
         Ok(())
     }
 
+    /// Publishes the problems found in the output of a task as diagnostics, replacing the ones
+    /// the previous run of the same task published.
+    pub fn update_task_diagnostics(
+        &mut self,
+        task_id: TaskId,
+        task_label: &str,
+        problems: Vec<Problem>,
+        cx: &mut Context<Self>,
+    ) -> Result<()> {
+        let languages = self.languages.clone();
+        let local = self
+            .as_local_mut()
+            .context("cannot publish task diagnostics on a remote LspStore")?;
+
+        let mut diagnostics_by_path =
+            HashMap::<PathBuf, Vec<DiagnosticEntry<Unclipped<PointUtf16>>>>::default();
+        for problem in problems {
+            let start = PointUtf16::new(
+                problem.line.saturating_sub(1),
+                problem.column.map_or(0, |column| column.saturating_sub(1)),
+            );
+            let end = match (problem.end_line, problem.end_column) {
+                (Some(line), Some(column)) => {
+                    PointUtf16::new(line.saturating_sub(1), column.saturating_sub(1))
+                }
+                (Some(line), None) => PointUtf16::new(line.saturating_sub(1), u32::MAX),
+                (None, Some(column)) => PointUtf16::new(start.row, column.saturating_sub(1)),
+                (None, None) if problem.column.is_none() => PointUtf16::new(start.row, u32::MAX),
+                (None, None) => start,
+            };
+            let severity = match problem.severity {
+                ProblemSeverity::Error => DiagnosticSeverity::ERROR,
+                ProblemSeverity::Warning => DiagnosticSeverity::WARNING,
+                ProblemSeverity::Info => DiagnosticSeverity::INFORMATION,
+                ProblemSeverity::Hint => DiagnosticSeverity::HINT,
+            };
+            diagnostics_by_path
+                .entry(problem.path)
+                .or_default()
+                .push(DiagnosticEntry {
+                    range: Unclipped(start)..Unclipped(end.max(start)),
+                    diagnostic: Diagnostic {
+                        source: Some(problem.source.unwrap_or_else(|| task_label.to_string())),
+                        code: problem.code.map(lsp::NumberOrString::String),
+                        severity,
+                        message: problem.message,
+                        group_id: post_inc(&mut local.next_diagnostic_group_id),
+                        is_primary: true,
+                        is_disk_based: true,
+                        ..Diagnostic::default()
+                    },
+                });
+        }
+
+        let task_diagnostics =
+            local
+                .task_diagnostics
+                .entry(task_id)
+                .or_insert_with(|| TaskDiagnostics {
+                    source_id: languages.next_language_server_id(),
+                    paths: HashSet::default(),
+                });
+        let source_id = task_diagnostics.source_id;
+        let stale_paths = mem::replace(
+            &mut task_diagnostics.paths,
+            diagnostics_by_path.keys().cloned().collect(),
+        );
+        for path in stale_paths {
+            diagnostics_by_path.entry(path).or_default();
+        }
+        for (abs_path, diagnostics) in diagnostics_by_path {
+            // Keep clearing the other paths if one of them can't be updated.
+            self.merge_diagnostic_entries(
+                source_id,
+                abs_path,
+                None,
+                None,
+                diagnostics,
+                |_, _, _| false,
+                cx,
+            )
+            .log_err();
+        }
+        Ok(())
+    }
+
     fn update_worktree_diagnostics(
         &mut self,
         worktree_id: WorktreeId,
@@ -7867,6 +8105,46 @@ impl LspStore {
                         .collect(),
                 })
             }
+            Some(proto::multi_lsp_query::Request::GetDocumentLinks(message)) => {
+                let get_document_links = GetDocumentLinks::from_proto(
+                    message,
+                    lsp_store.clone(),
+                    buffer.clone(),
+                    cx.clone(),
+                )
+                .await?;
+
+                let all_links = lsp_store
+                    .update(&mut cx, |project, cx| {
+                        project.request_multiple_lsp_locally(
+                            &buffer,
+                            None::<usize>,
+                            get_document_links,
+                            cx,
+                        )
+                    })?
+                    .await
+                    .into_iter();
+
+                lsp_store.update(&mut cx, |project, cx| proto::MultiLspQueryResponse {
+                    responses: all_links
+                        .map(|(server_id, links)| proto::LspResponse {
+                            server_id: server_id.to_proto(),
+                            response: Some(
+                                proto::lsp_response::Response::GetDocumentLinksResponse(
+                                    GetDocumentLinks::response_to_proto(
+                                        links,
+                                        project,
+                                        sender_id,
+                                        &buffer_version,
+                                        cx,
+                                    ),
+                                ),
+                            ),
+                        })
+                        .collect(),
+                })
+            }
             None => anyhow::bail!("empty multi lsp query request"),
         }
     }
@@ -8994,6 +9272,38 @@ impl LspStore {
         })
     }
 
+    async fn handle_resolve_document_link(
+        lsp_store: Entity<Self>,
+        envelope: TypedEnvelope<proto::ResolveDocumentLink>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::ResolveDocumentLinkResponse> {
+        let link = GetDocumentLinks::proto_to_project_link(
+            envelope
+                .payload
+                .link
+                .context("invalid document link resolve request")?,
+        )
+        .context("resolved proto document link conversion")?;
+        let buffer = lsp_store.update(&mut cx, |lsp_store, cx| {
+            let buffer_id = BufferId::new(envelope.payload.buffer_id)?;
+            lsp_store.buffer_store.read(cx).get_existing(buffer_id)
+        })??;
+        let resolved_link = lsp_store
+            .update(&mut cx, |lsp_store, cx| {
+                lsp_store.resolve_document_link(
+                    link,
+                    buffer,
+                    LanguageServerId::from_proto(envelope.payload.language_server_id),
+                    cx,
+                )
+            })?
+            .await
+            .context("resolving document link")?;
+        Ok(proto::ResolveDocumentLinkResponse {
+            link: Some(GetDocumentLinks::project_to_proto_link(resolved_link)),
+        })
+    }
+
     async fn handle_refresh_code_lens(
         this: Entity<Self>,
         _: TypedEnvelope<proto::RefreshCodeLens>,
@@ -113,7 +113,10 @@ use std::{
 
 use task_store::TaskStore;
 use terminals::Terminals;
-use text::{Anchor, BufferId, Point};
+use text::{
+    Anchor, BufferId, Point,
+    operation_queue::{Operation as _, OperationQueue},
+};
 use toolchain_store::EmptyToolchainStore;
 use util::{
     ResultExt as _,
@@ -131,7 +134,8 @@ pub use language::Location;
 #[cfg(any(test, feature = "test-support"))]
 pub use prettier::FORMAT_SUFFIX as TEST_PRETTIER_FORMAT_SUFFIX;
 pub use task_inventory::{
-    BasicContextProvider, ContextProviderWithTasks, Inventory, TaskContexts, TaskSourceKind,
+    BasicContextProvider, ContextProviderWithTasks, Inventory, TaskContexts, TaskGraph,
+    TaskSourceKind,
 };
 
 pub use buffer_store::ProjectTransaction;
@@ -206,6 +210,21 @@ pub struct Project {
     settings_observer: Entity<SettingsObserver>,
     toolchain_store: Option<Entity<ToolchainStore>>,
     agent_location: Option<AgentLocation>,
+    offline_operations: HashMap<BufferId, OfflineOperations>,
+    remote_listening_ports: Vec<u16>,
+    /// The links last found in each buffer, along with the version of the
+    /// buffer they were found in.
+    document_links: HashMap<BufferId, (clock::Global, Vec<DocumentLink>)>,
+}
+
+/// Local operations on a remote buffer that were made while the connection to
+/// the remote server was interrupted.
+#[derive(Default)]
+struct OfflineOperations {
+    queue: OperationQueue<language::Operation>,
+    /// The most recent text operation made while offline, which remote
+    /// operations must have observed in order not to conflict.
+    last_edit: Option<clock::Lamport>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -304,6 +323,7 @@ pub enum Event {
     RemoteIdChanged(Option<u64>),
     DisconnectedFromHost,
     DisconnectedFromSshRemote,
+    RemoteListeningPortsChanged,
     Closed,
     DeletedEntry(WorktreeId, ProjectEntryId),
     CollaboratorUpdated {
@@ -779,6 +799,16 @@ pub struct DocumentColor {
     pub color_presentations: Vec<ColorPresentation>,
 }
 
+#[derive(Clone, Debug)]
+pub struct DocumentLink {
+    pub range: Range<language::Anchor>,
+    /// A URL or a path relative to the buffer's file, if already known.
+    /// Language server links may need to be resolved before their target becomes available.
+    pub target: Option<String>,
+    pub tooltip: Option<String>,
+    pub resolve_state: ResolveState,
+}
+
 #[derive(Clone, Debug, PartialEq)]
 pub struct ColorPresentation {
     pub label: String,
@@ -1104,6 +1134,9 @@ impl Project {
                 toolchain_store: Some(toolchain_store),
 
                 agent_location: None,
+                offline_operations: HashMap::default(),
+                remote_listening_ports: Vec::new(),
+                document_links: HashMap::default(),
             }
         })
     }
@@ -1267,6 +1300,9 @@ impl Project {
 
                 toolchain_store: Some(toolchain_store),
                 agent_location: None,
+                offline_operations: HashMap::default(),
+                remote_listening_ports: Vec::new(),
+                document_links: HashMap::default(),
             };
 
             // ssh -> local machine handlers
@@ -1285,6 +1321,7 @@ impl Project {
             ssh_proto.add_entity_message_handler(Self::handle_toast);
             ssh_proto.add_entity_request_handler(Self::handle_language_server_prompt_request);
             ssh_proto.add_entity_message_handler(Self::handle_hide_toast);
+            ssh_proto.add_entity_message_handler(Self::handle_listening_ports_changed);
             ssh_proto.add_entity_request_handler(Self::handle_update_buffer_from_ssh);
             BufferStore::init(&ssh_proto);
             LspStore::init(&ssh_proto);
@@ -1517,6 +1554,9 @@ impl Project {
                 remotely_created_models: Arc::new(Mutex::new(RemotelyCreatedModels::default())),
                 toolchain_store: None,
                 agent_location: None,
+                offline_operations: HashMap::default(),
+                remote_listening_ports: Vec::new(),
+                document_links: HashMap::default(),
             };
             this.set_role(role, cx);
             for worktree in worktrees {
@@ -1838,6 +1878,27 @@ impl Project {
             .map(|ssh| ssh.read(cx).connection_options())
     }
 
+    /// Asks the remote server to report the ports that start listening on the
+    /// remote host from now on, see [`Self::remote_listening_ports`].
+    pub fn watch_remote_listening_ports(&self, cx: &App) -> Task<Result<()>> {
+        let Some(ssh) = &self.ssh_client else {
+            return Task::ready(Err(anyhow!("not a remote project")));
+        };
+        let request = ssh
+            .read(cx)
+            .proto_client()
+            .request(proto::WatchListeningPorts {});
+        cx.background_spawn(async move {
+            request.await?;
+            Ok(())
+        })
+    }
+
+    /// The ports that started listening on the remote host while watched.
+    pub fn remote_listening_ports(&self) -> &[u16] {
+        &self.remote_listening_ports
+    }
+
     pub fn replica_id(&self) -> ReplicaId {
         match self.client_state {
             ProjectClientState::Remote { replica_id, .. } => replica_id,
@@ -2762,6 +2823,7 @@ impl Project {
                 self.register_buffer(buffer, cx).log_err();
             }
             BufferStoreEvent::BufferDropped(buffer_id) => {
+                self.offline_operations.remove(buffer_id);
                 if let Some(ref ssh_client) = self.ssh_client {
                     ssh_client
                         .read(cx)
@@ -2824,10 +2886,16 @@ impl Project {
                 path: path.clone(),
                 language_server_id: *language_server_id,
             }),
-            LspStoreEvent::LanguageServerAdded(language_server_id, name, worktree_id) => cx.emit(
-                Event::LanguageServerAdded(*language_server_id, name.clone(), *worktree_id),
-            ),
+            LspStoreEvent::LanguageServerAdded(language_server_id, name, worktree_id) => {
+                self.document_links.clear();
+                cx.emit(Event::LanguageServerAdded(
+                    *language_server_id,
+                    name.clone(),
+                    *worktree_id,
+                ))
+            }
             LspStoreEvent::LanguageServerRemoved(language_server_id) => {
+                self.document_links.clear();
                 cx.emit(Event::LanguageServerRemoved(*language_server_id))
             }
             LspStoreEvent::LanguageServerLog(server_id, log_type, string) => cx.emit(
@@ -2910,6 +2978,31 @@ impl Project {
                 });
                 cx.emit(Event::DisconnectedFromSshRemote);
             }
+            remote::SshRemoteEvent::PortForwardsChanged => {}
+            remote::SshRemoteEvent::Reconnected => {
+                let Some(ssh) = &self.ssh_client else {
+                    return;
+                };
+                let client = ssh.read(cx).proto_client();
+                for (buffer_id, offline_operations) in &mut self.offline_operations {
+                    if offline_operations.queue.is_empty() {
+                        continue;
+                    }
+                    let operations = offline_operations
+                        .queue
+                        .drain()
+                        .iter()
+                        .map(language::proto::serialize_operation)
+                        .collect();
+                    client
+                        .send(proto::UpdateBuffer {
+                            project_id: 0,
+                            buffer_id: buffer_id.to_proto(),
+                            operations,
+                        })
+                        .log_err();
+                }
+            }
         }
     }
 
@@ -3022,25 +3115,54 @@ impl Project {
                 operation,
                 is_local: true,
             } => {
-                let operation = language::proto::serialize_operation(operation);
+                let serialized_operation = language::proto::serialize_operation(operation);
 
                 if let Some(ssh) = &self.ssh_client {
-                    ssh.read(cx)
-                        .proto_client()
-                        .send(proto::UpdateBuffer {
-                            project_id: 0,
-                            buffer_id: buffer_id.to_proto(),
-                            operations: vec![operation.clone()],
-                        })
-                        .ok();
+                    if ssh.read(cx).connection_state() == remote::ConnectionState::Connected {
+                        ssh.read(cx)
+                            .proto_client()
+                            .send(proto::UpdateBuffer {
+                                project_id: 0,
+                                buffer_id: buffer_id.to_proto(),
+                                operations: vec![serialized_operation.clone()],
+                            })
+                            .ok();
+                    } else {
+                        // Hold on to the operation until the connection is
+                        // restored, so that it can be replayed on the server.
+                        let offline_operations =
+                            self.offline_operations.entry(buffer_id).or_default();
+                        if let language::Operation::Buffer(_) = operation {
+                            offline_operations.last_edit = Some(operation.lamport_timestamp());
+                        }
+                        offline_operations.queue.insert(vec![operation.clone()]);
+                    }
                 }
 
                 self.enqueue_buffer_ordered_message(BufferOrderedMessage::Operation {
                     buffer_id,
-                    operation,
+                    operation: serialized_operation,
                 })
                 .ok();
             }
+            BufferEvent::Operation {
+                operation: language::Operation::Buffer(operation),
+                is_local: false,
+            } => {
+                let offline_operations = self.offline_operations.get(&buffer_id)?;
+                let last_edit = offline_operations.last_edit?;
+                let version = match operation {
+                    text::Operation::Edit(edit) => &edit.version,
+                    text::Operation::Undo(undo) => &undo.version,
+                };
+                if !version.observed(last_edit) {
+                    // The remote buffer changed concurrently with edits that
+                    // were made offline, e.g. because the file was reloaded.
+                    buffer.update(cx, |buffer, cx| buffer.set_conflict(true, cx));
+                } else if offline_operations.queue.is_empty() {
+                    self.offline_operations.remove(&buffer_id);
+                }
+            }
 
             _ => {}
         }
@@ -3588,6 +3710,69 @@ impl Project {
         })
     }
 
+    /// Returns the links in the buffer reported by its language servers
+    /// along with the ones matched by the `links.scm` queries of its languages.
+    /// The links are looked up again only once the buffer changes.
+    pub fn document_links(
+        &mut self,
+        buffer_handle: &Entity<Buffer>,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<Vec<DocumentLink>>> {
+        let buffer = buffer_handle.read(cx);
+        let buffer_id = buffer.remote_id();
+        let version = buffer.version();
+        if let Some((links_version, links)) = self.document_links.get(&buffer_id) {
+            if *links_version == version {
+                return Task::ready(Ok(links.clone()));
+            }
+        }
+
+        let snapshot = buffer.snapshot();
+        let lsp_links = self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.document_links(buffer_handle, cx)
+        });
+        let links_task = cx.background_spawn(async move {
+            let mut document_links = lsp_links.await?;
+            document_links.extend(snapshot.links_in_range(0..snapshot.len()).map(
+                |(range, target)| DocumentLink {
+                    range: snapshot.anchor_before(range.start)..snapshot.anchor_after(range.end),
+                    target: Some(target),
+                    tooltip: None,
+                    resolve_state: ResolveState::Resolved,
+                },
+            ));
+            anyhow::Ok(document_links)
+        });
+        let buffer_handle = buffer_handle.clone();
+        cx.spawn(async move |project, cx| {
+            let document_links = links_task.await?;
+            project.update(cx, |project, cx| {
+                if !project.document_links.contains_key(&buffer_id) {
+                    cx.observe_release(&buffer_handle, move |project, _, _| {
+                        project.document_links.remove(&buffer_id);
+                    })
+                    .detach();
+                }
+                project
+                    .document_links
+                    .insert(buffer_id, (version, document_links.clone()));
+            })?;
+            Ok(document_links)
+        })
+    }
+
+    pub fn resolve_document_link(
+        &self,
+        link: DocumentLink,
+        buffer_handle: Entity<Buffer>,
+        server_id: LanguageServerId,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<DocumentLink>> {
+        self.lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store.resolve_document_link(link, buffer_handle, server_id, cx)
+        })
+    }
+
     pub fn apply_code_action(
         &self,
         buffer_handle: Entity<Buffer>,
@@ -4492,6 +4677,22 @@ impl Project {
         })?
     }
 
+    async fn handle_listening_ports_changed(
+        this: Entity<Self>,
+        envelope: TypedEnvelope<proto::ListeningPortsChanged>,
+        mut cx: AsyncApp,
+    ) -> Result<()> {
+        this.update(&mut cx, |this, cx| {
+            this.remote_listening_ports = envelope
+                .payload
+                .ports
+                .into_iter()
+                .filter_map(|port| u16::try_from(port).ok())
+                .collect();
+            cx.emit(Event::RemoteListeningPortsChanged);
+        })
+    }
+
     async fn handle_language_server_prompt_request(
         this: Entity<Self>,
         envelope: TypedEnvelope<proto::LanguageServerPromptRequest>,
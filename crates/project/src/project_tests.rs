@@ -2503,6 +2503,126 @@ async fn test_diagnostics_from_multiple_language_servers(cx: &mut gpui::TestAppC
     });
 }
 
+#[gpui::test]
+async fn test_task_diagnostics(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "a.rs": "fn a() { b() }",
+            "b.rs": "fn b() {}",
+        }),
+    )
+    .await;
+
+    let project = Project::test(fs, [path!("/dir").as_ref()], cx).await;
+    let lsp_store = project.read_with(cx, |project, _| project.lsp_store.clone());
+    let task_id = task::TaskId("cargo-check".to_string());
+    let problem = |path: &str, line, severity, message: &str| task::Problem {
+        path: PathBuf::from(path),
+        line,
+        column: Some(10),
+        end_line: None,
+        end_column: Some(13),
+        severity,
+        code: None,
+        message: message.to_string(),
+        source: None,
+    };
+
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_task_diagnostics(
+                task_id.clone(),
+                "cargo check",
+                vec![
+                    problem(
+                        path!("/dir/a.rs"),
+                        1,
+                        task::ProblemSeverity::Error,
+                        "cannot find function `b`",
+                    ),
+                    problem(
+                        path!("/dir/b.rs"),
+                        1,
+                        task::ProblemSeverity::Warning,
+                        "function `b` is never used",
+                    ),
+                ],
+                cx,
+            )
+            .unwrap();
+    });
+    project.read_with(cx, |project, cx| {
+        let mut summaries = project
+            .diagnostic_summaries(false, cx)
+            .map(|(path, _, summary)| (path.path.to_string_lossy().into_owned(), summary))
+            .collect::<Vec<_>>();
+        summaries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        assert_eq!(
+            summaries,
+            vec![
+                (
+                    "a.rs".to_string(),
+                    DiagnosticSummary {
+                        error_count: 1,
+                        warning_count: 0,
+                    }
+                ),
+                (
+                    "b.rs".to_string(),
+                    DiagnosticSummary {
+                        error_count: 0,
+                        warning_count: 1,
+                    }
+                ),
+            ]
+        );
+    });
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/a.rs"), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, _| {
+        let diagnostics = buffer
+            .snapshot()
+            .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+            .map(|entry| (entry.range, entry.diagnostic.source))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            diagnostics,
+            [(
+                Point::new(0, 9)..Point::new(0, 12),
+                Some("cargo check".to_string())
+            )]
+        );
+    });
+
+    // Rerunning the task clears the diagnostics of its previous run.
+    lsp_store.update(cx, |lsp_store, cx| {
+        lsp_store
+            .update_task_diagnostics(task_id.clone(), "cargo check", Vec::new(), cx)
+            .unwrap();
+    });
+    project.read_with(cx, |project, cx| {
+        assert_eq!(project.diagnostic_summaries(false, cx).next(), None);
+    });
+    buffer.update(cx, |buffer, _| {
+        assert_eq!(
+            buffer
+                .snapshot()
+                .diagnostics_in_range::<_, Point>(0..buffer.len(), false)
+                .count(),
+            0
+        );
+    });
+}
+
 #[gpui::test]
 async fn test_edits_from_lsp2_with_past_version(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -5883,6 +6003,86 @@ async fn test_search_with_unicode(cx: &mut gpui::TestAppContext) {
     );
 }
 
+#[gpui::test]
+async fn test_structural_search(cx: &mut gpui::TestAppContext) {
+    init_test(cx);
+
+    let fs = FakeFs::new(cx.executor());
+    fs.insert_tree(
+        path!("/dir"),
+        json!({
+            "one.rs": "fn one() { a.unwrap(); b.unwrap_or(c); }",
+            "two.rs": "fn two() { bar(1, 2).unwrap(); /* x.unwrap() */ }",
+            "three.rs": "fn three() { max(x, x); max(x, y); }",
+        }),
+    )
+    .await;
+    let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+    let language_registry = project.read_with(cx, |project, _| project.languages().clone());
+    language_registry.add(rust_lang());
+
+    let query = SearchQuery::structural(
+        "$X.unwrap()",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        search(&project, query.clone(), cx).await.unwrap(),
+        HashMap::from_iter([
+            (path!("dir/one.rs").to_string(), vec![11..21]),
+            (path!("dir/two.rs").to_string(), vec![11..29]),
+        ])
+    );
+
+    let repeated_query = SearchQuery::structural(
+        "max($A, $A)",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .unwrap();
+    assert_eq!(
+        search(&project, repeated_query, cx).await.unwrap(),
+        HashMap::from_iter([(path!("dir/three.rs").to_string(), vec![13..22])])
+    );
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_local_buffer(path!("/dir/two.rs"), cx)
+        })
+        .await
+        .unwrap();
+    cx.executor().run_until_parked();
+    let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot());
+
+    let query = query.with_replacement("$X?".to_string());
+    assert_eq!(
+        query.replacement_in(&snapshot, 11..29).as_deref(),
+        Some("bar(1, 2)?")
+    );
+
+    let arguments_query = SearchQuery::structural(
+        "bar($$$ARGS)",
+        false,
+        Default::default(),
+        Default::default(),
+        false,
+        None,
+    )
+    .unwrap()
+    .with_replacement("baz($$$ARGS, 3)".to_string());
+    assert_eq!(
+        arguments_query.replacement_in(&snapshot, 11..20).as_deref(),
+        Some("baz(1, 2, 3)")
+    );
+}
+
 #[gpui::test]
 async fn test_create_entry(cx: &mut gpui::TestAppContext) {
     init_test(cx);
@@ -0,0 +1,343 @@
+use anyhow::{Result, anyhow};
+use collections::HashMap;
+use language::{BufferSnapshot, Language, Node, Tree, with_parser};
+use regex::Regex;
+use std::{
+    ops::Range,
+    sync::{Arc, LazyLock},
+};
+
+static METAVARIABLE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"\$\$\$([A-Z_][A-Z0-9_]*)?|\$([A-Z_][A-Z0-9_]*)")
+        .expect("Failed to create METAVARIABLE")
+});
+
+static WORD: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\w+").expect("Failed to create WORD"));
+
+/// A syntax-aware search pattern, matched against the syntax trees of buffers.
+///
+/// The pattern is a code snippet in the language being searched, where `$NAME`
+/// matches any single syntax node and `$$$NAME` matches any number of sibling
+/// nodes. Using the same metavariable twice requires both nodes to have the
+/// same text, while `$_` and `$$$` match without capturing anything.
+#[derive(Debug)]
+pub struct StructuralPattern {
+    /// The pattern with every metavariable replaced by a placeholder identifier,
+    /// so that it can be parsed by any grammar.
+    source: String,
+    placeholders: HashMap<String, Metavariable>,
+    words: Vec<String>,
+}
+
+#[derive(Clone, Debug)]
+struct Metavariable {
+    name: Option<Arc<str>>,
+    multiple: bool,
+}
+
+impl StructuralPattern {
+    pub fn new(query: &str) -> Result<Self> {
+        if query.trim().is_empty() {
+            return Err(anyhow!("Structural search pattern is empty"));
+        }
+
+        let mut source = String::new();
+        let mut placeholders = HashMap::default();
+        let mut last_end = 0;
+        for (ix, captures) in METAVARIABLE.captures_iter(query).enumerate() {
+            let whole = captures.get(0).unwrap();
+            let multiple = whole.as_str().starts_with("$$$");
+            let name = captures
+                .get(1)
+                .or_else(|| captures.get(2))
+                .map(|name| name.as_str())
+                .filter(|name| *name != "_")
+                .map(Arc::from);
+            let placeholder = if multiple {
+                format!("zed_metas_{ix}")
+            } else {
+                format!("zed_meta_{ix}")
+            };
+            source.push_str(&query[last_end..whole.start()]);
+            source.push_str(&placeholder);
+            placeholders.insert(placeholder, Metavariable { name, multiple });
+            last_end = whole.end();
+        }
+        source.push_str(&query[last_end..]);
+
+        let words = WORD
+            .find_iter(&METAVARIABLE.replace_all(query, " "))
+            .map(|word| word.as_str().to_string())
+            .collect();
+
+        Ok(Self {
+            source,
+            placeholders,
+            words,
+        })
+    }
+
+    /// Whether the given file contents may contain a match, judging by the
+    /// words of the pattern that are not metavariables.
+    pub fn is_candidate(&self, text: &str) -> bool {
+        self.words.iter().all(|word| text.contains(word.as_str()))
+    }
+
+    /// Returns the ranges of the syntax nodes matching the pattern within `range`.
+    pub fn search(&self, buffer: &BufferSnapshot, range: Range<usize>) -> Vec<Range<usize>> {
+        let mut matches = Vec::new();
+        self.for_each_tree(buffer, |language, root| {
+            let Some(pattern) = self.parse(language) else {
+                return;
+            };
+            let Some(pattern_root) = self.pattern_root(&pattern) else {
+                return;
+            };
+            let mut cursor = root.walk();
+            loop {
+                let node = cursor.node();
+                let mut descend = node.end_byte() > range.start && node.start_byte() < range.end;
+                if descend
+                    && node.kind_id() == pattern_root.kind_id()
+                    && range.start <= node.start_byte()
+                    && node.end_byte() <= range.end
+                    && Matcher::new(self, buffer).match_node(pattern_root, node)
+                {
+                    matches.push(node.byte_range());
+                    descend = false;
+                }
+
+                if descend && cursor.goto_first_child() {
+                    continue;
+                }
+                while !cursor.goto_next_sibling() {
+                    if !cursor.goto_parent() {
+                        return;
+                    }
+                }
+            }
+        });
+        matches.sort_unstable_by_key(|range| (range.start, range.end));
+        matches.dedup();
+        matches
+    }
+
+    /// Returns the replacement for the match at `range`, with the metavariables
+    /// in `replacement` substituted by the text they captured.
+    pub fn replacement(
+        &self,
+        buffer: &BufferSnapshot,
+        range: Range<usize>,
+        replacement: &str,
+    ) -> Option<String> {
+        let mut bindings = None;
+        self.for_each_tree(buffer, |language, root| {
+            if bindings.is_some() || root.byte_range().end < range.end {
+                return;
+            }
+            let Some(pattern) = self.parse(language) else {
+                return;
+            };
+            let Some(pattern_root) = self.pattern_root(&pattern) else {
+                return;
+            };
+            let mut node = root.descendant_for_byte_range(range.start, range.end);
+            while let Some(candidate) = node.filter(|node| node.byte_range() == range) {
+                let mut matcher = Matcher::new(self, buffer);
+                if matcher.match_node(pattern_root, candidate) {
+                    bindings = Some(matcher.bindings);
+                    return;
+                }
+                node = candidate.parent();
+            }
+        });
+        let bindings = bindings?;
+
+        Some(
+            METAVARIABLE
+                .replace_all(replacement, |captures: &regex::Captures| {
+                    let name = captures.get(1).or_else(|| captures.get(2));
+                    match name.and_then(|name| bindings.get(name.as_str())) {
+                        Some(range) => buffer.text_for_range(range.clone()).collect::<String>(),
+                        None => captures[0].to_string(),
+                    }
+                })
+                .into_owned(),
+        )
+    }
+
+    fn for_each_tree(&self, buffer: &BufferSnapshot, mut f: impl FnMut(&Arc<Language>, Node)) {
+        let mut has_layers = false;
+        for layer in buffer.syntax_layers() {
+            has_layers = true;
+            f(layer.language, layer.node());
+        }
+        // The buffer may not have been parsed yet, e.g. when it was just opened
+        // for a project search.
+        if !has_layers {
+            if let Some(language) = buffer.language() {
+                let text = buffer.text();
+                if let Some(tree) = parse(language, &text) {
+                    f(language, tree.root_node());
+                }
+            }
+        }
+    }
+
+    fn parse(&self, language: &Arc<Language>) -> Option<Tree> {
+        parse(language, &self.source)
+    }
+
+    /// Finds the node the pattern stands for, skipping the nodes the grammar
+    /// wraps around a lone snippet, such as a source file or an expression
+    /// statement lacking its semicolon.
+    fn pattern_root<'a>(&self, tree: &'a Tree) -> Option<Node<'a>> {
+        let mut node = tree.root_node();
+        loop {
+            let text = self.text(node).trim();
+            let mut children = named_children(node)
+                .into_iter()
+                .filter(|child| !child.is_missing());
+            let child = children.next();
+            match child {
+                Some(child) if children.next().is_none() && self.text(child).trim() == text => {
+                    node = child;
+                }
+                _ => break,
+            }
+        }
+        if node.has_error() || self.metavariable(node).is_some() {
+            return None;
+        }
+        Some(node)
+    }
+
+    fn text(&self, node: Node) -> &str {
+        &self.source[node.byte_range()]
+    }
+
+    fn metavariable(&self, node: Node) -> Option<&Metavariable> {
+        self.placeholders.get(self.text(node))
+    }
+}
+
+struct Matcher<'a> {
+    pattern: &'a StructuralPattern,
+    buffer: &'a BufferSnapshot,
+    bindings: HashMap<Arc<str>, Range<usize>>,
+}
+
+impl<'a> Matcher<'a> {
+    fn new(pattern: &'a StructuralPattern, buffer: &'a BufferSnapshot) -> Self {
+        Self {
+            pattern,
+            buffer,
+            bindings: HashMap::default(),
+        }
+    }
+
+    fn match_node(&mut self, pattern: Node, node: Node) -> bool {
+        if let Some(metavariable) = self.pattern.metavariable(pattern) {
+            return node.is_named() && self.bind(metavariable, node.byte_range());
+        }
+        if pattern.kind_id() != node.kind_id() {
+            return false;
+        }
+        if pattern.child_count() == 0 {
+            return self.text_eq(node.byte_range(), self.pattern.text(pattern));
+        }
+        let pattern_children = children(pattern)
+            .into_iter()
+            .filter(|child| !child.is_missing())
+            .collect::<Vec<_>>();
+        self.match_children(&pattern_children, &children(node), node.start_byte())
+    }
+
+    fn match_children(&mut self, patterns: &[Node], nodes: &[Node], position: usize) -> bool {
+        let Some((pattern, rest)) = patterns.split_first() else {
+            return nodes.is_empty();
+        };
+
+        if let Some(metavariable) = self
+            .pattern
+            .metavariable(*pattern)
+            .filter(|metavariable| metavariable.multiple)
+        {
+            for count in 0..=nodes.len() {
+                let range = match &nodes[..count] {
+                    [] => {
+                        let position = nodes.first().map_or(position, |node| node.start_byte());
+                        position..position
+                    }
+                    [only] => only.byte_range(),
+                    [first, .., last] => first.start_byte()..last.end_byte(),
+                };
+                let bindings = self.bindings.clone();
+                if self.bind(metavariable, range.clone())
+                    && self.match_children(rest, &nodes[count..], range.end)
+                {
+                    return true;
+                }
+                self.bindings = bindings;
+            }
+            return false;
+        }
+
+        let Some((node, nodes)) = nodes.split_first() else {
+            return false;
+        };
+        let bindings = self.bindings.clone();
+        if self.match_node(*pattern, *node) && self.match_children(rest, nodes, node.end_byte()) {
+            return true;
+        }
+        self.bindings = bindings;
+        false
+    }
+
+    fn bind(&mut self, metavariable: &Metavariable, range: Range<usize>) -> bool {
+        let Some(name) = metavariable.name.clone() else {
+            return true;
+        };
+        if let Some(bound) = self.bindings.get(&name) {
+            let text = self
+                .buffer
+                .text_for_range(bound.clone())
+                .collect::<String>();
+            self.text_eq(range, &text)
+        } else {
+            self.bindings.insert(name, range);
+            true
+        }
+    }
+
+    fn text_eq(&self, range: Range<usize>, text: &str) -> bool {
+        range.len() == text.len() && self.buffer.text_for_range(range).collect::<String>() == text
+    }
+}
+
+fn parse(language: &Arc<Language>, text: &str) -> Option<Tree> {
+    let grammar = language.grammar()?;
+    with_parser(|parser| {
+        parser.set_language(&grammar.ts_language).ok()?;
+        parser.parse(text, None)
+    })
+}
+
+/// The children of `node` that take part in matching, leaving out comments.
+fn children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    let children = node
+        .children(&mut cursor)
+        .filter(|child| !child.is_extra())
+        .collect();
+    children
+}
+
+fn named_children(node: Node) -> Vec<Node> {
+    let mut cursor = node.walk();
+    let children = node
+        .named_children(&mut cursor)
+        .filter(|child| !child.is_extra())
+        .collect();
+    children
+}
@@ -112,6 +112,21 @@ impl SearchHistory {
         cursor.selection = Some(prev_index);
         Some(&self.history[prev_index])
     }
+
+    /// The queries in the history, oldest first.
+    pub fn entries(&self) -> impl Iterator<Item = &str> {
+        self.history.iter().map(|query| query.as_str())
+    }
+
+    /// Replaces the history with previously stored queries, oldest first,
+    /// keeping only the most recent ones that fit.
+    pub fn restore(&mut self, queries: impl IntoIterator<Item = String>) {
+        self.history = queries.into_iter().collect();
+        if let Some(max_history_len) = self.max_history_len {
+            let excess = self.history.len().saturating_sub(max_history_len);
+            self.history.drain(..excess);
+        }
+    }
 }
 
 #[cfg(test)]
@@ -231,6 +246,26 @@ mod tests {
         assert_eq!(search_history.current(&cursor), None);
     }
 
+    #[test]
+    fn test_restore() {
+        let mut search_history = SearchHistory::new(Some(2), QueryInsertionBehavior::AlwaysInsert);
+        let mut cursor = SearchHistoryCursor::default();
+
+        search_history.restore(["Rust", "JavaScript", "TypeScript"].map(String::from));
+        assert_eq!(
+            search_history.entries().collect::<Vec<_>>(),
+            ["JavaScript", "TypeScript"],
+            "Should keep the most recent queries"
+        );
+        assert_eq!(search_history.previous(&mut cursor), Some("TypeScript"));
+
+        search_history.add(&mut cursor, "Python".to_string());
+        assert_eq!(
+            search_history.entries().collect::<Vec<_>>(),
+            ["TypeScript", "Python"]
+        );
+    }
+
     #[test]
     fn test_multiple_cursors() {
         let mut search_history = SearchHistory::new(None, QueryInsertionBehavior::AlwaysInsert);
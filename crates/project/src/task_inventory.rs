@@ -8,7 +8,7 @@ use std::{
     sync::Arc,
 };
 
-use anyhow::Result;
+use anyhow::{Context as _, Result};
 use collections::{HashMap, HashSet, VecDeque};
 use dap::DapRegistry;
 use fs::Fs;
@@ -150,6 +150,14 @@ pub enum TaskSourceKind {
     },
 }
 
+/// A task to run, along with the tasks that have to run before it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TaskGraph {
+    pub task_source_kind: TaskSourceKind,
+    pub task: ResolvedTask,
+    pub dependencies: Vec<TaskGraph>,
+}
+
 /// A collection of task contexts, derived from the current state of the workspace.
 /// Only contains worktrees that are visible and with their root being a directory.
 #[derive(Debug, Default)]
@@ -584,6 +592,22 @@ impl Inventory {
         }
     }
 
+    /// Resolves the tasks the given task depends on, recursively, with the same context the task was resolved with.
+    /// Dependencies are looked up by their labels among the worktree and global tasks.
+    pub fn resolve_task_graph(
+        &self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        worktree: Option<WorktreeId>,
+        cx: &App,
+    ) -> Task<Result<TaskGraph>> {
+        let templates = self.list_tasks(None, None, worktree, cx);
+        cx.background_spawn(async move {
+            let templates = templates.await;
+            build_task_graph(task_source_kind, resolved_task, &templates, &mut Vec::new())
+        })
+    }
+
     /// Deletes a resolved task from history, using its id.
     /// A similar may still resurface in `used_and_current_resolved_tasks` when its [`TaskTemplate`] is resolved again.
     pub fn delete_previously_used(&mut self, id: &TaskId) {
@@ -781,6 +805,44 @@ impl Inventory {
     }
 }
 
+fn build_task_graph(
+    task_source_kind: TaskSourceKind,
+    resolved_task: ResolvedTask,
+    templates: &[(TaskSourceKind, TaskTemplate)],
+    ancestors: &mut Vec<String>,
+) -> Result<TaskGraph> {
+    let label = resolved_task.original_task().label.clone();
+    ancestors.push(label.clone());
+    let mut dependencies = Vec::new();
+    for dependency in &resolved_task.original_task().depends_on {
+        if ancestors.contains(dependency) {
+            anyhow::bail!(
+                "Task dependency cycle: {} -> {dependency}",
+                ancestors.join(" -> ")
+            );
+        }
+        let (kind, template) = templates
+            .iter()
+            .find(|(_, template)| &template.label == dependency)
+            .with_context(|| format!("Task `{label}` depends on unknown task `{dependency}`"))?;
+        let resolved_dependency = template
+            .resolve_task(&kind.to_id_base(), resolved_task.task_context())
+            .with_context(|| format!("Could not resolve task `{dependency}`"))?;
+        dependencies.push(build_task_graph(
+            kind.clone(),
+            resolved_dependency,
+            templates,
+            ancestors,
+        )?);
+    }
+    ancestors.pop();
+    Ok(TaskGraph {
+        task_source_kind,
+        task: resolved_task,
+        dependencies,
+    })
+}
+
 fn task_lru_comparator(
     (kind_a, task_a, lru_score_a): &(TaskSourceKind, ResolvedTask, u32),
     (kind_b, task_b, lru_score_b): &(TaskSourceKind, ResolvedTask, u32),
@@ -1531,6 +1593,109 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    async fn test_resolving_task_graph(cx: &mut TestAppContext) {
+        init_test(cx);
+        let fs = FakeFs::new(cx.executor());
+        let inventory = cx.update(|cx| Inventory::new(fs, cx));
+        let worktree_id = WorktreeId::from_usize(1);
+        inventory.update(cx, |inventory, _| {
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Global(tasks_file()),
+                    Some(
+                        &json!([
+                            { "label": "lint", "command": "eslint" },
+                            { "label": "cyclic a", "command": "a", "depends_on": ["cyclic b"] },
+                            { "label": "cyclic b", "command": "b", "depends_on": ["cyclic a"] },
+                        ])
+                        .to_string(),
+                    ),
+                )
+                .unwrap();
+            inventory
+                .update_file_based_tasks(
+                    TaskSettingsLocation::Worktree(SettingsLocation {
+                        worktree_id,
+                        path: Path::new(".zed"),
+                    }),
+                    Some(
+                        &json!([
+                            { "label": "build", "command": "cargo", "depends_on": ["lint"] },
+                            { "label": "test", "command": "cargo", "depends_on": ["lint"] },
+                            {
+                                "label": "check",
+                                "depends_on": ["build", "test"],
+                                "depends_order": "parallel"
+                            },
+                            { "label": "broken", "command": "b", "depends_on": ["missing"] },
+                        ])
+                        .to_string(),
+                    ),
+                )
+                .unwrap();
+        });
+
+        fn labels(graph: &TaskGraph) -> String {
+            let label = graph.task.original_task().label.clone();
+            if graph.dependencies.is_empty() {
+                label
+            } else {
+                let dependencies = graph.dependencies.iter().map(labels).join(", ");
+                format!("{label}({dependencies})")
+            }
+        }
+
+        let graph = resolve_graph(&inventory, worktree_id, "check", cx)
+            .await
+            .unwrap();
+        assert_eq!(labels(&graph), "check(build(lint), test(lint))");
+        assert_eq!(graph.task.resolved.command, "");
+        assert!(matches!(
+            graph.dependencies[0].dependencies[0].task_source_kind,
+            TaskSourceKind::AbsPath { .. }
+        ));
+
+        let error = resolve_graph(&inventory, worktree_id, "cyclic a", cx)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Task dependency cycle: cyclic a -> cyclic b -> cyclic a"
+        );
+        let error = resolve_graph(&inventory, worktree_id, "broken", cx)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Task `broken` depends on unknown task `missing`"
+        );
+    }
+
+    async fn resolve_graph(
+        inventory: &Entity<Inventory>,
+        worktree_id: WorktreeId,
+        label: &str,
+        cx: &mut TestAppContext,
+    ) -> Result<TaskGraph> {
+        let (kind, template) = inventory
+            .update(cx, |inventory, cx| {
+                inventory.list_tasks(None, None, Some(worktree_id), cx)
+            })
+            .await
+            .into_iter()
+            .find(|(_, template)| template.label == label)
+            .unwrap();
+        let task = template
+            .resolve_task(&kind.to_id_base(), &TaskContext::default())
+            .unwrap();
+        inventory
+            .update(cx, |inventory, cx| {
+                inventory.resolve_task_graph(kind, task, Some(worktree_id), cx)
+            })
+            .await
+    }
+
     fn init_test(_cx: &mut TestAppContext) {
         zlog::init_test();
         TaskStore::init(None);
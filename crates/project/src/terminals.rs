@@ -1,6 +1,7 @@
 use crate::{Project, ProjectPath};
 use anyhow::{Context as _, Result};
 use collections::HashMap;
+use futures::FutureExt as _;
 use gpui::{AnyWindowHandle, App, AppContext as _, Context, Entity, Task, WeakEntity};
 use itertools::Itertools;
 use language::LanguageName;
@@ -11,10 +12,14 @@ use std::{
     env::{self},
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
+};
+use task::{
+    DEFAULT_REMOTE_SHELL, ProblemMatcherDefinition, ProblemScanner, Shell, ShellBuilder,
+    SpawnInTerminal, TaskId,
 };
-use task::{DEFAULT_REMOTE_SHELL, Shell, ShellBuilder, SpawnInTerminal};
 use terminal::{
-    TaskState, TaskStatus, Terminal, TerminalBuilder,
+    OutputPosition, TaskState, TaskStatus, Terminal, TerminalBuilder,
     terminal_settings::{self, TerminalSettings, VenvSettings},
 };
 use util::ResultExt;
@@ -33,6 +38,17 @@ pub enum TerminalKind {
     Task(SpawnInTerminal),
 }
 
+/// How often the output of a running task is matched for problems.
+const PROBLEM_MATCHING_INTERVAL: Duration = Duration::from_secs(1);
+
+/// What is needed to find problems in the output of a task.
+struct ProblemMatching {
+    task_id: TaskId,
+    label: String,
+    matchers: Vec<ProblemMatcherDefinition>,
+    cwd: Option<PathBuf>,
+}
+
 /// SshCommand describes how to connect to a remote server
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct SshCommand {
@@ -202,6 +218,12 @@ impl Project {
             }
         };
         let ssh_details = this.ssh_details(cx);
+        // Projects connected to a running remote server over a socket can't spawn processes
+        // on the remote host, so don't fall back to a local terminal.
+        anyhow::ensure!(
+            ssh_details.is_some() || this.ssh_client.is_none(),
+            "terminals require an SSH connection to the remote host"
+        );
 
         let mut settings_location = None;
         if let Some(path) = path.as_ref() {
@@ -233,6 +255,7 @@ impl Project {
         };
 
         let mut python_venv_activate_command = None;
+        let mut problem_matching = None;
 
         let (spawn_task, shell) = match kind {
             TerminalKind::Shell(_) => {
@@ -268,6 +291,14 @@ impl Project {
                 }
             }
             TerminalKind::Task(spawn_task) => {
+                if !spawn_task.problem_matchers.is_empty() && this.is_local() {
+                    problem_matching = Some(ProblemMatching {
+                        task_id: spawn_task.id.clone(),
+                        label: spawn_task.full_label.clone(),
+                        matchers: spawn_task.problem_matchers.clone(),
+                        cwd: path.as_deref().map(Path::to_path_buf),
+                    });
+                }
                 let task_state = Some(TaskState {
                     id: spawn_task.id,
                     full_label: spawn_task.full_label,
@@ -367,10 +398,91 @@ impl Project {
             if let Some(activate_command) = python_venv_activate_command {
                 this.activate_python_virtual_environment(activate_command, &terminal_handle, cx);
             }
+            if let Some(problem_matching) = problem_matching {
+                this.match_task_problems(problem_matching, &terminal_handle, cx);
+            }
             terminal_handle
         })
     }
 
+    /// Publishes the problems the task's problem matchers find in its output as diagnostics,
+    /// while the task runs and once it completes.
+    fn match_task_problems(
+        &self,
+        problem_matching: ProblemMatching,
+        terminal: &Entity<Terminal>,
+        cx: &mut Context<Self>,
+    ) {
+        let ProblemMatching {
+            task_id,
+            label,
+            matchers,
+            cwd,
+        } = problem_matching;
+        let lsp_store = self.lsp_store.clone();
+        // Clear the diagnostics of the previous run of the task.
+        lsp_store.update(cx, |lsp_store, cx| {
+            lsp_store
+                .update_task_diagnostics(task_id.clone(), &label, Vec::new(), cx)
+                .log_err();
+        });
+
+        let terminal = terminal.downgrade();
+        let mut scanner = ProblemScanner::new(&matchers, cwd);
+        cx.spawn(async move |_, cx| {
+            let Ok(completed) =
+                terminal.read_with(cx, |terminal, cx| terminal.wait_for_completed_task(cx))
+            else {
+                return;
+            };
+            let mut completed = completed.fuse();
+            let mut position = OutputPosition::default();
+            let mut problems = Vec::new();
+            loop {
+                let mut timer = cx
+                    .background_executor()
+                    .timer(PROBLEM_MATCHING_INTERVAL)
+                    .fuse();
+                let task_completed = futures::select_biased! {
+                    _ = completed => true,
+                    _ = timer => false,
+                };
+                let Ok((output, next_position)) =
+                    terminal.read_with(cx, |terminal, _| terminal.read_output_since(&position))
+                else {
+                    return;
+                };
+                position = next_position;
+                if !output.is_empty() {
+                    let new_problems;
+                    (scanner, new_problems) = cx
+                        .background_spawn(async move {
+                            let new_problems = scanner.scan(&output);
+                            (scanner, new_problems)
+                        })
+                        .await;
+                    if !new_problems.is_empty() {
+                        problems.extend(new_problems);
+                        lsp_store
+                            .update(cx, |lsp_store, cx| {
+                                lsp_store.update_task_diagnostics(
+                                    task_id.clone(),
+                                    &label,
+                                    problems.clone(),
+                                    cx,
+                                )
+                            })
+                            .log_err();
+                    }
+                }
+                if task_completed {
+                    return;
+                }
+            }
+        })
+        .detach();
+    }
+
     fn python_venv_directory(
         &self,
         abs_path: Arc<Path>,
@@ -225,6 +225,10 @@ messages!(
     (GetDocumentColorResponse, Background),
     (GetColorPresentation, Background),
     (GetColorPresentationResponse, Background),
+    (GetDocumentLinks, Background),
+    (GetDocumentLinksResponse, Background),
+    (ResolveDocumentLink, Background),
+    (ResolveDocumentLinkResponse, Background),
     (RefreshCodeLens, Background),
     (GetCodeLens, Background),
     (GetCodeLensResponse, Background),
@@ -243,6 +247,8 @@ messages!(
     (ShareProjectResponse, Foreground),
     (ShowContacts, Foreground),
     (ShutdownRemoteServer, Foreground),
+    (WatchListeningPorts, Background),
+    (ListeningPortsChanged, Background),
     (Stage, Background),
     (StartLanguageServer, Foreground),
     (SubscribeToChannels, Foreground),
@@ -406,6 +412,8 @@ request_messages!(
     (ResolveInlayHint, ResolveInlayHintResponse),
     (GetDocumentColor, GetDocumentColorResponse),
     (GetColorPresentation, GetColorPresentationResponse),
+    (GetDocumentLinks, GetDocumentLinksResponse),
+    (ResolveDocumentLink, ResolveDocumentLinkResponse),
     (RespondToChannelInvite, Ack),
     (RespondToContactRequest, Ack),
     (SaveBuffer, BufferSaved),
@@ -445,6 +453,7 @@ request_messages!(
     (LspExtClearFlycheck, Ack),
     (AddWorktree, AddWorktreeResponse),
     (ShutdownRemoteServer, Ack),
+    (WatchListeningPorts, Ack),
     (RemoveWorktree, Ack),
     (OpenServerSettings, OpenBufferResponse),
     (GetPermalinkToLine, GetPermalinkToLineResponse),
@@ -498,6 +507,7 @@ entity_messages!(
     CreateBufferForPeer,
     CreateProjectEntry,
     GetDocumentColor,
+    GetDocumentLinks,
     DeleteProjectEntry,
     ExpandProjectEntry,
     ExpandAllForProjectEntry,
@@ -542,6 +552,7 @@ entity_messages!(
     RenameProjectEntry,
     ResolveCompletionDocumentation,
     ResolveInlayHint,
+    ResolveDocumentLink,
     SaveBuffer,
     Stage,
     StartLanguageServer,
@@ -576,6 +587,7 @@ entity_messages!(
     LanguageServerLog,
     Toast,
     HideToast,
+    ListeningPortsChanged,
     OpenServerSettings,
     GetPermalinkToLine,
     LanguageServerPromptRequest,
@@ -0,0 +1,862 @@
+mod quickfix_panel_settings;
+
+use std::{ops::Range, path::Path, sync::Arc};
+
+use anyhow::Context as _;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{
+    Editor, EditorEvent,
+    actions::{GoToDiagnostic, GoToPreviousDiagnostic},
+};
+use futures::future;
+use gpui::{
+    Action, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    ListSizingBehavior, PathPromptOptions, Pixels, Render, ScrollStrategy, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, uniform_list,
+};
+use itertools::Itertools as _;
+use language::{Buffer, DiagnosticSeverity, Point, ToPoint as _};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::{DirectoryLister, Fs, Project, ProjectItem as _, ProjectPath};
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use ui::{ListItem, Tab, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+    notifications::DetachAndPromptErr,
+    quickfix::{self, Quickfix, QuickfixEntry, format_quickfix_line, parse_quickfix_line},
+};
+
+pub use quickfix_panel_settings::QuickfixPanelSettings;
+
+actions!(
+    quickfix_panel,
+    [ToggleFocus, LoadDiagnostics, LoadReferences]
+);
+
+const QUICKFIX_PANEL_KEY: &str = "QuickfixPanel";
+
+pub fn init(cx: &mut App) {
+    QuickfixPanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace
+            .register_action(|workspace, _: &ToggleFocus, window, cx| {
+                workspace.toggle_panel_focus::<QuickfixPanel>(window, cx);
+            })
+            .register_action(|workspace, _: &quickfix::Next, window, cx| {
+                // Without a quickfix list, go through the diagnostics like before.
+                if workspace.quickfix().read(cx).is_empty() {
+                    window.dispatch_action(GoToDiagnostic.boxed_clone(), cx);
+                } else {
+                    go_to_entry(workspace, Quickfix::select_next, window, cx);
+                }
+            })
+            .register_action(|workspace, _: &quickfix::Previous, window, cx| {
+                if workspace.quickfix().read(cx).is_empty() {
+                    window.dispatch_action(GoToPreviousDiagnostic.boxed_clone(), cx);
+                } else {
+                    go_to_entry(workspace, Quickfix::select_previous, window, cx);
+                }
+            })
+            .register_action(|workspace, _: &quickfix::First, window, cx| {
+                go_to_entry(workspace, Quickfix::select_first, window, cx);
+            })
+            .register_action(|workspace, _: &quickfix::Last, window, cx| {
+                go_to_entry(workspace, Quickfix::select_last, window, cx);
+            })
+            .register_action(|workspace, _: &quickfix::Older, _, cx| {
+                workspace
+                    .quickfix()
+                    .update(cx, |quickfix, cx| quickfix.older(cx));
+            })
+            .register_action(|workspace, _: &quickfix::Newer, _, cx| {
+                workspace
+                    .quickfix()
+                    .update(cx, |quickfix, cx| quickfix.newer(cx));
+            })
+            .register_action(export_list)
+            .register_action(import_list)
+            .register_action(load_diagnostics)
+            .register_action(load_references);
+    })
+    .detach();
+}
+
+/// Opens the location of a quickfix entry in an editor.
+pub fn open_entry(
+    workspace: &mut Workspace,
+    entry: QuickfixEntry,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let open_item = workspace.open_path(entry.path, None, true, window, cx);
+    cx.spawn_in(window, async move |_, cx| {
+        let item = open_item.await?;
+        if let Some(editor) = item.downcast::<Editor>() {
+            editor.update_in(cx, |editor, window, cx| {
+                editor.go_to_singleton_buffer_point(
+                    Point::new(entry.row, entry.column),
+                    window,
+                    cx,
+                );
+            })?;
+        }
+        anyhow::Ok(())
+    })
+    .detach_and_log_err(cx);
+}
+
+fn go_to_entry(
+    workspace: &mut Workspace,
+    select: impl FnOnce(&mut Quickfix, &mut Context<Quickfix>) -> Option<QuickfixEntry>,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    if let Some(entry) = workspace.quickfix().clone().update(cx, select) {
+        open_entry(workspace, entry, window, cx);
+    }
+}
+
+/// Makes a quickfix list out of the errors and warnings of the project. This
+/// includes the problems that the problem matchers of tasks found in their
+/// output, which are published as diagnostics.
+fn load_diagnostics(
+    workspace: &mut Workspace,
+    _: &LoadDiagnostics,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().clone();
+    let paths = project
+        .read(cx)
+        .diagnostic_summaries(false, cx)
+        .filter(|(_, _, summary)| summary.error_count + summary.warning_count > 0)
+        .map(|(path, _, _)| path)
+        .unique()
+        .collect::<Vec<_>>();
+    let open_buffers = paths
+        .iter()
+        .map(|path| project.update(cx, |project, cx| project.open_buffer(path.clone(), cx)))
+        .collect::<Vec<_>>();
+    let quickfix = workspace.quickfix().clone();
+    cx.spawn_in(window, async move |workspace, cx| {
+        let buffers = future::try_join_all(open_buffers).await?;
+        let mut entries = Vec::new();
+        for (path, buffer) in paths.into_iter().zip(buffers) {
+            let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+            for (_, group) in snapshot.diagnostic_groups(None) {
+                let primary = &group.entries[group.primary_ix];
+                let severity = match primary.diagnostic.severity {
+                    DiagnosticSeverity::ERROR => "error",
+                    DiagnosticSeverity::WARNING => "warning",
+                    _ => continue,
+                };
+                let point = primary.range.start.to_point(&snapshot);
+                let message = primary
+                    .diagnostic
+                    .message
+                    .lines()
+                    .next()
+                    .unwrap_or_default();
+                entries.push(QuickfixEntry {
+                    path: path.clone(),
+                    row: point.row,
+                    column: point.column,
+                    text: format!("{severity}: {message}").into(),
+                });
+            }
+        }
+        quickfix.update(cx, |quickfix, cx| {
+            quickfix.set_list("Diagnostics", entries, cx)
+        })?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.focus_panel::<QuickfixPanel>(window, cx);
+        })
+    })
+    .detach_and_prompt_err("Failed to load the diagnostics", window, cx, |_, _, _| None);
+}
+
+/// Makes a quickfix list out of the references to the symbol under the cursor
+/// of the active editor, as found by the language servers.
+fn load_references(
+    workspace: &mut Workspace,
+    _: &LoadReferences,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(editor) = workspace.active_item_as::<Editor>(cx) else {
+        return;
+    };
+    let Some((buffer, position)) = editor.update(cx, |editor, cx| {
+        let head = editor.selections.newest_anchor().head();
+        editor.buffer().read(cx).text_anchor_for_position(head, cx)
+    }) else {
+        return;
+    };
+    let references = workspace
+        .project()
+        .update(cx, |project, cx| project.references(&buffer, position, cx));
+    let quickfix = workspace.quickfix().clone();
+    cx.spawn_in(window, async move |workspace, cx| {
+        let locations = references.await?;
+        let mut entries = Vec::new();
+        for location in locations {
+            location.buffer.read_with(cx, |buffer, cx| {
+                let Some(path) = buffer.project_path(cx) else {
+                    return;
+                };
+                let point = location.range.start.to_point(buffer);
+                let line = buffer
+                    .text_for_range(
+                        Point::new(point.row, 0)..Point::new(point.row, buffer.line_len(point.row)),
+                    )
+                    .collect::<String>();
+                entries.push(QuickfixEntry {
+                    path,
+                    row: point.row,
+                    column: point.column,
+                    text: line.trim().to_string().into(),
+                });
+            })?;
+        }
+        quickfix.update(cx, |quickfix, cx| {
+            quickfix.set_list("References", entries, cx)
+        })?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            workspace.focus_panel::<QuickfixPanel>(window, cx);
+        })
+    })
+    .detach_and_prompt_err("Failed to find the references", window, cx, |_, _, _| None);
+}
+
+/// Writes the current quickfix list to a file, one `path:line:column: text`
+/// line per entry.
+fn export_list(
+    workspace: &mut Workspace,
+    _: &quickfix::Export,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(list) = workspace.quickfix().read(cx).current_list() else {
+        return;
+    };
+    let project = workspace.project().read(cx);
+    let text = list
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let path = project.absolute_path(&entry.path, cx)?;
+            Some(format_quickfix_line(&path, entry.row, entry.column, &entry.text) + "\n")
+        })
+        .collect::<String>();
+
+    let project = workspace.project().downgrade();
+    let lister = DirectoryLister::Project(workspace.project().clone());
+    let new_path = workspace.prompt_for_new_path(lister, window, cx);
+    cx.spawn_in(window, async move |_, cx| {
+        let Some(path) = new_path.await.ok().flatten().into_iter().flatten().next() else {
+            return Ok(());
+        };
+        let buffer = open_file_buffer(&project, &path, cx).await?;
+        buffer.update(cx, |buffer, cx| {
+            buffer.set_text(text, cx);
+        })?;
+        project
+            .update(cx, |project, cx| project.save_buffer(buffer, cx))?
+            .await
+    })
+    .detach_and_prompt_err(
+        "Failed to export the quickfix list",
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+/// Reads a quickfix list from a file, such as one written by
+/// `quickfix::Export` or the output of a compiler or `grep -n`. Lines that
+/// are not locations in the project are skipped.
+fn import_list(
+    workspace: &mut Workspace,
+    _: &quickfix::Import,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let project = workspace.project().downgrade();
+    let lister = DirectoryLister::Project(workspace.project().clone());
+    let paths = workspace.prompt_for_open_path(
+        PathPromptOptions {
+            files: true,
+            directories: false,
+            multiple: false,
+        },
+        lister,
+        window,
+        cx,
+    );
+    cx.spawn_in(window, async move |workspace, cx| {
+        let Some(path) = paths.await.ok().flatten().into_iter().flatten().next() else {
+            return Ok(());
+        };
+        let buffer = open_file_buffer(&project, &path, cx).await?;
+        let text = buffer.read_with(cx, |buffer, _| buffer.text())?;
+        workspace.update_in(cx, |workspace, window, cx| {
+            let project = workspace.project().read(cx);
+            let entries = text
+                .lines()
+                .filter_map(parse_quickfix_line)
+                .filter_map(|line| {
+                    Some(QuickfixEntry {
+                        path: project.find_project_path(&line.path, cx)?,
+                        row: line.row,
+                        column: line.column,
+                        text: line.text.into(),
+                    })
+                })
+                .collect();
+            let title = path
+                .file_name()
+                .map_or_else(|| path.to_string_lossy(), |name| name.to_string_lossy())
+                .to_string();
+            workspace
+                .quickfix()
+                .update(cx, |quickfix, cx| quickfix.set_list(title, entries, cx));
+            workspace.focus_panel::<QuickfixPanel>(window, cx);
+        })
+    })
+    .detach_and_prompt_err(
+        "Failed to import the quickfix list",
+        window,
+        cx,
+        |_, _, _| None,
+    );
+}
+
+/// Opens the file at the given path through the project, so that quickfix
+/// lists are read from and written to the remote host in remote projects. The
+/// file doesn't need to exist yet.
+async fn open_file_buffer(
+    project: &WeakEntity<Project>,
+    path: &Path,
+    cx: &mut AsyncWindowContext,
+) -> anyhow::Result<Entity<Buffer>> {
+    let (dir, file_name) = path
+        .parent()
+        .zip(path.file_name())
+        .with_context(|| format!("invalid path {path:?}"))?;
+    let (worktree, relative_dir) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(dir, false, cx)
+        })?
+        .await?;
+    project
+        .update(cx, |project, cx| {
+            let worktree_id = worktree.read(cx).id();
+            project.open_buffer(
+                ProjectPath {
+                    worktree_id,
+                    path: relative_dir.join(file_name).into(),
+                },
+                cx,
+            )
+        })?
+        .await
+}
+
+/// Shows the workspace's current quickfix list, and lets the user filter it,
+/// go to its entries and switch to older or newer lists.
+pub struct QuickfixPanel {
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    quickfix: Entity<Quickfix>,
+    focus_handle: FocusHandle,
+    filter_editor: Entity<Editor>,
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+    /// The indices of the entries of the current list that match the filter.
+    matches: Vec<usize>,
+    /// The selected position in `matches`.
+    selected_ix: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedQuickfixPanel {
+    width: Option<Pixels>,
+    height: Option<Pixels>,
+}
+
+impl QuickfixPanel {
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<anyhow::Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(QUICKFIX_PANEL_KEY) })
+                .await
+                .context("loading quickfix panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedQuickfixPanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten();
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let panel = Self::new(workspace, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        panel.height = serialized_panel.height.map(|height| height.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let quickfix = workspace.quickfix().clone();
+        let workspace_handle = cx.entity().downgrade();
+        cx.new(|cx| {
+            let filter_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Filter...", cx);
+                editor
+            });
+            let subscriptions = vec![
+                cx.observe(&quickfix, |panel, _, cx| panel.update_matches(cx)),
+                cx.subscribe(&filter_editor, |panel, _, event, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        panel.update_matches(cx);
+                    }
+                }),
+            ];
+
+            let mut panel = Self {
+                fs,
+                workspace: workspace_handle,
+                quickfix,
+                focus_handle: cx.focus_handle(),
+                filter_editor,
+                width: None,
+                height: None,
+                matches: Vec::new(),
+                selected_ix: None,
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                _subscriptions: subscriptions,
+            };
+            panel.update_matches(cx);
+            panel
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        let height = self.height;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        QUICKFIX_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedQuickfixPanel { width, height })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let filter = self.filter_editor.read(cx).text(cx).to_lowercase();
+        let filter = filter.trim();
+        let quickfix = self.quickfix.read(cx);
+        let Some(list) = quickfix.current_list() else {
+            self.matches.clear();
+            self.selected_ix = None;
+            cx.notify();
+            return;
+        };
+
+        self.matches = list
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                filter.is_empty()
+                    || entry.text.to_lowercase().contains(filter)
+                    || entry
+                        .path
+                        .path
+                        .to_string_lossy()
+                        .to_lowercase()
+                        .contains(filter)
+            })
+            .map(|(ix, _)| ix)
+            .collect();
+        self.selected_ix = list
+            .selected
+            .and_then(|selected| self.matches.iter().position(|ix| *ix == selected))
+            .or_else(|| (!self.matches.is_empty()).then_some(0));
+        if let Some(selected_ix) = self.selected_ix {
+            self.scroll_handle
+                .scroll_to_item(selected_ix, ScrollStrategy::Center);
+        }
+        cx.notify();
+    }
+
+    fn open_match(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(&entry_ix) = self.matches.get(ix) else {
+            return;
+        };
+        let Some(entry) = self
+            .quickfix
+            .update(cx, |quickfix, cx| quickfix.select(entry_ix, cx))
+        else {
+            return;
+        };
+        self.workspace
+            .update(cx, |workspace, cx| {
+                open_entry(workspace, entry, window, cx);
+            })
+            .ok();
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_ix) = self.selected_ix {
+            if selected_ix + 1 < self.matches.len() {
+                self.select(selected_ix + 1, cx);
+            }
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_ix) = self.selected_ix {
+            self.select(selected_ix.saturating_sub(1), cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.matches.is_empty() {
+            self.select(0, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.matches.is_empty() {
+            self.select(self.matches.len() - 1, cx);
+        }
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_ix = Some(ix);
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_ix) = self.selected_ix {
+            self.open_match(selected_ix, window, cx);
+        }
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let quickfix = self.quickfix.read(cx);
+        let title = quickfix
+            .current_list()
+            .map_or_else(|| "Quickfix".into(), |list| list.title.clone());
+        let history = quickfix
+            .current_list_ix()
+            .map(|ix| format!("{} of {}", ix + 1, quickfix.lists().count()));
+        let has_older = quickfix.current_list_ix().is_some_and(|ix| ix > 0);
+        let has_newer = quickfix
+            .current_list_ix()
+            .is_some_and(|ix| ix + 1 < quickfix.lists().count());
+        let has_list = quickfix.current_list().is_some();
+        let focus_handle = self.focus_handle.clone();
+
+        h_flex()
+            .px_2()
+            .gap_1()
+            .h(Tab::container_height(cx))
+            .flex_none()
+            .border_b_1()
+            .border_color(cx.theme().colors().border)
+            .child(Label::new(title).truncate())
+            .children(history.map(|history| {
+                Label::new(history)
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+            .child(div().flex_1())
+            .child(
+                IconButton::new("quickfix-older", IconName::ChevronLeft)
+                    .icon_size(IconSize::Small)
+                    .disabled(!has_older)
+                    .on_click(|_, window, cx| window.dispatch_action(Box::new(quickfix::Older), cx))
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Older List",
+                                &quickfix::Older,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("quickfix-newer", IconName::ChevronRight)
+                    .icon_size(IconSize::Small)
+                    .disabled(!has_newer)
+                    .on_click(|_, window, cx| window.dispatch_action(Box::new(quickfix::Newer), cx))
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Newer List",
+                                &quickfix::Newer,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("quickfix-load-diagnostics", IconName::Warning)
+                    .icon_size(IconSize::Small)
+                    .on_click(|_, window, cx| window.dispatch_action(Box::new(LoadDiagnostics), cx))
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Load Diagnostics",
+                                &LoadDiagnostics,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("quickfix-import", IconName::Download)
+                    .icon_size(IconSize::Small)
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(quickfix::Import), cx)
+                    })
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Import List",
+                                &quickfix::Import,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    }),
+            )
+            .child(
+                IconButton::new("quickfix-export", IconName::ArrowUpRight)
+                    .icon_size(IconSize::Small)
+                    .disabled(!has_list)
+                    .on_click(|_, window, cx| {
+                        window.dispatch_action(Box::new(quickfix::Export), cx)
+                    })
+                    .tooltip(move |window, cx| {
+                        Tooltip::for_action_in(
+                            "Export List",
+                            &quickfix::Export,
+                            &focus_handle,
+                            window,
+                            cx,
+                        )
+                    }),
+            )
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<ListItem> {
+        let quickfix = self.quickfix.read(cx);
+        let Some(list) = quickfix.current_list() else {
+            return Vec::new();
+        };
+        range
+            .filter_map(|ix| {
+                let entry_ix = *self.matches.get(ix)?;
+                let entry = list.entries.get(entry_ix)?;
+                let location = format!(
+                    "{}:{}:{}",
+                    entry.path.path.display(),
+                    entry.row + 1,
+                    entry.column + 1
+                );
+                let is_current = list.selected == Some(entry_ix);
+                Some(
+                    ListItem::new(ix)
+                        .spacing(ui::ListItemSpacing::Sparse)
+                        .toggle_state(self.selected_ix == Some(ix))
+                        .child(
+                            h_flex()
+                                .w_full()
+                                .gap_2()
+                                .child(Label::new(location).size(LabelSize::Small).color(
+                                    if is_current {
+                                        Color::Accent
+                                    } else {
+                                        Color::Muted
+                                    },
+                                ))
+                                .child(
+                                    Label::new(entry.text.clone())
+                                        .size(LabelSize::Small)
+                                        .truncate(),
+                                ),
+                        )
+                        .on_click(cx.listener(move |panel, _, window, cx| {
+                            panel.selected_ix = Some(ix);
+                            panel.open_match(ix, window, cx);
+                        })),
+                )
+            })
+            .collect()
+    }
+}
+
+impl Panel for QuickfixPanel {
+    fn persistent_name() -> &'static str {
+        "QuickfixPanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        QuickfixPanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, _: DockPosition) -> bool {
+        true
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<QuickfixPanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, window: &Window, cx: &App) -> Pixels {
+        let settings = QuickfixPanelSettings::get_global(cx);
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => {
+                self.width.unwrap_or(settings.default_width)
+            }
+            DockPosition::Bottom => self.height.unwrap_or(settings.default_height),
+        }
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, window: &mut Window, cx: &mut Context<Self>) {
+        match self.position(window, cx) {
+            DockPosition::Left | DockPosition::Right => self.width = size,
+            DockPosition::Bottom => self.height = size,
+        }
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        QuickfixPanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::ListTodo)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Quickfix Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        11
+    }
+}
+
+impl Focusable for QuickfixPanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for QuickfixPanel {}
+
+impl Render for QuickfixPanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let has_list = self.quickfix.read(cx).current_list().is_some();
+        v_flex()
+            .id("quickfix-panel")
+            .key_context("QuickfixPanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .child(self.render_header(cx))
+            .map(|this| {
+                if !has_list {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new(
+                                "Send project search matches here, load the diagnostics \
+                                 or import a list to fill the quickfix list.",
+                            )
+                            .color(Color::Muted)
+                            .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        h_flex()
+                            .p_2()
+                            .flex_none()
+                            .border_b_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .child(self.filter_editor.clone()),
+                    )
+                    .child(
+                        uniform_list(
+                            "quickfix-entries",
+                            self.matches.len(),
+                            cx.processor(|panel, range: Range<usize>, _, cx| {
+                                panel.render_entries(range, cx)
+                            }),
+                        )
+                        .with_sizing_behavior(ListSizingBehavior::Infer)
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full()
+                        .p_1(),
+                    )
+                }
+            })
+    }
+}
@@ -0,0 +1,48 @@
+use gpui::Pixels;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+use workspace::dock::DockPosition;
+
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct QuickfixPanelSettings {
+    pub button: bool,
+    pub dock: DockPosition,
+    pub default_width: Pixels,
+    pub default_height: Pixels,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct QuickfixPanelSettingsContent {
+    /// Whether to show the quickfix panel button in the status bar.
+    ///
+    /// Default: true
+    pub button: Option<bool>,
+    /// Where to dock the quickfix panel.
+    ///
+    /// Default: bottom
+    pub dock: Option<DockPosition>,
+    /// Default width of the quickfix panel in pixels, when docked to the side.
+    ///
+    /// Default: 360
+    pub default_width: Option<f32>,
+    /// Default height of the quickfix panel in pixels, when docked to the bottom.
+    ///
+    /// Default: 240
+    pub default_height: Option<f32>,
+}
+
+impl Settings for QuickfixPanelSettings {
+    const KEY: Option<&'static str> = Some("quickfix_panel");
+
+    type FileContent = QuickfixPanelSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::App,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+
+    fn import_from_vscode(_vscode: &settings::VsCodeSettings, _current: &mut Self::FileContent) {}
+}
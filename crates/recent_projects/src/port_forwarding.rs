@@ -0,0 +1,343 @@
+use std::{collections::HashSet, sync::Arc, time::Duration};
+
+use gpui::{
+    App, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable, Render, Subscription,
+    Task, Window, actions,
+};
+use picker::{Picker, PickerDelegate};
+use project::Project;
+use remote::{SshRemoteClient, SshRemoteEvent, ssh_session::SshPortForwardOption};
+use ui::{ListItem, ListItemSpacing, prelude::*};
+use util::ResultExt;
+use workspace::{
+    ModalView, Workspace,
+    notifications::{
+        DetachAndPromptErr, NotificationId, simple_message_notification::MessageNotification,
+    },
+};
+
+actions!(projects, [ManagePortForwards]);
+
+/// How often the remote server is asked to keep watching listening ports,
+/// which it stops doing when no longer asked.
+const WATCH_LISTENING_PORTS_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Offers to forward the ports that start listening on the remote host of an
+/// SSH project, and lets the forwarded ports be managed while connected.
+pub fn register(
+    workspace: &mut Workspace,
+    window: Option<&mut Window>,
+    cx: &mut Context<Workspace>,
+) {
+    let Some(window) = window else {
+        return;
+    };
+    let project = workspace.project().clone();
+    let Some(ssh_client) = project.read(cx).ssh_client() else {
+        return;
+    };
+    // Ports can only be forwarded over SSH.
+    if ssh_client.read(cx).ssh_args().is_none() {
+        return;
+    }
+
+    workspace.register_action(|workspace, _: &ManagePortForwards, window, cx| {
+        PortForwards::toggle(workspace, window, cx);
+    });
+
+    cx.spawn(async move |workspace, cx| {
+        loop {
+            let Ok(watch) = workspace.update(cx, |workspace, cx| {
+                workspace
+                    .project()
+                    .read(cx)
+                    .watch_remote_listening_ports(cx)
+            }) else {
+                break;
+            };
+            watch.await.log_err();
+            cx.background_executor()
+                .timer(WATCH_LISTENING_PORTS_INTERVAL)
+                .await;
+        }
+    })
+    .detach();
+
+    let mut prompted_ports = HashSet::new();
+    cx.subscribe_in(&project, window, move |workspace, project, event, _, cx| {
+        if *event != project::Event::RemoteListeningPortsChanged {
+            return;
+        }
+        let listening_ports = project.read(cx).remote_listening_ports().to_vec();
+        // Prompt again if a port is closed and then opened again.
+        prompted_ports.retain(|port| listening_ports.contains(port));
+        for port in listening_ports {
+            if ssh_client.read(cx).is_port_forwarded(port) || !prompted_ports.insert(port) {
+                continue;
+            }
+            show_forward_port_notification(workspace, ssh_client.clone(), port, cx);
+        }
+    })
+    .detach();
+}
+
+fn show_forward_port_notification(
+    workspace: &mut Workspace,
+    ssh_client: Entity<SshRemoteClient>,
+    port: u16,
+    cx: &mut Context<Workspace>,
+) {
+    struct ForwardPortNotification;
+
+    let notification_id = NotificationId::composite::<ForwardPortNotification>(port as usize);
+    workspace.show_notification(notification_id, cx, |cx| {
+        cx.new(move |cx| {
+            MessageNotification::new(format!("Port {port} is listening on the remote host"), cx)
+                .primary_message("Forward Port")
+                .primary_icon(IconName::ArrowUpRight)
+                .primary_on_click(move |window, cx| {
+                    ssh_client
+                        .update(cx, |ssh_client, cx| {
+                            ssh_client.add_port_forward(SshPortForwardOption::for_port(port), cx)
+                        })
+                        .detach_and_prompt_err("Failed to forward port", window, cx, |_, _, _| {
+                            None
+                        });
+                })
+        })
+    });
+}
+
+pub struct PortForwards {
+    picker: Entity<Picker<PortForwardsDelegate>>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ModalView for PortForwards {}
+
+impl PortForwards {
+    fn toggle(workspace: &mut Workspace, window: &mut Window, cx: &mut Context<Workspace>) {
+        let project = workspace.project().clone();
+        let Some(ssh_client) = project.read(cx).ssh_client() else {
+            return;
+        };
+        workspace.toggle_modal(window, cx, |window, cx| {
+            Self::new(project, ssh_client, window, cx)
+        });
+    }
+
+    fn new(
+        project: Entity<Project>,
+        ssh_client: Entity<SshRemoteClient>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let delegate = PortForwardsDelegate {
+            project: project.clone(),
+            ssh_client: ssh_client.clone(),
+            entries: Vec::new(),
+            selected_index: 0,
+        };
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let _subscriptions = vec![
+            cx.subscribe(&picker, |_, _, _, cx| cx.emit(DismissEvent)),
+            cx.subscribe_in(&ssh_client, window, |this, _, event, window, cx| {
+                if let SshRemoteEvent::PortForwardsChanged = event {
+                    this.refresh(window, cx);
+                }
+            }),
+            cx.subscribe_in(&project, window, |this, _, event, window, cx| {
+                if *event == project::Event::RemoteListeningPortsChanged {
+                    this.refresh(window, cx);
+                }
+            }),
+        ];
+        Self {
+            picker,
+            _subscriptions,
+        }
+    }
+
+    fn refresh(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.picker
+            .update(cx, |picker, cx| picker.refresh(window, cx));
+    }
+}
+
+impl EventEmitter<DismissEvent> for PortForwards {}
+
+impl Focusable for PortForwards {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for PortForwards {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+enum PortForwardEntry {
+    /// A forward typed into the query.
+    New(SshPortForwardOption),
+    Active {
+        forward: SshPortForwardOption,
+        removable: bool,
+    },
+    /// A port that started listening on the remote host and isn't forwarded.
+    Listening(u16),
+}
+
+pub struct PortForwardsDelegate {
+    project: Entity<Project>,
+    ssh_client: Entity<SshRemoteClient>,
+    entries: Vec<PortForwardEntry>,
+    selected_index: usize,
+}
+
+impl PickerDelegate for PortForwardsDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        "Forward a port, e.g. 8080 or 8080:localhost:3000…".into()
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No forwarded ports".into())
+    }
+
+    fn match_count(&self) -> usize {
+        self.entries.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let query = query.trim();
+        let ssh_client = self.ssh_client.read(cx);
+
+        self.entries.clear();
+        if let Ok(forward) = query.parse::<SshPortForwardOption>() {
+            if !ssh_client
+                .port_forwards()
+                .any(|(existing, _)| *existing == forward)
+            {
+                self.entries.push(PortForwardEntry::New(forward));
+            }
+        }
+        self.entries.extend(
+            ssh_client
+                .port_forwards()
+                .filter(|(forward, _)| forward.to_string().contains(query))
+                .map(|(forward, removable)| PortForwardEntry::Active {
+                    forward: forward.clone(),
+                    removable,
+                }),
+        );
+        self.entries.extend(
+            self.project
+                .read(cx)
+                .remote_listening_ports()
+                .iter()
+                .filter(|port| !ssh_client.is_port_forwarded(**port))
+                .filter(|port| port.to_string().contains(query))
+                .map(|port| PortForwardEntry::Listening(*port)),
+        );
+        self.selected_index = self
+            .selected_index
+            .min(self.entries.len().saturating_sub(1));
+        Task::ready(())
+    }
+
+    fn confirm(&mut self, _secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        let Some(entry) = self.entries.get(self.selected_index) else {
+            return;
+        };
+        let task = match entry {
+            PortForwardEntry::New(forward) => {
+                let forward = forward.clone();
+                self.ssh_client.update(cx, |ssh_client, cx| {
+                    ssh_client.add_port_forward(forward, cx)
+                })
+            }
+            PortForwardEntry::Listening(port) => {
+                let forward = SshPortForwardOption::for_port(*port);
+                self.ssh_client.update(cx, |ssh_client, cx| {
+                    ssh_client.add_port_forward(forward, cx)
+                })
+            }
+            PortForwardEntry::Active {
+                forward,
+                removable: true,
+            } => {
+                let forward = forward.clone();
+                self.ssh_client.update(cx, |ssh_client, cx| {
+                    ssh_client.remove_port_forward(forward, cx)
+                })
+            }
+            PortForwardEntry::Active {
+                removable: false, ..
+            } => return,
+        };
+        task.detach_and_prompt_err("Failed to update port forwards", window, cx, |_, _, _| None);
+    }
+
+    fn dismissed(&mut self, _window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        _cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let (icon, label, action) = match self.entries.get(ix)? {
+            PortForwardEntry::New(forward) => (IconName::Plus, format!("Forward {forward}"), None),
+            PortForwardEntry::Active { forward, removable } => (
+                IconName::ArrowUpRight,
+                forward.to_string(),
+                Some(if *removable {
+                    "Stop forwarding"
+                } else {
+                    "Configured in settings"
+                }),
+            ),
+            PortForwardEntry::Listening(port) => (
+                IconName::Server,
+                format!("Port {port} is listening"),
+                Some("Forward"),
+            ),
+        };
+
+        Some(
+            ListItem::new(ix)
+                .inset(true)
+                .spacing(ListItemSpacing::Sparse)
+                .toggle_state(selected)
+                .start_slot(Icon::new(icon).color(Color::Muted))
+                .child(Label::new(label))
+                .when_some(action, |item, action| {
+                    item.end_slot(Label::new(action).color(Color::Muted))
+                }),
+        )
+    }
+}
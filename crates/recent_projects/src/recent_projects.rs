@@ -1,4 +1,5 @@
 pub mod disconnected_overlay;
+mod port_forwarding;
 mod remote_servers;
 mod ssh_config;
 mod ssh_connections;
@@ -16,6 +17,7 @@ use picker::{
     Picker, PickerDelegate,
     highlighted_match_with_paths::{HighlightedMatch, HighlightedMatchWithPaths},
 };
+pub use port_forwarding::ManagePortForwards;
 pub use remote_servers::RemoteServerProjects;
 use settings::Settings;
 pub use ssh_connections::SshSettings;
@@ -65,6 +67,7 @@ pub fn init(cx: &mut App) {
     });
 
     cx.observe_new(DisconnectedOverlay::register).detach();
+    cx.observe_new(port_forwarding::register).detach();
 }
 
 pub struct RecentProjects {
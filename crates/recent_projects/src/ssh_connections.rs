@@ -16,7 +16,7 @@ use language::CursorShape;
 use markdown::{Markdown, MarkdownElement, MarkdownStyle};
 use release_channel::ReleaseChannel;
 use remote::ssh_session::{ConnectionIdentifier, SshPortForwardOption};
-use remote::{SshConnectionOptions, SshPlatform, SshRemoteClient};
+use remote::{SocketConnectionOptions, SshConnectionOptions, SshPlatform, SshRemoteClient};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 use settings::{Settings, SettingsSources};
@@ -58,6 +58,7 @@ impl SshSettings {
                     username,
                     port_forwards: conn.port_forwards,
                     password: None,
+                    socket: conn.socket,
                 };
             }
         }
@@ -94,6 +95,11 @@ pub struct SshConnection {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub port_forwards: Option<Vec<SshPortForwardOption>>,
+
+    /// Connect to a `remote_server` that is already running on the host and listening on
+    /// the given address, instead of starting one over SSH.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub socket: Option<SocketConnectionOptions>,
 }
 
 impl From<SshConnection> for SshConnectionOptions {
@@ -107,6 +113,7 @@ impl From<SshConnection> for SshConnectionOptions {
             nickname: val.nickname,
             upload_binary_over_ssh: val.upload_binary_over_ssh.unwrap_or_default(),
             port_forwards: val.port_forwards,
+            socket: val.socket,
         }
     }
 }
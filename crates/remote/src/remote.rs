@@ -1,8 +1,10 @@
 pub mod json_log;
 pub mod protocol;
 pub mod proxy;
+pub mod socket_connection;
 pub mod ssh_session;
 
+pub use socket_connection::{ServerAddress, SocketConnectionOptions};
 pub use ssh_session::{
     ConnectionState, SshClientDelegate, SshConnectionOptions, SshPlatform, SshRemoteClient,
     SshRemoteEvent,
@@ -0,0 +1,480 @@
+//! Connections to a `remote_server` that is already running and listening on a
+//! TCP or Unix socket, for hosts that can't be reached over SSH, such as
+//! containers, VMs and CI sandboxes.
+
+use crate::{
+    protocol::{read_message, write_message},
+    proxy::ProxyLaunchError,
+    ssh_session::{
+        RemoteConnection, SshClientDelegate, SshConnectionOptions, SshPortForwardOption,
+    },
+};
+use anyhow::{Context as _, Result, anyhow};
+use async_trait::async_trait;
+use futures::{
+    AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _, FutureExt as _, StreamExt as _,
+    channel::mpsc::{Sender, UnboundedReceiver, UnboundedSender},
+};
+use gpui::{App, AsyncApp, Task};
+use rpc::proto::Envelope;
+use schemars::JsonSchema;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{
+    fmt,
+    path::PathBuf,
+    pin::pin,
+    str::FromStr,
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering::SeqCst},
+    },
+    time::Duration,
+};
+
+pub type SocketReader = Box<dyn AsyncRead + Send + Unpin>;
+pub type SocketWriter = Box<dyn AsyncWrite + Send + Unpin>;
+
+const MAX_TOKEN_LEN: u32 = 1024;
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+const HANDSHAKE_ACCEPTED: u8 = 0;
+const HANDSHAKE_INVALID_TOKEN: u8 = 1;
+const HANDSHAKE_NO_SESSION: u8 = 2;
+
+/// The address that a `remote_server` started with `remote_server listen`
+/// accepts connections on.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum ServerAddress {
+    /// A `host:port` pair, written as `tcp:<host>:<port>`.
+    Tcp(String),
+    /// The path of a Unix socket, written as `unix:<path>`.
+    Unix(PathBuf),
+}
+
+impl ServerAddress {
+    async fn connect(&self) -> Result<(SocketReader, SocketWriter)> {
+        match self {
+            Self::Tcp(address) => {
+                let stream = smol::net::TcpStream::connect(address.as_str()).await?;
+                stream.set_nodelay(true)?;
+                Ok((Box::new(stream.clone()), Box::new(stream)))
+            }
+            #[cfg(unix)]
+            Self::Unix(path) => {
+                let stream = smol::net::unix::UnixStream::connect(path).await?;
+                Ok((Box::new(stream.clone()), Box::new(stream)))
+            }
+            #[cfg(not(unix))]
+            Self::Unix(_) => anyhow::bail!("Unix sockets are not supported on this platform"),
+        }
+    }
+}
+
+impl FromStr for ServerAddress {
+    type Err = anyhow::Error;
+
+    fn from_str(address: &str) -> Result<Self> {
+        if let Some(host_and_port) = address.strip_prefix("tcp:") {
+            let (host, port) = host_and_port
+                .rsplit_once(':')
+                .with_context(|| format!("missing port in address {address:?}"))?;
+            anyhow::ensure!(!host.is_empty(), "missing host in address {address:?}");
+            port.parse::<u16>()
+                .with_context(|| format!("invalid port in address {address:?}"))?;
+            Ok(Self::Tcp(host_and_port.to_string()))
+        } else if let Some(path) = address.strip_prefix("unix:") {
+            anyhow::ensure!(!path.is_empty(), "missing path in address {address:?}");
+            Ok(Self::Unix(PathBuf::from(path)))
+        } else {
+            anyhow::bail!(
+                "invalid address {address:?}, expected `tcp:<host>:<port>` or `unix:<path>`"
+            )
+        }
+    }
+}
+
+impl fmt::Display for ServerAddress {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Tcp(host_and_port) => write!(f, "tcp:{host_and_port}"),
+            Self::Unix(path) => write!(f, "unix:{}", path.display()),
+        }
+    }
+}
+
+impl Serialize for ServerAddress {
+    fn serialize<S: Serializer>(&self, serializer: S) -> std::result::Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for ServerAddress {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> std::result::Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// Options for connecting to a `remote_server` that is already running,
+/// instead of starting one over SSH.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Deserialize, Serialize, JsonSchema)]
+pub struct SocketConnectionOptions {
+    /// The address the server listens on, e.g. `tcp:localhost:7000` or
+    /// `unix:/tmp/zed.sock`.
+    #[schemars(with = "String")]
+    pub address: ServerAddress,
+    /// The token the server was started with.
+    pub token: String,
+}
+
+/// Accepts connections from clients on behalf of a `remote_server` started
+/// with `remote_server listen`.
+pub struct SocketListener {
+    listener: Listener,
+    token: String,
+    has_session: bool,
+}
+
+enum Listener {
+    Tcp(smol::net::TcpListener),
+    #[cfg(unix)]
+    Unix(smol::net::unix::UnixListener),
+}
+
+impl SocketListener {
+    pub async fn bind(address: &ServerAddress, token: String) -> Result<Self> {
+        anyhow::ensure!(!token.is_empty(), "the token must not be empty");
+        anyhow::ensure!(
+            token.len() <= MAX_TOKEN_LEN as usize,
+            "the token must be at most {MAX_TOKEN_LEN} bytes long"
+        );
+
+        let listener = match address {
+            ServerAddress::Tcp(host_and_port) => Listener::Tcp(
+                smol::net::TcpListener::bind(host_and_port.as_str())
+                    .await
+                    .with_context(|| format!("failed to listen on {address}"))?,
+            ),
+            #[cfg(unix)]
+            ServerAddress::Unix(path) => Listener::Unix(
+                smol::net::unix::UnixListener::bind(path)
+                    .with_context(|| format!("failed to listen on {address}"))?,
+            ),
+            #[cfg(not(unix))]
+            ServerAddress::Unix(_) => {
+                anyhow::bail!("Unix sockets are not supported on this platform")
+            }
+        };
+
+        Ok(Self {
+            listener,
+            token,
+            has_session: false,
+        })
+    }
+
+    /// Returns the address that the listener is bound to, which differs from
+    /// the one it was bound with when using port 0.
+    pub fn local_address(&self) -> Result<ServerAddress> {
+        match &self.listener {
+            Listener::Tcp(listener) => Ok(ServerAddress::Tcp(listener.local_addr()?.to_string())),
+            #[cfg(unix)]
+            Listener::Unix(listener) => {
+                let address = listener.local_addr()?;
+                let path = address.as_pathname().context("the socket has no path")?;
+                Ok(ServerAddress::Unix(path.to_path_buf()))
+            }
+        }
+    }
+
+    /// Waits for the next client that authenticates with the listener's token,
+    /// and returns the streams for exchanging messages with it.
+    pub async fn accept(&mut self) -> Result<(SocketReader, SocketWriter)> {
+        loop {
+            let (mut reader, mut writer): (SocketReader, SocketWriter) = match &self.listener {
+                Listener::Tcp(listener) => {
+                    let (stream, peer_address) = listener.accept().await?;
+                    log::info!("accepted connection from {peer_address}");
+                    stream.set_nodelay(true)?;
+                    (Box::new(stream.clone()), Box::new(stream))
+                }
+                #[cfg(unix)]
+                Listener::Unix(listener) => {
+                    let (stream, _) = listener.accept().await?;
+                    log::info!("accepted connection");
+                    (Box::new(stream.clone()), Box::new(stream))
+                }
+            };
+
+            let handshake =
+                server_handshake(&mut reader, &mut writer, &self.token, self.has_session).fuse();
+            let timeout = smol::Timer::after(HANDSHAKE_TIMEOUT).fuse();
+            let handshake = futures::select! {
+                result = pin!(handshake) => result,
+                _ = pin!(timeout) => Err(anyhow!("timed out")),
+            };
+            match handshake {
+                Ok(HANDSHAKE_ACCEPTED) => {
+                    self.has_session = true;
+                    return Ok((reader, writer));
+                }
+                Ok(HANDSHAKE_INVALID_TOKEN) => log::warn!("rejected client with invalid token"),
+                Ok(HANDSHAKE_NO_SESSION) => {
+                    log::warn!("rejected client reconnecting to a session that doesn't exist")
+                }
+                Ok(response) => log::error!("unexpected handshake response {response}"),
+                Err(error) => log::warn!("handshake with client failed: {error:#}"),
+            }
+        }
+    }
+}
+
+async fn client_handshake(
+    reader: &mut SocketReader,
+    writer: &mut SocketWriter,
+    token: &str,
+    reconnect: bool,
+) -> Result<u8> {
+    writer
+        .write_all(&(token.len() as u32).to_le_bytes())
+        .await?;
+    writer.write_all(token.as_bytes()).await?;
+    writer.write_all(&[reconnect as u8]).await?;
+    writer.flush().await?;
+
+    let mut response = [0];
+    reader.read_exact(&mut response).await?;
+    Ok(response[0])
+}
+
+async fn server_handshake(
+    reader: &mut SocketReader,
+    writer: &mut SocketWriter,
+    token: &str,
+    has_session: bool,
+) -> Result<u8> {
+    let mut token_len = [0; 4];
+    reader.read_exact(&mut token_len).await?;
+    let token_len = u32::from_le_bytes(token_len);
+    anyhow::ensure!(token_len <= MAX_TOKEN_LEN, "token is too long");
+    let mut client_token = vec![0; token_len as usize];
+    reader.read_exact(&mut client_token).await?;
+    let mut reconnect = [0];
+    reader.read_exact(&mut reconnect).await?;
+
+    let response = if !tokens_match(token.as_bytes(), &client_token) {
+        HANDSHAKE_INVALID_TOKEN
+    } else if reconnect[0] != 0 && !has_session {
+        HANDSHAKE_NO_SESSION
+    } else {
+        HANDSHAKE_ACCEPTED
+    };
+    writer.write_all(&[response]).await?;
+    writer.flush().await?;
+    Ok(response)
+}
+
+/// Compares the tokens in constant time, so that the server's token can't be
+/// guessed from how long it takes to reject a client.
+fn tokens_match(expected: &[u8], actual: &[u8]) -> bool {
+    expected.len() == actual.len()
+        && expected
+            .iter()
+            .zip(actual)
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+pub(crate) struct SocketRemoteConnection {
+    connection_options: SshConnectionOptions,
+    socket: SocketConnectionOptions,
+    killed: AtomicBool,
+}
+
+impl SocketRemoteConnection {
+    pub(crate) fn new(
+        connection_options: SshConnectionOptions,
+        socket: SocketConnectionOptions,
+    ) -> Self {
+        Self {
+            connection_options,
+            socket,
+            killed: AtomicBool::new(false),
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl RemoteConnection for SocketRemoteConnection {
+    fn start_proxy(
+        &self,
+        _unique_identifier: String,
+        reconnect: bool,
+        incoming_tx: UnboundedSender<Envelope>,
+        mut outgoing_rx: UnboundedReceiver<Envelope>,
+        mut connection_activity_tx: Sender<()>,
+        delegate: Arc<dyn SshClientDelegate>,
+        cx: &mut AsyncApp,
+    ) -> Task<Result<i32>> {
+        delegate.set_status(Some("Connecting to server"), cx);
+
+        let socket = self.socket.clone();
+        cx.background_spawn(async move {
+            let (mut reader, mut writer) = socket
+                .address
+                .connect()
+                .await
+                .with_context(|| format!("failed to connect to {}", socket.address))?;
+            match client_handshake(&mut reader, &mut writer, &socket.token, reconnect).await? {
+                HANDSHAKE_ACCEPTED => {}
+                HANDSHAKE_NO_SESSION => {
+                    return Ok(ProxyLaunchError::ServerNotRunning.to_exit_code());
+                }
+                HANDSHAKE_INVALID_TOKEN => {
+                    anyhow::bail!("the server at {} rejected the token", socket.address)
+                }
+                response => anyhow::bail!("unexpected handshake response {response}"),
+            }
+
+            let read_task = async {
+                let mut buffer = Vec::new();
+                loop {
+                    let envelope = read_message(&mut reader, &mut buffer)
+                        .await
+                        .context("failed to read from server")?;
+                    connection_activity_tx.try_send(()).ok();
+                    if incoming_tx.unbounded_send(envelope).is_err() {
+                        return anyhow::Ok(());
+                    }
+                }
+            };
+            let write_task = async {
+                let mut buffer = Vec::new();
+                while let Some(outgoing) = outgoing_rx.next().await {
+                    write_message(&mut writer, &mut buffer, outgoing)
+                        .await
+                        .context("failed to write to server")?;
+                    writer.flush().await?;
+                }
+                anyhow::Ok(())
+            };
+
+            let (result, _) = futures::future::select(pin!(read_task), pin!(write_task))
+                .await
+                .factor_first();
+            result.map(|_| 0)
+        })
+    }
+
+    fn upload_directory(
+        &self,
+        _src_path: PathBuf,
+        _dest_path: PathBuf,
+        _cx: &App,
+    ) -> Task<Result<()>> {
+        Task::ready(Err(anyhow!(
+            "uploading directories to {} is not supported",
+            self.socket.address
+        )))
+    }
+
+    fn add_port_forward(&self, _: SshPortForwardOption, _: &App) -> Task<Result<()>> {
+        Task::ready(Err(anyhow!(
+            "forwarding ports over {} is not supported",
+            self.socket.address
+        )))
+    }
+
+    fn remove_port_forward(&self, _: SshPortForwardOption, _: &App) -> Task<Result<()>> {
+        Task::ready(Err(anyhow!(
+            "forwarding ports over {} is not supported",
+            self.socket.address
+        )))
+    }
+
+    async fn kill(&self) -> Result<()> {
+        self.killed.store(true, SeqCst);
+        Ok(())
+    }
+
+    fn has_been_killed(&self) -> bool {
+        self.killed.load(SeqCst)
+    }
+
+    fn ssh_args(&self) -> Option<Vec<String>> {
+        None
+    }
+
+    fn connection_options(&self) -> SshConnectionOptions {
+        self.connection_options.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_server_address() {
+        assert_eq!(
+            "tcp:localhost:7000".parse::<ServerAddress>().unwrap(),
+            ServerAddress::Tcp("localhost:7000".into())
+        );
+        assert_eq!(
+            "tcp:[::1]:7000".parse::<ServerAddress>().unwrap(),
+            ServerAddress::Tcp("[::1]:7000".into())
+        );
+        assert_eq!(
+            "unix:/tmp/zed.sock".parse::<ServerAddress>().unwrap(),
+            ServerAddress::Unix("/tmp/zed.sock".into())
+        );
+        assert!("tcp:localhost".parse::<ServerAddress>().is_err());
+        assert!("tcp::7000".parse::<ServerAddress>().is_err());
+        assert!("localhost:7000".parse::<ServerAddress>().is_err());
+        assert!("unix:".parse::<ServerAddress>().is_err());
+
+        let address = ServerAddress::Tcp("localhost:7000".into());
+        assert_eq!(
+            address.to_string().parse::<ServerAddress>().unwrap(),
+            address
+        );
+    }
+
+    #[test]
+    fn test_handshake() {
+        smol::block_on(async {
+            let address = ServerAddress::Tcp("127.0.0.1:0".into());
+            let mut listener = SocketListener::bind(&address, "the-token".into())
+                .await
+                .unwrap();
+            let address = listener.local_address().unwrap();
+
+            let client = async {
+                let handshake = async |token: &str, reconnect: bool| {
+                    let (mut reader, mut writer) = address.connect().await.unwrap();
+                    client_handshake(&mut reader, &mut writer, token, reconnect)
+                        .await
+                        .unwrap()
+                };
+                assert_eq!(
+                    handshake("wrong-token", false).await,
+                    HANDSHAKE_INVALID_TOKEN
+                );
+                assert_eq!(handshake("the-token", true).await, HANDSHAKE_NO_SESSION);
+                assert_eq!(handshake("the-token", false).await, HANDSHAKE_ACCEPTED);
+            };
+            let (accepted, _) = futures::join!(listener.accept(), client);
+            accepted.unwrap();
+            assert!(listener.has_session);
+        });
+    }
+
+    #[test]
+    fn test_tokens_match() {
+        assert!(tokens_match(b"secret", b"secret"));
+        assert!(!tokens_match(b"secret", b"secrex"));
+        assert!(!tokens_match(b"secret", b"secret2"));
+        assert!(!tokens_match(b"secret", b""));
+    }
+}
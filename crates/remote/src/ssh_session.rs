@@ -4,6 +4,7 @@ use crate::{
         MESSAGE_LEN_SIZE, MessageId, message_len_from_buffer, read_message_with_len, write_message,
     },
     proxy::ProxyLaunchError,
+    socket_connection::{SocketConnectionOptions, SocketRemoteConnection},
 };
 use anyhow::{Context as _, Result, anyhow};
 use async_trait::async_trait;
@@ -72,6 +73,54 @@ pub struct SshPortForwardOption {
     pub remote_port: u16,
 }
 
+impl SshPortForwardOption {
+    /// Forwards a local port to the same port on the remote host.
+    pub fn for_port(port: u16) -> Self {
+        Self {
+            local_host: None,
+            local_port: port,
+            remote_host: None,
+            remote_port: port,
+        }
+    }
+
+    fn ssh_arg(&self) -> String {
+        let local_host = self.local_host.as_deref().unwrap_or("localhost");
+        let remote_host = self.remote_host.as_deref().unwrap_or("localhost");
+        format!(
+            "-L{}:{}:{}:{}",
+            local_host, self.local_port, remote_host, self.remote_port
+        )
+    }
+}
+
+impl fmt::Display for SshPortForwardOption {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}:{} → {}:{}",
+            self.local_host.as_deref().unwrap_or("localhost"),
+            self.local_port,
+            self.remote_host.as_deref().unwrap_or("localhost"),
+            self.remote_port
+        )
+    }
+}
+
+/// Parses either a single port, which is forwarded to the same port on the
+/// remote host, or a forward in the format of ssh's `-L` option.
+impl std::str::FromStr for SshPortForwardOption {
+    type Err = anyhow::Error;
+
+    fn from_str(spec: &str) -> Result<Self> {
+        let spec = spec.trim();
+        if let Ok(port) = spec.parse() {
+            return Ok(Self::for_port(port));
+        }
+        parse_port_forward_spec(spec)
+    }
+}
+
 #[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
 pub struct SshConnectionOptions {
     pub host: String,
@@ -83,6 +132,8 @@ pub struct SshConnectionOptions {
 
     pub nickname: Option<String>,
     pub upload_binary_over_ssh: bool,
+    /// Connect to an already running `remote_server` instead of over SSH.
+    pub socket: Option<SocketConnectionOptions>,
 }
 
 #[macro_export]
@@ -232,6 +283,7 @@ impl SshConnectionOptions {
             password: None,
             nickname: None,
             upload_binary_over_ssh: false,
+            socket: None,
         })
     }
 
@@ -255,21 +307,7 @@ impl SshConnectionOptions {
         let mut args = self.args.iter().flatten().cloned().collect::<Vec<String>>();
 
         if let Some(forwards) = &self.port_forwards {
-            args.extend(forwards.iter().map(|pf| {
-                let local_host = match &pf.local_host {
-                    Some(host) => host,
-                    None => "localhost",
-                };
-                let remote_host = match &pf.remote_host {
-                    Some(host) => host,
-                    None => "localhost",
-                };
-
-                format!(
-                    "-L{}:{}:{}:{}",
-                    local_host, pf.local_port, remote_host, pf.remote_port
-                )
-            }));
+            args.extend(forwards.iter().map(SshPortForwardOption::ssh_arg));
         }
 
         args
@@ -483,6 +521,18 @@ impl State {
         matches!(self, Self::Reconnecting { .. })
     }
 
+    fn is_connected(&self) -> bool {
+        matches!(self, Self::Connected { .. })
+    }
+
+    /// Whether the connection was lost, but may still be recovered.
+    fn is_interrupted(&self) -> bool {
+        matches!(
+            self,
+            Self::HeartbeatMissed { .. } | Self::Reconnecting | Self::ReconnectFailed { .. }
+        )
+    }
+
     fn heartbeat_recovered(self) -> Self {
         match self {
             Self::HeartbeatMissed {
@@ -561,11 +611,17 @@ pub struct SshRemoteClient {
     unique_identifier: String,
     connection_options: SshConnectionOptions,
     state: Arc<Mutex<Option<State>>>,
+    /// Ports forwarded while connected, in addition to those in the connection options.
+    port_forwards: Vec<SshPortForwardOption>,
 }
 
 #[derive(Debug)]
 pub enum SshRemoteEvent {
     Disconnected,
+    /// The connection was interrupted and has been restored, and messages
+    /// that were buffered in the meantime have been delivered.
+    Reconnected,
+    PortForwardsChanged,
 }
 
 impl EventEmitter<SshRemoteEvent> for SshRemoteClient {}
@@ -625,6 +681,7 @@ impl SshRemoteClient {
                     unique_identifier: unique_identifier.clone(),
                     connection_options: connection_options.clone(),
                     state: Arc::new(Mutex::new(Some(State::Connecting))),
+                    port_forwards: Vec::new(),
                 })?;
 
                 let ssh_connection = cx
@@ -841,6 +898,16 @@ impl SshRemoteClient {
                 failed!(error, attempts, ssh_connection, delegate);
             };
 
+            // Forwards added while connected belonged to the previous connection.
+            let port_forwards = this
+                .read_with(cx, |this, _| this.port_forwards.clone())
+                .unwrap_or_default();
+            for forward in port_forwards {
+                if let Ok(task) = cx.update(|cx| ssh_connection.add_port_forward(forward, cx)) {
+                    task.await.log_err();
+                }
+            }
+
             State::Connected {
                 ssh_connection,
                 delegate,
@@ -973,13 +1040,18 @@ impl SshRemoteClient {
         cx: &mut Context<Self>,
     ) -> ControlFlow<()> {
         let state = self.state.lock().take().unwrap();
+        let was_interrupted = state.is_interrupted();
         let next_state = if missed_heartbeats > 0 {
             state.heartbeat_missed()
         } else {
             state.heartbeat_recovered()
         };
+        let recovered = was_interrupted && next_state.is_connected();
 
         self.set_state(next_state, cx);
+        if recovered {
+            cx.emit(SshRemoteEvent::Reconnected);
+        }
 
         if missed_heartbeats >= MAX_MISSED_HEARTBEATS {
             log::error!(
@@ -1043,7 +1115,11 @@ impl SshRemoteClient {
         let new_state = lock.as_ref().and_then(map);
 
         if let Some(new_state) = new_state {
-            lock.replace(new_state);
+            let is_connected = new_state.is_connected();
+            let previous_state = lock.replace(new_state);
+            if is_connected && previous_state.is_some_and(|state| state.is_interrupted()) {
+                cx.emit(SshRemoteEvent::Reconnected);
+            }
             cx.notify();
         }
     }
@@ -1070,7 +1146,7 @@ impl SshRemoteClient {
             .lock()
             .as_ref()
             .and_then(|state| state.ssh_connection())
-            .map(|ssh_connection| ssh_connection.ssh_args())
+            .and_then(|ssh_connection| ssh_connection.ssh_args())
     }
 
     pub fn upload_directory(
@@ -1086,6 +1162,77 @@ impl SshRemoteClient {
         connection.upload_directory(src_path, dest_path, cx)
     }
 
+    /// All forwarded ports, including those configured in the connection
+    /// options, which can't be removed.
+    pub fn port_forwards(&self) -> impl Iterator<Item = (&SshPortForwardOption, bool)> {
+        self.connection_options
+            .port_forwards
+            .iter()
+            .flatten()
+            .map(|forward| (forward, false))
+            .chain(self.port_forwards.iter().map(|forward| (forward, true)))
+    }
+
+    pub fn is_port_forwarded(&self, remote_port: u16) -> bool {
+        self.port_forwards()
+            .any(|(forward, _)| forward.remote_port == remote_port)
+    }
+
+    pub fn add_port_forward(
+        &mut self,
+        forward: SshPortForwardOption,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        if self
+            .port_forwards()
+            .any(|(existing, _)| *existing == forward)
+        {
+            return Task::ready(Ok(()));
+        }
+        let task = {
+            let state = self.state.lock();
+            let Some(connection) = state.as_ref().and_then(|state| state.ssh_connection()) else {
+                return Task::ready(Err(anyhow!("no ssh connection")));
+            };
+            connection.add_port_forward(forward.clone(), cx)
+        };
+        cx.spawn(async move |this, cx| {
+            task.await?;
+            this.update(cx, |this, cx| {
+                this.port_forwards.push(forward);
+                cx.emit(SshRemoteEvent::PortForwardsChanged);
+                cx.notify();
+            })
+        })
+    }
+
+    pub fn remove_port_forward(
+        &mut self,
+        forward: SshPortForwardOption,
+        cx: &mut Context<Self>,
+    ) -> Task<Result<()>> {
+        let Some(ix) = self
+            .port_forwards
+            .iter()
+            .position(|existing| *existing == forward)
+        else {
+            return Task::ready(Err(anyhow!("{forward} was not forwarded while connected")));
+        };
+        let task = {
+            let state = self.state.lock();
+            state
+                .as_ref()
+                .and_then(|state| state.ssh_connection())
+                .map(|connection| connection.remove_port_forward(forward, cx))
+        };
+        // Without a connection there's nothing to cancel, so just stop
+        // restoring the forward when reconnecting.
+        self.port_forwards.remove(ix);
+        cx.emit(SshRemoteEvent::PortForwardsChanged);
+        cx.notify();
+        task.unwrap_or_else(|| Task::ready(Ok(())))
+    }
+
     pub fn proto_client(&self) -> AnyProtoClient {
         self.client.clone().into()
     }
@@ -1112,6 +1259,38 @@ impl SshRemoteClient {
 
     #[cfg(any(test, feature = "test-support"))]
     pub fn simulate_disconnect(&self, client_cx: &mut App) -> Task<()> {
+        self.with_test_connection(client_cx, |connection, cx| {
+            connection.simulate_disconnect(cx)
+        })
+    }
+
+    /// Disconnects, and keeps reconnect attempts waiting until this is called
+    /// again with `offline` set to false.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn simulate_offline(&self, offline: bool, client_cx: &mut App) -> Task<()> {
+        self.with_test_connection(client_cx, move |connection, cx| {
+            connection.simulate_offline(offline, cx)
+        })
+    }
+
+    /// The forwards added to the connection, including the ones added again
+    /// after reconnecting.
+    #[cfg(any(test, feature = "test-support"))]
+    pub fn added_port_forwards(&self) -> Vec<SshPortForwardOption> {
+        self.state
+            .lock()
+            .as_ref()
+            .and_then(|state| state.ssh_connection())
+            .map(|ssh_connection| ssh_connection.added_port_forwards())
+            .unwrap_or_default()
+    }
+
+    #[cfg(any(test, feature = "test-support"))]
+    fn with_test_connection(
+        &self,
+        client_cx: &mut App,
+        f: impl FnOnce(&dyn RemoteConnection, &AsyncApp) + 'static,
+    ) -> Task<()> {
         let opts = self.connection_options();
         client_cx.spawn(async move |cx| {
             let connection = cx
@@ -1126,7 +1305,7 @@ impl SshRemoteClient {
                 .await
                 .unwrap();
 
-            connection.simulate_disconnect(&cx);
+            f(connection.as_ref(), &cx);
         })
     }
 
@@ -1150,6 +1329,8 @@ impl SshRemoteClient {
             connection_options: opts.clone(),
             server_cx: fake::SendableCx::new(server_cx),
             server_channel: server_client.clone(),
+            offline: Default::default(),
+            added_port_forwards: Default::default(),
         });
 
         client_cx.update(|cx| {
@@ -1237,9 +1418,14 @@ impl ConnectionPool {
                 let opts = opts.clone();
                 let delegate = delegate.clone();
                 async move |cx| {
-                    let connection = SshRemoteConnection::new(opts.clone(), delegate, cx)
-                        .await
-                        .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>);
+                    let connection = if let Some(socket) = opts.socket.clone() {
+                        Ok(Arc::new(SocketRemoteConnection::new(opts.clone(), socket))
+                            as Arc<dyn RemoteConnection>)
+                    } else {
+                        SshRemoteConnection::new(opts.clone(), delegate, cx)
+                            .await
+                            .map(|connection| Arc::new(connection) as Arc<dyn RemoteConnection>)
+                    };
 
                     cx.update_global(|pool: &mut Self, _| {
                         debug_assert!(matches!(
@@ -1277,7 +1463,7 @@ impl From<SshRemoteClient> for AnyProtoClient {
 }
 
 #[async_trait(?Send)]
-trait RemoteConnection: Send + Sync {
+pub(crate) trait RemoteConnection: Send + Sync {
     fn start_proxy(
         &self,
         unique_identifier: String,
@@ -1290,13 +1476,21 @@ trait RemoteConnection: Send + Sync {
     ) -> Task<Result<i32>>;
     fn upload_directory(&self, src_path: PathBuf, dest_path: PathBuf, cx: &App)
     -> Task<Result<()>>;
+    fn add_port_forward(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>>;
+    fn remove_port_forward(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>>;
     async fn kill(&self) -> Result<()>;
     fn has_been_killed(&self) -> bool;
-    fn ssh_args(&self) -> Vec<String>;
+    fn ssh_args(&self) -> Option<Vec<String>>;
     fn connection_options(&self) -> SshConnectionOptions;
 
     #[cfg(any(test, feature = "test-support"))]
     fn simulate_disconnect(&self, _: &AsyncApp) {}
+    #[cfg(any(test, feature = "test-support"))]
+    fn simulate_offline(&self, _offline: bool, _: &AsyncApp) {}
+    #[cfg(any(test, feature = "test-support"))]
+    fn added_port_forwards(&self) -> Vec<SshPortForwardOption> {
+        Vec::new()
+    }
 }
 
 struct SshRemoteConnection {
@@ -1321,8 +1515,8 @@ impl RemoteConnection for SshRemoteConnection {
         self.master_process.lock().is_none()
     }
 
-    fn ssh_args(&self) -> Vec<String> {
-        self.socket.ssh_args()
+    fn ssh_args(&self) -> Option<Vec<String>> {
+        Some(self.socket.ssh_args())
     }
 
     fn connection_options(&self) -> SshConnectionOptions {
@@ -1371,6 +1565,14 @@ impl RemoteConnection for SshRemoteConnection {
         })
     }
 
+    fn add_port_forward(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>> {
+        self.control_port_forward("forward", forward, cx)
+    }
+
+    fn remove_port_forward(&self, forward: SshPortForwardOption, cx: &App) -> Task<Result<()>> {
+        self.control_port_forward("cancel", forward, cx)
+    }
+
     fn start_proxy(
         &self,
         unique_identifier: String,
@@ -1435,6 +1637,36 @@ impl RemoteConnection for SshRemoteConnection {
 }
 
 impl SshRemoteConnection {
+    /// Asks the control master to start or stop forwarding a port, which takes
+    /// effect without restarting the connection.
+    fn control_port_forward(
+        &self,
+        control_command: &'static str,
+        forward: SshPortForwardOption,
+        cx: &App,
+    ) -> Task<Result<()>> {
+        let mut command = util::command::new_smol_command("ssh");
+        let output = self
+            .socket
+            .ssh_options(&mut command)
+            .args(["-O", control_command])
+            .arg(forward.ssh_arg())
+            .arg(self.socket.connection_options.ssh_url())
+            .output();
+
+        cx.background_spawn(async move {
+            let output = output.await?;
+
+            anyhow::ensure!(
+                output.status.success(),
+                "failed to {control_command} port {forward}: {}",
+                String::from_utf8_lossy(&output.stderr)
+            );
+
+            Ok(())
+        })
+    }
+
     #[cfg(not(unix))]
     async fn new(
         _connection_options: SshConnectionOptions,
@@ -2445,20 +2677,26 @@ mod fake {
             mpsc::{self, Sender},
             oneshot,
         },
+        future::Shared,
         select_biased,
     };
     use gpui::{App, AppContext as _, AsyncApp, SemanticVersion, Task, TestAppContext};
+    use parking_lot::Mutex;
     use release_channel::ReleaseChannel;
     use rpc::proto::Envelope;
 
     use super::{
         ChannelClient, RemoteConnection, SshClientDelegate, SshConnectionOptions, SshPlatform,
+        SshPortForwardOption,
     };
 
     pub(super) struct FakeRemoteConnection {
         pub(super) connection_options: SshConnectionOptions,
         pub(super) server_channel: Arc<ChannelClient>,
         pub(super) server_cx: SendableCx,
+        /// While offline, holds the signal that lets new connections through.
+        pub(super) offline: Mutex<Option<(oneshot::Sender<()>, Shared<oneshot::Receiver<()>>)>>,
+        pub(super) added_port_forwards: Mutex<Vec<SshPortForwardOption>>,
     }
 
     pub(super) struct SendableCx(AsyncApp);
@@ -2488,8 +2726,8 @@ mod fake {
             false
         }
 
-        fn ssh_args(&self) -> Vec<String> {
-            Vec::new()
+        fn ssh_args(&self) -> Option<Vec<String>> {
+            Some(Vec::new())
         }
         fn upload_directory(
             &self,
@@ -2500,6 +2738,15 @@ mod fake {
             unreachable!()
         }
 
+        fn add_port_forward(&self, forward: SshPortForwardOption, _: &App) -> Task<Result<()>> {
+            self.added_port_forwards.lock().push(forward);
+            Task::ready(Ok(()))
+        }
+
+        fn remove_port_forward(&self, _: SshPortForwardOption, _: &App) -> Task<Result<()>> {
+            Task::ready(Ok(()))
+        }
+
         fn connection_options(&self) -> SshConnectionOptions {
             self.connection_options.clone()
         }
@@ -2511,6 +2758,20 @@ mod fake {
                 .reconnect(incoming_rx, outgoing_tx, &self.server_cx.get(&cx));
         }
 
+        fn simulate_offline(&self, offline: bool, cx: &AsyncApp) {
+            if offline {
+                let (online_tx, online_rx) = oneshot::channel();
+                *self.offline.lock() = Some((online_tx, online_rx.shared()));
+                self.simulate_disconnect(cx);
+            } else if let Some((online_tx, _)) = self.offline.lock().take() {
+                online_tx.send(()).ok();
+            }
+        }
+
+        fn added_port_forwards(&self) -> Vec<SshPortForwardOption> {
+            self.added_port_forwards.lock().clone()
+        }
+
         fn start_proxy(
             &self,
 
@@ -2525,30 +2786,39 @@ mod fake {
             let (mut server_incoming_tx, server_incoming_rx) = mpsc::unbounded::<Envelope>();
             let (server_outgoing_tx, mut server_outgoing_rx) = mpsc::unbounded::<Envelope>();
 
-            self.server_channel.reconnect(
-                server_incoming_rx,
-                server_outgoing_tx,
-                &self.server_cx.get(cx),
-            );
-
-            cx.background_spawn(async move {
-                loop {
-                    select_biased! {
-                        server_to_client = server_outgoing_rx.next().fuse() => {
-                            let Some(server_to_client) = server_to_client else {
-                                return Ok(1)
-                            };
-                            connection_activity_tx.try_send(()).ok();
-                            client_incoming_tx.send(server_to_client).await.ok();
-                        }
-                        client_to_server = client_outgoing_rx.next().fuse() => {
-                            let Some(client_to_server) = client_to_server else {
-                                return Ok(1)
-                            };
-                            server_incoming_tx.send(client_to_server).await.ok();
+            let online = self
+                .offline
+                .lock()
+                .as_ref()
+                .map(|(_, online)| online.clone());
+            let server_channel = self.server_channel.clone();
+            let server_cx = self.server_cx.get(cx);
+            cx.spawn(async move |cx| {
+                if let Some(online) = online {
+                    online.await.ok();
+                }
+                server_channel.reconnect(server_incoming_rx, server_outgoing_tx, &server_cx);
+
+                cx.background_spawn(async move {
+                    loop {
+                        select_biased! {
+                            server_to_client = server_outgoing_rx.next().fuse() => {
+                                let Some(server_to_client) = server_to_client else {
+                                    return Ok(1)
+                                };
+                                connection_activity_tx.try_send(()).ok();
+                                client_incoming_tx.send(server_to_client).await.ok();
+                            }
+                            client_to_server = client_outgoing_rx.next().fuse() => {
+                                let Some(client_to_server) = client_to_server else {
+                                    return Ok(1)
+                                };
+                                server_incoming_tx.send(client_to_server).await.ok();
+                            }
                         }
                     }
-                }
+                })
+                .await
             })
         }
     }
@@ -4,7 +4,7 @@ use anyhow::{Context as _, Result, anyhow};
 use extension::ExtensionHostProxy;
 use extension_host::headless_host::HeadlessExtensionStore;
 use fs::Fs;
-use gpui::{App, AppContext as _, AsyncApp, Context, Entity, PromptLevel};
+use gpui::{App, AppContext as _, AsyncApp, Context, Entity, PromptLevel, Task};
 use http_client::HttpClient;
 use language::{Buffer, BufferEvent, LanguageRegistry, proto::serialize_operation};
 use node_runtime::NodeRuntime;
@@ -28,12 +28,24 @@ use rpc::{
 use settings::initial_server_settings_content;
 use smol::stream::StreamExt;
 use std::{
+    collections::BTreeSet,
     path::{Path, PathBuf},
-    sync::{Arc, atomic::AtomicUsize},
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
 };
 use util::ResultExt;
 use worktree::Worktree;
 
+use crate::listening_ports::listening_ports;
+
+const LISTENING_PORTS_POLL_INTERVAL: Duration = Duration::from_secs(2);
+/// How many times ports are polled after the client last asked to watch them,
+/// so that watching stops once the client goes away.
+const LISTENING_PORTS_WATCH_POLLS: usize = 15;
+
 pub struct HeadlessProject {
     pub fs: Arc<dyn Fs>,
     pub session: AnyProtoClient,
@@ -47,6 +59,8 @@ pub struct HeadlessProject {
     pub languages: Arc<LanguageRegistry>,
     pub extensions: Entity<HeadlessExtensionStore>,
     pub git_store: Entity<GitStore>,
+    listening_ports_watcher: Option<Task<()>>,
+    listening_ports_polls_left: Arc<AtomicUsize>,
 }
 
 pub struct HeadlessAppState {
@@ -223,6 +237,7 @@ impl HeadlessProject {
         client.add_request_handler(cx.weak_entity(), Self::handle_get_path_metadata);
         client.add_request_handler(cx.weak_entity(), Self::handle_shutdown_remote_server);
         client.add_request_handler(cx.weak_entity(), Self::handle_ping);
+        client.add_request_handler(cx.weak_entity(), Self::handle_watch_listening_ports);
 
         client.add_entity_request_handler(Self::handle_add_worktree);
         client.add_request_handler(cx.weak_entity(), Self::handle_remove_worktree);
@@ -268,9 +283,52 @@ impl HeadlessProject {
             languages,
             extensions,
             git_store,
+            listening_ports_watcher: None,
+            listening_ports_polls_left: Default::default(),
         }
     }
 
+    /// Reports the ports that start listening after this is called, which are
+    /// likely to have been opened by the client's tasks and terminals. Stops
+    /// once the client hasn't renewed the watch for a while.
+    fn watch_listening_ports(&self, cx: &mut Context<Self>) -> Task<()> {
+        let session = self.session.clone();
+        let polls_left = self.listening_ports_polls_left.clone();
+        let executor = cx.background_executor().clone();
+        cx.background_spawn(async move {
+            let Some(initial_ports) = listening_ports().await.log_err() else {
+                polls_left.store(0, Ordering::SeqCst);
+                return;
+            };
+            let mut opened_ports = BTreeSet::new();
+            loop {
+                executor.timer(LISTENING_PORTS_POLL_INTERVAL).await;
+                let previous_polls_left =
+                    polls_left.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |polls| {
+                        polls.checked_sub(1)
+                    });
+                if !matches!(previous_polls_left, Ok(polls) if polls > 1) {
+                    log::debug!("Stopped watching listening ports");
+                    return;
+                }
+                let Some(ports) = listening_ports().await.log_err() else {
+                    continue;
+                };
+                let ports: BTreeSet<u16> = ports.difference(&initial_ports).copied().collect();
+                if ports == opened_ports {
+                    continue;
+                }
+                opened_ports = ports;
+                session
+                    .send(proto::ListeningPortsChanged {
+                        project_id: SSH_PROJECT_ID,
+                        ports: opened_ports.iter().map(|port| *port as u32).collect(),
+                    })
+                    .log_err();
+            }
+        })
+    }
+
     fn on_buffer_event(
         &mut self,
         buffer: Entity<Buffer>,
@@ -658,6 +716,22 @@ impl HeadlessProject {
         Ok(proto::Ack {})
     }
 
+    pub async fn handle_watch_listening_ports(
+        this: Entity<Self>,
+        _envelope: TypedEnvelope<proto::WatchListeningPorts>,
+        mut cx: AsyncApp,
+    ) -> Result<proto::Ack> {
+        this.update(&mut cx, |this, cx| {
+            let polls_left = this
+                .listening_ports_polls_left
+                .swap(LISTENING_PORTS_WATCH_POLLS, Ordering::SeqCst);
+            if polls_left == 0 {
+                this.listening_ports_watcher = Some(this.watch_listening_ports(cx));
+            }
+        })?;
+        Ok(proto::Ack {})
+    }
+
     pub async fn handle_ping(
         _this: Entity<Self>,
         _envelope: TypedEnvelope<proto::Ping>,
@@ -0,0 +1,108 @@
+//! Detects TCP ports that are listening on the remote host, so that the client
+//! can offer to forward the ones opened by its tasks and terminals.
+
+use anyhow::Result;
+use std::collections::BTreeSet;
+
+/// Returns the TCP ports that are currently listening on any address, opened
+/// by processes running as the same user as this server. Tasks and terminals
+/// run in their own SSH sessions, so they aren't started by this server.
+#[cfg(target_os = "linux")]
+pub async fn listening_ports() -> Result<BTreeSet<u16>> {
+    use anyhow::Context as _;
+
+    // SAFETY: getuid is always successful.
+    let uid = unsafe { libc::getuid() };
+    let mut ports = BTreeSet::new();
+    for path in ["/proc/net/tcp", "/proc/net/tcp6"] {
+        match smol::fs::read_to_string(path).await {
+            Ok(table) => ports.extend(
+                parse_proc_net_tcp(&table)
+                    .filter(|(_, owner)| *owner == uid)
+                    .map(|(port, _)| port),
+            ),
+            // The IPv6 table is missing when IPv6 is disabled.
+            Err(error) if error.kind() == std::io::ErrorKind::NotFound => {}
+            Err(error) => return Err(error).with_context(|| format!("reading {path}")),
+        }
+    }
+    Ok(ports)
+}
+
+#[cfg(target_os = "macos")]
+pub async fn listening_ports() -> Result<BTreeSet<u16>> {
+    // SAFETY: getuid is always successful.
+    let uid = unsafe { libc::getuid() }.to_string();
+    let output = util::command::new_smol_command("lsof")
+        .args(["-nP", "-a", "-u", &uid, "-iTCP", "-sTCP:LISTEN", "-Fn"])
+        .output()
+        .await?;
+    // lsof exits with 1 when nothing is listening.
+    anyhow::ensure!(
+        output.status.success() || output.stderr.is_empty(),
+        "failed to list listening ports: {}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    Ok(parse_lsof(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+pub async fn listening_ports() -> Result<BTreeSet<u16>> {
+    anyhow::bail!("detecting listening ports is not supported on this platform")
+}
+
+/// Parses the format of `/proc/net/tcp`, where each socket's local address is
+/// written as `ADDRESS:PORT` in hex, and `0A` is the listening state. Returns
+/// the port of every listening socket along with the user that owns it.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_net_tcp(table: &str) -> impl Iterator<Item = (u16, u32)> + '_ {
+    const TCP_LISTEN: &str = "0A";
+
+    table.lines().skip(1).filter_map(|line| {
+        let mut fields = line.split_whitespace();
+        let local_address = fields.nth(1)?;
+        let state = fields.nth(1)?;
+        if state != TCP_LISTEN {
+            return None;
+        }
+        let uid = fields.nth(3)?.parse().ok()?;
+        let (_, port) = local_address.rsplit_once(':')?;
+        Some((u16::from_str_radix(port, 16).ok()?, uid))
+    })
+}
+
+/// Parses the name fields of lsof's machine readable output, which look like
+/// `n*:8080`, `n127.0.0.1:3000` or `n[::1]:3000`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_lsof(output: &str) -> BTreeSet<u16> {
+    output
+        .lines()
+        .filter_map(|line| line.strip_prefix('n'))
+        .filter_map(|name| name.rsplit_once(':')?.1.parse().ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_proc_net_tcp() {
+        let table = "\
+  sl  local_address rem_address   st tx_queue rx_queue tr tm->when retrnsmt   uid  timeout inode
+   0: 0100007F:1F90 00000000:0000 0A 00000000:00000000 00:00000000 00000000  1000        0 1 1 0 100 0 0 10 0
+   1: 00000000:0BB8 00000000:0000 0A 00000000:00000000 00:00000000 00000000     0        0 2 1 0 100 0 0 10 0
+   2: 0100007F:1F90 0100007F:C350 01 00000000:00000000 00:00000000 00000000  1000        0 3 1 0 20 4 30 10 -1
+";
+        assert_eq!(
+            parse_proc_net_tcp(table).collect::<BTreeSet<_>>(),
+            BTreeSet::from([(3000, 0), (8080, 1000)])
+        );
+    }
+
+    #[test]
+    fn test_parse_lsof() {
+        let output = "p123\nf5\nn*:8080\nf6\nn127.0.0.1:3000\np456\nf7\nn[::1]:5173\n";
+        assert_eq!(parse_lsof(output), BTreeSet::from([3000, 5173, 8080]));
+    }
+}
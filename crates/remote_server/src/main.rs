@@ -31,6 +31,16 @@ enum Commands {
         #[arg(long)]
         stderr_socket: PathBuf,
     },
+    /// Listen for clients connecting directly over TCP or a Unix socket, instead of over SSH.
+    ///
+    /// Clients authenticate with the token in the ZED_REMOTE_SERVER_TOKEN environment variable.
+    Listen {
+        /// The address to listen on, either `tcp:<host>:<port>` or `unix:<path>`.
+        #[arg(long)]
+        address: String,
+        #[arg(long)]
+        log_file: PathBuf,
+    },
     Proxy {
         #[arg(long)]
         reconnect: bool,
@@ -49,7 +59,7 @@ fn main() {
 fn main() {
     use release_channel::{RELEASE_CHANNEL, ReleaseChannel};
     use remote::proxy::ProxyLaunchError;
-    use remote_server::unix::{execute_proxy, execute_run};
+    use remote_server::unix::{execute_listen, execute_proxy, execute_run};
 
     let cli = Cli::parse();
 
@@ -77,6 +87,7 @@ fn main() {
             stdout_socket,
             stderr_socket,
         ),
+        Some(Commands::Listen { address, log_file }) => execute_listen(address, log_file),
         Some(Commands::Proxy {
             identifier,
             reconnect,
@@ -105,7 +116,7 @@ fn main() {
             std::process::exit(0);
         }
         None => {
-            eprintln!("usage: remote <run|proxy|version>");
+            eprintln!("usage: remote <run|listen|proxy|version>");
             std::process::exit(1);
         }
     };
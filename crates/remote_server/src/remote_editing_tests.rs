@@ -22,14 +22,18 @@ use project::{
     Project, ProjectPath,
     search::{SearchQuery, SearchResult},
 };
-use remote::SshRemoteClient;
+use remote::{ConnectionState, SshRemoteClient, ssh_session::SshPortForwardOption};
+use rpc::proto::{self, SSH_PROJECT_ID};
 use serde_json::json;
 use settings::{Settings, SettingsLocation, SettingsStore, initial_server_settings_content};
 use smol::stream::StreamExt;
 use std::{
     collections::HashSet,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        Arc,
+        atomic::{self, AtomicUsize},
+    },
 };
 #[cfg(not(windows))]
 use unindent::Unindent as _;
@@ -550,6 +554,117 @@ async fn test_remote_lsp(cx: &mut TestAppContext, server_cx: &mut TestAppContext
     })
 }
 
+#[gpui::test]
+async fn test_remote_document_links(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                ".zed": {
+                    "settings.json": r#"{"languages": {"Rust": {"language_servers": ["rust-analyzer"]}}}"#
+                },
+                "src": {
+                    "lib.rs": "// see zed.dev"
+                }
+            },
+        }),
+    )
+    .await;
+
+    let (project, headless) = init_test(&fs, cx, server_cx).await;
+
+    cx.update_entity(&project, |project, _| {
+        project.languages().register_test_language(LanguageConfig {
+            name: "Rust".into(),
+            matcher: LanguageMatcher {
+                path_suffixes: vec!["rs".into()],
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        project.languages().register_fake_lsp_adapter(
+            "Rust",
+            FakeLspAdapter {
+                name: "rust-analyzer",
+                ..Default::default()
+            },
+        )
+    });
+
+    let mut fake_lsp = server_cx.update(|cx| {
+        headless.read(cx).languages.register_fake_language_server(
+            LanguageServerName("rust-analyzer".into()),
+            lsp::ServerCapabilities {
+                document_link_provider: Some(lsp::DocumentLinkOptions {
+                    resolve_provider: None,
+                    work_done_progress_options: Default::default(),
+                }),
+                ..Default::default()
+            },
+            None,
+        )
+    });
+
+    cx.run_until_parked();
+
+    let worktree_id = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap()
+        .0
+        .read_with(cx, |worktree, _| worktree.id());
+    cx.run_until_parked();
+
+    let (buffer, _handle) = project
+        .update(cx, |project, cx| {
+            project.open_buffer_with_lsp((worktree_id, Path::new("src/lib.rs")), cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    let fake_lsp = fake_lsp.next().await.unwrap();
+    let link_requests = Arc::new(AtomicUsize::new(0));
+    fake_lsp.set_request_handler::<lsp::request::DocumentLinkRequest, _, _>({
+        let link_requests = link_requests.clone();
+        move |_, _| {
+            link_requests.fetch_add(1, atomic::Ordering::SeqCst);
+            async move {
+                Ok(Some(vec![lsp::DocumentLink {
+                    range: lsp::Range::new(lsp::Position::new(0, 7), lsp::Position::new(0, 14)),
+                    target: Some(lsp::Url::parse("https://zed.dev").unwrap()),
+                    tooltip: None,
+                    data: None,
+                }]))
+            }
+        }
+    });
+
+    let document_links = |cx: &mut TestAppContext| {
+        project.update(cx, |project, cx| project.document_links(&buffer, cx))
+    };
+    let links = document_links(cx).await.unwrap();
+    assert_eq!(
+        links
+            .iter()
+            .map(|link| link.target.as_deref())
+            .collect::<Vec<_>>(),
+        [Some("https://zed.dev/")]
+    );
+    assert_eq!(link_requests.load(atomic::Ordering::SeqCst), 1);
+
+    // The links are only requested again once the buffer changes.
+    document_links(cx).await.unwrap();
+    assert_eq!(link_requests.load(atomic::Ordering::SeqCst), 1);
+    buffer.update(cx, |buffer, cx| buffer.edit([(0..0, "\n")], None, cx));
+    cx.run_until_parked();
+    document_links(cx).await.unwrap();
+    assert_eq!(link_requests.load(atomic::Ordering::SeqCst), 2);
+}
+
 #[gpui::test]
 async fn test_remote_cancel_language_server_work(
     cx: &mut TestAppContext,
@@ -1128,6 +1243,239 @@ async fn test_reconnect(cx: &mut TestAppContext, server_cx: &mut TestAppContext)
     );
 }
 
+#[gpui::test]
+async fn test_offline_editing(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                ".git": {},
+                "src": {
+                    "lib.rs": "fn one() -> usize { 1 }"
+                }
+            },
+        }),
+    )
+    .await;
+
+    let (project, headless) = init_test(&fs, cx, server_cx).await;
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, Path::new("src/lib.rs")), cx)
+        })
+        .await
+        .unwrap();
+    let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+    let server_buffer = server_cx.read(|cx| {
+        headless
+            .read(cx)
+            .buffer_store
+            .read(cx)
+            .get(buffer_id)
+            .unwrap()
+    });
+
+    let client = cx.read(|cx| project.read(cx).ssh_client().unwrap());
+    client
+        .update(cx, |client, cx| client.simulate_offline(true, cx))
+        .await;
+    server_cx.run_until_parked();
+    cx.run_until_parked();
+    client.read_with(cx, |client, _| {
+        assert_eq!(client.connection_state(), ConnectionState::Reconnecting);
+    });
+
+    buffer.update(cx, |buffer, cx| {
+        let ix = buffer.text().find('1').unwrap();
+        buffer.edit([(ix..ix + 1, "100")], None, cx);
+    });
+    cx.run_until_parked();
+    server_cx.run_until_parked();
+    server_buffer.read_with(server_cx, |buffer, _| {
+        assert_eq!(buffer.text(), "fn one() -> usize { 1 }");
+    });
+
+    client
+        .update(cx, |client, cx| client.simulate_offline(false, cx))
+        .await;
+    cx.run_until_parked();
+    server_cx.run_until_parked();
+    client.read_with(cx, |client, _| {
+        assert_eq!(client.connection_state(), ConnectionState::Connected);
+    });
+    server_buffer.read_with(server_cx, |buffer, _| {
+        assert_eq!(buffer.text(), "fn one() -> usize { 100 }");
+    });
+    buffer.read_with(cx, |buffer, _| assert!(!buffer.has_conflict()));
+
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.load(path!("/code/project1/src/lib.rs").as_ref())
+            .await
+            .unwrap(),
+        "fn one() -> usize { 100 }"
+    );
+}
+
+#[gpui::test]
+async fn test_offline_editing_conflict(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                ".git": {},
+                "src": {
+                    "lib.rs": "fn one() -> usize { 1 }"
+                }
+            },
+        }),
+    )
+    .await;
+
+    let (project, headless) = init_test(&fs, cx, server_cx).await;
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, Path::new("src/lib.rs")), cx)
+        })
+        .await
+        .unwrap();
+    let buffer_id = buffer.read_with(cx, |buffer, _| buffer.remote_id());
+    let server_buffer = server_cx.read(|cx| {
+        headless
+            .read(cx)
+            .buffer_store
+            .read(cx)
+            .get(buffer_id)
+            .unwrap()
+    });
+
+    let client = cx.read(|cx| project.read(cx).ssh_client().unwrap());
+    client
+        .update(cx, |client, cx| client.simulate_offline(true, cx))
+        .await;
+    server_cx.run_until_parked();
+    cx.run_until_parked();
+
+    // Both sides edit the buffer while the connection is down.
+    server_buffer.update(server_cx, |buffer, cx| {
+        buffer.edit([(0..0, "// remote\n")], None, cx);
+    });
+    server_cx.run_until_parked();
+    buffer.update(cx, |buffer, cx| {
+        let ix = buffer.text().find('1').unwrap();
+        buffer.edit([(ix..ix + 1, "100")], None, cx);
+    });
+    cx.run_until_parked();
+
+    client
+        .update(cx, |client, cx| client.simulate_offline(false, cx))
+        .await;
+    cx.run_until_parked();
+    server_cx.run_until_parked();
+
+    let text = buffer.read_with(cx, |buffer, _| {
+        assert!(buffer.has_conflict());
+        buffer.text()
+    });
+    assert_eq!(text, "// remote\nfn one() -> usize { 100 }");
+    server_buffer.read_with(server_cx, |buffer, _| assert_eq!(buffer.text(), text));
+
+    project
+        .update(cx, |project, cx| {
+            project.reload_buffers([buffer.clone()].into_iter().collect(), false, cx)
+        })
+        .await
+        .unwrap();
+    cx.run_until_parked();
+
+    buffer.read_with(cx, |buffer, _| {
+        assert!(!buffer.has_conflict());
+        assert_eq!(buffer.text(), "fn one() -> usize { 1 }");
+    });
+}
+
+#[gpui::test]
+async fn test_remote_port_forwarding(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(path!("/code"), json!({ "project1": {} }))
+        .await;
+
+    let (project, headless) = init_test(&fs, cx, server_cx).await;
+    let client = cx.read(|cx| project.read(cx).ssh_client().unwrap());
+
+    headless.read_with(server_cx, |headless, _| {
+        headless
+            .session
+            .send(proto::ListeningPortsChanged {
+                project_id: SSH_PROJECT_ID,
+                ports: vec![3000, 5173],
+            })
+            .unwrap();
+    });
+    server_cx.run_until_parked();
+    cx.run_until_parked();
+    project.read_with(cx, |project, _| {
+        assert_eq!(project.remote_listening_ports(), &[3000, 5173]);
+    });
+
+    let forward = SshPortForwardOption::for_port(3000);
+    client
+        .update(cx, |client, cx| {
+            client.add_port_forward(forward.clone(), cx)
+        })
+        .await
+        .unwrap();
+    client.read_with(cx, |client, _| {
+        assert!(client.is_port_forwarded(3000));
+        assert!(!client.is_port_forwarded(5173));
+        assert_eq!(client.added_port_forwards(), vec![forward.clone()]);
+    });
+
+    // Forwards added while connected are restored after reconnecting.
+    client
+        .update(cx, |client, cx| client.simulate_disconnect(cx))
+        .detach();
+    server_cx.run_until_parked();
+    cx.run_until_parked();
+    client.read_with(cx, |client, _| {
+        assert_eq!(client.connection_state(), ConnectionState::Connected);
+        assert!(client.is_port_forwarded(3000));
+        assert_eq!(
+            client.added_port_forwards(),
+            vec![forward.clone(), forward.clone()]
+        );
+    });
+
+    client
+        .update(cx, |client, cx| client.remove_port_forward(forward, cx))
+        .await
+        .unwrap();
+    client.read_with(cx, |client, _| {
+        assert_eq!(client.port_forwards().count(), 0);
+    });
+}
+
 #[gpui::test]
 async fn test_remote_root_rename(cx: &mut TestAppContext, server_cx: &mut TestAppContext) {
     let fs = FakeFs::new(server_cx.executor());
@@ -1759,6 +2107,121 @@ async fn test_remote_agent_fs_tool_calls(cx: &mut TestAppContext, server_cx: &mu
     does_not_exist_result.output.await.unwrap_err();
 }
 
+#[cfg(not(windows))]
+#[gpui::test]
+async fn test_remote_editing_over_tcp_socket(
+    cx: &mut TestAppContext,
+    server_cx: &mut TestAppContext,
+) {
+    let address = remote::ServerAddress::Tcp("127.0.0.1:0".into());
+    test_remote_editing_over_socket(address, cx, server_cx).await;
+}
+
+#[cfg(not(windows))]
+#[gpui::test]
+async fn test_remote_editing_over_unix_socket(
+    cx: &mut TestAppContext,
+    server_cx: &mut TestAppContext,
+) {
+    let dir = tempfile::tempdir().unwrap();
+    let address = remote::ServerAddress::Unix(dir.path().join("remote_server.sock"));
+    test_remote_editing_over_socket(address, cx, server_cx).await;
+}
+
+#[cfg(not(windows))]
+async fn test_remote_editing_over_socket(
+    address: remote::ServerAddress,
+    cx: &mut TestAppContext,
+    server_cx: &mut TestAppContext,
+) {
+    use crate::unix::{ServerListeners, start_server};
+    use remote::{
+        SocketConnectionOptions, SshConnectionOptions, socket_connection::SocketListener,
+    };
+
+    // Both ends talk over a real socket, so the executors must wait for IO.
+    cx.executor().allow_parking();
+    server_cx.executor().allow_parking();
+    cx.update(|cx| release_channel::init(SemanticVersion::default(), cx));
+    server_cx.update(|cx| release_channel::init(SemanticVersion::default(), cx));
+    init_logger();
+
+    let fs = FakeFs::new(server_cx.executor());
+    fs.insert_tree(
+        path!("/code"),
+        json!({
+            "project1": {
+                "README.md": "# project 1",
+            },
+        }),
+    )
+    .await;
+
+    let listener = SocketListener::bind(&address, "the-token".into())
+        .await
+        .unwrap();
+    let address = listener.local_address().unwrap();
+    let (_log_tx, log_rx) = smol::channel::unbounded();
+    let session =
+        server_cx.update(|cx| start_server(ServerListeners::Socket(listener), log_rx, cx));
+    server_cx.update(HeadlessProject::init);
+    let _headless = server_cx.new(|cx| {
+        client::init_settings(cx);
+        HeadlessProject::new(
+            crate::HeadlessAppState {
+                session,
+                fs: fs.clone(),
+                http_client: Arc::new(BlockedHttpClient),
+                node_runtime: NodeRuntime::unavailable(),
+                languages: Arc::new(LanguageRegistry::new(cx.background_executor().clone())),
+                extension_host_proxy: Arc::new(ExtensionHostProxy::new()),
+            },
+            cx,
+        )
+    });
+
+    let opts = SshConnectionOptions {
+        host: "container".into(),
+        socket: Some(SocketConnectionOptions {
+            address,
+            token: "the-token".into(),
+        }),
+        ..Default::default()
+    };
+    let ssh = SshRemoteClient::fake_client(opts, cx).await;
+    ssh.read_with(cx, |ssh, _| assert_eq!(ssh.ssh_args(), None));
+    let project = build_project(ssh, cx);
+
+    let (worktree, _) = project
+        .update(cx, |project, cx| {
+            project.find_or_create_worktree(path!("/code/project1"), true, cx)
+        })
+        .await
+        .unwrap();
+    let worktree_id = worktree.read_with(cx, |worktree, _| worktree.id());
+
+    let buffer = project
+        .update(cx, |project, cx| {
+            project.open_buffer((worktree_id, Path::new("README.md")), cx)
+        })
+        .await
+        .unwrap();
+    buffer.update(cx, |buffer, cx| {
+        assert_eq!(buffer.text(), "# project 1");
+        buffer.edit([(0..0, "Hello\n")], None, cx);
+    });
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer.clone(), cx))
+        .await
+        .unwrap();
+    assert_eq!(
+        fs.load(path!("/code/project1/README.md").as_ref())
+            .await
+            .unwrap(),
+        "Hello\n# project 1"
+    );
+}
+
 pub async fn init_test(
     server_fs: &Arc<FakeFs>,
     cx: &mut TestAppContext,
@@ -19,6 +19,7 @@ use project::project_settings::ProjectSettings;
 
 use release_channel::{AppVersion, RELEASE_CHANNEL, ReleaseChannel};
 use remote::proxy::ProxyLaunchError;
+use remote::socket_connection::{ServerAddress, SocketListener, SocketReader, SocketWriter};
 use remote::ssh_session::ChannelClient;
 use remote::{
     json_log::LogRecord,
@@ -46,6 +47,10 @@ use std::{
 use telemetry_events::LocationData;
 use util::ResultExt;
 
+/// The environment variable that `remote_server listen` reads the token clients must
+/// authenticate with from, so that it doesn't show up in the process list.
+pub const TOKEN_ENV_VAR: &str = "ZED_REMOTE_SERVER_TOKEN";
+
 fn init_logging_proxy() {
     env_logger::builder()
         .format(|buf, record| {
@@ -236,28 +241,55 @@ fn handle_panic_requests(project: &Entity<HeadlessProject>, client: &Arc<Channel
     );
 }
 
-struct ServerListeners {
-    stdin: UnixListener,
-    stdout: UnixListener,
-    stderr: UnixListener,
+pub(crate) enum ServerListeners {
+    /// Sockets that the proxy, started by the client over SSH, connects to.
+    Proxy {
+        stdin: UnixListener,
+        stdout: UnixListener,
+        stderr: UnixListener,
+    },
+    /// A socket that clients connect to directly, started with `remote_server listen`.
+    Socket(SocketListener),
 }
 
 impl ServerListeners {
     pub fn new(stdin_path: PathBuf, stdout_path: PathBuf, stderr_path: PathBuf) -> Result<Self> {
-        Ok(Self {
+        Ok(Self::Proxy {
             stdin: UnixListener::bind(stdin_path).context("failed to bind stdin socket")?,
             stdout: UnixListener::bind(stdout_path).context("failed to bind stdout socket")?,
             stderr: UnixListener::bind(stderr_path).context("failed to bind stderr socket")?,
         })
     }
+
+    /// Waits for the next client, returning the streams that messages are read from and
+    /// written to, and the stream that logs are forwarded to, if the client accepts logs.
+    async fn accept(&mut self) -> Result<(SocketReader, SocketWriter, Option<SocketWriter>)> {
+        match self {
+            Self::Proxy {
+                stdin,
+                stdout,
+                stderr,
+            } => {
+                let (stdin, stdout, stderr) =
+                    futures::future::join3(stdin.accept(), stdout.accept(), stderr.accept()).await;
+                let ((stdin, _), (stdout, _), (stderr, _)) = (stdin?, stdout?, stderr?);
+                Ok((Box::new(stdin), Box::new(stdout), Some(Box::new(stderr))))
+            }
+            Self::Socket(listener) => {
+                let (reader, writer) = listener.accept().await?;
+                Ok((reader, writer, None))
+            }
+        }
+    }
 }
 
-fn start_server(
-    listeners: ServerListeners,
+pub(crate) fn start_server(
+    mut listeners: ServerListeners,
     log_rx: Receiver<Vec<u8>>,
     cx: &mut App,
 ) -> Arc<ChannelClient> {
     // This is the server idle timeout. If no connection comes in this timeout, the server will shut down.
+    // Servers started with `remote_server listen` keep waiting for connections until they're stopped.
     const IDLE_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10 * 60);
 
     let (incoming_tx, incoming_rx) = mpsc::unbounded::<Envelope>();
@@ -274,22 +306,31 @@ fn start_server(
     .detach();
 
     cx.spawn(async move |cx| {
-        let mut stdin_incoming = listeners.stdin.incoming();
-        let mut stdout_incoming = listeners.stdout.incoming();
-        let mut stderr_incoming = listeners.stderr.incoming();
+        let is_proxy = matches!(listeners, ServerListeners::Proxy { .. });
 
         loop {
-            let streams = futures::future::join3(stdin_incoming.next(), stdout_incoming.next(), stderr_incoming.next());
+            let idle_timeout = async {
+                if is_proxy {
+                    smol::Timer::after(IDLE_TIMEOUT).await;
+                } else {
+                    futures::future::pending::<()>().await;
+                }
+            };
+            // Logs are only forwarded to proxies, which receive the ones logged before they
+            // connected. Discard them while waiting for socket clients instead.
+            let discard_logs = async {
+                if !is_proxy {
+                    while log_rx.recv().await.is_ok() {}
+                }
+                futures::future::pending::<()>().await;
+            };
 
             log::info!("accepting new connections");
             let result = select! {
-                streams = streams.fuse() => {
-                    let (Some(Ok(stdin_stream)), Some(Ok(stdout_stream)), Some(Ok(stderr_stream))) = streams else {
-                        break;
-                    };
-                    anyhow::Ok((stdin_stream, stdout_stream, stderr_stream))
+                streams = listeners.accept().fuse() => {
+                    streams
                 }
-                _ = futures::FutureExt::fuse(smol::Timer::after(IDLE_TIMEOUT)) => {
+                _ = idle_timeout.fuse() => {
                     log::warn!("timed out waiting for new connections after {:?}. exiting.", IDLE_TIMEOUT);
                     cx.update(|cx| {
                         // TODO: This is a hack, because in a headless project, shutdown isn't executed
@@ -299,13 +340,20 @@ fn start_server(
                     })?;
                     break;
                 }
+                _ = discard_logs.fuse() => {
+                    unreachable!()
+                }
                 _ = app_quit_rx.next().fuse() => {
                     break;
                 }
             };
 
-            let Ok((mut stdin_stream, mut stdout_stream, mut stderr_stream)) = result else {
-                break;
+            let (mut stdin_stream, mut stdout_stream, mut stderr_stream) = match result {
+                Ok(streams) => streams,
+                Err(error) => {
+                    log::error!("failed to accept connection: {error:?}. exiting.");
+                    break;
+                }
             };
 
             let mut input_buffer = Vec::new();
@@ -357,7 +405,7 @@ fn start_server(
                     }
 
                     log_message = log_rx.recv().fuse() => {
-                        if let Ok(log_message) = log_message {
+                        if let (Ok(log_message), Some(stderr_stream)) = (log_message, stderr_stream.as_mut()) {
                             if let Err(error) = stderr_stream.write_all(&log_message).await {
                                 log::error!("failed to write log message to stderr: {:?}", error);
                                 break;
@@ -424,6 +472,25 @@ pub fn execute_run(
 
     let listeners = ServerListeners::new(stdin_socket, stdout_socket, stderr_socket)?;
 
+    run_server(listeners, log_rx)
+}
+
+pub fn execute_listen(address: String, log_file: PathBuf) -> Result<()> {
+    init_paths()?;
+    init_panic_hook();
+    let log_rx = init_logging_server(log_file)?;
+
+    let address = ServerAddress::from_str(&address)?;
+    let token = env::var(TOKEN_ENV_VAR).with_context(|| {
+        format!("{TOKEN_ENV_VAR} must be set to the token clients connect with")
+    })?;
+    let listener = smol::block_on(SocketListener::bind(&address, token))?;
+    log::info!("starting up. listening on {}", listener.local_address()?);
+
+    run_server(ServerListeners::Socket(listener), log_rx)
+}
+
+fn run_server(listeners: ServerListeners, log_rx: Receiver<Vec<u8>>) -> Result<()> {
     let git_hosting_provider_registry = Arc::new(GitHostingProviderRegistry::new());
     gpui::Application::headless().run(move |cx| {
         settings::init(cx);
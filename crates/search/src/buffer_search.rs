@@ -3,7 +3,8 @@ mod registrar;
 use crate::{
     FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext, SearchOptions,
     SelectAllMatches, SelectNextMatch, SelectPreviousMatch, ToggleCaseSensitive, ToggleRegex,
-    ToggleReplace, ToggleSelection, ToggleWholeWord, search_bar::render_nav_button,
+    ToggleReplace, ToggleSelection, ToggleStructural, ToggleWholeWord, persistence,
+    search_bar::render_nav_button,
 };
 use any_vec::AnyVec;
 use anyhow::Context as _;
@@ -36,7 +37,7 @@ use ui::{
 };
 use util::ResultExt;
 use workspace::{
-    ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace,
+    ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView, Workspace, WorkspaceId,
     item::ItemHandle,
     searchable::{Direction, SearchEvent, SearchableItemHandle, WeakSearchableItemHandle},
 };
@@ -105,6 +106,7 @@ pub struct BufferSearchBar {
     dismissed: bool,
     search_history: SearchHistory,
     search_history_cursor: SearchHistoryCursor,
+    workspace_id: Option<WorkspaceId>,
     replace_enabled: bool,
     selection_search_enabled: bool,
     scroll_handle: ScrollHandle,
@@ -283,6 +285,15 @@ impl Render for BufferSearchBar {
                                             this.toggle_regex(&ToggleRegex, window, cx)
                                         }),
                                     )
+                                }))
+                                .children(supported_options.structural.then(|| {
+                                    self.render_search_option_button(
+                                        SearchOptions::STRUCTURAL,
+                                        focus_handle.clone(),
+                                        cx.listener(|this, _, window, cx| {
+                                            this.toggle_structural(&ToggleStructural, window, cx)
+                                        }),
+                                    )
                                 })),
                         )
                     }),
@@ -503,6 +514,9 @@ impl Render for BufferSearchBar {
             .when(self.supported_options(cx).regex, |this| {
                 this.on_action(cx.listener(Self::toggle_regex))
             })
+            .when(self.supported_options(cx).structural, |this| {
+                this.on_action(cx.listener(Self::toggle_structural))
+            })
             .when(self.supported_options(cx).selection, |this| {
                 this.on_action(cx.listener(Self::toggle_selection))
             })
@@ -603,6 +617,13 @@ impl BufferSearchBar {
                 this.toggle_regex(action, window, cx);
             }
         }));
+        registrar.register_handler(ForDeployed(
+            |this, action: &ToggleStructural, window, cx| {
+                if this.supported_options(cx).structural {
+                    this.toggle_structural(action, window, cx);
+                }
+            },
+        ));
         registrar.register_handler(ForDeployed(|this, action: &ToggleSelection, window, cx| {
             if this.supported_options(cx).selection {
                 this.toggle_selection(action, window, cx);
@@ -735,6 +756,7 @@ impl BufferSearchBar {
                 project::search_history::QueryInsertionBehavior::ReplacePreviousIfContains,
             ),
             search_history_cursor: Default::default(),
+            workspace_id: None,
             active_search: None,
             replace_enabled: false,
             selection_search_enabled: false,
@@ -745,6 +767,60 @@ impl BufferSearchBar {
         }
     }
 
+    /// Restores the query history saved for the given workspace, and keeps
+    /// adding the queries searched for to it. The history is loaded in the
+    /// background, as a search bar is created for every pane.
+    pub fn set_workspace_id(&mut self, workspace_id: Option<WorkspaceId>, cx: &mut Context<Self>) {
+        self.workspace_id = workspace_id;
+        let Some(workspace_id) = workspace_id else {
+            return;
+        };
+        let history = cx.background_spawn(async move {
+            persistence::DB.search_history(workspace_id, persistence::BUFFER_SEARCH_HISTORY)
+        });
+        cx.spawn(async move |this, cx| {
+            let history = history.await;
+            this.update(cx, |this, _| {
+                // Keep the queries that were searched for while loading.
+                let searched = this
+                    .search_history
+                    .entries()
+                    .map(ToOwned::to_owned)
+                    .collect::<Vec<_>>();
+                this.search_history.restore(history);
+                this.search_history_cursor.reset();
+                for query in searched {
+                    this.search_history
+                        .add(&mut this.search_history_cursor, query);
+                }
+                this.search_history_cursor.reset();
+            })
+            .ok();
+        })
+        .detach();
+    }
+
+    /// Adds the query to the history stored for the workspace. Every pane has
+    /// its own search bar, so the query is added to the stored history rather
+    /// than replacing it with the history of this pane.
+    fn save_search_query(&self, query: String, cx: &App) {
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        cx.background_spawn(async move {
+            persistence::DB
+                .add_to_search_history(
+                    workspace_id,
+                    persistence::BUFFER_SEARCH_HISTORY,
+                    query,
+                    MAX_BUFFER_SEARCH_HISTORY_SIZE,
+                )
+                .await
+                .log_err();
+        })
+        .detach();
+    }
+
     pub fn is_dismissed(&self) -> bool {
         self.dismissed
     }
@@ -965,7 +1041,7 @@ impl BufferSearchBar {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
-        self.search_options.toggle(search_option);
+        self.search_options.toggle_option(search_option);
         self.default_options = self.search_options;
         drop(self.update_matches(false, window, cx));
         self.adjust_query_regex_language(cx);
@@ -1188,6 +1264,15 @@ impl BufferSearchBar {
         self.toggle_search_option(SearchOptions::REGEX, window, cx)
     }
 
+    fn toggle_structural(
+        &mut self,
+        _: &ToggleStructural,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.toggle_search_option(SearchOptions::STRUCTURAL, window, cx)
+    }
+
     fn clear_active_searchable_item_matches(&mut self, window: &mut Window, cx: &mut App) {
         if let Some(active_searchable_item) = self.active_searchable_item.as_ref() {
             self.active_match_index = None;
@@ -1262,6 +1347,23 @@ impl BufferSearchBar {
                                 return done_rx;
                             }
                         }
+                    } else if self.search_options.contains(SearchOptions::STRUCTURAL) {
+                        match SearchQuery::structural(
+                            query,
+                            false,
+                            Default::default(),
+                            Default::default(),
+                            false,
+                            None,
+                        ) {
+                            Ok(query) => query.with_replacement(self.replacement(cx)),
+                            Err(_) => {
+                                self.query_contains_error = true;
+                                self.clear_active_searchable_item_matches(window, cx);
+                                cx.notify();
+                                return done_rx;
+                            }
+                        }
                     } else {
                         match SearchQuery::text(
                             query,
@@ -1303,7 +1405,8 @@ impl BufferSearchBar {
 
                             this.update_match_index(window, cx);
                             this.search_history
-                                .add(&mut this.search_history_cursor, query_text);
+                                .add(&mut this.search_history_cursor, query_text.clone());
+                            this.save_search_query(query_text, cx);
                             if !this.dismissed {
                                 let matches = this
                                     .searchable_items_with_matches
@@ -0,0 +1,223 @@
+use anyhow::Result;
+use db::sqlez_macros::sql;
+use db::{define_connection, query};
+use project::search::SearchInputKind;
+use project::search_history::{QueryInsertionBehavior, SearchHistory, SearchHistoryCursor};
+use workspace::{WorkspaceDb, WorkspaceId};
+
+define_connection!(
+    // Current schema shape using pseudo-rust syntax:
+    // search_histories(
+    //   workspace_id: usize,
+    //   kind: String,
+    //   queries: String, // JSON array, oldest query first
+    // )
+    //
+    // saved_searches(
+    //   workspace_id: usize,
+    //   name: String,
+    //   search: String, // JSON encoded `SavedSearch`
+    // )
+    pub static ref DB: SearchDb<WorkspaceDb> = &[
+        sql! (
+            CREATE TABLE search_histories(
+                workspace_id INTEGER NOT NULL,
+                kind TEXT NOT NULL,
+                queries TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, kind),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+
+            CREATE TABLE saved_searches(
+                workspace_id INTEGER NOT NULL,
+                name TEXT NOT NULL,
+                search TEXT NOT NULL,
+                PRIMARY KEY(workspace_id, name),
+                FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+                ON DELETE CASCADE
+                ON UPDATE CASCADE
+            ) STRICT;
+        ),
+    ];
+);
+
+/// The history stored for the queries of the buffer search bar.
+pub(crate) const BUFFER_SEARCH_HISTORY: &str = "buffer_query";
+
+/// The history stored for each of the inputs of the project search.
+pub(crate) fn project_search_history(kind: SearchInputKind) -> &'static str {
+    match kind {
+        SearchInputKind::Query => "project_query",
+        SearchInputKind::Include => "project_include",
+        SearchInputKind::Exclude => "project_exclude",
+    }
+}
+
+impl SearchDb {
+    pub(crate) fn search_history(&self, workspace_id: WorkspaceId, kind: &str) -> Vec<String> {
+        self.get_search_history(workspace_id, kind.to_string())
+            .ok()
+            .flatten()
+            .and_then(|queries| serde_json::from_str(&queries).ok())
+            .unwrap_or_default()
+    }
+
+    pub(crate) async fn save_search_history(
+        &self,
+        workspace_id: WorkspaceId,
+        kind: &str,
+        queries: Vec<String>,
+    ) -> Result<()> {
+        let queries = serde_json::to_string(&queries)?;
+        self.set_search_history(workspace_id, kind.to_string(), queries)
+            .await
+    }
+
+    /// Adds a query to the stored history, in the same way that it is added to
+    /// the history of a search bar, so that search bars sharing the history
+    /// don't overwrite the queries of each other. The history is read and
+    /// written in the same write, so that no other write happens in between.
+    pub(crate) async fn add_to_search_history(
+        &self,
+        workspace_id: WorkspaceId,
+        kind: &str,
+        query: String,
+        max_history_len: usize,
+    ) -> Result<()> {
+        let kind = kind.to_string();
+        self.write(move |conn| {
+            let queries = conn.select_row_bound::<_, String>(sql!(
+                SELECT queries
+                FROM search_histories
+                WHERE workspace_id = ? AND kind = ?
+            ))?((workspace_id, kind.as_str()))?;
+            let mut history = SearchHistory::new(
+                Some(max_history_len),
+                QueryInsertionBehavior::ReplacePreviousIfContains,
+            );
+            history.restore(
+                queries
+                    .and_then(|queries| serde_json::from_str::<Vec<String>>(&queries).ok())
+                    .unwrap_or_default(),
+            );
+            history.add(&mut SearchHistoryCursor::default(), query);
+            let queries = serde_json::to_string(&history.entries().collect::<Vec<_>>())?;
+            conn.exec_bound(sql!(
+                INSERT INTO search_histories
+                    (workspace_id, kind, queries)
+                VALUES
+                    (?1, ?2, ?3)
+                ON CONFLICT DO UPDATE SET
+                    queries = ?3
+            ))?((workspace_id, kind.as_str(), queries))
+        })
+        .await
+    }
+
+    query! {
+        fn get_search_history(workspace_id: WorkspaceId, kind: String) -> Result<Option<String>> {
+            SELECT queries
+            FROM search_histories
+            WHERE workspace_id = ? AND kind = ?
+        }
+    }
+
+    query! {
+        async fn set_search_history(workspace_id: WorkspaceId, kind: String, queries: String) -> Result<()> {
+            INSERT INTO search_histories
+                (workspace_id, kind, queries)
+            VALUES
+                (?1, ?2, ?3)
+            ON CONFLICT DO UPDATE SET
+                queries = ?3
+        }
+    }
+
+    query! {
+        pub(crate) fn get_saved_searches(workspace_id: WorkspaceId) -> Result<Vec<String>> {
+            SELECT search
+            FROM saved_searches
+            WHERE workspace_id = ?
+            ORDER BY name
+        }
+    }
+
+    query! {
+        pub(crate) async fn save_saved_search(workspace_id: WorkspaceId, name: String, search: String) -> Result<()> {
+            INSERT INTO saved_searches
+                (workspace_id, name, search)
+            VALUES
+                (?1, ?2, ?3)
+            ON CONFLICT DO UPDATE SET
+                search = ?3
+        }
+    }
+
+    query! {
+        pub(crate) async fn delete_saved_search(workspace_id: WorkspaceId, name: String) -> Result<()> {
+            DELETE FROM saved_searches
+            WHERE workspace_id = ? AND name = ?
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[gpui::test]
+    async fn test_search_history_and_saved_searches() {
+        let workspace_id = workspace::WORKSPACE_DB.next_id().await.unwrap();
+
+        assert!(
+            DB.search_history(workspace_id, BUFFER_SEARCH_HISTORY)
+                .is_empty()
+        );
+        DB.save_search_history(
+            workspace_id,
+            BUFFER_SEARCH_HISTORY,
+            vec!["foo".to_string(), "bar".to_string()],
+        )
+        .await
+        .unwrap();
+        DB.save_search_history(
+            workspace_id,
+            BUFFER_SEARCH_HISTORY,
+            vec!["bar".to_string(), "baz".to_string()],
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            DB.search_history(workspace_id, BUFFER_SEARCH_HISTORY),
+            ["bar", "baz"]
+        );
+        DB.add_to_search_history(workspace_id, BUFFER_SEARCH_HISTORY, "qux".to_string(), 2)
+            .await
+            .unwrap();
+        DB.add_to_search_history(workspace_id, BUFFER_SEARCH_HISTORY, "quxx".to_string(), 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            DB.search_history(workspace_id, BUFFER_SEARCH_HISTORY),
+            ["baz", "quxx"]
+        );
+        assert!(
+            DB.search_history(workspace_id, project_search_history(SearchInputKind::Query))
+                .is_empty()
+        );
+
+        DB.save_saved_search(workspace_id, "todos".to_string(), "{}".to_string())
+            .await
+            .unwrap();
+        DB.save_saved_search(workspace_id, "fixmes".to_string(), "[]".to_string())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_saved_searches(workspace_id).unwrap(), ["[]", "{}"]);
+        DB.delete_saved_search(workspace_id, "todos".to_string())
+            .await
+            .unwrap();
+        assert_eq!(DB.get_saved_searches(workspace_id).unwrap(), ["[]"]);
+    }
+}
@@ -1,13 +1,18 @@
 use crate::{
     BufferSearchBar, FocusSearch, NextHistoryQuery, PreviousHistoryQuery, ReplaceAll, ReplaceNext,
     SearchOptions, SelectNextMatch, SelectPreviousMatch, ToggleCaseSensitive, ToggleIncludeIgnored,
-    ToggleRegex, ToggleReplace, ToggleWholeWord, buffer_search::Deploy,
+    ToggleRegex, ToggleReplace, ToggleSemantic, ToggleStructural, ToggleWholeWord,
+    buffer_search::Deploy, persistence, saved_searches::SavedSearch,
 };
-use anyhow::Context as _;
+use anyhow::{Context as _, anyhow};
 use collections::{HashMap, HashSet};
 use editor::{
     Anchor, Editor, EditorElement, EditorEvent, EditorSettings, EditorStyle, MAX_TAB_TITLE_LEN,
-    MultiBuffer, actions::SelectAll, items::active_match_index, scroll::Autoscroll,
+    MultiBuffer, MultiBufferRow, PathKey,
+    actions::SelectAll,
+    display_map::{BlockPlacement, BlockProperties, BlockStyle, CustomBlockId},
+    items::active_match_index,
+    scroll::Autoscroll,
 };
 use futures::{StreamExt, stream::FuturesOrdered};
 use gpui::{
@@ -16,13 +21,14 @@ use gpui::{
     Render, SharedString, Styled, Subscription, Task, TextStyle, UpdateGlobal, WeakEntity, Window,
     actions, div,
 };
-use language::{Buffer, Language};
+use language::{Bias, Buffer, BufferId, Language, ToPoint as _};
 use menu::Confirm;
 use project::{
     Project, ProjectPath,
     search::{SearchInputKind, SearchQuery},
     search_history::SearchHistoryCursor,
 };
+use semantic_index::SemanticDb;
 use settings::Settings;
 use std::{
     any::{Any, TypeId},
@@ -42,12 +48,24 @@ use workspace::{
     DeploySearch, ItemNavHistory, NewSearch, ToolbarItemEvent, ToolbarItemLocation,
     ToolbarItemView, Workspace, WorkspaceId,
     item::{BreadcrumbText, Item, ItemEvent, ItemHandle, SaveOptions},
+    quickfix::QuickfixEntry,
     searchable::{Direction, SearchableItem, SearchableItemHandle},
 };
 
 actions!(
     project_search,
-    [SearchInNew, ToggleFocus, NextField, ToggleFilters]
+    [
+        SearchInNew,
+        ToggleFocus,
+        NextField,
+        ToggleFilters,
+        PreviewReplacements,
+        AcceptReplacement,
+        RejectReplacement,
+        ApplyReplacements,
+        CancelReplacements,
+        SendToQuickfix
+    ]
 );
 
 #[derive(Default)]
@@ -57,7 +75,8 @@ impl Global for ActiveSettings {}
 
 pub fn init(cx: &mut App) {
     cx.set_global(ActiveSettings::default());
-    cx.observe_new(|workspace: &mut Workspace, _window, _cx| {
+    cx.observe_new(|workspace: &mut Workspace, _window, cx| {
+        restore_search_history(workspace, cx);
         register_workspace_action(workspace, move |search_bar, _: &Deploy, window, cx| {
             search_bar.focus_search(window, cx);
         });
@@ -85,6 +104,18 @@ pub fn init(cx: &mut App) {
         register_workspace_action(workspace, move |search_bar, _: &ToggleRegex, window, cx| {
             search_bar.toggle_search_option(SearchOptions::REGEX, window, cx);
         });
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ToggleStructural, window, cx| {
+                search_bar.toggle_search_option(SearchOptions::STRUCTURAL, window, cx);
+            },
+        );
+        register_workspace_action(
+            workspace,
+            move |search_bar, _: &ToggleSemantic, window, cx| {
+                search_bar.toggle_search_option(SearchOptions::SEMANTIC, window, cx);
+            },
+        );
         register_workspace_action(
             workspace,
             move |search_bar, action: &ToggleReplace, window, cx| {
@@ -164,19 +195,73 @@ pub fn init(cx: &mut App) {
     .detach();
 }
 
+const SEARCH_INPUT_KINDS: [SearchInputKind; 3] = [
+    SearchInputKind::Query,
+    SearchInputKind::Include,
+    SearchInputKind::Exclude,
+];
+
+fn restore_search_history(workspace: &Workspace, cx: &mut App) {
+    let Some(workspace_id) = workspace.database_id() else {
+        return;
+    };
+    workspace.project().update(cx, |project, _| {
+        for kind in SEARCH_INPUT_KINDS {
+            let history = project.search_history_mut(kind);
+            if history.entries().next().is_none() {
+                history.restore(
+                    persistence::DB
+                        .search_history(workspace_id, persistence::project_search_history(kind)),
+                );
+            }
+        }
+    });
+}
+
+fn save_search_history(workspace_id: Option<WorkspaceId>, project: &Project, cx: &App) {
+    let Some(workspace_id) = workspace_id else {
+        return;
+    };
+    for kind in SEARCH_INPUT_KINDS {
+        let queries = project
+            .search_history(kind)
+            .entries()
+            .map(ToOwned::to_owned)
+            .collect();
+        cx.background_spawn(async move {
+            persistence::DB
+                .save_search_history(
+                    workspace_id,
+                    persistence::project_search_history(kind),
+                    queries,
+                )
+                .await
+                .log_err();
+        })
+        .detach();
+    }
+}
+
 fn contains_uppercase(str: &str) -> bool {
     str.chars().any(|c| c.is_uppercase())
 }
 
+const SEMANTIC_SEARCH_LIMIT: usize = 64;
+
 pub struct ProjectSearch {
     project: Entity<Project>,
     excerpts: Entity<MultiBuffer>,
     pending_search: Option<Task<Option<()>>>,
     match_ranges: Vec<Range<Anchor>>,
+    /// The similarity of each match to the query, for semantic searches.
+    match_scores: Vec<f32>,
+    semantic: bool,
     active_query: Option<SearchQuery>,
     last_search_query_text: Option<String>,
     search_id: usize,
     no_results: Option<bool>,
+    /// Why the last search failed, for searches that can fail as a whole.
+    search_error: Option<SharedString>,
     limit_reached: bool,
     search_history_cursor: SearchHistoryCursor,
     search_included_history_cursor: SearchHistoryCursor,
@@ -207,9 +292,42 @@ pub struct ProjectSearchView {
     replace_enabled: bool,
     included_opened_only: bool,
     regex_language: Option<Arc<Language>>,
+    score_blocks: HashSet<CustomBlockId>,
+    replace_preview: Option<ReplacePreview>,
     _subscriptions: Vec<Subscription>,
 }
 
+/// The replacements of the current matches, shown below each of them in the
+/// results editor until they are applied or cancelled.
+struct ReplacePreview {
+    replacements: Vec<PendingReplacement>,
+    blocks: HashSet<CustomBlockId>,
+}
+
+struct PendingReplacement {
+    range: Range<Anchor>,
+    text: Arc<str>,
+    /// The line of the match, as it reads after the replacement.
+    new_line: SharedString,
+    buffer_id: Option<BufferId>,
+    /// Whether this is the first replacement in its buffer, where the
+    /// controls for the whole file are shown.
+    first_in_buffer: bool,
+    status: ReplacementStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum ReplacementStatus {
+    Pending,
+    Accepted,
+    Rejected,
+}
+
+/// Highlights the matches whose replacement was accepted.
+enum ReplacePreviewHighlight {}
+/// Highlights the matches whose replacement wasn't reviewed yet.
+enum PendingReplacementHighlight {}
+
 #[derive(Debug, Clone)]
 pub struct ProjectSearchSettings {
     search_options: SearchOptions,
@@ -230,10 +348,13 @@ impl ProjectSearch {
             excerpts: cx.new(|_| MultiBuffer::new(capability)),
             pending_search: Default::default(),
             match_ranges: Default::default(),
+            match_scores: Default::default(),
+            semantic: false,
             active_query: None,
             last_search_query_text: None,
             search_id: 0,
             no_results: None,
+            search_error: None,
             limit_reached: false,
             search_history_cursor: Default::default(),
             search_included_history_cursor: Default::default(),
@@ -249,10 +370,13 @@ impl ProjectSearch {
                 .update(cx, |excerpts, cx| cx.new(|cx| excerpts.clone(cx))),
             pending_search: Default::default(),
             match_ranges: self.match_ranges.clone(),
+            match_scores: self.match_scores.clone(),
+            semantic: self.semantic,
             active_query: self.active_query.clone(),
             last_search_query_text: self.last_search_query_text.clone(),
             search_id: self.search_id,
             no_results: self.no_results,
+            search_error: self.search_error.clone(),
             limit_reached: self.limit_reached,
             search_history_cursor: self.search_history_cursor.clone(),
             search_included_history_cursor: self.search_included_history_cursor.clone(),
@@ -274,8 +398,8 @@ impl ProjectSearch {
         }
     }
 
-    fn search(&mut self, query: SearchQuery, cx: &mut Context<Self>) {
-        let search = self.project.update(cx, |project, cx| {
+    fn add_to_history(&mut self, query: &SearchQuery, cx: &mut Context<Self>) {
+        self.project.update(cx, |project, _| {
             project
                 .search_history_mut(SearchInputKind::Query)
                 .add(&mut self.search_history_cursor, query.as_str().to_string());
@@ -291,12 +415,21 @@ impl ProjectSearch {
                     .search_history_mut(SearchInputKind::Exclude)
                     .add(&mut self.search_excluded_history_cursor, excluded);
             }
-            project.search(query.clone(), cx)
         });
+    }
+
+    fn search(&mut self, query: SearchQuery, cx: &mut Context<Self>) {
+        self.add_to_history(&query, cx);
+        let search = self
+            .project
+            .update(cx, |project, cx| project.search(query.clone(), cx));
         self.last_search_query_text = Some(query.as_str().to_string());
         self.search_id += 1;
         self.active_query = Some(query);
+        self.semantic = false;
+        self.search_error = None;
         self.match_ranges.clear();
+        self.match_scores.clear();
         self.pending_search = Some(cx.spawn(async move |project_search, cx| {
             let mut matches = pin!(search.ready_chunks(1024));
             project_search
@@ -366,6 +499,155 @@ impl ProjectSearch {
         }));
         cx.notify();
     }
+
+    /// Searches the project index for the chunks most similar to the query,
+    /// showing them ranked by their best chunk's similarity.
+    fn semantic_search(&mut self, query: SearchQuery, cx: &mut Context<Self>) {
+        self.add_to_history(&query, cx);
+        let project = self.project.clone();
+        let search = cx.has_global::<SemanticDb>().then(|| {
+            let project_index = cx.update_global::<SemanticDb, _>(|semantic_db, cx| {
+                semantic_db
+                    .project_index(project.clone(), cx)
+                    .unwrap_or_else(|| semantic_db.create_project_index(project.clone(), cx))
+            });
+            project_index.read(cx).search(
+                vec![query.as_str().to_string()],
+                SEMANTIC_SEARCH_LIMIT,
+                cx,
+            )
+        });
+        self.last_search_query_text = Some(query.as_str().to_string());
+        self.search_id += 1;
+        self.active_query = Some(query.clone());
+        self.semantic = true;
+        self.search_error = None;
+        self.match_ranges.clear();
+        self.match_scores.clear();
+        self.pending_search = Some(cx.spawn(async move |project_search, cx| {
+            project_search
+                .update(cx, |project_search, cx| {
+                    project_search.match_ranges.clear();
+                    project_search.match_scores.clear();
+                    project_search
+                        .excerpts
+                        .update(cx, |excerpts, cx| excerpts.clear(cx));
+                    project_search.no_results = Some(true);
+                    project_search.limit_reached = false;
+                })
+                .ok()?;
+
+            let results = match search {
+                Some(search) => search.await,
+                None => Err(anyhow!(
+                    "semantic search is disabled, enable it with the `semantic_index.enabled` setting"
+                )),
+            };
+            let results = match results {
+                Ok(results) => results,
+                Err(error) => {
+                    project_search
+                        .update(cx, |project_search, cx| {
+                            project_search.search_error = Some(error.to_string().into());
+                            project_search.pending_search.take();
+                            cx.notify();
+                        })
+                        .ok()?;
+                    return None;
+                }
+            };
+
+            let mut chunks_by_path =
+                Vec::<(ProjectPath, Arc<Path>, Vec<(Range<usize>, f32)>)>::new();
+            for result in results {
+                let (worktree_id, full_path) = result
+                    .worktree
+                    .read_with(cx, |worktree, _| {
+                        (
+                            worktree.id(),
+                            Path::new(worktree.root_name()).join(&result.path),
+                        )
+                    })
+                    .ok()?;
+                let matched_path = if query.match_full_paths() {
+                    full_path.as_path()
+                } else {
+                    result.path.as_ref()
+                };
+                if !query.match_path(matched_path) {
+                    continue;
+                }
+                let project_path = ProjectPath {
+                    worktree_id,
+                    path: result.path,
+                };
+                let chunk = (result.range, result.score);
+                match chunks_by_path
+                    .iter_mut()
+                    .find(|(path, _, _)| *path == project_path)
+                {
+                    Some((_, _, chunks)) => chunks.push(chunk),
+                    None => chunks_by_path.push((project_path, full_path.into(), vec![chunk])),
+                }
+            }
+
+            // Results come sorted by score, so files are ranked by their best chunk.
+            for (rank, (project_path, full_path, mut chunks)) in
+                chunks_by_path.into_iter().enumerate()
+            {
+                let open_buffer = project
+                    .update(cx, |project, cx| project.open_buffer(project_path, cx))
+                    .ok()?;
+                let Some(buffer) = open_buffer.await.log_err() else {
+                    continue;
+                };
+                chunks.sort_by_key(|(range, _)| range.start);
+                project_search
+                    .update(cx, |project_search, cx| {
+                        let snapshot = buffer.read(cx).snapshot();
+                        let ranges = chunks
+                            .iter()
+                            .map(|(range, _)| {
+                                let start = snapshot
+                                    .clip_offset(range.start.min(snapshot.len()), Bias::Left);
+                                let end = snapshot
+                                    .clip_offset(range.end.min(snapshot.len()), Bias::Right);
+                                start.to_point(&snapshot)..end.to_point(&snapshot)
+                            })
+                            .collect::<Vec<_>>();
+                        let (match_ranges, _) =
+                            project_search.excerpts.update(cx, |excerpts, cx| {
+                                excerpts.set_excerpts_for_path(
+                                    PathKey::namespaced(rank as u32, full_path),
+                                    buffer,
+                                    ranges,
+                                    editor::DEFAULT_MULTIBUFFER_CONTEXT,
+                                    cx,
+                                )
+                            });
+                        project_search.match_ranges.extend(match_ranges);
+                        project_search
+                            .match_scores
+                            .extend(chunks.iter().map(|(_, score)| *score));
+                        cx.notify();
+                    })
+                    .ok()?;
+            }
+
+            project_search
+                .update(cx, |project_search, cx| {
+                    if !project_search.match_ranges.is_empty() {
+                        project_search.no_results = Some(false);
+                    }
+                    project_search.pending_search.take();
+                    cx.notify();
+                })
+                .ok()?;
+
+            None
+        }));
+        cx.notify();
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -385,14 +667,23 @@ impl Render for ProjectSearchView {
                 .flex_1()
                 .size_full()
                 .track_focus(&self.focus_handle(cx))
+                .on_action(cx.listener(Self::preview_replacements))
+                .on_action(cx.listener(Self::accept_replacement))
+                .on_action(cx.listener(Self::reject_replacement))
+                .on_action(cx.listener(Self::apply_replacements))
+                .on_action(cx.listener(Self::cancel_replacements))
+                .on_action(cx.listener(Self::send_to_quickfix))
                 .child(self.results_editor.clone())
         } else {
             let model = self.entity.read(cx);
             let has_no_results = model.no_results.unwrap_or(false);
             let is_search_underway = model.pending_search.is_some();
+            let search_error = model.search_error.clone().filter(|_| !is_search_underway);
 
             let heading_text = if is_search_underway {
                 "Searching…"
+            } else if search_error.is_some() {
+                "Search Failed"
             } else if has_no_results {
                 "No Results"
             } else {
@@ -403,7 +694,14 @@ impl Render for ProjectSearchView {
                 .justify_center()
                 .child(Label::new(heading_text).size(LabelSize::Large));
 
-            let page_content: Option<AnyElement> = if let Some(no_results) = model.no_results {
+            let page_content: Option<AnyElement> = if let Some(error) = search_error {
+                Some(
+                    Label::new(error)
+                        .size(LabelSize::Small)
+                        .color(Color::Error)
+                        .into_any_element(),
+                )
+            } else if let Some(no_results) = model.no_results {
                 if model.pending_search.is_none() && no_results {
                     Some(
                         Label::new("No results found in this project for the provided query")
@@ -654,7 +952,7 @@ impl ProjectSearchView {
     }
 
     fn toggle_search_option(&mut self, option: SearchOptions, cx: &mut Context<Self>) {
-        self.search_options.toggle(option);
+        self.search_options.toggle_option(option);
         ActiveSettings::update_global(cx, |settings, cx| {
             settings.0.insert(
                 self.entity.read(cx).project.downgrade(),
@@ -669,7 +967,8 @@ impl ProjectSearchView {
     }
 
     fn replace_next(&mut self, _: &ReplaceNext, window: &mut Window, cx: &mut Context<Self>) {
-        if self.entity.read(cx).match_ranges.is_empty() {
+        // Semantic matches are whole chunks of code, which are not meant to be replaced.
+        if self.entity.read(cx).match_ranges.is_empty() || self.entity.read(cx).semantic {
             return;
         }
         let Some(active_index) = self.active_match_index else {
@@ -691,8 +990,426 @@ impl ProjectSearchView {
     pub fn replacement(&self, cx: &App) -> String {
         self.replacement_editor.read(cx).text(cx)
     }
+
+    /// Shows what replacing each match would do, so that replacements can be
+    /// accepted or rejected one by one before applying them.
+    /// Makes the matches the workspace's current quickfix list, so they can be
+    /// gone through with `quickfix::Next` after the search is closed.
+    fn send_to_quickfix(&mut self, _: &SendToQuickfix, _: &mut Window, cx: &mut Context<Self>) {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return;
+        };
+        let project_search = self.entity.read(cx);
+        let Some(query) = project_search.active_query.as_ref() else {
+            return;
+        };
+        let title = format!("Search: {}", query.as_str());
+        let multi_buffer = project_search.excerpts.read(cx);
+        let entries = project_search
+            .match_ranges
+            .iter()
+            .filter_map(|range| {
+                let buffer = multi_buffer.buffer(range.start.buffer_id?)?;
+                let buffer = buffer.read(cx);
+                let file = buffer.file()?;
+                let point = buffer.summary_for_anchor::<language::Point>(&range.start.text_anchor);
+                let line_end = language::Point::new(point.row, buffer.line_len(point.row));
+                let text = buffer
+                    .text_for_range(language::Point::new(point.row, 0)..line_end)
+                    .collect::<String>();
+                Some(QuickfixEntry {
+                    path: ProjectPath {
+                        worktree_id: file.worktree_id(cx),
+                        path: file.path().clone(),
+                    },
+                    row: point.row,
+                    column: point.column,
+                    text: text.trim().to_string().into(),
+                })
+            })
+            .collect::<Vec<_>>();
+        if entries.is_empty() {
+            return;
+        }
+        workspace.update(cx, |workspace, cx| {
+            workspace
+                .quickfix()
+                .update(cx, |quickfix, cx| quickfix.set_list(title, entries, cx));
+        });
+    }
+
+    fn preview_replacements(
+        &mut self,
+        _: &PreviewReplacements,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project_search = self.entity.read(cx);
+        if project_search.semantic {
+            return;
+        }
+        let Some(query) = project_search.active_query.clone() else {
+            return;
+        };
+        let query = query.with_replacement(self.replacement(cx));
+        let match_ranges = project_search.match_ranges.clone();
+        let snapshot = self.results_editor.read(cx).buffer().read(cx).snapshot(cx);
+
+        let mut replacements = Vec::<PendingReplacement>::new();
+        for range in match_ranges {
+            let Some(text) = editor::items::replacement_for_match(&snapshot, &range, &query) else {
+                continue;
+            };
+            let start = snapshot.summary_for_anchor::<language::Point>(&range.start);
+            let end = snapshot.summary_for_anchor::<language::Point>(&range.end);
+            let line_start = language::Point::new(start.row, 0);
+            let line_end =
+                language::Point::new(end.row, snapshot.line_len(MultiBufferRow(end.row)));
+            let mut new_line = snapshot
+                .text_for_range(line_start..start)
+                .collect::<String>();
+            new_line.push_str(&text);
+            new_line.extend(snapshot.text_for_range(end..line_end));
+
+            let buffer_id = range.start.buffer_id;
+            let first_in_buffer = replacements
+                .last()
+                .is_none_or(|previous| previous.buffer_id != buffer_id);
+            replacements.push(PendingReplacement {
+                range,
+                text: text.into(),
+                new_line: new_line.into(),
+                buffer_id,
+                first_in_buffer,
+                status: ReplacementStatus::Pending,
+            });
+        }
+        if replacements.is_empty() {
+            return;
+        }
+
+        self.clear_replace_preview(cx);
+        self.replace_preview = Some(ReplacePreview {
+            replacements,
+            blocks: HashSet::default(),
+        });
+        self.insert_replace_preview_blocks(cx);
+        self.update_replace_preview_highlights(cx);
+        cx.notify();
+    }
+
+    fn insert_replace_preview_blocks(&mut self, cx: &mut Context<Self>) {
+        let Some(preview) = self.replace_preview.as_mut() else {
+            return;
+        };
+        let this = cx.entity().downgrade();
+        let blocks = preview
+            .replacements
+            .iter()
+            .enumerate()
+            .map(|(ix, replacement)| {
+                let this = this.clone();
+                BlockProperties {
+                    placement: BlockPlacement::Below(replacement.range.end),
+                    height: Some(replacement.new_line.lines().count().max(1) as u32),
+                    style: BlockStyle::Flex,
+                    render: Arc::new(move |cx| {
+                        let Some(this) = this.upgrade() else {
+                            return div().into_any_element();
+                        };
+                        this.read(cx)
+                            .render_pending_replacement(ix, &this, cx.anchor_x, cx.app)
+                    }),
+                    priority: 0,
+                    render_in_minimap: false,
+                }
+            })
+            .collect::<Vec<_>>();
+        preview.blocks = self.results_editor.update(cx, |editor, cx| {
+            editor.insert_blocks(blocks, None, cx).into_iter().collect()
+        });
+    }
+
+    fn render_pending_replacement(
+        &self,
+        ix: usize,
+        this: &Entity<Self>,
+        anchor_x: Pixels,
+        cx: &App,
+    ) -> AnyElement {
+        let Some(replacement) = self
+            .replace_preview
+            .as_ref()
+            .and_then(|preview| preview.replacements.get(ix))
+        else {
+            return div().into_any_element();
+        };
+        let status = replacement.status;
+        let buffer_id = replacement.buffer_id;
+        let button = |id: &'static str, icon: IconName, tooltip: &'static str| {
+            IconButton::new((id, ix), icon)
+                .shape(IconButtonShape::Square)
+                .icon_size(IconSize::Small)
+                .tooltip(Tooltip::text(tooltip))
+        };
+
+        h_flex()
+            .id(("pending-replacement", ix))
+            .w_full()
+            .pl(anchor_x)
+            .gap_1()
+            .map(|this| match status {
+                ReplacementStatus::Accepted => this.bg(cx.theme().status().created_background),
+                ReplacementStatus::Pending => this.bg(cx.theme().colors().element_background),
+                ReplacementStatus::Rejected => this,
+            })
+            .child(
+                Label::new(replacement.new_line.clone())
+                    .buffer_font(cx)
+                    .when(status == ReplacementStatus::Rejected, |label| {
+                        label.strikethrough().color(Color::Muted)
+                    }),
+            )
+            .child(
+                button("accept-replacement", IconName::Check, "Accept Replacement")
+                    .toggle_state(status == ReplacementStatus::Accepted)
+                    .on_click({
+                        let this = this.downgrade();
+                        move |_, _, cx| {
+                            this.update(cx, |this, cx| {
+                                this.set_replacement_status(ix, ReplacementStatus::Accepted, cx)
+                            })
+                            .ok();
+                        }
+                    }),
+            )
+            .child(
+                button("reject-replacement", IconName::Close, "Reject Replacement")
+                    .toggle_state(status == ReplacementStatus::Rejected)
+                    .on_click({
+                        let this = this.downgrade();
+                        move |_, _, cx| {
+                            this.update(cx, |this, cx| {
+                                this.set_replacement_status(ix, ReplacementStatus::Rejected, cx)
+                            })
+                            .ok();
+                        }
+                    }),
+            )
+            .when(replacement.first_in_buffer, |element| {
+                element
+                    .child(
+                        button(
+                            "accept-file-replacements",
+                            IconName::CheckDouble,
+                            "Accept Replacements in File",
+                        )
+                        .on_click({
+                            let this = this.downgrade();
+                            move |_, _, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.set_file_replacements_status(
+                                        buffer_id,
+                                        ReplacementStatus::Accepted,
+                                        cx,
+                                    )
+                                })
+                                .ok();
+                            }
+                        }),
+                    )
+                    .child(
+                        button(
+                            "reject-file-replacements",
+                            IconName::ListX,
+                            "Reject Replacements in File",
+                        )
+                        .on_click({
+                            let this = this.downgrade();
+                            move |_, _, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.set_file_replacements_status(
+                                        buffer_id,
+                                        ReplacementStatus::Rejected,
+                                        cx,
+                                    )
+                                })
+                                .ok();
+                            }
+                        }),
+                    )
+            })
+            .into_any_element()
+    }
+
+    fn set_replacement_status(
+        &mut self,
+        ix: usize,
+        status: ReplacementStatus,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(replacement) = self
+            .replace_preview
+            .as_mut()
+            .and_then(|preview| preview.replacements.get_mut(ix))
+        {
+            replacement.status = status;
+            self.update_replace_preview_highlights(cx);
+        }
+    }
+
+    fn set_file_replacements_status(
+        &mut self,
+        buffer_id: Option<BufferId>,
+        status: ReplacementStatus,
+        cx: &mut Context<Self>,
+    ) {
+        if let Some(preview) = self.replace_preview.as_mut() {
+            for replacement in &mut preview.replacements {
+                if replacement.buffer_id == buffer_id {
+                    replacement.status = status;
+                }
+            }
+            self.update_replace_preview_highlights(cx);
+        }
+    }
+
+    /// Marks the replacement of the active match, and moves on to the next one.
+    fn review_active_replacement(
+        &mut self,
+        status: ReplacementStatus,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(active_range) = self
+            .active_match_index
+            .and_then(|ix| self.entity.read(cx).match_ranges.get(ix).cloned())
+        else {
+            return;
+        };
+        let Some(ix) = self.replace_preview.as_ref().and_then(|preview| {
+            preview
+                .replacements
+                .iter()
+                .position(|replacement| replacement.range == active_range)
+        }) else {
+            return;
+        };
+        self.set_replacement_status(ix, status, cx);
+        self.select_match(Direction::Next, window, cx);
+    }
+
+    fn accept_replacement(
+        &mut self,
+        _: &AcceptReplacement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.review_active_replacement(ReplacementStatus::Accepted, window, cx);
+    }
+
+    fn reject_replacement(
+        &mut self,
+        _: &RejectReplacement,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.review_active_replacement(ReplacementStatus::Rejected, window, cx);
+    }
+
+    /// Applies the accepted replacements as a single transaction, which can be
+    /// undone at once from the results editor.
+    fn apply_replacements(
+        &mut self,
+        _: &ApplyReplacements,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(preview) = self.replace_preview.as_ref() else {
+            return;
+        };
+        let edits = preview
+            .replacements
+            .iter()
+            .filter(|replacement| replacement.status == ReplacementStatus::Accepted)
+            .map(|replacement| (replacement.range.clone(), replacement.text.clone()))
+            .collect::<Vec<_>>();
+        self.clear_replace_preview(cx);
+        if !edits.is_empty() {
+            self.results_editor.update(cx, |editor, cx| {
+                editor.transact(window, cx, |editor, _, cx| {
+                    editor.edit(edits, cx);
+                });
+            });
+        }
+        cx.notify();
+    }
+
+    fn cancel_replacements(
+        &mut self,
+        _: &CancelReplacements,
+        _: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.clear_replace_preview(cx);
+        cx.notify();
+    }
+
+    fn clear_replace_preview(&mut self, cx: &mut Context<Self>) {
+        let Some(preview) = self.replace_preview.take() else {
+            return;
+        };
+        self.results_editor.update(cx, |editor, cx| {
+            editor.remove_blocks(preview.blocks, None, cx);
+            editor.clear_background_highlights::<ReplacePreviewHighlight>(cx);
+            editor.clear_background_highlights::<PendingReplacementHighlight>(cx);
+        });
+    }
+
+    fn update_replace_preview_highlights(&mut self, cx: &mut Context<Self>) {
+        let Some(preview) = self.replace_preview.as_ref() else {
+            return;
+        };
+        let ranges_with_status = |status| {
+            preview
+                .replacements
+                .iter()
+                .filter(|replacement| replacement.status == status)
+                .map(|replacement| replacement.range.clone())
+                .collect::<Vec<_>>()
+        };
+        // Only accepted replacements are applied, so only they are shown as
+        // deleting the matches.
+        let accepted_ranges = ranges_with_status(ReplacementStatus::Accepted);
+        let pending_ranges = ranges_with_status(ReplacementStatus::Pending);
+        self.results_editor.update(cx, |editor, cx| {
+            editor.highlight_background::<ReplacePreviewHighlight>(
+                &accepted_ranges,
+                |theme| theme.status().deleted_background,
+                cx,
+            );
+            editor.highlight_background::<PendingReplacementHighlight>(
+                &pending_ranges,
+                |theme| theme.colors().search_match_background,
+                cx,
+            );
+            cx.notify();
+        });
+        cx.notify();
+    }
+
+    /// The number of replacements accepted in the preview, and their total.
+    fn replace_preview_counts(&self) -> Option<(usize, usize)> {
+        let preview = self.replace_preview.as_ref()?;
+        let accepted = preview
+            .replacements
+            .iter()
+            .filter(|replacement| replacement.status == ReplacementStatus::Accepted)
+            .count();
+        Some((accepted, preview.replacements.len()))
+    }
     fn replace_all(&mut self, _: &ReplaceAll, window: &mut Window, cx: &mut Context<Self>) {
-        if self.active_match_index.is_none() {
+        if self.active_match_index.is_none() || self.entity.read(cx).semantic {
             return;
         }
 
@@ -747,6 +1464,7 @@ impl ProjectSearchView {
                 query_text = active_query.as_str().to_string();
                 replacement_text = active_query.replacement().map(ToOwned::to_owned);
                 options = SearchOptions::from_query(active_query);
+                options.set(SearchOptions::SEMANTIC, entity.semantic);
             }
         }
         subscriptions.push(cx.observe_in(&entity, window, |this, _, window, cx| {
@@ -875,6 +1593,8 @@ impl ProjectSearchView {
             replace_enabled: false,
             included_opened_only: false,
             regex_language: None,
+            score_blocks: HashSet::default(),
+            replace_preview: None,
             _subscriptions: subscriptions,
         };
         this.entity_changed(window, cx);
@@ -934,23 +1654,33 @@ impl ProjectSearchView {
         {
             let new_query = search_view.update(cx, |search_view, cx| {
                 let new_query = search_view.build_search_query(cx);
+                let semantic = search_view.search_options.contains(SearchOptions::SEMANTIC);
                 if new_query.is_some() {
                     if let Some(old_query) = search_view.entity.read(cx).active_query.clone() {
                         search_view.query_editor.update(cx, |editor, cx| {
                             editor.set_text(old_query.as_str(), window, cx);
                         });
                         search_view.search_options = SearchOptions::from_query(&old_query);
+                        search_view.search_options.set(
+                            SearchOptions::SEMANTIC,
+                            search_view.entity.read(cx).semantic,
+                        );
                         search_view.adjust_query_regex_language(cx);
                     }
                 }
-                new_query
+                new_query.map(|new_query| (new_query, semantic))
             });
-            if let Some(new_query) = new_query {
+            if let Some((new_query, semantic)) = new_query {
                 let entity = cx.new(|cx| {
                     let mut entity = ProjectSearch::new(workspace.project().clone(), cx);
-                    entity.search(new_query, cx);
+                    if semantic {
+                        entity.semantic_search(new_query, cx);
+                    } else {
+                        entity.search(new_query, cx);
+                    }
                     entity
                 });
+                save_search_history(workspace.database_id(), workspace.project().read(cx), cx);
                 let weak_workspace = cx.entity().downgrade();
                 workspace.add_item_to_active_pane(
                     Box::new(cx.new(|cx| {
@@ -996,20 +1726,72 @@ impl ProjectSearchView {
             workspace.activate_item(&existing, true, true, window, cx);
             existing
         } else {
-            let settings = cx
-                .global::<ActiveSettings>()
-                .0
-                .get(&workspace.project().downgrade());
-
-            let settings = settings.cloned();
-
+            let settings = cx
+                .global::<ActiveSettings>()
+                .0
+                .get(&workspace.project().downgrade());
+
+            let settings = settings.cloned();
+
+            let weak_workspace = cx.entity().downgrade();
+
+            let project_search = cx.new(|cx| ProjectSearch::new(workspace.project().clone(), cx));
+            let project_search_view = cx.new(|cx| {
+                ProjectSearchView::new(weak_workspace, project_search, window, cx, settings)
+            });
+
+            workspace.add_item_to_active_pane(
+                Box::new(project_search_view.clone()),
+                None,
+                true,
+                window,
+                cx,
+            );
+            project_search_view
+        };
+
+        search.update(cx, |search, cx| {
+            search.replace_enabled = action.replace_enabled;
+            if let Some(query) = query {
+                search.set_query(&query, window, cx);
+            }
+            if let Some(included_files) = action.included_files.as_deref() {
+                search
+                    .included_files_editor
+                    .update(cx, |editor, cx| editor.set_text(included_files, window, cx));
+                search.filters_enabled = true;
+            }
+            if let Some(excluded_files) = action.excluded_files.as_deref() {
+                search
+                    .excluded_files_editor
+                    .update(cx, |editor, cx| editor.set_text(excluded_files, window, cx));
+                search.filters_enabled = true;
+            }
+            search.focus_query_editor(window, cx)
+        });
+    }
+
+    /// Runs a saved search in the project search of the active pane, creating
+    /// one if there is none.
+    pub(crate) fn run_saved_search(
+        workspace: &mut Workspace,
+        saved_search: &SavedSearch,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) {
+        let existing = workspace
+            .active_pane()
+            .read(cx)
+            .items()
+            .find_map(|item| item.downcast::<ProjectSearchView>());
+        let search = if let Some(existing) = existing {
+            workspace.activate_item(&existing, true, true, window, cx);
+            existing
+        } else {
             let weak_workspace = cx.entity().downgrade();
-
             let project_search = cx.new(|cx| ProjectSearch::new(workspace.project().clone(), cx));
-            let project_search_view = cx.new(|cx| {
-                ProjectSearchView::new(weak_workspace, project_search, window, cx, settings)
-            });
-
+            let project_search_view = cx
+                .new(|cx| ProjectSearchView::new(weak_workspace, project_search, window, cx, None));
             workspace.add_item_to_active_pane(
                 Box::new(project_search_view.clone()),
                 None,
@@ -1021,26 +1803,30 @@ impl ProjectSearchView {
         };
 
         search.update(cx, |search, cx| {
-            search.replace_enabled = action.replace_enabled;
-            if let Some(query) = query {
-                search.set_query(&query, window, cx);
-            }
-            if let Some(included_files) = action.included_files.as_deref() {
-                search
-                    .included_files_editor
-                    .update(cx, |editor, cx| editor.set_text(included_files, window, cx));
-                search.filters_enabled = true;
-            }
-            if let Some(excluded_files) = action.excluded_files.as_deref() {
-                search
-                    .excluded_files_editor
-                    .update(cx, |editor, cx| editor.set_text(excluded_files, window, cx));
+            search.set_search_editor(SearchInputKind::Query, &saved_search.query, window, cx);
+            search.set_search_editor(SearchInputKind::Include, &saved_search.include, window, cx);
+            search.set_search_editor(SearchInputKind::Exclude, &saved_search.exclude, window, cx);
+            if !saved_search.include.is_empty() || !saved_search.exclude.is_empty() {
                 search.filters_enabled = true;
             }
-            search.focus_query_editor(window, cx)
+            search.search_options = saved_search.options();
+            search.adjust_query_regex_language(cx);
+            search.search(cx);
+            search.focus_results_editor(window, cx);
         });
     }
 
+    /// Captures the current query, options and filters as a named search.
+    pub(crate) fn saved_search(&self, name: String, cx: &App) -> SavedSearch {
+        SavedSearch::new(
+            name,
+            self.search_query_text(cx),
+            self.search_options,
+            self.included_files_editor.read(cx).text(cx),
+            self.excluded_files_editor.read(cx).text(cx),
+        )
+    }
+
     fn prompt_to_save_if_dirty_then_search(
         &mut self,
         window: &mut Window,
@@ -1116,7 +1902,17 @@ impl ProjectSearchView {
 
     fn search(&mut self, cx: &mut Context<Self>) {
         if let Some(query) = self.build_search_query(cx) {
-            self.entity.update(cx, |model, cx| model.search(query, cx));
+            if self.search_options.contains(SearchOptions::SEMANTIC) {
+                self.entity
+                    .update(cx, |model, cx| model.semantic_search(query, cx));
+            } else {
+                self.entity.update(cx, |model, cx| model.search(query, cx));
+            }
+            let workspace_id = self
+                .workspace
+                .upgrade()
+                .and_then(|workspace| workspace.read(cx).database_id());
+            save_search_history(workspace_id, self.entity.read(cx).project.read(cx), cx);
         }
     }
 
@@ -1218,6 +2014,32 @@ impl ProjectSearchView {
                         cx.notify();
                     }
 
+                    None
+                }
+            }
+        } else if self.search_options.contains(SearchOptions::STRUCTURAL) {
+            match SearchQuery::structural(
+                text,
+                self.search_options.contains(SearchOptions::INCLUDE_IGNORED),
+                included_files,
+                excluded_files,
+                match_full_paths,
+                open_buffers,
+            ) {
+                Ok(query) => {
+                    let should_unmark_error = self.panels_with_errors.remove(&InputPanel::Query);
+                    if should_unmark_error {
+                        cx.notify();
+                    }
+
+                    Some(query)
+                }
+                Err(_e) => {
+                    let should_mark_error = self.panels_with_errors.insert(InputPanel::Query);
+                    if should_mark_error {
+                        cx.notify();
+                    }
+
                     None
                 }
             }
@@ -1357,6 +2179,9 @@ impl ProjectSearchView {
     }
 
     fn entity_changed(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        if self.replace_preview.is_some() && self.entity.read(cx).search_id != self.search_id {
+            self.cancel_replacements(&CancelReplacements, window, cx);
+        }
         let match_ranges = self.entity.read(cx).match_ranges.clone();
         if match_ranges.is_empty() {
             self.active_match_index = None;
@@ -1385,11 +2210,50 @@ impl ProjectSearchView {
                 self.focus_results_editor(window, cx);
             }
         }
+        self.update_score_blocks(cx);
 
         cx.emit(ViewEvent::UpdateTab);
         cx.notify();
     }
 
+    /// Shows the similarity of each semantic match above it.
+    fn update_score_blocks(&mut self, cx: &mut Context<Self>) {
+        let project_search = self.entity.read(cx);
+        if self.score_blocks.is_empty() && project_search.match_scores.is_empty() {
+            return;
+        }
+        let blocks = project_search
+            .match_ranges
+            .iter()
+            .zip(&project_search.match_scores)
+            .map(|(range, score)| {
+                let label: SharedString = format!("Similarity: {score:.2}").into();
+                BlockProperties {
+                    placement: BlockPlacement::Above(range.start),
+                    height: Some(1),
+                    style: BlockStyle::Flex,
+                    render: Arc::new(move |cx| {
+                        h_flex()
+                            .pl(cx.anchor_x)
+                            .child(
+                                Label::new(label.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted),
+                            )
+                            .into_any_element()
+                    }),
+                    priority: 0,
+                    render_in_minimap: false,
+                }
+            })
+            .collect::<Vec<_>>();
+        let old_blocks = mem::take(&mut self.score_blocks);
+        self.score_blocks = self.results_editor.update(cx, |editor, cx| {
+            editor.remove_blocks(old_blocks, None, cx);
+            editor.insert_blocks(blocks, None, cx).into_iter().collect()
+        });
+    }
+
     fn update_match_index(&mut self, cx: &mut Context<Self>) {
         let results_editor = self.results_editor.read(cx);
         let new_index = active_match_index(
@@ -1462,6 +2326,21 @@ impl ProjectSearchView {
                         window.dispatch_action(ToggleRegex.boxed_clone(), cx)
                     }),
             )
+            .child(
+                Button::new("structural", "Match syntax structure")
+                    .icon(IconName::Code)
+                    .icon_position(IconPosition::Start)
+                    .icon_size(IconSize::Small)
+                    .key_binding(KeyBinding::for_action_in(
+                        &ToggleStructural,
+                        &focus_handle,
+                        window,
+                        cx,
+                    ))
+                    .on_click(|_event, window, cx| {
+                        window.dispatch_action(ToggleStructural.boxed_clone(), cx)
+                    }),
+            )
             .child(
                 Button::new("match-case", "Match case")
                     .icon(IconName::CaseSensitive)
@@ -1995,7 +2874,23 @@ impl Render for ProjectSearchBar {
                         cx.listener(|this, _, window, cx| {
                             this.toggle_search_option(SearchOptions::REGEX, window, cx);
                         }),
-                    )),
+                    ))
+                    .child(SearchOptions::STRUCTURAL.as_button(
+                        self.is_option_enabled(SearchOptions::STRUCTURAL, cx),
+                        focus_handle.clone(),
+                        cx.listener(|this, _, window, cx| {
+                            this.toggle_search_option(SearchOptions::STRUCTURAL, window, cx);
+                        }),
+                    ))
+                    .when(cx.has_global::<SemanticDb>(), |this| {
+                        this.child(SearchOptions::SEMANTIC.as_button(
+                            self.is_option_enabled(SearchOptions::SEMANTIC, cx),
+                            focus_handle.clone(),
+                            cx.listener(|this, _, window, cx| {
+                                this.toggle_search_option(SearchOptions::SEMANTIC, window, cx);
+                            }),
+                        ))
+                    }),
             );
 
         let mode_column = h_flex()
@@ -2127,6 +3022,30 @@ impl Render for ProjectSearchBar {
                         }
                     }),
             )
+            .child(
+                IconButton::new("project-search-send-to-quickfix", IconName::ListTree)
+                    .shape(IconButtonShape::Square)
+                    .disabled(search.active_match_index.is_none())
+                    .on_click(cx.listener(|this, _, window, cx| {
+                        if let Some(search) = this.active_project_search.as_ref() {
+                            search.update(cx, |this, cx| {
+                                this.send_to_quickfix(&SendToQuickfix, window, cx);
+                            })
+                        }
+                    }))
+                    .tooltip({
+                        let focus_handle = focus_handle.clone();
+                        move |window, cx| {
+                            Tooltip::for_action_in(
+                                "Send Matches to Quickfix List",
+                                &SendToQuickfix,
+                                &focus_handle,
+                                window,
+                                cx,
+                            )
+                        }
+                    }),
+            )
             .child(
                 div()
                     .id("matches")
@@ -2208,6 +3127,110 @@ impl Render for ProjectSearchBar {
                                     }
                                 }),
                         )
+                        .map(|this| {
+                            match search.replace_preview_counts() {
+                                None => this.child(
+                                    IconButton::new(
+                                        "project-search-preview-replacements",
+                                        IconName::Eye,
+                                    )
+                                    .shape(IconButtonShape::Square)
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        if let Some(search) = this.active_project_search.as_ref() {
+                                            search.update(cx, |this, cx| {
+                                                this.preview_replacements(
+                                                    &PreviewReplacements,
+                                                    window,
+                                                    cx,
+                                                );
+                                            })
+                                        }
+                                    }))
+                                    .tooltip({
+                                        let focus_handle = focus_handle.clone();
+                                        move |window, cx| {
+                                            Tooltip::for_action_in(
+                                                "Preview Replacements",
+                                                &PreviewReplacements,
+                                                &focus_handle,
+                                                window,
+                                                cx,
+                                            )
+                                        }
+                                    }),
+                                ),
+                                Some((accepted, total)) => this
+                                    .child(
+                                        IconButton::new(
+                                            "project-search-apply-replacements",
+                                            IconName::Check,
+                                        )
+                                        .shape(IconButtonShape::Square)
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            if let Some(search) =
+                                                this.active_project_search.as_ref()
+                                            {
+                                                search.update(cx, |this, cx| {
+                                                    this.apply_replacements(
+                                                        &ApplyReplacements,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                })
+                                            }
+                                        }))
+                                        .tooltip({
+                                            let focus_handle = focus_handle.clone();
+                                            move |window, cx| {
+                                                Tooltip::for_action_in(
+                                                    "Apply Accepted Replacements",
+                                                    &ApplyReplacements,
+                                                    &focus_handle,
+                                                    window,
+                                                    cx,
+                                                )
+                                            }
+                                        }),
+                                    )
+                                    .child(
+                                        IconButton::new(
+                                            "project-search-cancel-replacements",
+                                            IconName::Close,
+                                        )
+                                        .shape(IconButtonShape::Square)
+                                        .on_click(cx.listener(|this, _, window, cx| {
+                                            if let Some(search) =
+                                                this.active_project_search.as_ref()
+                                            {
+                                                search.update(cx, |this, cx| {
+                                                    this.cancel_replacements(
+                                                        &CancelReplacements,
+                                                        window,
+                                                        cx,
+                                                    );
+                                                })
+                                            }
+                                        }))
+                                        .tooltip({
+                                            let focus_handle = focus_handle.clone();
+                                            move |window, cx| {
+                                                Tooltip::for_action_in(
+                                                    "Discard Replacements",
+                                                    &CancelReplacements,
+                                                    &focus_handle,
+                                                    window,
+                                                    cx,
+                                                )
+                                            }
+                                        }),
+                                    )
+                                    .child(
+                                        Label::new(format!("{accepted}/{total} accepted"))
+                                            .size(LabelSize::Small)
+                                            .color(Color::Muted),
+                                    ),
+                            }
+                        })
                     });
 
             h_flex()
@@ -2330,6 +3353,27 @@ impl Render for ProjectSearchBar {
                     })
                 }
             }))
+            .on_action(cx.listener(|this, action, window, cx| {
+                if let Some(search) = this.active_project_search.as_ref() {
+                    search.update(cx, |this, cx| {
+                        this.preview_replacements(action, window, cx);
+                    })
+                }
+            }))
+            .on_action(cx.listener(|this, action, window, cx| {
+                if let Some(search) = this.active_project_search.as_ref() {
+                    search.update(cx, |this, cx| {
+                        this.apply_replacements(action, window, cx);
+                    })
+                }
+            }))
+            .on_action(cx.listener(|this, action, window, cx| {
+                if let Some(search) = this.active_project_search.as_ref() {
+                    search.update(cx, |this, cx| {
+                        this.cancel_replacements(action, window, cx);
+                    })
+                }
+            }))
             .when(search.filters_enabled, |this| {
                 this.on_action(cx.listener(|this, _: &ToggleIncludeIgnored, window, cx| {
                     this.toggle_search_option(SearchOptions::INCLUDE_IGNORED, window, cx);
@@ -2443,8 +3487,12 @@ pub mod tests {
 
     use super::*;
     use editor::{DisplayPoint, display_map::DisplayRow};
+    use feature_flags::FeatureFlagAppExt as _;
+    use futures::AsyncReadExt as _;
     use gpui::{Action, TestAppContext, VisualTestContext, WindowHandle};
+    use http_client::{FakeHttpClient, Response};
     use project::FakeFs;
+    use semantic_index::{OllamaEmbeddingModel, OllamaEmbeddingProvider};
     use serde_json::json;
     use settings::SettingsStore;
     use util::path;
@@ -2577,6 +3625,262 @@ pub mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_semantic_search(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+        init_test(cx);
+        cx.update(|cx| {
+            // Indexing embeddings is staff-flagged.
+            cx.update_flags(true, vec![]);
+        });
+
+        // An Ollama server that embeds texts by how much they are about apples and cars.
+        let http_client = FakeHttpClient::create(|request| async move {
+            assert_eq!(request.uri().path(), "/api/embeddings");
+            let mut body = String::new();
+            request.into_body().read_to_string(&mut body).await?;
+            let request: serde_json::Value = serde_json::from_str(&body)?;
+            let prompt = request["prompt"].as_str().unwrap_or_default();
+            let embedding = [
+                prompt.matches("apple").count() as f32,
+                prompt.matches("car").count() as f32,
+                1.,
+            ];
+            Ok(Response::new(
+                json!({ "embedding": embedding }).to_string().into(),
+            ))
+        });
+        let temp_dir = tempfile::tempdir().unwrap();
+        let semantic_db = SemanticDb::new(
+            temp_dir.path().into(),
+            Arc::new(OllamaEmbeddingProvider::new(
+                http_client,
+                OllamaEmbeddingModel::NomicEmbedText,
+            )),
+            &mut cx.to_async(),
+        )
+        .await
+        .unwrap();
+        cx.update(|cx| cx.set_global(semantic_db));
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "apples.md": "apple apple apple",
+                "cars.md": "car car car",
+                "orchard.md": "an apple and a car",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+
+        cx.run_until_parked();
+        let project_index = cx
+            .update(|cx| {
+                cx.update_global::<SemanticDb, _>(|semantic_db, cx| {
+                    semantic_db.project_index(project.clone(), cx)
+                })
+            })
+            .unwrap();
+        while cx.update(|cx| project_index.read(cx).status()) != semantic_index::Status::Idle {
+            cx.run_until_parked();
+        }
+
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+        search_view
+            .update(cx, |search_view, _, cx| {
+                search_view.toggle_search_option(SearchOptions::SEMANTIC, cx);
+            })
+            .unwrap();
+        perform_search(search_view, "apple", cx);
+
+        search_view
+            .update(cx, |search_view, _, cx| {
+                let results_text = search_view
+                    .results_editor
+                    .read(cx)
+                    .buffer()
+                    .read(cx)
+                    .snapshot(cx)
+                    .text();
+                assert_eq!(
+                    results_text, "apple apple apple\nan apple and a car\ncar car car",
+                    "files should be ranked by their similarity to the query"
+                );
+                let scores = search_view
+                    .entity
+                    .read(cx)
+                    .match_scores
+                    .iter()
+                    .map(|score| format!("{score:.2}"))
+                    .collect::<Vec<_>>();
+                assert_eq!(scores, ["0.89", "0.82", "0.22"]);
+                assert_eq!(
+                    search_view.score_blocks.len(),
+                    3,
+                    "each match should show its score"
+                );
+            })
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_replace_preview(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize = one::ONE + one::ONE;",
+                "three.rs": "const THREE: usize = one::ONE + two::TWO;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "ONE", cx);
+        let original_text = search_view
+            .update(cx, |search_view, _, cx| {
+                search_view.results_editor.read(cx).text(cx)
+            })
+            .unwrap();
+
+        search_view
+            .update(cx, |search_view, window, cx| {
+                search_view.replacement_editor.update(cx, |editor, cx| {
+                    editor.set_text("UNO", window, cx);
+                });
+                search_view.preview_replacements(&PreviewReplacements, window, cx);
+                assert_eq!(search_view.replace_preview_counts(), Some((0, 4)));
+                assert_eq!(
+                    search_view.results_editor.read(cx).text(cx),
+                    original_text,
+                    "Previewing should not edit the buffers"
+                );
+
+                let new_lines = search_view
+                    .replace_preview
+                    .as_ref()
+                    .unwrap()
+                    .replacements
+                    .iter()
+                    .map(|replacement| replacement.new_line.to_string())
+                    .collect::<HashSet<_>>();
+                assert_eq!(
+                    new_lines,
+                    HashSet::from_iter([
+                        "const UNO: usize = 1;".to_string(),
+                        "const TWO: usize = one::UNO + one::ONE;".to_string(),
+                        "const TWO: usize = one::ONE + one::UNO;".to_string(),
+                        "const THREE: usize = one::UNO + two::TWO;".to_string(),
+                    ])
+                );
+
+                // Accept everything, then reject the replacements in `two.rs` as a whole.
+                for ix in 0..4 {
+                    search_view.set_replacement_status(ix, ReplacementStatus::Accepted, cx);
+                }
+                let two_rs = search_view
+                    .replace_preview
+                    .as_ref()
+                    .unwrap()
+                    .replacements
+                    .iter()
+                    .find(|replacement| replacement.new_line.starts_with("const TWO"))
+                    .unwrap()
+                    .buffer_id;
+                search_view.set_file_replacements_status(two_rs, ReplacementStatus::Rejected, cx);
+                assert_eq!(search_view.replace_preview_counts(), Some((2, 4)));
+
+                search_view.apply_replacements(&ApplyReplacements, window, cx);
+                assert!(search_view.replace_preview.is_none());
+                let text = search_view.results_editor.read(cx).text(cx);
+                assert!(text.contains("const UNO: usize = 1;"));
+                assert!(text.contains("const THREE: usize = one::UNO + two::TWO;"));
+                assert!(text.contains("const TWO: usize = one::ONE + one::ONE;"));
+
+                search_view.results_editor.update(cx, |editor, cx| {
+                    editor.undo(&editor::actions::Undo, window, cx);
+                });
+                assert_eq!(
+                    search_view.results_editor.read(cx).text(cx),
+                    original_text,
+                    "All applied replacements should be undone at once"
+                );
+            })
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_send_to_quickfix(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.background_executor.clone());
+        fs.insert_tree(
+            path!("/dir"),
+            json!({
+                "one.rs": "const ONE: usize = 1;",
+                "two.rs": "const TWO: usize =\n    one::ONE + one::ONE;",
+            }),
+        )
+        .await;
+        let project = Project::test(fs.clone(), [path!("/dir").as_ref()], cx).await;
+        let window = cx.add_window(|window, cx| Workspace::test_new(project.clone(), window, cx));
+        let workspace = window.root(cx).unwrap();
+        let search = cx.new(|cx| ProjectSearch::new(project.clone(), cx));
+        let search_view = cx.add_window(|window, cx| {
+            ProjectSearchView::new(workspace.downgrade(), search.clone(), window, cx, None)
+        });
+
+        perform_search(search_view, "ONE", cx);
+        search_view
+            .update(cx, |search_view, window, cx| {
+                search_view.send_to_quickfix(&SendToQuickfix, window, cx);
+            })
+            .unwrap();
+
+        workspace.read_with(cx, |workspace, cx| {
+            let quickfix = workspace.quickfix().read(cx);
+            let list = quickfix.current_list().unwrap();
+            assert_eq!(list.title, "Search: ONE");
+            let entries = list
+                .entries
+                .iter()
+                .map(|entry| {
+                    format!(
+                        "{}:{}:{}: {}",
+                        entry.path.path.display(),
+                        entry.row,
+                        entry.column,
+                        entry.text
+                    )
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(
+                entries,
+                [
+                    "one.rs:0:6: const ONE: usize = 1;",
+                    "two.rs:1:9: one::ONE + one::ONE;",
+                    "two.rs:1:20: one::ONE + one::ONE;",
+                ]
+            );
+        });
+    }
+
     #[gpui::test]
     async fn test_deploy_project_search_focus(cx: &mut TestAppContext) {
         init_test(cx);
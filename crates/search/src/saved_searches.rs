@@ -0,0 +1,713 @@
+use crate::{OpenSavedSearches, ProjectSearchView, SearchOptions, persistence};
+use anyhow::{Context as _, Result};
+use fuzzy::StringMatchCandidate;
+use gpui::{
+    App, AsyncApp, Context, DismissEvent, Entity, EventEmitter, FocusHandle, Focusable,
+    ParentElement, Render, SharedString, Styled, Subscription, Task, WeakEntity, Window, rems,
+};
+use picker::{Picker, PickerDelegate};
+use project::{Project, ProjectPath};
+use serde::{Deserialize, Serialize};
+use std::sync::Arc;
+use ui::{HighlightedLabel, IconButtonShape, ListItem, ListItemSpacing, Tooltip, prelude::*};
+use util::ResultExt as _;
+use workspace::{ModalView, Workspace, WorkspaceId, notifications::DetachAndPromptErr};
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(toggle);
+    })
+    .detach();
+}
+
+/// A named project search that can be run again from the saved searches
+/// picker, stored with the workspace or shared in `.zed/searches.json`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SavedSearch {
+    pub name: String,
+    pub query: String,
+    #[serde(default)]
+    pub regex: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+    #[serde(default)]
+    pub whole_word: bool,
+    #[serde(default)]
+    pub include_ignored: bool,
+    #[serde(default)]
+    pub structural: bool,
+    #[serde(default)]
+    pub semantic: bool,
+    /// Comma-separated globs of the files to search.
+    #[serde(default)]
+    pub include: String,
+    /// Comma-separated globs of the files to skip.
+    #[serde(default)]
+    pub exclude: String,
+}
+
+impl SavedSearch {
+    pub fn new(
+        name: String,
+        query: String,
+        options: SearchOptions,
+        include: String,
+        exclude: String,
+    ) -> Self {
+        Self {
+            name,
+            query,
+            regex: options.contains(SearchOptions::REGEX),
+            case_sensitive: options.contains(SearchOptions::CASE_SENSITIVE),
+            whole_word: options.contains(SearchOptions::WHOLE_WORD),
+            include_ignored: options.contains(SearchOptions::INCLUDE_IGNORED),
+            structural: options.contains(SearchOptions::STRUCTURAL),
+            semantic: options.contains(SearchOptions::SEMANTIC),
+            include,
+            exclude,
+        }
+    }
+
+    pub fn options(&self) -> SearchOptions {
+        let mut options = SearchOptions::NONE;
+        options.set(SearchOptions::REGEX, self.regex);
+        options.set(SearchOptions::CASE_SENSITIVE, self.case_sensitive);
+        options.set(SearchOptions::WHOLE_WORD, self.whole_word);
+        options.set(SearchOptions::INCLUDE_IGNORED, self.include_ignored);
+        options.set(SearchOptions::STRUCTURAL, self.structural);
+        options.set(SearchOptions::SEMANTIC, self.semantic);
+        options
+    }
+}
+
+fn toggle(
+    workspace: &mut Workspace,
+    _: &OpenSavedSearches,
+    window: &mut Window,
+    cx: &mut Context<Workspace>,
+) {
+    let current_search = workspace
+        .active_item(cx)
+        .and_then(|item| item.downcast::<ProjectSearchView>())
+        .map(|search_view| search_view.read(cx).saved_search(String::new(), cx))
+        .filter(|search| !search.query.is_empty());
+
+    let project = workspace.project().clone();
+    let shared_files = project
+        .read(cx)
+        .visible_worktrees(cx)
+        .map(|worktree| ProjectPath {
+            worktree_id: worktree.read(cx).id(),
+            path: paths::local_searches_file_relative_path().into(),
+        })
+        .collect::<Vec<_>>();
+    let delegate = SavedSearchesDelegate {
+        workspace: cx.entity().downgrade(),
+        workspace_id: workspace.database_id(),
+        project,
+        searches: Vec::new(),
+        current_search,
+        shared_file: shared_files.first().cloned(),
+        matches: Vec::new(),
+        selected_index: 0,
+    };
+    workspace.toggle_modal(window, cx, |window, cx| {
+        SavedSearches::new(delegate, shared_files, window, cx)
+    });
+}
+
+pub struct SavedSearches {
+    picker: Entity<Picker<SavedSearchesDelegate>>,
+    _subscription: Subscription,
+}
+
+impl SavedSearches {
+    fn new(
+        delegate: SavedSearchesDelegate,
+        shared_files: Vec<ProjectPath>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let project = delegate.project.clone();
+        let workspace_id = delegate.workspace_id;
+        let picker = cx.new(|cx| Picker::uniform_list(delegate, window, cx));
+        let _subscription = cx.subscribe(&picker, |_, _, _, cx| {
+            cx.emit(DismissEvent);
+        });
+
+        cx.spawn_in(window, async move |this, cx| {
+            let stored_searches = match workspace_id {
+                Some(workspace_id) => cx
+                    .background_spawn(
+                        async move { persistence::DB.get_saved_searches(workspace_id) },
+                    )
+                    .await
+                    .log_err()
+                    .unwrap_or_default(),
+                None => Vec::new(),
+            };
+            let mut searches = stored_searches
+                .into_iter()
+                .filter_map(|search| serde_json::from_str::<SavedSearch>(&search).log_err())
+                .map(|search| SavedSearchEntry {
+                    search,
+                    shared_in: None,
+                })
+                .collect::<Vec<_>>();
+            for shared_file in shared_files {
+                let shared_searches = load_shared_searches(&project, shared_file.clone(), cx)
+                    .await
+                    .log_err()
+                    .unwrap_or_default();
+                searches.extend(shared_searches.into_iter().map(|search| SavedSearchEntry {
+                    search,
+                    shared_in: Some(shared_file.clone()),
+                }));
+            }
+            this.update_in(cx, |this, window, cx| {
+                this.picker.update(cx, |picker, cx| {
+                    picker.delegate.searches.extend(searches);
+                    picker.refresh(window, cx);
+                })
+            })
+        })
+        .detach_and_log_err(cx);
+
+        Self {
+            picker,
+            _subscription,
+        }
+    }
+}
+
+impl ModalView for SavedSearches {}
+impl EventEmitter<DismissEvent> for SavedSearches {}
+
+impl Focusable for SavedSearches {
+    fn focus_handle(&self, cx: &App) -> FocusHandle {
+        self.picker.focus_handle(cx)
+    }
+}
+
+impl Render for SavedSearches {
+    fn render(&mut self, _: &mut Window, _: &mut Context<Self>) -> impl IntoElement {
+        v_flex().w(rems(34.)).child(self.picker.clone())
+    }
+}
+
+struct SavedSearchEntry {
+    search: SavedSearch,
+    /// The `.zed/searches.json` file the search comes from, if it is shared
+    /// through the project rather than stored with the workspace.
+    shared_in: Option<ProjectPath>,
+}
+
+enum SavedSearchMatch {
+    Saved {
+        ix: usize,
+        positions: Vec<usize>,
+    },
+    /// Saves the search of the active project search under the given name.
+    Save {
+        name: String,
+    },
+}
+
+pub struct SavedSearchesDelegate {
+    workspace: WeakEntity<Workspace>,
+    workspace_id: Option<WorkspaceId>,
+    project: Entity<Project>,
+    searches: Vec<SavedSearchEntry>,
+    current_search: Option<SavedSearch>,
+    /// Where searches are shared when saved with the secondary confirm.
+    shared_file: Option<ProjectPath>,
+    matches: Vec<SavedSearchMatch>,
+    selected_index: usize,
+}
+
+impl SavedSearchesDelegate {
+    fn save(
+        &mut self,
+        name: String,
+        share: bool,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) {
+        let Some(current_search) = self.current_search.clone() else {
+            return;
+        };
+        let search = SavedSearch {
+            name,
+            ..current_search
+        };
+        match (share, self.shared_file.clone(), self.workspace_id) {
+            (true, Some(shared_file), _) => {
+                let project = self.project.clone();
+                cx.spawn(async move |_, cx| share_search(&project, shared_file, search, cx).await)
+                    .detach_and_prompt_err("Failed to share search", window, cx, |error, _, _| {
+                        Some(error.to_string())
+                    });
+            }
+            (_, _, Some(workspace_id)) => {
+                let Some(serialized) = serde_json::to_string(&search).log_err() else {
+                    return;
+                };
+                cx.background_spawn(async move {
+                    persistence::DB
+                        .save_saved_search(workspace_id, search.name, serialized)
+                        .await
+                })
+                .detach_and_log_err(cx);
+            }
+            _ => {}
+        }
+        cx.emit(DismissEvent);
+    }
+
+    fn delete(&mut self, ix: usize, cx: &mut Context<Picker<Self>>) {
+        if ix >= self.searches.len() {
+            return;
+        }
+        let entry = self.searches.remove(ix);
+        // Keep the matches pointing at the right searches until they are
+        // recomputed, as that happens in the background.
+        self.matches.retain_mut(|search_match| match search_match {
+            SavedSearchMatch::Saved { ix: match_ix, .. } if *match_ix == ix => false,
+            SavedSearchMatch::Saved { ix: match_ix, .. } => {
+                if *match_ix > ix {
+                    *match_ix -= 1;
+                }
+                true
+            }
+            SavedSearchMatch::Save { .. } => true,
+        });
+        self.selected_index = self
+            .selected_index
+            .min(self.matches.len().saturating_sub(1));
+
+        let Some(workspace_id) = self.workspace_id else {
+            return;
+        };
+        cx.background_spawn(async move {
+            persistence::DB
+                .delete_saved_search(workspace_id, entry.search.name)
+                .await
+        })
+        .detach_and_log_err(cx);
+    }
+}
+
+impl PickerDelegate for SavedSearchesDelegate {
+    type ListItem = ListItem;
+
+    fn placeholder_text(&self, _window: &mut Window, _cx: &mut App) -> Arc<str> {
+        if self.current_search.is_some() {
+            "Run a saved search or name the current one…".into()
+        } else {
+            "Run a saved search…".into()
+        }
+    }
+
+    fn match_count(&self) -> usize {
+        self.matches.len()
+    }
+
+    fn selected_index(&self) -> usize {
+        self.selected_index
+    }
+
+    fn set_selected_index(
+        &mut self,
+        ix: usize,
+        _window: &mut Window,
+        _: &mut Context<Picker<Self>>,
+    ) {
+        self.selected_index = ix;
+    }
+
+    fn update_matches(
+        &mut self,
+        query: String,
+        window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Task<()> {
+        let candidates = self
+            .searches
+            .iter()
+            .enumerate()
+            .map(|(ix, entry)| StringMatchCandidate::new(ix, &entry.search.name))
+            .collect::<Vec<_>>();
+        cx.spawn_in(window, async move |picker, cx| {
+            let mut matches = if query.is_empty() {
+                candidates
+                    .iter()
+                    .map(|candidate| SavedSearchMatch::Saved {
+                        ix: candidate.id,
+                        positions: Vec::new(),
+                    })
+                    .collect::<Vec<_>>()
+            } else {
+                fuzzy::match_strings(
+                    &candidates,
+                    &query,
+                    true,
+                    true,
+                    100,
+                    &Default::default(),
+                    cx.background_executor().clone(),
+                )
+                .await
+                .into_iter()
+                .map(|candidate| SavedSearchMatch::Saved {
+                    ix: candidate.candidate_id,
+                    positions: candidate.positions,
+                })
+                .collect()
+            };
+            picker
+                .update(cx, |picker, _| {
+                    let delegate = &mut picker.delegate;
+                    let name = query.trim();
+                    if delegate.current_search.is_some() && !name.is_empty() {
+                        matches.push(SavedSearchMatch::Save {
+                            name: name.to_string(),
+                        });
+                    }
+                    delegate.matches = matches;
+                    delegate.selected_index = delegate
+                        .selected_index
+                        .min(delegate.matches.len().saturating_sub(1));
+                })
+                .log_err();
+        })
+    }
+
+    fn confirm(&mut self, secondary: bool, window: &mut Window, cx: &mut Context<Picker<Self>>) {
+        match self.matches.get(self.selected_index) {
+            Some(SavedSearchMatch::Saved { ix, .. }) => {
+                let Some(entry) = self.searches.get(*ix) else {
+                    return;
+                };
+                let search = entry.search.clone();
+                self.workspace
+                    .update(cx, |workspace, cx| {
+                        ProjectSearchView::run_saved_search(workspace, &search, window, cx);
+                    })
+                    .log_err();
+                cx.emit(DismissEvent);
+            }
+            Some(SavedSearchMatch::Save { name }) => {
+                let name = name.clone();
+                self.save(name, secondary, window, cx);
+            }
+            None => {}
+        }
+    }
+
+    fn dismissed(&mut self, _: &mut Window, cx: &mut Context<Picker<Self>>) {
+        cx.emit(DismissEvent);
+    }
+
+    fn render_match(
+        &self,
+        ix: usize,
+        selected: bool,
+        _window: &mut Window,
+        cx: &mut Context<Picker<Self>>,
+    ) -> Option<Self::ListItem> {
+        let item = ListItem::new(SharedString::from(format!("saved-search-{ix}")))
+            .inset(true)
+            .spacing(ListItemSpacing::Sparse)
+            .toggle_state(selected);
+        match self.matches.get(ix)? {
+            SavedSearchMatch::Saved {
+                ix: search_ix,
+                positions,
+            } => {
+                let search_ix = *search_ix;
+                let entry = self.searches.get(search_ix)?;
+                Some(
+                    item.child(
+                        v_flex()
+                            .child(HighlightedLabel::new(
+                                entry.search.name.clone(),
+                                positions.clone(),
+                            ))
+                            .child(
+                                Label::new(entry.search.query.clone())
+                                    .size(LabelSize::Small)
+                                    .color(Color::Muted)
+                                    .truncate(),
+                            ),
+                    )
+                    .end_slot(if entry.shared_in.is_some() {
+                        Label::new("Shared")
+                            .size(LabelSize::Small)
+                            .color(Color::Muted)
+                            .into_any_element()
+                    } else {
+                        IconButton::new(("delete-saved-search", search_ix), IconName::Trash)
+                            .shape(IconButtonShape::Square)
+                            .icon_size(IconSize::Small)
+                            .tooltip(Tooltip::text("Delete Saved Search"))
+                            .on_click(cx.listener(move |picker, _, window, cx| {
+                                cx.stop_propagation();
+                                picker.delegate.delete(search_ix, cx);
+                                picker.refresh(window, cx);
+                            }))
+                            .into_any_element()
+                    }),
+                )
+            }
+            SavedSearchMatch::Save { name } => Some(
+                item.child(
+                    v_flex()
+                        .child(Label::new(format!("Save current search as \"{name}\"…")))
+                        .when(self.shared_file.is_some(), |this| {
+                            this.child(
+                                Label::new(
+                                    "Use secondary confirm to share it in .zed/searches.json",
+                                )
+                                .size(LabelSize::Small)
+                                .color(Color::Muted),
+                            )
+                        }),
+                ),
+            ),
+        }
+    }
+
+    fn no_matches_text(&self, _window: &mut Window, _cx: &mut App) -> Option<SharedString> {
+        Some("No saved searches".into())
+    }
+}
+
+/// Loads the searches shared in the given `.zed/searches.json`. It is read
+/// through the project, so that searches are shared in remote projects too.
+async fn load_shared_searches(
+    project: &Entity<Project>,
+    path: ProjectPath,
+    cx: &mut AsyncApp,
+) -> Result<Vec<SavedSearch>> {
+    let buffer = project
+        .update(cx, |project, cx| project.open_buffer(path, cx))?
+        .await?;
+    let contents = buffer.read_with(cx, |buffer, _| buffer.text())?;
+    if contents.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+    settings::parse_json_with_comments(&contents)
+}
+
+/// Adds the search to the given `.zed/searches.json`, replacing any search
+/// with the same name. The file is edited in place, keeping its comments and
+/// the formatting of the other searches.
+async fn share_search(
+    project: &Entity<Project>,
+    path: ProjectPath,
+    search: SavedSearch,
+    cx: &mut AsyncApp,
+) -> Result<()> {
+    const TAB_SIZE: usize = 2;
+
+    let buffer = project
+        .update(cx, |project, cx| project.open_buffer(path.clone(), cx))?
+        .await?;
+    buffer.update(cx, |buffer, cx| {
+        let contents = buffer.text();
+        let searches: Vec<SavedSearch> = if contents.trim().is_empty() {
+            Vec::new()
+        } else {
+            settings::parse_json_with_comments(&contents)?
+        };
+        let value = serde_json::to_value(&search)?;
+        let edit = match searches
+            .iter()
+            .position(|existing| existing.name == search.name)
+        {
+            Some(ix) => settings::replace_top_level_array_value_in_json_text(
+                &contents, ix, &value, TAB_SIZE,
+            ),
+            None => {
+                settings::append_top_level_array_value_in_json_text(&contents, &value, TAB_SIZE)
+            }
+        };
+        let (range, replacement) =
+            edit.with_context(|| format!("{:?} does not contain a list of searches", path.path))?;
+        buffer.edit([(range, replacement)], None, cx);
+        anyhow::Ok(())
+    })??;
+    project
+        .update(cx, |project, cx| project.save_buffer(buffer, cx))?
+        .await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use gpui::TestAppContext;
+    use project::{FakeFs, Fs as _};
+    use serde_json::json;
+    use settings::SettingsStore;
+    use std::path::Path;
+
+    fn init_test(cx: &mut TestAppContext) {
+        cx.update(|cx| {
+            let settings = SettingsStore::test(cx);
+            cx.set_global(settings);
+            theme::init(theme::LoadThemes::JustBase, cx);
+            language::init(cx);
+            editor::init(cx);
+            Project::init_settings(cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_delete_then_confirm(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        let project = Project::test(fs, [], cx).await;
+        let searches = ["todos", "unwraps", "fixmes"]
+            .into_iter()
+            .map(|name| SavedSearchEntry {
+                search: SavedSearch {
+                    name: name.into(),
+                    query: name.to_uppercase(),
+                    ..Default::default()
+                },
+                shared_in: None,
+            })
+            .collect();
+        let delegate = SavedSearchesDelegate {
+            workspace: WeakEntity::new_invalid(),
+            workspace_id: None,
+            project,
+            searches,
+            current_search: None,
+            shared_file: None,
+            matches: Vec::new(),
+            selected_index: 0,
+        };
+        let (picker, cx) =
+            cx.add_window_view(|window, cx| Picker::uniform_list(delegate, window, cx));
+        cx.run_until_parked();
+
+        picker.update_in(cx, |picker, window, cx| {
+            picker.delegate.set_selected_index(2, window, cx);
+            picker.delegate.delete(1, cx);
+
+            let delegate = &picker.delegate;
+            let names = delegate
+                .matches
+                .iter()
+                .filter_map(|search_match| match search_match {
+                    SavedSearchMatch::Saved { ix, .. } => {
+                        Some(delegate.searches[*ix].search.name.as_str())
+                    }
+                    SavedSearchMatch::Save { .. } => None,
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(names, ["todos", "fixmes"]);
+            assert_eq!(delegate.selected_index, 1);
+
+            // Confirming before the matches are recomputed runs the search
+            // that is still selected.
+            picker.delegate.confirm(false, window, cx);
+        });
+    }
+
+    #[gpui::test]
+    async fn test_share_search(cx: &mut TestAppContext) {
+        init_test(cx);
+
+        let fs = FakeFs::new(cx.executor());
+        fs.insert_tree(
+            "/project",
+            json!({
+                ".zed": {
+                    "searches.json": r#"[
+                        // Shared with the team
+                        {"name": "todos", "query": "TODO"}
+                    ]"#
+                }
+            }),
+        )
+        .await;
+        fs.insert_tree("/other", json!({})).await;
+        let project =
+            Project::test(fs.clone(), [Path::new("/project"), Path::new("/other")], cx).await;
+        let (path, other_path) = project.read_with(cx, |project, cx| {
+            let mut paths = project.visible_worktrees(cx).map(|worktree| ProjectPath {
+                worktree_id: worktree.read(cx).id(),
+                path: paths::local_searches_file_relative_path().into(),
+            });
+            (paths.next().unwrap(), paths.next().unwrap())
+        });
+        let cx = &mut cx.to_async();
+
+        let searches = load_shared_searches(&project, path.clone(), cx)
+            .await
+            .unwrap();
+        assert_eq!(
+            searches,
+            [SavedSearch {
+                name: "todos".into(),
+                query: "TODO".into(),
+                ..Default::default()
+            }]
+        );
+
+        let todos = SavedSearch::new(
+            "todos".into(),
+            "TODO|FIXME".into(),
+            SearchOptions::REGEX | SearchOptions::CASE_SENSITIVE,
+            "*.rs".into(),
+            String::new(),
+        );
+        let unwraps = SavedSearch::new(
+            "unwraps".into(),
+            "$X.unwrap()".into(),
+            SearchOptions::STRUCTURAL,
+            String::new(),
+            "tests/".into(),
+        );
+        share_search(&project, path.clone(), todos.clone(), cx)
+            .await
+            .unwrap();
+        share_search(&project, path.clone(), unwraps.clone(), cx)
+            .await
+            .unwrap();
+        assert_eq!(
+            load_shared_searches(&project, path, cx).await.unwrap(),
+            [todos.clone(), unwraps]
+        );
+        assert_eq!(
+            todos.options(),
+            SearchOptions::REGEX | SearchOptions::CASE_SENSITIVE
+        );
+        assert!(
+            fs.load(Path::new("/project/.zed/searches.json"))
+                .await
+                .unwrap()
+                .contains("// Shared with the team")
+        );
+
+        assert!(
+            load_shared_searches(&project, other_path.clone(), cx)
+                .await
+                .unwrap()
+                .is_empty()
+        );
+        share_search(&project, other_path.clone(), todos.clone(), cx)
+            .await
+            .unwrap();
+        assert_eq!(
+            load_shared_searches(&project, other_path, cx)
+                .await
+                .unwrap(),
+            [todos.clone()]
+        );
+        assert!(fs.is_file(Path::new("/other/.zed/searches.json")).await);
+    }
+}
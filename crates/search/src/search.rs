@@ -10,7 +10,9 @@ use workspace::notifications::NotificationId;
 use workspace::{Toast, Workspace};
 
 pub mod buffer_search;
+mod persistence;
 pub mod project_search;
+pub mod saved_searches;
 pub(crate) mod search_bar;
 pub mod search_status_button;
 
@@ -18,6 +20,7 @@ pub fn init(cx: &mut App) {
     menu::init();
     buffer_search::init(cx);
     project_search::init(cx);
+    saved_searches::init(cx);
 }
 
 actions!(
@@ -28,6 +31,8 @@ actions!(
         ToggleCaseSensitive,
         ToggleIncludeIgnored,
         ToggleRegex,
+        ToggleStructural,
+        ToggleSemantic,
         ToggleReplace,
         ToggleSelection,
         SelectNextMatch,
@@ -37,6 +42,7 @@ actions!(
         PreviousHistoryQuery,
         ReplaceAll,
         ReplaceNext,
+        OpenSavedSearches,
     ]
 );
 
@@ -49,6 +55,10 @@ bitflags! {
         const INCLUDE_IGNORED = 0b100;
         const REGEX = 0b1000;
         const ONE_MATCH_PER_LINE = 0b100000;
+        /// Match syntax nodes against a code pattern with metavariables
+        const STRUCTURAL = 0b1000000;
+        /// Rank chunks of the project by their similarity to a natural-language query
+        const SEMANTIC = 0b10000000;
         /// If set, reverse direction when finding the active match
         const BACKWARDS = 0b10000;
     }
@@ -61,6 +71,8 @@ impl SearchOptions {
             SearchOptions::CASE_SENSITIVE => "Match Case Sensitively",
             SearchOptions::INCLUDE_IGNORED => "Also search files ignored by configuration",
             SearchOptions::REGEX => "Use Regular Expressions",
+            SearchOptions::STRUCTURAL => "Match Syntax Structure",
+            SearchOptions::SEMANTIC => "Search by Meaning",
             _ => panic!("{:?} is not a named SearchOption", self),
         }
     }
@@ -71,6 +83,8 @@ impl SearchOptions {
             SearchOptions::CASE_SENSITIVE => ui::IconName::CaseSensitive,
             SearchOptions::INCLUDE_IGNORED => ui::IconName::Sliders,
             SearchOptions::REGEX => ui::IconName::Regex,
+            SearchOptions::STRUCTURAL => ui::IconName::Code,
+            SearchOptions::SEMANTIC => ui::IconName::Sparkle,
             _ => panic!("{:?} is not a named SearchOption", self),
         }
     }
@@ -81,6 +95,8 @@ impl SearchOptions {
             SearchOptions::CASE_SENSITIVE => Box::new(ToggleCaseSensitive),
             SearchOptions::INCLUDE_IGNORED => Box::new(ToggleIncludeIgnored),
             SearchOptions::REGEX => Box::new(ToggleRegex),
+            SearchOptions::STRUCTURAL => Box::new(ToggleStructural),
+            SearchOptions::SEMANTIC => Box::new(ToggleSemantic),
             _ => panic!("{:?} is not a named SearchOption", self),
         }
     }
@@ -95,6 +111,7 @@ impl SearchOptions {
         options.set(SearchOptions::CASE_SENSITIVE, query.case_sensitive());
         options.set(SearchOptions::INCLUDE_IGNORED, query.include_ignored());
         options.set(SearchOptions::REGEX, query.is_regex());
+        options.set(SearchOptions::STRUCTURAL, query.is_structural());
         options
     }
 
@@ -107,6 +124,22 @@ impl SearchOptions {
         options
     }
 
+    /// The options picking how the query is interpreted, at most one of which
+    /// can be enabled.
+    const MODES: SearchOptions = SearchOptions::REGEX
+        .union(SearchOptions::STRUCTURAL)
+        .union(SearchOptions::SEMANTIC);
+
+    /// Toggles `option`, turning off the other search modes when it enables
+    /// one, as they cannot be combined.
+    pub fn toggle_option(&mut self, option: SearchOptions) {
+        self.toggle(option);
+        let mode = option.intersection(SearchOptions::MODES);
+        if !mode.is_empty() && self.contains(mode) {
+            self.remove(SearchOptions::MODES.difference(mode));
+        }
+    }
+
     pub fn as_button<Action: Fn(&gpui::ClickEvent, &mut Window, &mut App) + 'static>(
         &self,
         active: bool,
@@ -6,14 +6,26 @@ use std::sync::Arc;
 
 use crate::{Embedding, EmbeddingProvider, TextToEmbed};
 
+pub const OLLAMA_API_URL: &str = "http://localhost:11434";
+
 pub enum OllamaEmbeddingModel {
     NomicEmbedText,
     MxbaiEmbedLarge,
 }
 
+impl OllamaEmbeddingModel {
+    pub fn name(&self) -> &'static str {
+        match self {
+            OllamaEmbeddingModel::NomicEmbedText => "nomic-embed-text",
+            OllamaEmbeddingModel::MxbaiEmbedLarge => "mxbai-embed-large",
+        }
+    }
+}
+
 pub struct OllamaEmbeddingProvider {
     client: Arc<dyn HttpClient>,
-    model: OllamaEmbeddingModel,
+    model: String,
+    api_url: String,
 }
 
 #[derive(Serialize)]
@@ -29,31 +41,38 @@ struct OllamaEmbeddingResponse {
 
 impl OllamaEmbeddingProvider {
     pub fn new(client: Arc<dyn HttpClient>, model: OllamaEmbeddingModel) -> Self {
-        Self { client, model }
+        Self::with_model_name(client, model.name(), OLLAMA_API_URL)
+    }
+
+    /// Creates a provider for any embedding model served by an Ollama-compatible
+    /// server at `api_url`.
+    pub fn with_model_name(
+        client: Arc<dyn HttpClient>,
+        model: impl Into<String>,
+        api_url: impl Into<String>,
+    ) -> Self {
+        Self {
+            client,
+            model: model.into(),
+            api_url: api_url.into(),
+        }
     }
 }
 
 impl EmbeddingProvider for OllamaEmbeddingProvider {
     fn embed<'a>(&'a self, texts: &'a [TextToEmbed<'a>]) -> BoxFuture<'a, Result<Vec<Embedding>>> {
-        //
-        let model = match self.model {
-            OllamaEmbeddingModel::NomicEmbedText => "nomic-embed-text",
-            OllamaEmbeddingModel::MxbaiEmbedLarge => "mxbai-embed-large",
-        };
-
+        let url = format!("{}/api/embeddings", self.api_url.trim_end_matches('/'));
         futures::future::try_join_all(texts.iter().map(|to_embed| {
             let request = OllamaEmbeddingRequest {
-                model: model.to_string(),
+                model: self.model.clone(),
                 prompt: to_embed.text.to_string(),
             };
 
             let request = serde_json::to_string(&request).unwrap();
+            let url = url.clone();
 
-            async {
-                let response = self
-                    .client
-                    .post_json("http://localhost:11434/api/embeddings", request.into())
-                    .await?;
+            async move {
+                let response = self.client.post_json(&url, request.into()).await?;
 
                 let mut body = String::new();
                 response.into_body().read_to_string(&mut body).await?;
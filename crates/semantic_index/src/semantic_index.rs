@@ -4,6 +4,7 @@ mod embedding_index;
 mod indexing;
 mod project_index;
 mod project_index_debug_view;
+mod semantic_index_settings;
 mod summary_backlog;
 mod summary_index;
 mod worktree_index;
@@ -11,9 +12,13 @@ mod worktree_index;
 use anyhow::{Context as _, Result};
 use collections::HashMap;
 use fs::Fs;
-use gpui::{App, AppContext as _, AsyncApp, BorrowAppContext, Context, Entity, Global, WeakEntity};
+use gpui::{
+    App, AppContext as _, AsyncApp, BorrowAppContext, Context, Entity, Global, Task, WeakEntity,
+};
+use http_client::HttpClient;
 use language::LineEnding;
 use project::{Project, Worktree};
+use settings::{Settings as _, SettingsStore};
 use std::{
     cmp::Ordering,
     path::{Path, PathBuf},
@@ -25,8 +30,75 @@ use workspace::Workspace;
 pub use embedding::*;
 pub use project_index::{LoadedSearchResult, ProjectIndex, SearchResult, Status};
 pub use project_index_debug_view::ProjectIndexDebugView;
+pub use semantic_index_settings::{EmbeddingProviderSettings, SemanticIndexSettings};
 pub use summary_index::FileSummary;
 
+/// Registers the semantic index settings, and keeps the [`SemanticDb`] global
+/// around while they enable it.
+pub fn init(http_client: Arc<dyn HttpClient>, cx: &mut App) {
+    SemanticIndexSettings::register(cx);
+    observe_settings(paths::embeddings_dir().clone(), http_client, cx);
+}
+
+/// The settings the [`SemanticDb`] global was last updated for.
+#[derive(Default)]
+struct SemanticDbState {
+    settings: Option<SemanticIndexSettings>,
+    /// Opens the database for the current settings. It is replaced when the
+    /// settings change, which cancels it, so a database opened for outdated
+    /// settings never becomes the global.
+    open_task: Option<Task<()>>,
+}
+
+fn observe_settings(embeddings_dir: PathBuf, http_client: Arc<dyn HttpClient>, cx: &mut App) {
+    let mut state = SemanticDbState::default();
+    update_semantic_db(&mut state, &embeddings_dir, http_client.clone(), cx);
+    cx.observe_global::<SettingsStore>(move |cx| {
+        update_semantic_db(&mut state, &embeddings_dir, http_client.clone(), cx);
+    })
+    .detach();
+}
+
+fn update_semantic_db(
+    state: &mut SemanticDbState,
+    embeddings_dir: &Path,
+    http_client: Arc<dyn HttpClient>,
+    cx: &mut App,
+) {
+    let settings = SemanticIndexSettings::get_global(cx).clone();
+    if state.settings.as_ref() == Some(&settings) {
+        return;
+    }
+    state.settings = Some(settings.clone());
+    state.open_task = None;
+
+    if cx.has_global::<SemanticDb>() {
+        cx.remove_global::<SemanticDb>();
+    }
+    if !settings.enabled {
+        return;
+    }
+
+    let EmbeddingProviderSettings::Ollama { api_url, model } = settings.embedding_provider;
+    // Embeddings of different models cannot be compared, so each gets its own database.
+    let db_name = format!("ollama-{model}").replace(|c: char| !c.is_alphanumeric(), "-");
+    let db_path = embeddings_dir.join(db_name);
+    let embedding_provider = Arc::new(OllamaEmbeddingProvider::with_model_name(
+        http_client,
+        model,
+        api_url,
+    ));
+    state.open_task = Some(cx.spawn(async move |cx| {
+        let Some(semantic_db) = SemanticDb::new(db_path, embedding_provider, cx)
+            .await
+            .log_err()
+        else {
+            return;
+        };
+        cx.update(|cx| cx.set_global(semantic_db)).log_err();
+    }));
+}
+
 pub struct SemanticDb {
     embedding_provider: Arc<dyn EmbeddingProvider>,
     db_connection: Option<heed::Env>,
@@ -270,6 +342,7 @@ mod tests {
     use fs::FakeFs;
     use futures::{FutureExt, future::BoxFuture};
     use gpui::TestAppContext;
+    use http_client::FakeHttpClient;
     use indexing::IndexingEntrySet;
     use language::language_settings::AllLanguageSettings;
     use project::{Project, ProjectEntryId};
@@ -440,6 +513,51 @@ mod tests {
         assert!(content.contains("garbage in, garbage out"));
     }
 
+    #[gpui::test]
+    async fn test_enable_and_disable(cx: &mut TestAppContext) {
+        cx.executor().allow_parking();
+        init_test(cx);
+
+        let temp_dir = tempfile::tempdir().unwrap();
+        cx.update(|cx| {
+            SemanticIndexSettings::register(cx);
+            observe_settings(
+                temp_dir.path().into(),
+                FakeHttpClient::with_404_response(),
+                cx,
+            );
+        });
+        let set_enabled = |enabled: bool, cx: &mut TestAppContext| {
+            cx.update(|cx| {
+                SettingsStore::update(cx, |store, cx| {
+                    store.update_user_settings::<SemanticIndexSettings>(cx, |settings| {
+                        settings.enabled = Some(enabled);
+                    });
+                });
+            });
+        };
+        let has_semantic_db =
+            |cx: &mut TestAppContext| cx.update(|cx| cx.has_global::<SemanticDb>());
+
+        assert!(!has_semantic_db(cx));
+        set_enabled(true, cx);
+        cx.run_until_parked();
+        assert!(has_semantic_db(cx));
+
+        set_enabled(false, cx);
+        assert!(!has_semantic_db(cx));
+
+        // Disabling while the database is being opened keeps it from being used.
+        set_enabled(true, cx);
+        set_enabled(false, cx);
+        cx.run_until_parked();
+        assert!(!has_semantic_db(cx));
+
+        set_enabled(true, cx);
+        cx.run_until_parked();
+        assert!(has_semantic_db(cx));
+    }
+
     #[gpui::test]
     async fn test_embed_files(cx: &mut TestAppContext) {
         cx.executor().allow_parking();
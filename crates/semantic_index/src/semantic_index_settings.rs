@@ -0,0 +1,51 @@
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings, SettingsSources};
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+pub struct SemanticIndexSettings {
+    pub enabled: bool,
+    pub embedding_provider: EmbeddingProviderSettings,
+}
+
+/// The service used to embed the chunks of the project and the search queries.
+#[derive(Serialize, Deserialize, JsonSchema, Debug, Clone, PartialEq)]
+#[serde(tag = "name", rename_all = "snake_case")]
+pub enum EmbeddingProviderSettings {
+    /// A local server speaking the Ollama API, which keeps indexing and
+    /// searching offline.
+    Ollama {
+        /// The URL of the server.
+        api_url: String,
+        /// The embedding model to use, which must be pulled on the server.
+        model: String,
+    },
+}
+
+#[derive(Clone, Default, Serialize, Deserialize, JsonSchema, Debug)]
+pub struct SemanticIndexSettingsContent {
+    /// Whether to index the projects' files, enabling semantic search in the
+    /// project search.
+    ///
+    /// Default: false
+    pub enabled: Option<bool>,
+    /// The service used to compute embeddings.
+    ///
+    /// Default: {"name": "ollama", "api_url": "http://localhost:11434", "model": "nomic-embed-text"}
+    pub embedding_provider: Option<EmbeddingProviderSettings>,
+}
+
+impl Settings for SemanticIndexSettings {
+    const KEY: Option<&'static str> = Some("semantic_index");
+
+    type FileContent = SemanticIndexSettingsContent;
+
+    fn load(
+        sources: SettingsSources<Self::FileContent>,
+        _: &mut gpui::App,
+    ) -> anyhow::Result<Self> {
+        sources.json_merge()
+    }
+
+    fn import_from_vscode(_vscode: &settings::VsCodeSettings, _current: &mut Self::FileContent) {}
+}
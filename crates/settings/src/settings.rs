@@ -21,7 +21,8 @@ pub use keymap_file::{
 pub use settings_file::*;
 pub use settings_store::{
     InvalidSettingsError, LocalSettingsKind, Settings, SettingsLocation, SettingsSources,
-    SettingsStore, parse_json_with_comments,
+    SettingsStore, append_top_level_array_value_in_json_text, parse_json_with_comments,
+    replace_top_level_array_value_in_json_text,
 };
 pub use vscode_import::{VsCodeSettings, VsCodeSettingsSource};
 
@@ -135,3 +135,13 @@ pub fn update_settings_file<T: Settings>(
 ) {
     SettingsStore::global(cx).update_settings_file::<T>(fs, update);
 }
+
+pub fn update_settings_value(
+    fs: Arc<dyn Fs>,
+    cx: &App,
+    settings_path: PathBuf,
+    key_path: Vec<String>,
+    value: Option<serde_json::Value>,
+) {
+    SettingsStore::global(cx).update_settings_value(fs, settings_path, key_path, value);
+}
@@ -418,6 +418,18 @@ impl SettingsStore {
         self.raw_global_settings.as_ref()
     }
 
+    /// Access the raw JSON value of the default settings.
+    pub fn raw_default_settings(&self) -> &Value {
+        &self.raw_default_settings
+    }
+
+    /// Access the raw JSON value of the local settings defined in the given
+    /// directory of a worktree.
+    pub fn raw_local_settings(&self, root_id: WorktreeId, directory_path: &Path) -> Option<&Value> {
+        self.raw_local_settings
+            .get(&(root_id, Arc::from(directory_path)))
+    }
+
     #[cfg(any(test, feature = "test-support"))]
     pub fn test(cx: &mut App) -> Self {
         let mut this = Self::new(cx);
@@ -478,31 +490,15 @@ impl SettingsStore {
         self.setting_file_updates_tx
             .unbounded_send(Box::new(move |cx: AsyncApp| {
                 async move {
-                    let old_text = Self::load_settings(&fs).await?;
-                    let new_text = cx.read_global(|store: &SettingsStore, cx| {
-                        store.new_text_for_update::<T>(old_text, |content| update(content, cx))
-                    })?;
-                    let settings_path = paths::settings_file().as_path();
-                    if fs.is_file(settings_path).await {
-                        let resolved_path =
-                            fs.canonicalize(settings_path).await.with_context(|| {
-                                format!("Failed to canonicalize settings path {:?}", settings_path)
-                            })?;
-
-                        fs.atomic_write(resolved_path.clone(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", resolved_path)
-                            })?;
-                    } else {
-                        fs.atomic_write(settings_path.to_path_buf(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", settings_path)
-                            })?;
-                    }
-
-                    anyhow::Ok(())
+                    Self::update_settings_file_text(
+                        &fs,
+                        paths::settings_file(),
+                        &cx,
+                        |store, old_text, cx| {
+                            store.new_text_for_update::<T>(old_text, |content| update(content, cx))
+                        },
+                    )
+                    .await
                 }
                 .boxed_local()
             }))
@@ -513,37 +509,127 @@ impl SettingsStore {
         self.setting_file_updates_tx
             .unbounded_send(Box::new(move |cx: AsyncApp| {
                 async move {
-                    let old_text = Self::load_settings(&fs).await?;
-                    let new_text = cx.read_global(|store: &SettingsStore, _cx| {
-                        store.get_vscode_edits(old_text, &vscode_settings)
-                    })?;
-                    let settings_path = paths::settings_file().as_path();
-                    if fs.is_file(settings_path).await {
-                        let resolved_path =
-                            fs.canonicalize(settings_path).await.with_context(|| {
-                                format!("Failed to canonicalize settings path {:?}", settings_path)
-                            })?;
-
-                        fs.atomic_write(resolved_path.clone(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", resolved_path)
-                            })?;
-                    } else {
-                        fs.atomic_write(settings_path.to_path_buf(), new_text)
-                            .await
-                            .with_context(|| {
-                                format!("Failed to write settings to file {:?}", settings_path)
-                            })?;
-                    }
+                    Self::update_settings_file_text(
+                        &fs,
+                        paths::settings_file(),
+                        &cx,
+                        |store, old_text, _cx| store.get_vscode_edits(old_text, &vscode_settings),
+                    )
+                    .await
+                }
+                .boxed_local()
+            }))
+            .ok();
+    }
 
-                    anyhow::Ok(())
+    /// Sets the value at the given path of keys in a settings file, or removes
+    /// it when `value` is `None`.
+    ///
+    /// Unlike [`Self::update_settings_file`], this doesn't need the type of the
+    /// setting, so any key of the settings schema can be written, and it can
+    /// write to local settings files as well as to the user's settings file.
+    pub fn update_settings_value(
+        &self,
+        fs: Arc<dyn Fs>,
+        settings_path: PathBuf,
+        key_path: Vec<String>,
+        value: Option<Value>,
+    ) {
+        self.setting_file_updates_tx
+            .unbounded_send(Box::new(move |cx: AsyncApp| {
+                async move {
+                    Self::update_settings_file_text(
+                        &fs,
+                        &settings_path,
+                        &cx,
+                        |store, old_text, _cx| {
+                            let key_path = key_path.iter().map(String::as_str).collect::<Vec<_>>();
+                            store.new_text_for_value_update(old_text, &key_path, value.as_ref())
+                        },
+                    )
+                    .await
                 }
                 .boxed_local()
             }))
             .ok();
     }
 
+    /// Replaces the text of a settings file with the text returned by `update`
+    /// for its current text. A missing file is created, and when the file is
+    /// a symlink, the file it points to is written.
+    async fn update_settings_file_text(
+        fs: &Arc<dyn Fs>,
+        settings_path: &Path,
+        cx: &AsyncApp,
+        update: impl FnOnce(&SettingsStore, String, &App) -> String,
+    ) -> Result<()> {
+        let is_file = fs.is_file(settings_path).await;
+        let old_text = if settings_path == paths::settings_file().as_path() {
+            Self::load_settings(fs).await?
+        } else if is_file {
+            fs.load(settings_path).await?
+        } else {
+            String::new()
+        };
+        let new_text = cx.read_global(|store: &SettingsStore, cx| update(store, old_text, cx))?;
+
+        let resolved_path = if is_file {
+            fs.canonicalize(settings_path).await.with_context(|| {
+                format!("Failed to canonicalize settings path {:?}", settings_path)
+            })?
+        } else {
+            if let Some(parent) = settings_path.parent() {
+                fs.create_dir(parent).await?;
+            }
+            settings_path.to_path_buf()
+        };
+        fs.atomic_write(resolved_path.clone(), new_text)
+            .await
+            .with_context(|| format!("Failed to write settings to file {:?}", resolved_path))
+    }
+
+    /// Sets the value at the given path of keys in a JSON file, or removes it
+    /// when `value` is `None`, returning the new text for that JSON file. The
+    /// comments and formatting of the rest of the file are preserved.
+    pub fn new_text_for_value_update(
+        &self,
+        old_text: String,
+        key_path: &[&str],
+        value: Option<&Value>,
+    ) -> String {
+        let mut text = if old_text.trim().is_empty() {
+            "{\n}\n".to_string()
+        } else {
+            old_text
+        };
+        let raw_settings = parse_json_with_comments::<Value>(&text).unwrap_or_default();
+        let old_value = key_path
+            .iter()
+            .try_fold(&raw_settings, |value, key| value.get(key));
+        match value {
+            Some(new_value) => {
+                let mut key_path = key_path.to_vec();
+                let mut edits = Vec::new();
+                update_value_in_json_text(
+                    &mut text,
+                    &mut key_path,
+                    self.json_tab_size(),
+                    old_value.unwrap_or(&Value::Null),
+                    new_value,
+                    &[],
+                    &mut edits,
+                );
+            }
+            None => {
+                if old_value.is_some() {
+                    let (range, replacement) = replace_value_in_json_text(&text, key_path, 0, None);
+                    text.replace_range(range, &replacement);
+                }
+            }
+        }
+        text
+    }
+
     /// Updates the value of a setting in a JSON file, returning the new text
     /// for that JSON file.
     pub fn new_text_for_update<T: Settings>(
@@ -1570,6 +1656,116 @@ fn replace_value_in_json_text(
     }
 }
 
+/// Replaces the value at the given index of the array at the root of a JSON
+/// file, returning the range of the text to replace and the replacement. The
+/// comments and formatting of the other values are preserved.
+pub fn replace_top_level_array_value_in_json_text(
+    text: &str,
+    index: usize,
+    new_value: &Value,
+    tab_size: usize,
+) -> Option<(Range<usize>, String)> {
+    let syntax_tree = parse_json_text(text);
+    let array = top_level_array(&syntax_tree)?;
+    let mut cursor = array.walk();
+    let value = array
+        .named_children(&mut cursor)
+        .filter(|node| is_array_value(node))
+        .nth(index)?;
+    let indent = value.start_position().column;
+    Some((
+        value.byte_range(),
+        to_pretty_json(new_value, tab_size, indent),
+    ))
+}
+
+/// Appends a value to the array at the root of a JSON file, returning the
+/// range of the text to replace and the replacement. The array is created when
+/// the file is empty.
+pub fn append_top_level_array_value_in_json_text(
+    text: &str,
+    new_value: &Value,
+    tab_size: usize,
+) -> Option<(Range<usize>, String)> {
+    if text.trim().is_empty() {
+        let new_value = to_pretty_json(new_value, tab_size, tab_size);
+        let indent = " ".repeat(tab_size);
+        return Some((0..text.len(), format!("[\n{indent}{new_value}\n]\n")));
+    }
+
+    let syntax_tree = parse_json_text(text);
+    let array = top_level_array(&syntax_tree)?;
+    let mut cursor = array.walk();
+    let last_value = array
+        .named_children(&mut cursor)
+        .filter(|node| is_array_value(node))
+        .last();
+    match last_value {
+        Some(last_value) => {
+            // The new value is indented like the last one, and a trailing
+            // comma after the last value is kept.
+            let indent_len = last_value.start_position().column;
+            let indent = " ".repeat(indent_len);
+            let new_value = to_pretty_json(new_value, tab_size, indent_len);
+            let mut insertion_point = last_value.end_byte();
+            let mut separator = ",";
+            let rest = &text[insertion_point..];
+            if rest.trim_start().starts_with(',') {
+                insertion_point += rest.len() - rest.trim_start().len() + 1;
+                separator = "";
+            }
+            Some((
+                insertion_point..insertion_point,
+                format!("{separator}\n{indent}{new_value}"),
+            ))
+        }
+        None => {
+            // The array only has comments, after which the value is put.
+            let new_value = to_pretty_json(new_value, tab_size, tab_size);
+            let indent = " ".repeat(tab_size);
+            let mut cursor = array.walk();
+            let last_comment = array
+                .named_children(&mut cursor)
+                .filter(|node| node.kind() == "comment")
+                .last();
+            let open_bracket = array.child(0)?;
+            let close_bracket = array.child(array.child_count().checked_sub(1)?)?;
+            match last_comment {
+                Some(comment) => Some((
+                    comment.end_byte()..comment.end_byte(),
+                    format!("\n{indent}{new_value}"),
+                )),
+                None => Some((
+                    open_bracket.end_byte()..close_bracket.start_byte(),
+                    format!("\n{indent}{new_value}\n"),
+                )),
+            }
+        }
+    }
+}
+
+/// Returns whether a child of an array is one of its values, rather than a
+/// comment or the trailing comma that JSON with comments allows.
+fn is_array_value(node: &tree_sitter::Node) -> bool {
+    node.kind() != "comment" && !node.is_error()
+}
+
+fn parse_json_text(text: &str) -> tree_sitter::Tree {
+    let mut parser = tree_sitter::Parser::new();
+    parser
+        .set_language(&tree_sitter_json::LANGUAGE.into())
+        .unwrap();
+    parser.parse(text, None).unwrap()
+}
+
+fn top_level_array(syntax_tree: &tree_sitter::Tree) -> Option<tree_sitter::Node<'_>> {
+    let root = syntax_tree.root_node();
+    let mut cursor = root.walk();
+    root.named_children(&mut cursor)
+        .find(|node| node.kind() != "comment")
+        .filter(|node| node.kind() == "array")
+}
+
 fn to_pretty_json(value: &impl Serialize, indent_size: usize, indent_prefix_len: usize) -> String {
     const SPACES: [u8; 32] = [b' '; 32];
 
@@ -1959,6 +2155,100 @@ mod tests {
         );
     }
 
+    #[gpui::test]
+    fn test_setting_value_update(cx: &mut App) {
+        let store = SettingsStore::new(cx);
+        let text = r#"{
+              // The user's name
+              "user": {
+                "name": "John Doe"
+              },
+              "languages": {
+                "JSON": {
+                  // Set for every JSON file
+                  "language_setting_1": true
+                }
+              }
+            }
+        "#
+        .unindent();
+
+        let text =
+            store.new_text_for_value_update(text, &["user", "age"], Some(&serde_json::json!(31)));
+        let text = store.new_text_for_value_update(
+            text,
+            &["languages", "JSON", "language_setting_1"],
+            Some(&serde_json::json!(false)),
+        );
+        let text = store.new_text_for_value_update(text, &["user", "name"], None);
+        // Removing a key that isn't set leaves the file as it is.
+        let text = store.new_text_for_value_update(text, &["user", "missing"], None);
+        pretty_assertions::assert_eq!(
+            text,
+            r#"{
+                  // The user's name
+                  "user": {
+                    "age": 31
+                  },
+                  "languages": {
+                    "JSON": {
+                      // Set for every JSON file
+                      "language_setting_1": false
+                    }
+                  }
+                }
+            "#
+            .unindent()
+        );
+    }
+
+    #[test]
+    fn test_top_level_array_value_update() {
+        fn apply_edit(text: &mut String, edit: Option<(Range<usize>, String)>) {
+            let (range, replacement) = edit.unwrap();
+            text.replace_range(range, &replacement);
+        }
+
+        let mut text = r#"[
+              // Shared with the team
+              {"name": "todos"},
+            ]"#
+        .unindent();
+        let todos = serde_json::json!({ "name": "todos", "query": "TODO" });
+        let edit = replace_top_level_array_value_in_json_text(&text, 0, &todos, 2);
+        apply_edit(&mut text, edit);
+        let edit = append_top_level_array_value_in_json_text(
+            &text,
+            &serde_json::json!({ "name": "fixmes" }),
+            2,
+        );
+        apply_edit(&mut text, edit);
+        pretty_assertions::assert_eq!(
+            text,
+            r#"[
+                  // Shared with the team
+                  {
+                    "name": "todos",
+                    "query": "TODO"
+                  },
+                  {
+                    "name": "fixmes"
+                  }
+                ]"#
+            .unindent()
+        );
+
+        let mut text = String::new();
+        let edit = append_top_level_array_value_in_json_text(&text, &todos, 2);
+        apply_edit(&mut text, edit);
+        assert_eq!(
+            serde_json::from_str::<Value>(&text).unwrap(),
+            serde_json::json!([todos])
+        );
+        assert!(append_top_level_array_value_in_json_text("{}", &todos, 2).is_none());
+        assert!(replace_top_level_array_value_in_json_text("[]", 0, &todos, 2).is_none());
+    }
+
     #[gpui::test]
     fn test_vscode_import(cx: &mut App) {
         let mut store = SettingsStore::new(cx);
@@ -2,7 +2,10 @@ use std::sync::Arc;
 
 use gpui::{App, FontFeatures, FontWeight};
 use settings::{EditableSettingControl, Settings};
-use theme::{FontFamilyCache, SystemAppearance, ThemeMode, ThemeRegistry, ThemeSettings};
+use theme::{
+    Appearance, FontFamilyCache, SystemAppearance, ThemeMode, ThemeRegistry, ThemeSettings,
+    appearance_for_mode,
+};
 use ui::{
     CheckboxWithLabel, ContextMenu, DropdownMenu, NumericStepper, SettingsContainer, SettingsGroup,
     ToggleButton, prelude::*,
@@ -57,12 +60,12 @@ impl EditableSettingControl for ThemeControl {
 
     fn read(cx: &App) -> Self::Value {
         let settings = ThemeSettings::get_global(cx);
-        let appearance = SystemAppearance::global(cx);
+        let appearance = Self::appearance(cx);
         settings
             .theme_selection
             .as_ref()
-            .map(|selection| selection.theme(appearance.0).to_string())
-            .unwrap_or_else(|| ThemeSettings::default_theme(*appearance).to_string())
+            .map(|selection| selection.theme(appearance).to_string())
+            .unwrap_or_else(|| ThemeSettings::default_theme(appearance).to_string())
     }
 
     fn apply(
@@ -70,8 +73,18 @@ impl EditableSettingControl for ThemeControl {
         value: Self::Value,
         cx: &App,
     ) {
-        let appearance = SystemAppearance::global(cx);
-        settings.set_theme(value, appearance.0);
+        settings.set_theme(value, Self::appearance(cx));
+    }
+}
+
+impl ThemeControl {
+    /// Returns the appearance that the selected theme mode follows.
+    fn appearance(cx: &App) -> Appearance {
+        let mode = ThemeSettings::get_global(cx)
+            .theme_selection
+            .as_ref()
+            .and_then(|selection| selection.mode());
+        appearance_for_mode(mode, SystemAppearance::global(cx).0, cx)
     }
 }
 
@@ -157,6 +170,14 @@ impl RenderOnce for ThemeModeControl {
                     .on_click(|_, _, cx| Self::write(ThemeMode::System, cx))
                     .middle(),
             )
+            .child(
+                ToggleButton::new("scheduled", "Scheduled")
+                    .style(ButtonStyle::Filled)
+                    .size(ButtonSize::Large)
+                    .toggle_state(value == ThemeMode::Scheduled)
+                    .on_click(|_, _, cx| Self::write(ThemeMode::Scheduled, cx))
+                    .middle(),
+            )
             .child(
                 ToggleButton::new("dark", "Dark")
                     .style(ButtonStyle::Filled)
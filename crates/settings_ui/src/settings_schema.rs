@@ -0,0 +1,329 @@
+//! Turns the JSON schema of the settings into the list of settings that the
+//! settings page shows.
+
+use serde_json::Value;
+use ui::SharedString;
+
+/// How deep references and nested objects are followed, which keeps
+/// recursive schemas from being expanded forever.
+const MAX_DEPTH: usize = 8;
+
+/// A setting that can be edited on its own.
+#[derive(Debug)]
+pub(crate) struct SettingEntry {
+    /// The keys leading to the setting, like `["git", "inline_blame", "enabled"]`.
+    pub key_path: Vec<String>,
+    /// The keys joined with dots, like `git.inline_blame.enabled`.
+    pub name: SharedString,
+    pub description: Option<SharedString>,
+    pub kind: SettingKind,
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum SettingKind {
+    Boolean,
+    /// One of a fixed set of values.
+    Enum(Vec<Value>),
+    Number,
+    String,
+    /// Any other value, like a list or a map, which is edited as JSON.
+    Json,
+}
+
+impl SettingEntry {
+    /// Returns whether the setting matches every word of the query, either
+    /// by its name or by its description.
+    pub fn matches(&self, query: &str) -> bool {
+        let name = self.name.to_lowercase();
+        let description = self
+            .description
+            .as_ref()
+            .map(|description| description.to_lowercase())
+            .unwrap_or_default();
+        query.split_whitespace().all(|word| {
+            let word = word.to_lowercase();
+            name.contains(&word) || description.contains(&word)
+        })
+    }
+}
+
+/// Lists the settings of the object described by `schema`, where `root` is
+/// the whole settings schema, which holds the definitions that are referenced.
+/// Nested objects are flattened into their settings, and the keys in
+/// `skipped_keys` are left out.
+pub(crate) fn setting_entries(
+    root: &Value,
+    schema: &Value,
+    skipped_keys: &[&str],
+) -> Vec<SettingEntry> {
+    let mut entries = Vec::new();
+    let (schema, _) = resolve(root, schema);
+    if let Some(properties) = object_properties(schema) {
+        for (key, property) in properties {
+            if skipped_keys.contains(&key.as_str()) {
+                continue;
+            }
+            collect_entries(root, property, &mut vec![key.clone()], &mut entries);
+        }
+    }
+    entries
+}
+
+fn collect_entries(
+    root: &Value,
+    schema: &Value,
+    key_path: &mut Vec<String>,
+    entries: &mut Vec<SettingEntry>,
+) {
+    let (schema, description) = resolve(root, schema);
+    if key_path.len() < MAX_DEPTH {
+        if let Some(properties) = object_properties(schema) {
+            for (key, property) in properties {
+                key_path.push(key.clone());
+                collect_entries(root, property, key_path, entries);
+                key_path.pop();
+            }
+            return;
+        }
+    }
+
+    entries.push(SettingEntry {
+        key_path: key_path.clone(),
+        name: key_path.join(".").into(),
+        description: description.map(|description| description.trim().to_string().into()),
+        kind: setting_kind(root, schema),
+    });
+}
+
+/// Follows the references of a schema to the schema that describes the value,
+/// returning it along with the first description that was found on the way.
+///
+/// Optional values are described by a schema that also allows `null`, and
+/// documented references are wrapped in an `allOf`, which are both looked
+/// through.
+fn resolve<'a>(root: &'a Value, mut schema: &'a Value) -> (&'a Value, Option<&'a str>) {
+    let mut description = None;
+    for _ in 0..MAX_DEPTH {
+        description = description.or_else(|| schema.get("description")?.as_str());
+
+        if let Some(reference) = schema.get("$ref").and_then(Value::as_str) {
+            let Some(definition) = reference
+                .strip_prefix("#/definitions/")
+                .and_then(|name| root.get("definitions")?.get(name))
+            else {
+                break;
+            };
+            schema = definition;
+            continue;
+        }
+
+        let subschemas = ["allOf", "anyOf", "oneOf"]
+            .into_iter()
+            .find_map(|key| schema.get(key)?.as_array());
+        if let Some(subschemas) = subschemas {
+            let mut values = subschemas
+                .iter()
+                .filter(|subschema| !is_null_schema(subschema));
+            if let (Some(value), None) = (values.next(), values.next()) {
+                schema = value;
+                continue;
+            }
+        }
+        break;
+    }
+    (schema, description)
+}
+
+fn object_properties(schema: &Value) -> Option<&serde_json::Map<String, Value>> {
+    let types = instance_types(schema);
+    if !types.is_empty() && types != ["object"] {
+        return None;
+    }
+    schema
+        .get("properties")?
+        .as_object()
+        .filter(|properties| !properties.is_empty())
+}
+
+/// Returns the types that a schema allows, leaving out `null`.
+fn instance_types(schema: &Value) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(instance_type)) => vec![instance_type.as_str()],
+        Some(Value::Array(instance_types)) => {
+            instance_types.iter().filter_map(Value::as_str).collect()
+        }
+        _ => Vec::new(),
+    }
+    .into_iter()
+    .filter(|instance_type| *instance_type != "null")
+    .collect()
+}
+
+fn is_null_schema(schema: &Value) -> bool {
+    schema.get("type").and_then(Value::as_str) == Some("null")
+}
+
+fn setting_kind(root: &Value, schema: &Value) -> SettingKind {
+    if let Some(values) = enum_values(root, schema, 0) {
+        return SettingKind::Enum(values);
+    }
+    match instance_types(schema).as_slice() {
+        ["boolean"] => SettingKind::Boolean,
+        ["integer"] | ["number"] => SettingKind::Number,
+        ["string"] => SettingKind::String,
+        _ => SettingKind::Json,
+    }
+}
+
+/// Returns the values that a schema allows, if it only allows a fixed set of
+/// values. Enums whose variants are documented are written as a `oneOf` of
+/// the single variants.
+fn enum_values(root: &Value, schema: &Value, depth: usize) -> Option<Vec<Value>> {
+    if let Some(values) = schema.get("enum").and_then(Value::as_array) {
+        return Some(values.iter().filter(|v| !v.is_null()).cloned().collect());
+    }
+    if let Some(value) = schema.get("const") {
+        return Some(vec![value.clone()]);
+    }
+    if depth >= MAX_DEPTH {
+        return None;
+    }
+
+    let variants = schema
+        .get("oneOf")
+        .or_else(|| schema.get("anyOf"))?
+        .as_array()?;
+    let mut values = Vec::new();
+    for variant in variants {
+        let (variant, _) = resolve(root, variant);
+        if !is_null_schema(variant) {
+            values.extend(enum_values(root, variant, depth + 1)?);
+        }
+    }
+    (!values.is_empty()).then_some(values)
+}
+
+/// Returns the value at the given path of keys in a settings file.
+pub(crate) fn value_at_path<'a>(value: &'a Value, key_path: &[String]) -> Option<&'a Value> {
+    key_path
+        .iter()
+        .try_fold(value, |value, key| value.get(key.as_str()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "autosave": {
+                    "description": "When to save edited buffers.",
+                    "allOf": [{ "$ref": "#/definitions/AutosaveSetting" }]
+                },
+                "buffer_font_size": {
+                    "type": ["number", "null"],
+                    "format": "float"
+                },
+                "git": {
+                    "anyOf": [{ "$ref": "#/definitions/GitSettings" }, { "type": "null" }]
+                },
+                "file_types": {
+                    "type": "object",
+                    "additionalProperties": { "type": "array", "items": { "type": "string" } }
+                },
+                "stable": {
+                    "type": "object",
+                    "properties": { "buffer_font_size": { "type": "number" } }
+                }
+            },
+            "definitions": {
+                "AutosaveSetting": {
+                    "oneOf": [
+                        { "description": "Disable autosave.", "type": "string", "enum": ["off"] },
+                        { "type": "string", "enum": ["on_focus_change", "on_window_change"] }
+                    ]
+                },
+                "GitSettings": {
+                    "type": "object",
+                    "properties": {
+                        "inline_blame": {
+                            "type": "object",
+                            "properties": {
+                                "enabled": {
+                                    "description": "Whether to show the blame of the current line.",
+                                    "type": ["boolean", "null"]
+                                }
+                            }
+                        },
+                        "branch_name": { "type": ["string", "null"] }
+                    }
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn test_setting_entries() {
+        let schema = schema();
+        let entries = setting_entries(&schema, &schema, &["stable"]);
+        let names = entries
+            .iter()
+            .map(|entry| entry.name.to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            names,
+            [
+                "autosave",
+                "buffer_font_size",
+                "git.inline_blame.enabled",
+                "git.branch_name",
+                "file_types",
+            ]
+        );
+
+        assert_eq!(
+            entries[0].kind,
+            SettingKind::Enum(vec![
+                json!("off"),
+                json!("on_focus_change"),
+                json!("on_window_change"),
+            ])
+        );
+        assert_eq!(
+            entries[0].description.as_deref(),
+            Some("When to save edited buffers.")
+        );
+        assert_eq!(entries[1].kind, SettingKind::Number);
+        assert_eq!(entries[2].kind, SettingKind::Boolean);
+        assert_eq!(entries[2].key_path, ["git", "inline_blame", "enabled"]);
+        assert_eq!(entries[3].kind, SettingKind::String);
+        assert_eq!(entries[4].kind, SettingKind::Json);
+    }
+
+    #[test]
+    fn test_setting_entry_matches() {
+        let schema = schema();
+        let entries = setting_entries(&schema, &schema, &[]);
+        let blame = entries
+            .iter()
+            .find(|entry| entry.name == "git.inline_blame.enabled")
+            .unwrap();
+        assert!(blame.matches("inline_blame"));
+        assert!(blame.matches("GIT current line"));
+        assert!(!blame.matches("git font"));
+    }
+
+    #[test]
+    fn test_value_at_path() {
+        let settings = json!({ "git": { "inline_blame": { "enabled": false } } });
+        let key_path = ["git".to_string(), "inline_blame".to_string()];
+        assert_eq!(
+            value_at_path(&settings, &key_path),
+            Some(&json!({ "enabled": false }))
+        );
+        assert_eq!(value_at_path(&settings, &["vim_mode".to_string()]), None);
+    }
+}
@@ -1,24 +1,40 @@
 mod appearance_settings_controls;
+mod settings_schema;
 
 use std::any::TypeId;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
 use command_palette_hooks::CommandPaletteFilter;
-use editor::EditorSettingsControls;
+use editor::{Editor, EditorEvent, EditorSettingsControls};
 use feature_flags::{FeatureFlag, FeatureFlagViewExt};
 use fs::Fs;
 use gpui::{
-    Action, App, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable, Task, actions,
+    Action, App, AsyncWindowContext, Entity, EventEmitter, FocusHandle, Focusable,
+    ListSizingBehavior, Subscription, Task, UniformListScrollHandle, WeakEntity, actions,
+    uniform_list,
 };
+use paths::local_settings_file_relative_path;
 use schemars::JsonSchema;
 use serde::Deserialize;
-use settings::{SettingsStore, VsCodeSettingsSource};
-use ui::prelude::*;
-use util::truncate_and_remove_front;
+use serde_json::Value;
+use settings::{SettingsJsonSchemaParams, SettingsStore, VsCodeSettingsSource, WorktreeId};
+use ui::{
+    ContextMenu, DropdownMenu, Indicator, ListItem, ListItemSpacing, Switch, ToggleButton, Tooltip,
+    prelude::*,
+};
+use util::{truncate_and_remove_front, truncate_and_trailoff};
 use workspace::item::{Item, ItemEvent};
 use workspace::{Workspace, with_active_or_new_workspace};
 
 use crate::appearance_settings_controls::AppearanceSettingsControls;
+use crate::settings_schema::{SettingEntry, SettingKind, setting_entries, value_at_path};
+
+/// The keys of the settings schema that the settings page doesn't list. The
+/// release channel keys repeat the whole schema, and the language overrides
+/// are edited in the language scope.
+const SKIPPED_KEYS: &[&str] = &["dev", "nightly", "stable", "preview", "languages"];
 
 pub struct SettingsUiFeatureFlag;
 
@@ -53,7 +69,7 @@ pub fn init(cx: &mut App) {
             if let Some(existing) = existing {
                 workspace.activate_item(&existing, true, true, window, cx);
             } else {
-                let settings_page = SettingsPage::new(workspace, cx);
+                let settings_page = SettingsPage::new(workspace, window, cx);
                 workspace.add_item_to_active_pane(Box::new(settings_page), None, true, window, cx)
             }
         });
@@ -174,16 +190,647 @@ async fn handle_import_vscode_settings(
     .ok();
 }
 
+/// The settings file that the settings page shows and edits.
+#[derive(Clone, Debug, PartialEq)]
+enum SettingsScope {
+    /// The user's settings file.
+    User,
+    /// The settings file in the root of one of the project's worktrees.
+    Project {
+        worktree_id: WorktreeId,
+        worktree_name: SharedString,
+        settings_path: PathBuf,
+    },
+    /// The overrides of a language in the user's settings file.
+    Language(SharedString),
+}
+
+impl SettingsScope {
+    fn settings_path(&self) -> PathBuf {
+        match self {
+            Self::Project { settings_path, .. } => settings_path.clone(),
+            Self::User | Self::Language(_) => paths::settings_file().clone(),
+        }
+    }
+
+    /// Returns the keys leading to a setting in the scope's settings file.
+    fn key_path(&self, entry: &SettingEntry) -> Vec<String> {
+        let mut key_path = match self {
+            Self::Language(language) => vec!["languages".to_string(), language.to_string()],
+            Self::User | Self::Project { .. } => Vec::new(),
+        };
+        key_path.extend(entry.key_path.iter().cloned());
+        key_path
+    }
+
+    fn raw_settings<'a>(&self, store: &'a SettingsStore) -> Option<&'a Value> {
+        match self {
+            Self::User | Self::Language(_) => Some(store.raw_user_settings()),
+            Self::Project { worktree_id, .. } => {
+                store.raw_local_settings(*worktree_id, Path::new(""))
+            }
+        }
+    }
+
+    fn label(&self) -> SharedString {
+        match self {
+            Self::User => "User".into(),
+            Self::Project { worktree_name, .. } => worktree_name.clone(),
+            Self::Language(language) => language.clone(),
+        }
+    }
+}
+
 pub struct SettingsPage {
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
     focus_handle: FocusHandle,
+    search_editor: Entity<Editor>,
+    value_editor: Entity<Editor>,
+    scope: SettingsScope,
+    /// The settings of the settings schema.
+    entries: Vec<SettingEntry>,
+    /// The settings that can be overridden for a language.
+    language_entries: Vec<SettingEntry>,
+    language_names: Vec<SharedString>,
+    /// The indices of the entries of the scope that match the search.
+    matches: Vec<usize>,
+    modified_only: bool,
+    /// The index of the entry being edited.
+    selected_entry: Option<usize>,
+    value_error: Option<SharedString>,
+    scroll_handle: UniformListScrollHandle,
+    _subscriptions: Vec<Subscription>,
 }
 
 impl SettingsPage {
-    pub fn new(_workspace: &Workspace, cx: &mut Context<Workspace>) -> Entity<Self> {
-        cx.new(|cx| Self {
-            focus_handle: cx.focus_handle(),
+    pub fn new(
+        workspace: &Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let language_names = workspace.app_state().languages.language_names();
+        let workspace = workspace.weak_handle();
+        cx.new(|cx| {
+            let font_names = cx.text_system().all_font_names();
+            let schema = cx.global::<SettingsStore>().json_schema(
+                &SettingsJsonSchemaParams {
+                    language_names: &language_names,
+                    font_names: &font_names,
+                },
+                cx,
+            );
+            let entries = setting_entries(&schema, &schema, SKIPPED_KEYS);
+            let language_entries = schema
+                .pointer("/definitions/LanguageSettingsContent")
+                .map(|language_schema| setting_entries(&schema, language_schema, &[]))
+                .unwrap_or_default();
+
+            let search_editor = cx.new(|cx| {
+                let mut editor = Editor::single_line(window, cx);
+                editor.set_placeholder_text("Search settings...", cx);
+                editor
+            });
+            let value_editor = cx.new(|cx| Editor::auto_height(1, 12, window, cx));
+            let subscriptions = vec![
+                cx.subscribe(&search_editor, |this, _, event, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        this.update_matches(cx);
+                    }
+                }),
+                cx.subscribe(&value_editor, |this, _, event, cx| {
+                    if let EditorEvent::BufferEdited = event {
+                        if this.value_error.take().is_some() {
+                            cx.notify();
+                        }
+                    }
+                }),
+                cx.observe_global::<SettingsStore>(|this, cx| this.update_matches(cx)),
+            ];
+
+            let mut this = Self {
+                fs,
+                workspace,
+                focus_handle: cx.focus_handle(),
+                search_editor,
+                value_editor,
+                scope: SettingsScope::User,
+                entries,
+                language_entries,
+                language_names: language_names.into_iter().map(Into::into).collect(),
+                matches: Vec::new(),
+                modified_only: false,
+                selected_entry: None,
+                value_error: None,
+                scroll_handle: UniformListScrollHandle::new(),
+                _subscriptions: subscriptions,
+            };
+            this.update_matches(cx);
+            this
         })
     }
+
+    fn scope_entries(&self) -> &[SettingEntry] {
+        match self.scope {
+            SettingsScope::Language(_) => &self.language_entries,
+            SettingsScope::User | SettingsScope::Project { .. } => &self.entries,
+        }
+    }
+
+    /// Lists the worktrees of the project whose settings files can be edited.
+    fn project_scopes(&self, cx: &App) -> Vec<SettingsScope> {
+        let Some(workspace) = self.workspace.upgrade() else {
+            return Vec::new();
+        };
+        let project = workspace.read(cx).project().read(cx);
+        // The settings files of remote projects can't be written from here.
+        if !project.is_local() {
+            return Vec::new();
+        }
+        project
+            .visible_worktrees(cx)
+            .map(|worktree| {
+                let worktree = worktree.read(cx);
+                SettingsScope::Project {
+                    worktree_id: worktree.id(),
+                    worktree_name: worktree.root_name().to_string().into(),
+                    settings_path: worktree
+                        .abs_path()
+                        .join(local_settings_file_relative_path()),
+                }
+            })
+            .collect()
+    }
+
+    /// Returns the value of a setting in the scope's settings file.
+    fn scope_value(&self, entry: &SettingEntry, cx: &App) -> Option<Value> {
+        let store = cx.global::<SettingsStore>();
+        let settings = self.scope.raw_settings(store)?;
+        value_at_path(settings, &self.scope.key_path(entry)).cloned()
+    }
+
+    /// Returns the default value of a setting, which language overrides
+    /// take from the defaults of the language when there is one.
+    fn default_value(&self, entry: &SettingEntry, cx: &App) -> Option<Value> {
+        let defaults = cx.global::<SettingsStore>().raw_default_settings();
+        value_at_path(defaults, &self.scope.key_path(entry))
+            .or_else(|| value_at_path(defaults, &entry.key_path))
+            .cloned()
+    }
+
+    /// Returns the value that applies when the scope doesn't set the setting.
+    fn inherited_value(&self, entry: &SettingEntry, cx: &App) -> Option<Value> {
+        let store = cx.global::<SettingsStore>();
+        let user_settings = store.raw_user_settings();
+        let user_value = match self.scope {
+            SettingsScope::User => None,
+            SettingsScope::Project { .. } => value_at_path(user_settings, &entry.key_path),
+            // A language's default is more specific than the user's setting
+            // for all languages.
+            SettingsScope::Language(_) => {
+                let language_default =
+                    value_at_path(store.raw_default_settings(), &self.scope.key_path(entry));
+                language_default.or_else(|| value_at_path(user_settings, &entry.key_path))
+            }
+        };
+        user_value
+            .cloned()
+            .or_else(|| self.default_value(entry, cx))
+    }
+
+    /// Returns whether the scope's settings file sets the setting to a value
+    /// other than its default.
+    fn is_modified(&self, entry: &SettingEntry, cx: &App) -> bool {
+        self.scope_value(entry, cx)
+            .is_some_and(|value| Some(value) != self.default_value(entry, cx))
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let query = self.search_editor.read(cx).text(cx);
+        self.matches = self
+            .scope_entries()
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.matches(&query))
+            .filter(|(_, entry)| !self.modified_only || self.is_modified(entry, cx))
+            .map(|(ix, _)| ix)
+            .collect();
+        cx.notify();
+    }
+
+    fn set_scope(&mut self, scope: SettingsScope, window: &mut Window, cx: &mut Context<Self>) {
+        if self.scope == scope {
+            return;
+        }
+        let selected_key_path = self
+            .selected_entry
+            .and_then(|entry_ix| self.scope_entries().get(entry_ix))
+            .map(|entry| entry.key_path.clone());
+        self.scope = scope;
+        self.selected_entry = None;
+        self.value_error = None;
+        self.update_matches(cx);
+
+        // Keep the same setting selected when it exists in the new scope.
+        let entry_ix = selected_key_path.and_then(|key_path| {
+            self.scope_entries()
+                .iter()
+                .position(|entry| entry.key_path == key_path)
+        });
+        if let Some(entry_ix) = entry_ix {
+            self.select_entry(entry_ix, window, cx);
+        }
+    }
+
+    fn toggle_modified_only(&mut self, cx: &mut Context<Self>) {
+        self.modified_only = !self.modified_only;
+        self.update_matches(cx);
+    }
+
+    fn select_entry(&mut self, entry_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.scope_entries().get(entry_ix) else {
+            return;
+        };
+        let value = self
+            .scope_value(entry, cx)
+            .or_else(|| self.inherited_value(entry, cx));
+        let text = value
+            .map(|value| value_text(&entry.kind, &value))
+            .unwrap_or_default();
+        self.selected_entry = Some(entry_ix);
+        self.value_error = None;
+        self.value_editor
+            .update(cx, |editor, cx| editor.set_text(text, window, cx));
+        cx.notify();
+    }
+
+    /// Writes the value of a setting to the scope's settings file, or removes
+    /// the setting from it when `value` is `None`.
+    fn write_value(&self, entry_ix: usize, value: Option<Value>, cx: &mut Context<Self>) {
+        let Some(entry) = self.scope_entries().get(entry_ix) else {
+            return;
+        };
+        settings::update_settings_value(
+            self.fs.clone(),
+            cx,
+            self.scope.settings_path(),
+            self.scope.key_path(entry),
+            value,
+        );
+    }
+
+    fn apply_value_editor(&mut self, cx: &mut Context<Self>) {
+        let Some(entry_ix) = self.selected_entry else {
+            return;
+        };
+        let Some(entry) = self.scope_entries().get(entry_ix) else {
+            return;
+        };
+        let is_string = entry.kind == SettingKind::String;
+        let text = self.value_editor.read(cx).text(cx);
+        let value = if is_string {
+            Value::String(text)
+        } else {
+            match settings::parse_json_with_comments::<Value>(&text) {
+                Ok(value) => value,
+                Err(error) => {
+                    self.value_error = Some(format!("Invalid JSON: {error}").into());
+                    cx.notify();
+                    return;
+                }
+            }
+        };
+        self.write_value(entry_ix, Some(value), cx);
+    }
+
+    fn reset_selected_entry(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry_ix) = self.selected_entry else {
+            return;
+        };
+        let Some(entry) = self.scope_entries().get(entry_ix) else {
+            return;
+        };
+        let text = self
+            .inherited_value(entry, cx)
+            .map(|value| value_text(&entry.kind, &value))
+            .unwrap_or_default();
+        self.write_value(entry_ix, None, cx);
+        self.value_error = None;
+        self.value_editor
+            .update(cx, |editor, cx| editor.set_text(text, window, cx));
+    }
+
+    fn render_scope_picker(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let project_scopes = self.project_scopes(cx);
+        let first_project_scope = project_scopes.first().cloned();
+        let first_language_scope = self
+            .language_names
+            .first()
+            .cloned()
+            .map(SettingsScope::Language);
+        let this = cx.weak_entity();
+
+        h_flex()
+            .gap_2()
+            .child(
+                h_flex()
+                    .child(
+                        ToggleButton::new("user-scope", "User")
+                            .style(ButtonStyle::Filled)
+                            .toggle_state(self.scope == SettingsScope::User)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.set_scope(SettingsScope::User, window, cx)
+                            }))
+                            .first(),
+                    )
+                    .child(
+                        ToggleButton::new("project-scope", "Project")
+                            .style(ButtonStyle::Filled)
+                            .toggle_state(matches!(self.scope, SettingsScope::Project { .. }))
+                            .disabled(first_project_scope.is_none())
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                if matches!(this.scope, SettingsScope::Project { .. }) {
+                                    return;
+                                }
+                                if let Some(scope) = first_project_scope.clone() {
+                                    this.set_scope(scope, window, cx)
+                                }
+                            }))
+                            .middle(),
+                    )
+                    .child(
+                        ToggleButton::new("language-scope", "Language")
+                            .style(ButtonStyle::Filled)
+                            .toggle_state(matches!(self.scope, SettingsScope::Language(_)))
+                            .disabled(first_language_scope.is_none())
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                if matches!(this.scope, SettingsScope::Language(_)) {
+                                    return;
+                                }
+                                if let Some(scope) = first_language_scope.clone() {
+                                    this.set_scope(scope, window, cx)
+                                }
+                            }))
+                            .last(),
+                    ),
+            )
+            .map(|picker| {
+                let scopes = match self.scope {
+                    SettingsScope::User => return picker,
+                    SettingsScope::Project { .. } => project_scopes,
+                    SettingsScope::Language(_) => self
+                        .language_names
+                        .iter()
+                        .cloned()
+                        .map(SettingsScope::Language)
+                        .collect(),
+                };
+                picker.child(DropdownMenu::new(
+                    "settings-scope",
+                    self.scope.label(),
+                    ContextMenu::build(window, cx, move |mut menu, _, _| {
+                        for scope in scopes {
+                            let this = this.clone();
+                            menu = menu.entry(scope.label(), None, move |window, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.set_scope(scope.clone(), window, cx)
+                                })
+                                .ok();
+                            });
+                        }
+                        menu
+                    }),
+                ))
+            })
+    }
+
+    fn render_header(&self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border_variant;
+        v_flex()
+            .p_2()
+            .gap_2()
+            .flex_none()
+            .border_b_1()
+            .border_color(border_color)
+            .child(
+                h_flex()
+                    .justify_between()
+                    .child(self.render_scope_picker(window, cx))
+                    .child(
+                        Switch::new("modified-only", self.modified_only.into())
+                            .label("Modified Only")
+                            .on_click(cx.listener(|this, _, _, cx| this.toggle_modified_only(cx))),
+                    ),
+            )
+            .child(
+                div()
+                    .px_2()
+                    .py_1()
+                    .border_1()
+                    .border_color(border_color)
+                    .rounded_sm()
+                    .child(self.search_editor.clone()),
+            )
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<ListItem> {
+        range
+            .filter_map(|ix| {
+                let entry_ix = *self.matches.get(ix)?;
+                let entry = self.scope_entries().get(entry_ix)?;
+                let scope_value = self.scope_value(entry, cx);
+                let is_set = scope_value.is_some();
+                let value = scope_value.or_else(|| self.inherited_value(entry, cx));
+                let value_label = match (&entry.kind, &value) {
+                    (SettingKind::Boolean, Some(Value::Bool(enabled))) => {
+                        Switch::new(("setting-switch", ix), (*enabled).into())
+                            .on_click(cx.listener(move |this, state: &ToggleState, _, cx| {
+                                this.write_value(entry_ix, Some(Value::Bool(state.selected())), cx)
+                            }))
+                            .into_any_element()
+                    }
+                    (_, value) => Label::new(value.as_ref().map(value_summary).unwrap_or_default())
+                        .size(LabelSize::Small)
+                        .color(if is_set { Color::Default } else { Color::Muted })
+                        .into_any_element(),
+                };
+
+                Some(
+                    ListItem::new(ix)
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(self.selected_entry == Some(entry_ix))
+                        .child(
+                            h_flex()
+                                .id(("setting-name", ix))
+                                .w_full()
+                                .gap_2()
+                                .child(Label::new(entry.name.clone()).size(LabelSize::Small))
+                                .when(self.is_modified(entry, cx), |this| {
+                                    this.child(Indicator::dot().color(Color::Modified))
+                                })
+                                .when_some(entry.description.clone(), |this, description| {
+                                    this.tooltip(Tooltip::text(description))
+                                }),
+                        )
+                        .end_slot(value_label)
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_entry(entry_ix, window, cx);
+                        })),
+                )
+            })
+            .collect()
+    }
+
+    fn render_setting_editor(
+        &self,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let panel = v_flex()
+            .id("setting-editor")
+            .w(rems(28.))
+            .h_full()
+            .flex_none()
+            .overflow_y_scroll()
+            .p_4()
+            .gap_3()
+            .border_l_1()
+            .border_color(cx.theme().colors().border_variant);
+        let Some((entry_ix, entry)) = self
+            .selected_entry
+            .and_then(|entry_ix| Some((entry_ix, self.scope_entries().get(entry_ix)?)))
+        else {
+            // Show the controls of the most common settings until a
+            // setting is selected.
+            return panel
+                .when(self.scope == SettingsScope::User, |panel| {
+                    panel
+                        .child(
+                            v_flex().gap_1().child(Label::new("Appearance")).child(
+                                v_flex()
+                                    .elevation_2(cx)
+                                    .child(AppearanceSettingsControls::new()),
+                            ),
+                        )
+                        .child(
+                            v_flex().gap_1().child(Label::new("Editor")).child(
+                                v_flex()
+                                    .elevation_2(cx)
+                                    .child(EditorSettingsControls::new()),
+                            ),
+                        )
+                })
+                .child(
+                    Label::new("Select a setting to edit it")
+                        .size(LabelSize::Small)
+                        .color(Color::Muted),
+                );
+        };
+
+        let scope_value = self.scope_value(entry, cx);
+        let is_set = scope_value.is_some();
+        let value = scope_value.or_else(|| self.inherited_value(entry, cx));
+        let default_value = self.default_value(entry, cx);
+        let this = cx.weak_entity();
+
+        let control = match &entry.kind {
+            SettingKind::Boolean => Switch::new(
+                "setting-value",
+                value
+                    .as_ref()
+                    .and_then(Value::as_bool)
+                    .unwrap_or(false)
+                    .into(),
+            )
+            .label("Enabled")
+            .on_click(cx.listener(move |this, state: &ToggleState, _, cx| {
+                this.write_value(entry_ix, Some(Value::Bool(state.selected())), cx)
+            }))
+            .into_any_element(),
+            SettingKind::Enum(values) => {
+                let values = values.clone();
+                DropdownMenu::new(
+                    "setting-value",
+                    value.as_ref().map(value_summary).unwrap_or_default(),
+                    ContextMenu::build(window, cx, move |mut menu, _, _| {
+                        for value in values {
+                            let this = this.clone();
+                            menu = menu.entry(value_summary(&value), None, move |_, cx| {
+                                this.update(cx, |this, cx| {
+                                    this.write_value(entry_ix, Some(value.clone()), cx)
+                                })
+                                .ok();
+                            });
+                        }
+                        menu
+                    }),
+                )
+                .full_width(true)
+                .into_any_element()
+            }
+            SettingKind::Number | SettingKind::String | SettingKind::Json => v_flex()
+                .gap_2()
+                .child(
+                    div()
+                        .px_2()
+                        .py_1()
+                        .border_1()
+                        .border_color(cx.theme().colors().border_variant)
+                        .rounded_sm()
+                        .child(self.value_editor.clone()),
+                )
+                .children(
+                    self.value_error
+                        .clone()
+                        .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+                )
+                .child(
+                    Button::new("apply-setting", "Apply")
+                        .icon(IconName::Check)
+                        .icon_position(IconPosition::Start)
+                        .on_click(cx.listener(|this, _, _, cx| this.apply_value_editor(cx))),
+                )
+                .into_any_element(),
+        };
+
+        panel
+            .child(Headline::new(entry.name.clone()).size(HeadlineSize::Small))
+            .children(
+                entry
+                    .description
+                    .clone()
+                    .map(|description| Label::new(description).size(LabelSize::Small)),
+            )
+            .child(control)
+            .child(
+                Label::new(if is_set {
+                    format!("Set in {}", self.scope.label())
+                } else {
+                    format!("Not set in {}", self.scope.label())
+                })
+                .size(LabelSize::Small)
+                .color(Color::Muted),
+            )
+            .children(default_value.map(|default_value| {
+                Label::new(format!("Default: {}", value_summary(&default_value)))
+                    .size(LabelSize::Small)
+                    .color(Color::Muted)
+            }))
+            .child(
+                Button::new("reset-setting", "Reset")
+                    .icon(IconName::RotateCcw)
+                    .icon_position(IconPosition::Start)
+                    .disabled(!is_set)
+                    .tooltip(Tooltip::text(format!(
+                        "Remove the setting from {}",
+                        self.scope.settings_path().display()
+                    )))
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.reset_selected_entry(window, cx)),
+                    ),
+            )
+    }
 }
 
 impl EventEmitter<ItemEvent> for SettingsPage {}
@@ -215,25 +862,49 @@ impl Item for SettingsPage {
 }
 
 impl Render for SettingsPage {
-    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
-        v_flex()
-            .p_4()
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .key_context("SettingsPage")
+            .track_focus(&self.focus_handle)
             .size_full()
-            .gap_4()
-            .child(Label::new("Settings").size(LabelSize::Large))
-            .child(
-                v_flex().gap_1().child(Label::new("Appearance")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(AppearanceSettingsControls::new()),
-                ),
-            )
+            .bg(cx.theme().colors().editor_background)
             .child(
-                v_flex().gap_1().child(Label::new("Editor")).child(
-                    v_flex()
-                        .elevation_2(cx)
-                        .child(EditorSettingsControls::new()),
-                ),
+                v_flex()
+                    .flex_1()
+                    .h_full()
+                    .child(self.render_header(window, cx))
+                    .child(
+                        uniform_list(
+                            "settings",
+                            self.matches.len(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .with_sizing_behavior(ListSizingBehavior::Infer)
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full()
+                        .p_1(),
+                    ),
             )
+            .child(self.render_setting_editor(window, cx))
+    }
+}
+
+/// Returns the text that a value is edited as, which is the string itself
+/// for string settings and JSON for other settings.
+fn value_text(kind: &SettingKind, value: &Value) -> String {
+    match (kind, value) {
+        (SettingKind::String, Value::String(string)) => string.clone(),
+        (_, value) => serde_json::to_string_pretty(value).unwrap_or_default(),
     }
 }
+
+/// Describes a value in a single line.
+fn value_summary(value: &Value) -> String {
+    let summary = match value {
+        Value::String(string) => string.clone(),
+        value => value.to_string(),
+    };
+    truncate_and_trailoff(&summary, 48)
+}
@@ -1,6 +1,11 @@
 use anyhow::{Context as _, Result};
+use regex::{Captures, Regex, RegexBuilder};
 use smallvec::SmallVec;
-use std::{collections::BTreeMap, ops::Range};
+use std::{
+    cell::RefCell,
+    collections::{BTreeMap, HashMap},
+    ops::Range,
+};
 
 #[derive(Clone, Debug, Default, PartialEq)]
 pub struct Snippet {
@@ -12,25 +17,108 @@ pub struct Snippet {
 pub struct TabStop {
     pub ranges: SmallVec<[Range<isize>; 2]>,
     pub choices: Option<Vec<String>>,
+    /// Ranges whose text is derived from the text of this tabstop, such as
+    /// `${1/(.*)/${1:/upcase}/}`.
+    pub transforms: Vec<(Range<isize>, Transform)>,
+}
+
+/// A regular expression transform, such as the `/(.*)/${1:/upcase}/g` in
+/// `${1/(.*)/${1:/upcase}/g}`.
+#[derive(Clone, Debug)]
+pub struct Transform {
+    regex: Regex,
+    format: Vec<FormatItem>,
+    global: bool,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum FormatItem {
+    Text(String),
+    Group(usize, Option<CaseModifier>),
+    Conditional {
+        group: usize,
+        /// The text to insert when the group matched, or `None` to insert the group itself.
+        if_matched: Option<String>,
+        otherwise: String,
+    },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum CaseModifier {
+    Upcase,
+    Downcase,
+    Capitalize,
+    Camelcase,
+    Pascalcase,
 }
 
 impl Snippet {
     pub fn parse(source: &str) -> Result<Self> {
-        let mut text = String::with_capacity(source.len());
-        let mut tabstops = BTreeMap::new();
-        parse_snippet(source, false, &mut text, &mut tabstops)
-            .context("failed to parse snippet")?;
+        Self::parse_with_variables(source, |_| None)
+    }
+
+    /// Parses a snippet, replacing variables such as `$TM_FILENAME` with the
+    /// values returned by `resolve_variable`.
+    ///
+    /// Variables for which `resolve_variable` returns `None` are unknown, and
+    /// are inserted as placeholders containing their name.
+    pub fn parse_with_variables(
+        source: &str,
+        resolve_variable: impl Fn(&str) -> Option<String>,
+    ) -> Result<Self> {
+        // Resolve each variable once, so that variables such as `$UUID` are
+        // stable across both parsing passes.
+        let resolved_variables = RefCell::new(HashMap::<String, Option<String>>::default());
+        let resolve_variable = |name: &str| {
+            resolved_variables
+                .borrow_mut()
+                .entry(name.to_string())
+                .or_insert_with(|| resolve_variable(name))
+                .clone()
+        };
+
+        let mut parser = Parser::new(&resolve_variable, BTreeMap::new());
+        parser.parse(source)?;
+
+        // The text of a transform depends on the text of its tabstop, which may
+        // only appear later in the snippet, so parse again once it is known.
+        if parser.has_transforms {
+            let tabstop_texts = parser
+                .tabstops
+                .iter()
+                .filter_map(|(index, tabstop)| {
+                    let range = tabstop.ranges.first()?;
+                    let text = &parser.text[range.start as usize..range.end as usize];
+                    Some((*index, text.to_string()))
+                })
+                .collect();
+            parser = Parser::new(&resolve_variable, tabstop_texts);
+            parser.parse(source)?;
+        }
+
+        let Parser {
+            text,
+            mut tabstops,
+            variable_tabstops,
+            ..
+        } = parser;
 
         let len = text.len() as isize;
-        let final_tabstop = tabstops.remove(&0);
-        let mut tabstops = tabstops.into_values().collect::<Vec<_>>();
+        let final_tabstop = tabstops
+            .remove(&0)
+            .filter(|tabstop| !tabstop.ranges.is_empty());
+        let mut tabstops = tabstops
+            .into_values()
+            .chain(variable_tabstops.into_iter().map(|(_, tabstop)| tabstop))
+            .filter(|tabstop| !tabstop.ranges.is_empty())
+            .collect::<Vec<_>>();
 
         if let Some(final_tabstop) = final_tabstop {
             tabstops.push(final_tabstop);
         } else {
             let end_tabstop = TabStop {
                 ranges: [len..len].into_iter().collect(),
-                choices: None,
+                ..Default::default()
             };
 
             if !tabstops.last().map_or(false, |t| *t == end_tabstop) {
@@ -42,95 +130,330 @@ impl Snippet {
     }
 }
 
-fn parse_snippet<'a>(
-    mut source: &'a str,
-    nested: bool,
-    text: &mut String,
-    tabstops: &mut BTreeMap<usize, TabStop>,
-) -> Result<&'a str> {
-    loop {
-        match source.chars().next() {
-            None => return Ok(""),
-            Some('$') => {
-                source = parse_tabstop(&source[1..], text, tabstops)?;
+impl Transform {
+    fn new(pattern: &str, format: Vec<FormatItem>, options: &str) -> Result<Self> {
+        let mut builder = RegexBuilder::new(pattern);
+        let mut global = false;
+        for option in options.chars() {
+            match option {
+                'g' => global = true,
+                'i' => {
+                    builder.case_insensitive(true);
+                }
+                'm' => {
+                    builder.multi_line(true);
+                }
+                's' => {
+                    builder.dot_matches_new_line(true);
+                }
+                _ => {}
             }
-            Some('\\') => {
-                // As specified in the LSP spec (`Grammar` section),
-                // backslashes can escape some characters:
-                // https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#snippet_syntax
-                source = &source[1..];
-                if let Some(c) = source.chars().next() {
-                    if c == '$' || c == '\\' || c == '}' {
-                        text.push(c);
-                        // All escapable characters are 1 byte long:
-                        source = &source[1..];
+        }
+        let regex = builder
+            .build()
+            .with_context(|| format!("invalid regular expression {pattern:?}"))?;
+        Ok(Self {
+            regex,
+            format,
+            global,
+        })
+    }
+
+    /// Replaces the matches of the regular expression in `input` with the
+    /// format, leaving the rest of `input` unchanged.
+    pub fn apply(&self, input: &str) -> String {
+        let mut result = String::with_capacity(input.len());
+        let mut last_match_end = 0;
+        for captures in self.regex.captures_iter(input) {
+            let Some(whole_match) = captures.get(0) else {
+                continue;
+            };
+            result.push_str(&input[last_match_end..whole_match.start()]);
+            self.format_into(&captures, &mut result);
+            last_match_end = whole_match.end();
+            if !self.global {
+                break;
+            }
+        }
+        result.push_str(&input[last_match_end..]);
+        result
+    }
+
+    fn format_into(&self, captures: &Captures, result: &mut String) {
+        let group = |index: usize| captures.get(index).map_or("", |group| group.as_str());
+        for item in &self.format {
+            match item {
+                FormatItem::Text(text) => result.push_str(text),
+                FormatItem::Group(index, None) => result.push_str(group(*index)),
+                FormatItem::Group(index, Some(modifier)) => {
+                    result.push_str(&modifier.apply(group(*index)))
+                }
+                FormatItem::Conditional {
+                    group: index,
+                    if_matched,
+                    otherwise,
+                } => {
+                    let value = group(*index);
+                    if value.is_empty() {
+                        result.push_str(otherwise);
                     } else {
-                        text.push('\\');
+                        result.push_str(if_matched.as_deref().unwrap_or(value));
                     }
-                } else {
-                    text.push('\\');
                 }
             }
-            Some('}') => {
-                if nested {
-                    return Ok(source);
-                } else {
-                    text.push('}');
+        }
+    }
+}
+
+impl PartialEq for Transform {
+    fn eq(&self, other: &Self) -> bool {
+        self.regex.as_str() == other.regex.as_str()
+            && self.format == other.format
+            && self.global == other.global
+    }
+}
+
+impl CaseModifier {
+    fn parse(name: &str) -> Result<Self> {
+        Ok(match name {
+            "upcase" => Self::Upcase,
+            "downcase" => Self::Downcase,
+            "capitalize" => Self::Capitalize,
+            "camelcase" => Self::Camelcase,
+            "pascalcase" => Self::Pascalcase,
+            _ => anyhow::bail!("unknown case modifier {name:?}"),
+        })
+    }
+
+    fn apply(self, text: &str) -> String {
+        match self {
+            Self::Upcase => text.to_uppercase(),
+            Self::Downcase => text.to_lowercase(),
+            Self::Capitalize => capitalize(text),
+            Self::Camelcase | Self::Pascalcase => text
+                .split(|c: char| !c.is_alphanumeric())
+                .filter(|word| !word.is_empty())
+                .enumerate()
+                .map(|(ix, word)| {
+                    if ix == 0 && self == Self::Camelcase {
+                        let mut chars = word.chars();
+                        chars
+                            .next()
+                            .map(|first| first.to_lowercase().chain(chars).collect())
+                            .unwrap_or_default()
+                    } else {
+                        capitalize(word)
+                    }
+                })
+                .collect(),
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+struct Parser<'a> {
+    text: String,
+    tabstops: BTreeMap<usize, TabStop>,
+    /// Placeholders for unknown variables, in the order they appear.
+    variable_tabstops: Vec<(String, TabStop)>,
+    resolve_variable: &'a dyn Fn(&str) -> Option<String>,
+    /// The initial text of each tabstop, from which the text of its transforms is computed.
+    tabstop_texts: BTreeMap<usize, String>,
+    has_transforms: bool,
+}
+
+impl<'a> Parser<'a> {
+    fn new(
+        resolve_variable: &'a dyn Fn(&str) -> Option<String>,
+        tabstop_texts: BTreeMap<usize, String>,
+    ) -> Self {
+        Self {
+            text: String::new(),
+            tabstops: BTreeMap::new(),
+            variable_tabstops: Vec::new(),
+            resolve_variable,
+            tabstop_texts,
+            has_transforms: false,
+        }
+    }
+
+    fn parse(&mut self, source: &str) -> Result<()> {
+        self.text.reserve(source.len());
+        self.parse_snippet(source, false)
+            .context("failed to parse snippet")?;
+        Ok(())
+    }
+
+    fn parse_snippet<'s>(&mut self, mut source: &'s str, nested: bool) -> Result<&'s str> {
+        loop {
+            match source.chars().next() {
+                None => return Ok(""),
+                Some('$') => {
+                    source = self.parse_tabstop(&source[1..])?;
+                }
+                Some('\\') => {
+                    // As specified in the LSP spec (`Grammar` section),
+                    // backslashes can escape some characters:
+                    // https://microsoft.github.io/language-server-protocol/specifications/lsp/3.17/specification/#snippet_syntax
                     source = &source[1..];
+                    if let Some(c) = source.chars().next() {
+                        if c == '$' || c == '\\' || c == '}' {
+                            self.text.push(c);
+                            // All escapable characters are 1 byte long:
+                            source = &source[1..];
+                        } else {
+                            self.text.push('\\');
+                        }
+                    } else {
+                        self.text.push('\\');
+                    }
+                }
+                Some('}') => {
+                    if nested {
+                        return Ok(source);
+                    } else {
+                        self.text.push('}');
+                        source = &source[1..];
+                    }
+                }
+                Some(_) => {
+                    let chunk_end = source.find(['}', '$', '\\']).unwrap_or(source.len());
+                    let (chunk, rest) = source.split_at(chunk_end);
+                    self.text.push_str(chunk);
+                    source = rest;
                 }
-            }
-            Some(_) => {
-                let chunk_end = source.find(['}', '$', '\\']).unwrap_or(source.len());
-                let (chunk, rest) = source.split_at(chunk_end);
-                text.push_str(chunk);
-                source = rest;
             }
         }
     }
-}
 
-fn parse_tabstop<'a>(
-    mut source: &'a str,
-    text: &mut String,
-    tabstops: &mut BTreeMap<usize, TabStop>,
-) -> Result<&'a str> {
-    let tabstop_start = text.len();
-    let tabstop_index;
-    let mut choices = None;
-
-    if source.starts_with('{') {
-        let (index, rest) = parse_int(&source[1..])?;
-        tabstop_index = index;
-        source = rest;
-
-        if source.starts_with("|") {
-            (source, choices) = parse_choices(&source[1..], text)?;
+    fn parse_tabstop<'s>(&mut self, mut source: &'s str) -> Result<&'s str> {
+        let name_start = source.strip_prefix('{').unwrap_or(source);
+        if name_start.starts_with(|c: char| c == '_' || c.is_ascii_alphabetic()) {
+            return self.parse_variable(source);
+        }
+
+        let tabstop_start = self.text.len();
+        let tabstop_index;
+        let mut choices = None;
+
+        if source.starts_with('{') {
+            let (index, rest) = parse_int(&source[1..])?;
+            tabstop_index = index;
+            source = rest;
+
+            if let Some(rest) = source.strip_prefix('/') {
+                let (transform, rest) = parse_transform(rest)?;
+                let tabstop_text = self
+                    .tabstop_texts
+                    .get(&tabstop_index)
+                    .map_or("", String::as_str);
+                self.text.push_str(&transform.apply(tabstop_text));
+                self.has_transforms = true;
+                self.tabstops
+                    .entry(tabstop_index)
+                    .or_default()
+                    .transforms
+                    .push((tabstop_start as isize..self.text.len() as isize, transform));
+                return Ok(rest);
+            }
+
+            if source.starts_with("|") {
+                (source, choices) = parse_choices(&source[1..], &mut self.text)?;
+            }
+
+            if source.starts_with(':') {
+                source = self.parse_snippet(&source[1..], true)?;
+            }
+
+            if source.starts_with('}') {
+                source = &source[1..];
+            } else {
+                anyhow::bail!("expected a closing brace");
+            }
+        } else {
+            let (index, rest) = parse_int(source)?;
+            tabstop_index = index;
+            source = rest;
         }
 
-        if source.starts_with(':') {
-            source = parse_snippet(&source[1..], true, text, tabstops)?;
+        let tabstop = self.tabstops.entry(tabstop_index).or_default();
+        if tabstop.ranges.is_empty() {
+            tabstop.choices = choices;
         }
+        tabstop
+            .ranges
+            .push(tabstop_start as isize..self.text.len() as isize);
+        Ok(source)
+    }
 
-        if source.starts_with('}') {
-            source = &source[1..];
+    fn parse_variable<'s>(&mut self, source: &'s str) -> Result<&'s str> {
+        let variable_start = self.text.len();
+        let Some(source) = source.strip_prefix('{') else {
+            let (name, rest) = parse_variable_name(source);
+            match (self.resolve_variable)(name) {
+                Some(value) => self.text.push_str(&value),
+                None => self.push_variable_placeholder(name, variable_start),
+            }
+            return Ok(rest);
+        };
+
+        let (name, mut source) = parse_variable_name(source);
+        let value = (self.resolve_variable)(name);
+
+        if let Some(rest) = source.strip_prefix('/') {
+            let (transform, rest) = parse_transform(rest)?;
+            match value {
+                Some(value) => self.text.push_str(&transform.apply(&value)),
+                None => self.push_variable_placeholder(name, variable_start),
+            }
+            return Ok(rest);
+        }
+
+        if let Some(rest) = source.strip_prefix(':') {
+            match value.filter(|value| !value.is_empty()) {
+                Some(value) => {
+                    self.text.push_str(&value);
+                    // Parse the unused default only to find where it ends.
+                    let mut default = Parser::new(self.resolve_variable, BTreeMap::new());
+                    source = default.parse_snippet(rest, true)?;
+                }
+                None => source = self.parse_snippet(rest, true)?,
+            }
         } else {
-            anyhow::bail!("expected a closing brace");
+            match value {
+                Some(value) => self.text.push_str(&value),
+                None => self.push_variable_placeholder(name, variable_start),
+            }
         }
-    } else {
-        let (index, rest) = parse_int(source)?;
-        tabstop_index = index;
-        source = rest;
+
+        source.strip_prefix('}').context("expected a closing brace")
     }
 
-    tabstops
-        .entry(tabstop_index)
-        .or_insert_with(|| TabStop {
-            ranges: Default::default(),
-            choices,
-        })
-        .ranges
-        .push(tabstop_start as isize..text.len() as isize);
-    Ok(source)
+    fn push_variable_placeholder(&mut self, name: &str, start: usize) {
+        self.text.push_str(name);
+        let range = start as isize..self.text.len() as isize;
+        match self
+            .variable_tabstops
+            .iter_mut()
+            .find(|(variable, _)| variable == name)
+        {
+            Some((_, tabstop)) => tabstop.ranges.push(range),
+            None => self.variable_tabstops.push((
+                name.to_string(),
+                TabStop {
+                    ranges: [range].into_iter().collect(),
+                    ..Default::default()
+                },
+            )),
+        }
+    }
 }
 
 fn parse_int(source: &str) -> Result<(usize, &str)> {
@@ -142,6 +465,159 @@ fn parse_int(source: &str) -> Result<(usize, &str)> {
     Ok((prefix.parse()?, suffix))
 }
 
+fn parse_variable_name(source: &str) -> (&str, &str) {
+    let len = source
+        .find(|c: char| c != '_' && !c.is_ascii_alphanumeric())
+        .unwrap_or(source.len());
+    source.split_at(len)
+}
+
+/// Parses the `regex/format/options}` following the first slash of a transform.
+fn parse_transform(source: &str) -> Result<(Transform, &str)> {
+    let mut pattern = String::new();
+    let mut chars = source.char_indices();
+    let format_start = loop {
+        match chars.next() {
+            None => anyhow::bail!("expected a closing slash after the regular expression"),
+            Some((ix, '/')) => break ix + 1,
+            Some((_, '\\')) => match chars.next() {
+                Some((_, '/')) => pattern.push('/'),
+                Some((_, c)) => {
+                    pattern.push('\\');
+                    pattern.push(c);
+                }
+                None => pattern.push('\\'),
+            },
+            Some((_, c)) => pattern.push(c),
+        }
+    };
+
+    let (format, source) = parse_format(&source[format_start..])?;
+    let options_end = source.find('}').context("expected a closing brace")?;
+    let transform = Transform::new(&pattern, format, &source[..options_end])?;
+    Ok((transform, &source[options_end + 1..]))
+}
+
+/// Parses the format of a transform, up to and including its closing slash.
+fn parse_format(mut source: &str) -> Result<(Vec<FormatItem>, &str)> {
+    let mut items = Vec::new();
+    let mut text = String::new();
+
+    loop {
+        match source.chars().next() {
+            None => anyhow::bail!("expected a closing slash after the format"),
+            Some('/') => {
+                if !text.is_empty() {
+                    items.push(FormatItem::Text(text));
+                }
+                return Ok((items, &source[1..]));
+            }
+            Some('\\') => {
+                source = &source[1..];
+                match source.chars().next() {
+                    Some(c @ ('/' | '$' | '\\' | '}')) => {
+                        text.push(c);
+                        source = &source[1..];
+                    }
+                    _ => text.push('\\'),
+                }
+            }
+            Some('$') => {
+                let rest = &source[1..];
+                let item = if let Some(rest) = rest.strip_prefix('{') {
+                    let (group, rest) = parse_int(rest)?;
+                    let (item, rest) = parse_format_group(group, rest)?;
+                    source = rest;
+                    item
+                } else if let Ok((group, rest)) = parse_int(rest) {
+                    source = rest;
+                    FormatItem::Group(group, None)
+                } else {
+                    text.push('$');
+                    source = rest;
+                    continue;
+                };
+                if !text.is_empty() {
+                    items.push(FormatItem::Text(std::mem::take(&mut text)));
+                }
+                items.push(item);
+            }
+            Some(c) => {
+                text.push(c);
+                source = &source[c.len_utf8()..];
+            }
+        }
+    }
+}
+
+/// Parses the rest of a `${1...}` format item, following the group index.
+fn parse_format_group(group: usize, source: &str) -> Result<(FormatItem, &str)> {
+    if let Some(rest) = source.strip_prefix('}') {
+        return Ok((FormatItem::Group(group, None), rest));
+    }
+    let source = source
+        .strip_prefix(':')
+        .context("expected a colon or a closing brace")?;
+
+    if let Some(rest) = source.strip_prefix('/') {
+        let name_end = rest.find('}').context("expected a closing brace")?;
+        let modifier = CaseModifier::parse(&rest[..name_end])?;
+        Ok((
+            FormatItem::Group(group, Some(modifier)),
+            &rest[name_end + 1..],
+        ))
+    } else if let Some(rest) = source.strip_prefix('+') {
+        let (if_matched, rest) = parse_format_text(rest, '}')?;
+        let item = FormatItem::Conditional {
+            group,
+            if_matched: Some(if_matched),
+            otherwise: String::new(),
+        };
+        Ok((item, rest))
+    } else if let Some(rest) = source.strip_prefix('?') {
+        let (if_matched, rest) = parse_format_text(rest, ':')?;
+        let (otherwise, rest) = parse_format_text(rest, '}')?;
+        let item = FormatItem::Conditional {
+            group,
+            if_matched: Some(if_matched),
+            otherwise,
+        };
+        Ok((item, rest))
+    } else {
+        let rest = source.strip_prefix('-').unwrap_or(source);
+        let (otherwise, rest) = parse_format_text(rest, '}')?;
+        let item = FormatItem::Conditional {
+            group,
+            if_matched: None,
+            otherwise,
+        };
+        Ok((item, rest))
+    }
+}
+
+/// Parses the text of a conditional format item, up to and including `terminator`.
+fn parse_format_text(source: &str, terminator: char) -> Result<(String, &str)> {
+    let mut text = String::new();
+    let mut chars = source.char_indices();
+    loop {
+        match chars.next() {
+            None => anyhow::bail!("expected {terminator:?}"),
+            Some((ix, c)) if c == terminator => {
+                return Ok((text, &source[ix + c.len_utf8()..]));
+            }
+            Some((_, '\\')) => match chars.next() {
+                Some((_, c @ (':' | '}' | '\\' | '/' | '$'))) => text.push(c),
+                Some((_, c)) => {
+                    text.push('\\');
+                    text.push(c);
+                }
+                None => text.push('\\'),
+            },
+            Some((_, c)) => text.push(c),
+        }
+    }
+}
+
 fn parse_choices<'a>(
     mut source: &'a str,
     text: &mut String,
@@ -324,6 +800,121 @@ mod tests {
         assert_eq!(tabstops(&snippet), &[vec![4..4], vec![7..7]]);
     }
 
+    #[test]
+    fn test_snippet_with_variables() {
+        let resolve_variable = |name: &str| match name {
+            "TM_FILENAME" => Some("main.rs".to_string()),
+            "TM_SELECTED_TEXT" => Some(String::new()),
+            _ => None,
+        };
+
+        let snippet =
+            Snippet::parse_with_variables("// $TM_FILENAME: ${TM_FILENAME}$1", resolve_variable)
+                .unwrap();
+        assert_eq!(snippet.text, "// main.rs: main.rs");
+        assert_eq!(tabstops(&snippet), &[vec![19..19]]);
+
+        // Empty variables are replaced with their default, which can contain tabstops.
+        let snippet = Snippet::parse_with_variables(
+            "${TM_SELECTED_TEXT:${1:value}} ${TM_FILENAME:unused}",
+            resolve_variable,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "value main.rs");
+        assert_eq!(tabstops(&snippet), &[vec![0..5], vec![13..13]]);
+
+        // Unknown variables are inserted as placeholders, after the numbered tabstops.
+        let snippet = Snippet::parse_with_variables("$1 $FOO ${FOO} $0", resolve_variable).unwrap();
+        assert_eq!(snippet.text, " FOO FOO ");
+        assert_eq!(
+            tabstops(&snippet),
+            &[vec![0..0], vec![1..4, 5..8], vec![9..9]]
+        );
+
+        // `Snippet::parse` treats every variable as unknown.
+        let snippet = Snippet::parse("${TM_FILENAME/(.*)/${1:/upcase}/}").unwrap();
+        assert_eq!(snippet.text, "TM_FILENAME");
+        assert_eq!(tabstops(&snippet), &[vec![0..11], vec![11..11]]);
+    }
+
+    #[test]
+    fn test_snippet_with_variable_transforms() {
+        let resolve_variable = |name: &str| match name {
+            "TM_FILENAME" => Some("snippet_parser.rs".to_string()),
+            _ => None,
+        };
+
+        let snippet = Snippet::parse_with_variables(
+            r"struct ${TM_FILENAME/(.*)\..+$/${1:/pascalcase}/};",
+            resolve_variable,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "struct SnippetParser;");
+
+        let snippet = Snippet::parse_with_variables(
+            "${TM_FILENAME/_/-/g} ${TM_FILENAME/[a-z]+/${0:/capitalize}/}",
+            resolve_variable,
+        )
+        .unwrap();
+        assert_eq!(snippet.text, "snippet-parser.rs Snippet_parser.rs");
+    }
+
+    #[test]
+    fn test_snippet_with_tabstop_transforms() {
+        let snippet = Snippet::parse("${1:foo_bar} ${1/(.*)/${1:/upcase}/} $0").unwrap();
+        assert_eq!(snippet.text, "foo_bar FOO_BAR ");
+        assert_eq!(tabstops(&snippet), &[vec![0..7], vec![16..16]]);
+        assert_eq!(
+            snippet.tabstops[0]
+                .transforms
+                .iter()
+                .map(|(range, _)| range.clone())
+                .collect::<Vec<_>>(),
+            &[8..15]
+        );
+
+        // Transforms can appear before their tabstop.
+        let snippet = Snippet::parse("${1/(.*)/${1:/camelcase}/} = ${1:foo bar}").unwrap();
+        assert_eq!(snippet.text, "fooBar = foo bar");
+        assert_eq!(tabstops(&snippet), &[vec![9..16], vec![16..16]]);
+        assert_eq!(snippet.tabstops[0].transforms[0].0, 0..6);
+
+        let transform = &snippet.tabstops[0].transforms[0].1;
+        assert_eq!(transform.apply("hello world"), "helloWorld");
+        assert_eq!(transform.apply(""), "");
+    }
+
+    #[test]
+    fn test_transform_formats() {
+        let transform = |source: &str| {
+            let snippet = Snippet::parse(&format!("$1${{1{source}}}")).unwrap();
+            snippet.tabstops[0].transforms[0].1.clone()
+        };
+
+        assert_eq!(transform("/a/b/").apply("aaa"), "baa");
+        assert_eq!(transform("/a/b/g").apply("aaa"), "bbb");
+        assert_eq!(transform("/A/b/gi").apply("aAa"), "bbb");
+        assert_eq!(transform(r"/\//-/g").apply("a/b/c"), "a-b-c");
+        assert_eq!(transform("/(x)?y/$1${1}/").apply("xy y"), "xx y");
+        assert_eq!(transform("/(x)?y/${1:+yes}/g").apply("xy y"), "yes ");
+        assert_eq!(transform("/(x)?y/${1:?yes:no}/g").apply("xy y"), "yes no");
+        assert_eq!(transform("/(x)?y/${1:-none}/g").apply("xy y"), "x none");
+        assert_eq!(transform("/(x)?y/${1:none}/g").apply("xy y"), "x none");
+        assert_eq!(
+            transform("/(.*)/${1:/downcase}/").apply("Hello World"),
+            "hello world"
+        );
+        assert_eq!(
+            transform("/(.*)/${1:/pascalcase}/").apply("hello-big world"),
+            "HelloBigWorld"
+        );
+        assert_eq!(transform(r"/x/\\\$\//").apply("x"), r"\$/");
+
+        assert!(Snippet::parse("${1/(/x/}").is_err());
+        assert!(Snippet::parse("${1/a/${1:/unknown}/}").is_err());
+        assert!(Snippet::parse("${1/a/b}").is_err());
+    }
+
     fn tabstops(snippet: &Snippet) -> Vec<Vec<Range<isize>>> {
         snippet.tabstops.iter().map(|t| t.ranges.to_vec()).collect()
     }
@@ -0,0 +1,659 @@
+use std::path::{Path, PathBuf};
+
+use anyhow::Context as _;
+use regex::{Captures, Regex};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use util::ResultExt as _;
+
+/// A way to find problems (errors, warnings) in the output of a task, to show them as diagnostics.
+/// Either the name of a built-in matcher (`$rustc`, `$cargo`, `$tsc`, `$gcc`, `$eslint-compact`, `$eslint-stylish`),
+/// or a custom matcher definition.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemMatcher {
+    BuiltIn(String),
+    Custom(ProblemMatcherDefinition),
+}
+
+/// A custom problem matcher: one or more regex patterns, matched against consecutive lines of the task output.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemMatcherDefinition {
+    /// The name of a built-in matcher to take the fields that are not set from.
+    #[serde(default)]
+    pub base: Option<String>,
+    /// The name to show as the source of the diagnostics, defaults to the task's label.
+    #[serde(default)]
+    pub source: Option<String>,
+    /// The severity of the problems whose pattern doesn't capture one, defaults to `error`.
+    #[serde(default)]
+    pub severity: Option<ProblemSeverity>,
+    /// The directory that relative paths in the output are resolved against, defaults to the task's working directory.
+    #[serde(default)]
+    pub file_location: Option<String>,
+    /// A pattern, or a list of patterns that have to match consecutive lines of the output.
+    #[serde(default)]
+    pub pattern: Option<ProblemPatterns>,
+}
+
+/// One or several patterns of a problem matcher.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(untagged)]
+pub enum ProblemPatterns {
+    Single(ProblemPattern),
+    Multiple(Vec<ProblemPattern>),
+}
+
+impl ProblemPatterns {
+    pub fn as_slice(&self) -> &[ProblemPattern] {
+        match self {
+            Self::Single(pattern) => std::slice::from_ref(pattern),
+            Self::Multiple(patterns) => patterns,
+        }
+    }
+}
+
+/// A regex matched against a line of the task output, along with the indices of the capture groups holding the parts of a problem.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct ProblemPattern {
+    /// The regular expression to match a line with.
+    pub regexp: String,
+    /// The group with the path of the file.
+    #[serde(default)]
+    pub file: Option<usize>,
+    /// The group with the 1-based line of the problem.
+    #[serde(default)]
+    pub line: Option<usize>,
+    /// The group with the 1-based column of the problem.
+    #[serde(default)]
+    pub column: Option<usize>,
+    /// The group with the 1-based line the problem ends at.
+    #[serde(default)]
+    pub end_line: Option<usize>,
+    /// The group with the 1-based column the problem ends at.
+    #[serde(default)]
+    pub end_column: Option<usize>,
+    /// The group with the severity of the problem: `error`, `warning`, `info` or `hint`.
+    #[serde(default)]
+    pub severity: Option<usize>,
+    /// The group with the code of the problem.
+    #[serde(default)]
+    pub code: Option<usize>,
+    /// The group with the message of the problem.
+    #[serde(default)]
+    pub message: Option<usize>,
+    /// Whether the last pattern of a multi-line matcher may match several lines in a row, producing a problem for each.
+    #[serde(default, rename = "loop")]
+    pub repeat: bool,
+}
+
+/// How severe a problem found in a task's output is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ProblemSeverity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl ProblemSeverity {
+    fn parse(severity: &str) -> Option<Self> {
+        match severity.to_ascii_lowercase().as_str() {
+            "error" | "fatal" => Some(Self::Error),
+            "warning" | "warn" => Some(Self::Warning),
+            "info" | "information" | "note" => Some(Self::Info),
+            "hint" | "help" => Some(Self::Hint),
+            _ => None,
+        }
+    }
+}
+
+/// A problem found in the output of a task.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Problem {
+    pub path: PathBuf,
+    /// The 1-based line of the problem.
+    pub line: u32,
+    /// The 1-based column of the problem, if known.
+    pub column: Option<u32>,
+    pub end_line: Option<u32>,
+    pub end_column: Option<u32>,
+    pub severity: ProblemSeverity,
+    pub code: Option<String>,
+    pub message: String,
+    pub source: Option<String>,
+}
+
+impl ProblemMatcher {
+    /// Returns the definition of the matcher, with the fields it doesn't set taken from its base, if any.
+    pub fn resolve(&self) -> anyhow::Result<ProblemMatcherDefinition> {
+        let definition = match self {
+            Self::BuiltIn(name) => return built_in_matcher(name),
+            Self::Custom(definition) => definition.clone(),
+        };
+        let Some(base) = definition.base.as_deref() else {
+            anyhow::ensure!(
+                definition.pattern.is_some(),
+                "Problem matcher has neither a `pattern` nor a `base`"
+            );
+            return Ok(definition);
+        };
+        let base = built_in_matcher(base)?;
+        Ok(ProblemMatcherDefinition {
+            base: None,
+            source: definition.source.or(base.source),
+            severity: definition.severity.or(base.severity),
+            file_location: definition.file_location.or(base.file_location),
+            pattern: definition.pattern.or(base.pattern),
+        })
+    }
+}
+
+fn built_in_matcher(name: &str) -> anyhow::Result<ProblemMatcherDefinition> {
+    let (source, patterns) = match name {
+        "$rustc" | "$cargo" => (
+            "rustc",
+            vec![
+                ProblemPattern {
+                    regexp: r"^(warning|error)(?:\[(\w+)\])?: (.*)$".into(),
+                    severity: Some(1),
+                    code: Some(2),
+                    message: Some(3),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s*-->\s+(.+?):(\d+):(\d+)$".into(),
+                    file: Some(1),
+                    line: Some(2),
+                    column: Some(3),
+                    ..ProblemPattern::default()
+                },
+            ],
+        ),
+        "$tsc" | "$tsc-watch" => (
+            "tsc",
+            vec![ProblemPattern {
+                regexp: r"^([^\s].*)[\(:](\d+)[,:](\d+)(?:\):\s+|\s+-\s+)(error|warning|info)\s+(TS\d+)\s*:\s*(.*)$".into(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                code: Some(5),
+                message: Some(6),
+                ..ProblemPattern::default()
+            }],
+        ),
+        "$gcc" => (
+            "gcc",
+            vec![ProblemPattern {
+                regexp: r"^(.+?):(\d+):(\d+):\s+(?:fatal\s+)?(warning|error):\s+(.*)$".into(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                ..ProblemPattern::default()
+            }],
+        ),
+        "$eslint-compact" => (
+            "eslint",
+            vec![ProblemPattern {
+                regexp: r"^(.+):\sline\s(\d+),\scol\s(\d+),\s(Error|Warning|Info)\s-\s(.+)\s\((.+)\)$"
+                    .into(),
+                file: Some(1),
+                line: Some(2),
+                column: Some(3),
+                severity: Some(4),
+                message: Some(5),
+                code: Some(6),
+                ..ProblemPattern::default()
+            }],
+        ),
+        "$eslint-stylish" | "$eslint" => (
+            "eslint",
+            vec![
+                ProblemPattern {
+                    regexp: r"^((?:[a-zA-Z]:)*[./\\]+.*?)$".into(),
+                    file: Some(1),
+                    ..ProblemPattern::default()
+                },
+                ProblemPattern {
+                    regexp: r"^\s+(\d+):(\d+)\s+(error|warning|info)\s+(.+?)(?:\s\s+(.*))?$".into(),
+                    line: Some(1),
+                    column: Some(2),
+                    severity: Some(3),
+                    message: Some(4),
+                    code: Some(5),
+                    repeat: true,
+                    ..ProblemPattern::default()
+                },
+            ],
+        ),
+        _ => anyhow::bail!("Unknown problem matcher `{name}`"),
+    };
+    Ok(ProblemMatcherDefinition {
+        source: Some(source.to_string()),
+        pattern: Some(ProblemPatterns::Multiple(patterns)),
+        ..ProblemMatcherDefinition::default()
+    })
+}
+
+/// Finds the problems in the output of a task, with relative paths resolved against `cwd`
+/// unless the matcher specifies its own `file_location`.
+pub fn match_problems(
+    matchers: &[ProblemMatcherDefinition],
+    output: &str,
+    cwd: Option<&Path>,
+) -> Vec<Problem> {
+    ProblemScanner::new(matchers, cwd.map(Path::to_path_buf)).scan(output)
+}
+
+/// Finds the problems in the output of a task as it is written, with the patterns of its
+/// matchers compiled once.
+pub struct ProblemScanner {
+    matchers: Vec<CompiledProblemMatcher>,
+    cwd: Option<PathBuf>,
+}
+
+struct CompiledProblemMatcher {
+    definition: ProblemMatcherDefinition,
+    regexes: Vec<Regex>,
+    /// The index of the pattern the next line has to match to continue the current problem.
+    next_pattern: usize,
+    partial: PartialProblem,
+}
+
+impl ProblemScanner {
+    /// Compiles the patterns of the matchers, skipping the matchers with invalid ones.
+    pub fn new(matchers: &[ProblemMatcherDefinition], cwd: Option<PathBuf>) -> Self {
+        let matchers = matchers
+            .iter()
+            .filter_map(|definition| {
+                let patterns = definition.pattern.as_ref()?.as_slice();
+                let regexes = patterns
+                    .iter()
+                    .map(|pattern| {
+                        Regex::new(&pattern.regexp).with_context(|| {
+                            format!("invalid problem pattern {:?}", pattern.regexp)
+                        })
+                    })
+                    .collect::<anyhow::Result<Vec<_>>>()
+                    .log_err()?;
+                if regexes.is_empty() {
+                    return None;
+                }
+                Some(CompiledProblemMatcher {
+                    definition: definition.clone(),
+                    regexes,
+                    next_pattern: 0,
+                    partial: PartialProblem::default(),
+                })
+            })
+            .collect();
+        Self { matchers, cwd }
+    }
+
+    /// Returns the problems found in the given lines of output. A problem spanning several lines
+    /// may be continued by the output passed to the next call.
+    pub fn scan(&mut self, output: &str) -> Vec<Problem> {
+        let mut problems = Vec::new();
+        for matcher in &mut self.matchers {
+            for line in output.lines() {
+                matcher.scan_line(line, self.cwd.as_deref(), &mut problems);
+            }
+        }
+        problems
+    }
+}
+
+impl CompiledProblemMatcher {
+    fn scan_line(&mut self, line: &str, cwd: Option<&Path>, problems: &mut Vec<Problem>) {
+        let Some(patterns) = self
+            .definition
+            .pattern
+            .as_ref()
+            .map(ProblemPatterns::as_slice)
+        else {
+            return;
+        };
+        let base_directory = self
+            .definition
+            .file_location
+            .as_deref()
+            .map(Path::new)
+            .or(cwd);
+        let mut emit = |partial: PartialProblem| {
+            if let Some(problem) = partial.into_problem(&self.definition, base_directory) {
+                problems.push(problem);
+            }
+        };
+
+        if self.next_pattern > 0 {
+            let pattern = &patterns[self.next_pattern];
+            if let Some(captures) = self.regexes[self.next_pattern].captures(line) {
+                if self.next_pattern + 1 == patterns.len() {
+                    let mut problem = self.partial.clone();
+                    problem.fill(pattern, &captures);
+                    emit(problem);
+                    if !pattern.repeat {
+                        self.next_pattern = 0;
+                        self.partial = PartialProblem::default();
+                    }
+                } else {
+                    self.partial.fill(pattern, &captures);
+                    self.next_pattern += 1;
+                }
+                return;
+            }
+            self.next_pattern = 0;
+            self.partial = PartialProblem::default();
+        }
+
+        if let Some(captures) = self.regexes[0].captures(line) {
+            self.partial.fill(&patterns[0], &captures);
+            if patterns.len() == 1 {
+                emit(std::mem::take(&mut self.partial));
+            } else {
+                self.next_pattern = 1;
+            }
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct PartialProblem {
+    file: Option<String>,
+    line: Option<u32>,
+    column: Option<u32>,
+    end_line: Option<u32>,
+    end_column: Option<u32>,
+    severity: Option<ProblemSeverity>,
+    code: Option<String>,
+    message: Option<String>,
+}
+
+impl PartialProblem {
+    fn fill(&mut self, pattern: &ProblemPattern, captures: &Captures) {
+        let group = |index: Option<usize>| {
+            captures
+                .get(index?)
+                .map(|group| group.as_str().trim())
+                .filter(|group| !group.is_empty())
+        };
+        let number = |index: Option<usize>| group(index)?.parse::<u32>().ok();
+        if let Some(file) = group(pattern.file) {
+            self.file = Some(file.to_string());
+        }
+        if let Some(line) = number(pattern.line) {
+            self.line = Some(line);
+        }
+        if let Some(column) = number(pattern.column) {
+            self.column = Some(column);
+        }
+        if let Some(end_line) = number(pattern.end_line) {
+            self.end_line = Some(end_line);
+        }
+        if let Some(end_column) = number(pattern.end_column) {
+            self.end_column = Some(end_column);
+        }
+        if let Some(severity) = group(pattern.severity).and_then(ProblemSeverity::parse) {
+            self.severity = Some(severity);
+        }
+        if let Some(code) = group(pattern.code) {
+            self.code = Some(code.to_string());
+        }
+        if let Some(message) = group(pattern.message) {
+            self.message = Some(message.to_string());
+        }
+    }
+
+    fn into_problem(
+        self,
+        matcher: &ProblemMatcherDefinition,
+        base_directory: Option<&Path>,
+    ) -> Option<Problem> {
+        let file = PathBuf::from(self.file?);
+        let path = match base_directory {
+            Some(base_directory) if file.is_relative() => base_directory.join(file),
+            _ => file,
+        };
+        Some(Problem {
+            path,
+            line: self.line.unwrap_or(1),
+            column: self.column,
+            end_line: self.end_line,
+            end_column: self.end_column,
+            severity: self
+                .severity
+                .or(matcher.severity)
+                .unwrap_or(ProblemSeverity::Error),
+            code: self.code,
+            message: self.message?,
+            source: matcher.source.clone(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn built_in(name: &str) -> ProblemMatcherDefinition {
+        ProblemMatcher::BuiltIn(name.to_string()).resolve().unwrap()
+    }
+
+    #[test]
+    fn test_multi_line_rustc_problems() {
+        let output = "\
+   Compiling demo v0.1.0 (/demo)
+warning: unused variable: `x`
+ --> src/main.rs:2:9
+  |
+2 |     let x = 5;
+  |         ^ help: if this is intentional, prefix it with an underscore: `_x`
+error[E0308]: mismatched types
+  --> src/lib.rs:10:18
+   |
+error: could not compile `demo` (bin \"demo\") due to 1 previous error
+";
+        let problems = match_problems(&[built_in("$cargo")], output, Some(Path::new("/demo")));
+        assert_eq!(
+            problems,
+            vec![
+                Problem {
+                    path: PathBuf::from("/demo/src/main.rs"),
+                    line: 2,
+                    column: Some(9),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Warning,
+                    code: None,
+                    message: "unused variable: `x`".to_string(),
+                    source: Some("rustc".to_string()),
+                },
+                Problem {
+                    path: PathBuf::from("/demo/src/lib.rs"),
+                    line: 10,
+                    column: Some(18),
+                    end_line: None,
+                    end_column: None,
+                    severity: ProblemSeverity::Error,
+                    code: Some("E0308".to_string()),
+                    message: "mismatched types".to_string(),
+                    source: Some("rustc".to_string()),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_single_line_problems() {
+        let output = "\
+src/index.ts(3,7): error TS2322: Type 'string' is not assignable to type 'number'.
+src/util.ts:12:1 - warning TS6133: 'foo' is declared but its value is never read.
+Found 2 errors.
+";
+        let problems = match_problems(&[built_in("$tsc")], output, None);
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.path.to_string_lossy().into_owned(),
+                    problem.line,
+                    problem.column,
+                    problem.severity,
+                    problem.code.as_deref(),
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "src/index.ts".to_string(),
+                    3,
+                    Some(7),
+                    ProblemSeverity::Error,
+                    Some("TS2322")
+                ),
+                (
+                    "src/util.ts".to_string(),
+                    12,
+                    Some(1),
+                    ProblemSeverity::Warning,
+                    Some("TS6133")
+                ),
+            ]
+        );
+
+        let problems = match_problems(
+            &[built_in("$gcc")],
+            "main.c:4:5: fatal error: stdio.h: No such file or directory\n",
+            Some(Path::new("/project")),
+        );
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].path, PathBuf::from("/project/main.c"));
+        assert_eq!(
+            problems[0].message,
+            "stdio.h: No such file or directory".to_string()
+        );
+    }
+
+    #[test]
+    fn test_scanning_output_in_chunks() {
+        let mut scanner = ProblemScanner::new(&[built_in("$cargo")], Some(PathBuf::from("/demo")));
+        assert_eq!(
+            scanner.scan("   Compiling demo v0.1.0 (/demo)\nerror[E0425]: cannot find value `y`\n"),
+            Vec::new()
+        );
+        let problems = scanner.scan(" --> src/main.rs:3:13\n  |\n");
+        assert_eq!(problems.len(), 1);
+        assert_eq!(problems[0].path, PathBuf::from("/demo/src/main.rs"));
+        assert_eq!(problems[0].line, 3);
+        assert_eq!(problems[0].code.as_deref(), Some("E0425"));
+        assert_eq!(
+            scanner.scan("error: could not compile `demo`\n"),
+            Vec::new()
+        );
+
+        let invalid = ProblemMatcherDefinition {
+            pattern: Some(ProblemPatterns::Single(ProblemPattern {
+                regexp: "(".to_string(),
+                ..ProblemPattern::default()
+            })),
+            ..ProblemMatcherDefinition::default()
+        };
+        let mut scanner = ProblemScanner::new(&[invalid, built_in("$gcc")], None);
+        assert_eq!(scanner.scan("main.c:4:5: error: oops\n").len(), 1);
+    }
+
+    #[test]
+    fn test_looping_pattern() {
+        let output = "\
+/project/src/a.js
+   1:10  error    'foo' is defined but never used  no-unused-vars
+   3:1   warning  Unexpected console statement     no-console
+
+/project/src/b.js
+  7:3  error  Missing semicolon  semi
+
+3 problems (2 errors, 1 warning)
+";
+        let problems = match_problems(&[built_in("$eslint-stylish")], output, None);
+        assert_eq!(
+            problems
+                .iter()
+                .map(|problem| (
+                    problem.path.to_string_lossy().into_owned(),
+                    problem.line,
+                    problem.message.as_str(),
+                    problem.code.as_deref(),
+                ))
+                .collect::<Vec<_>>(),
+            vec![
+                (
+                    "/project/src/a.js".to_string(),
+                    1,
+                    "'foo' is defined but never used",
+                    Some("no-unused-vars")
+                ),
+                (
+                    "/project/src/a.js".to_string(),
+                    3,
+                    "Unexpected console statement",
+                    Some("no-console")
+                ),
+                (
+                    "/project/src/b.js".to_string(),
+                    7,
+                    "Missing semicolon",
+                    Some("semi")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_custom_matchers() {
+        let matcher: ProblemMatcher = serde_json_lenient::from_str(
+            r#"{
+                "source": "lint",
+                "severity": "warning",
+                "file_location": "/base",
+                "pattern": { "regexp": "^(\\S+):(\\d+) (.*)$", "file": 1, "line": 2, "message": 3 }
+            }"#,
+        )
+        .unwrap();
+        let problems = match_problems(
+            &[matcher.resolve().unwrap()],
+            "src/a.txt:3 trailing whitespace\nall done\n",
+            Some(Path::new("/cwd")),
+        );
+        assert_eq!(
+            problems,
+            vec![Problem {
+                path: PathBuf::from("/base/src/a.txt"),
+                line: 3,
+                column: None,
+                end_line: None,
+                end_column: None,
+                severity: ProblemSeverity::Warning,
+                code: None,
+                message: "trailing whitespace".to_string(),
+                source: Some("lint".to_string()),
+            }]
+        );
+
+        let with_base: ProblemMatcher =
+            serde_json_lenient::from_str(r#"{ "base": "$tsc", "source": "typescript" }"#).unwrap();
+        let resolved = with_base.resolve().unwrap();
+        assert_eq!(resolved.source.as_deref(), Some("typescript"));
+        assert_eq!(resolved.pattern, built_in("$tsc").pattern);
+
+        assert!(
+            ProblemMatcher::BuiltIn("$unknown".to_string())
+                .resolve()
+                .is_err()
+        );
+    }
+}
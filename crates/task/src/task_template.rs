@@ -8,8 +8,8 @@ use util::serde::default_true;
 use util::{ResultExt, truncate_and_remove_front};
 
 use crate::{
-    AttachRequest, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext, TaskId,
-    VariableName, ZED_VARIABLE_NAME_PREFIX,
+    AttachRequest, ProblemMatcher, ResolvedTask, RevealTarget, Shell, SpawnInTerminal, TaskContext,
+    TaskId, VariableName, ZED_VARIABLE_NAME_PREFIX,
     serde_helpers::{non_empty_string_vec, non_empty_string_vec_json_schema},
 };
 
@@ -72,6 +72,25 @@ pub struct TaskTemplate {
     /// Whether to show the command line in the task output.
     #[serde(default = "default_true")]
     pub show_command: bool,
+    /// Labels of the tasks that have to run before this one.
+    /// A task with dependencies may omit its own command, to only run the tasks it depends on.
+    #[serde(default)]
+    pub depends_on: Vec<String>,
+    /// How to run the tasks this task depends on:
+    /// * `sequence` — one after another, in the order they are listed (default)
+    /// * `parallel` — all at once
+    #[serde(default)]
+    pub depends_order: DependsOrder,
+    /// What to do when one of the tasks this task depends on fails:
+    /// * `abort` — do not run the remaining dependencies, nor the task itself (default)
+    /// * `continue` — run the remaining dependencies and the task regardless
+    #[serde(default)]
+    pub on_dependency_failure: DependencyFailurePolicy,
+    /// Problem matchers to turn the output of the task into diagnostics with.
+    /// Each is either the name of a built-in matcher (`$rustc`, `$cargo`, `$tsc`, `$gcc`, `$eslint-compact`, `$eslint-stylish`),
+    /// or a custom matcher with one or more regex patterns.
+    #[serde(default)]
+    pub problem_matchers: Vec<ProblemMatcher>,
 }
 
 #[derive(Deserialize, Eq, PartialEq, Clone, Debug)]
@@ -109,6 +128,28 @@ pub enum HideStrategy {
     OnSuccess,
 }
 
+/// How to run the tasks a task depends on.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependsOrder {
+    /// Run the dependencies one after another, in the order they are listed.
+    #[default]
+    Sequence,
+    /// Run all dependencies at once.
+    Parallel,
+}
+
+/// What to do when one of the tasks a task depends on fails.
+#[derive(Default, Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyFailurePolicy {
+    /// Do not run the remaining dependencies, nor the task itself.
+    #[default]
+    Abort,
+    /// Run the remaining dependencies and the task regardless.
+    Continue,
+}
+
 /// A group of Tasks defined in a JSON file.
 #[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct TaskTemplates(pub Vec<TaskTemplate>);
@@ -133,7 +174,9 @@ impl TaskTemplate {
     /// Every [`ResolvedTask`] gets a [`TaskId`], based on the `id_base` (to avoid collision with various task sources),
     /// and hashes of its template and [`TaskContext`], see [`ResolvedTask`] fields' documentation for more details.
     pub fn resolve_task(&self, id_base: &str, cx: &TaskContext) -> Option<ResolvedTask> {
-        if self.label.trim().is_empty() || self.command.trim().is_empty() {
+        if self.label.trim().is_empty()
+            || (self.command.trim().is_empty() && self.depends_on.is_empty())
+        {
             return None;
         }
 
@@ -209,6 +252,22 @@ impl TaskTemplate {
             &mut substituted_variables,
         )?;
 
+        let mut problem_matchers = Vec::new();
+        for matcher in &self.problem_matchers {
+            let Some(mut matcher) = matcher.resolve().log_err() else {
+                continue;
+            };
+            if let Some(file_location) = matcher.file_location.as_deref() {
+                matcher.file_location = Some(substitute_all_template_variables_in_str(
+                    file_location,
+                    &task_variables,
+                    &variable_names,
+                    &mut substituted_variables,
+                )?);
+            }
+            problem_matchers.push(matcher);
+        }
+
         let task_hash = to_hex_hash(self)
             .context("hashing task template")
             .log_err()?;
@@ -241,6 +300,7 @@ impl TaskTemplate {
             id: id.clone(),
             substituted_variables,
             original_task: self.clone(),
+            task_context: cx.clone(),
             resolved_label: full_label.clone(),
             resolved: SpawnInTerminal {
                 id,
@@ -267,6 +327,7 @@ impl TaskTemplate {
                 show_summary: self.show_summary,
                 show_command: self.show_command,
                 show_rerun: true,
+                problem_matchers,
             },
         })
     }
@@ -464,6 +525,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_resolving_templates_with_dependencies_only() {
+        let task = TaskTemplate {
+            label: "build all".to_string(),
+            depends_on: vec!["build server".to_string(), "build client".to_string()],
+            ..TaskTemplate::default()
+        };
+        let resolved_task = task
+            .resolve_task(TEST_ID_BASE, &TaskContext::default())
+            .expect("should resolve a task that only runs its dependencies");
+        assert!(resolved_task.has_dependencies());
+        assert_eq!(resolved_task.resolved.command, "");
+        assert_eq!(resolved_task.original_task().depends_on, task.depends_on);
+    }
+
     #[test]
     fn test_template_cwd_resolution() {
         let task_without_cwd = TaskTemplate {
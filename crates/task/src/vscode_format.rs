@@ -1,9 +1,12 @@
-use anyhow::bail;
+use anyhow::{anyhow, bail};
 use collections::HashMap;
 use serde::Deserialize;
 use util::ResultExt;
 
-use crate::{EnvVariableReplacer, TaskTemplate, TaskTemplates, VariableName};
+use crate::{
+    DependsOrder, EnvVariableReplacer, ProblemMatcher, ProblemMatcherDefinition, ProblemPattern,
+    ProblemPatterns, ProblemSeverity, TaskTemplate, TaskTemplates, VariableName,
+};
 
 #[derive(Clone, Debug, Deserialize, PartialEq)]
 #[serde(rename_all = "camelCase")]
@@ -41,27 +44,146 @@ enum Command {
     },
 }
 
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemMatcher {
+    base: Option<String>,
+    owner: Option<String>,
+    source: Option<String>,
+    severity: Option<String>,
+    file_location: Option<serde_json_lenient::Value>,
+    pattern: Option<VsCodeProblemPatterns>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum VsCodeProblemPatterns {
+    Single(VsCodeProblemPattern),
+    Multiple(Vec<VsCodeProblemPattern>),
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct VsCodeProblemPattern {
+    regexp: String,
+    file: Option<usize>,
+    line: Option<usize>,
+    column: Option<usize>,
+    end_line: Option<usize>,
+    end_column: Option<usize>,
+    severity: Option<usize>,
+    code: Option<usize>,
+    message: Option<usize>,
+    #[serde(default)]
+    r#loop: bool,
+}
+
+impl From<VsCodeProblemPattern> for ProblemPattern {
+    fn from(pattern: VsCodeProblemPattern) -> Self {
+        Self {
+            regexp: pattern.regexp,
+            file: pattern.file,
+            line: pattern.line,
+            column: pattern.column,
+            end_line: pattern.end_line,
+            end_column: pattern.end_column,
+            severity: pattern.severity,
+            code: pattern.code,
+            message: pattern.message,
+            repeat: pattern.r#loop,
+        }
+    }
+}
+
+fn problem_matchers(
+    value: &serde_json_lenient::Value,
+    replacer: &EnvVariableReplacer,
+) -> Vec<ProblemMatcher> {
+    let matchers = match value {
+        serde_json_lenient::Value::Array(matchers) => matchers.iter().collect(),
+        matcher => vec![matcher],
+    };
+    matchers
+        .into_iter()
+        .filter_map(|matcher| problem_matcher(matcher, replacer).log_err())
+        .collect()
+}
+
+fn problem_matcher(
+    value: &serde_json_lenient::Value,
+    replacer: &EnvVariableReplacer,
+) -> anyhow::Result<ProblemMatcher> {
+    let matcher = if let serde_json_lenient::Value::String(name) = value {
+        ProblemMatcher::BuiltIn(name.clone())
+    } else {
+        let matcher: VsCodeProblemMatcher = serde_json_lenient::from_value(value.clone())?;
+        // Code's `fileLocation` is either a kind of location, or a kind followed by the directory paths are relative to.
+        let file_location = match matcher.file_location {
+            Some(serde_json_lenient::Value::Array(location)) => location
+                .get(1)
+                .and_then(|directory| directory.as_str())
+                .map(|directory| replacer.replace(directory)),
+            _ => None,
+        };
+        let severity = match matcher.severity.as_deref() {
+            Some("error") => Some(ProblemSeverity::Error),
+            Some("warning") => Some(ProblemSeverity::Warning),
+            Some("info") => Some(ProblemSeverity::Info),
+            _ => None,
+        };
+        let pattern = matcher.pattern.map(|pattern| match pattern {
+            VsCodeProblemPatterns::Single(pattern) => ProblemPatterns::Single(pattern.into()),
+            VsCodeProblemPatterns::Multiple(patterns) => {
+                ProblemPatterns::Multiple(patterns.into_iter().map(Into::into).collect())
+            }
+        });
+        ProblemMatcher::Custom(ProblemMatcherDefinition {
+            base: matcher.base,
+            source: matcher.source.or(matcher.owner),
+            severity,
+            file_location,
+            pattern,
+        })
+    };
+    matcher.resolve()?;
+    Ok(matcher)
+}
+
 impl VsCodeTaskDefinition {
     fn into_zed_format(
         self,
         replacer: &EnvVariableReplacer,
     ) -> anyhow::Result<Option<TaskTemplate>> {
-        if self.other_attributes.contains_key("dependsOn") {
-            log::warn!("Skipping deserializing of a task with the unsupported `dependsOn` key");
-            return Ok(None);
-        }
-        // `type` might not be set in e.g. tasks that use `dependsOn`; we still want to deserialize the whole object though (hence command is an Option),
-        // as that way we can provide more specific description of why deserialization failed.
-        // E.g. if the command is missing due to `dependsOn` presence, we can check other_attributes first before doing this (and provide nice error message)
-        // catch-all if on value.command presence.
-        let Some(command) = self.command else {
-            bail!("Missing `type` field in task");
+        let depends_on = match self.other_attributes.get("dependsOn") {
+            None => Vec::new(),
+            Some(serde_json_lenient::Value::String(label)) => vec![label.clone()],
+            Some(serde_json_lenient::Value::Array(labels)) => labels
+                .iter()
+                .map(|label| match label {
+                    serde_json_lenient::Value::String(label) => Ok(label.clone()),
+                    other => Err(anyhow!("Unsupported `dependsOn` entry: {other}")),
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Some(other) => bail!("Unsupported `dependsOn` value: {other}"),
+        };
+        // Code runs the dependencies in parallel unless told otherwise.
+        let depends_order = match self
+            .other_attributes
+            .get("dependsOrder")
+            .and_then(|order| order.as_str())
+        {
+            Some("sequence") => DependsOrder::Sequence,
+            _ => DependsOrder::Parallel,
         };
 
-        let (command, args) = match command {
-            Command::Npm { script } => ("npm".to_owned(), vec!["run".to_string(), script]),
-            Command::Shell { command, args } => (command, args),
-            Command::Gulp { task } => ("gulp".to_owned(), vec![task]),
+        // `type` is not set in tasks that only run their `dependsOn` tasks; we still want to deserialize the whole object though (hence command is an Option),
+        // as that way we can provide more specific description of why deserialization failed.
+        let (command, args) = match self.command {
+            Some(Command::Npm { script }) => ("npm".to_owned(), vec!["run".to_string(), script]),
+            Some(Command::Shell { command, args }) => (command, args),
+            Some(Command::Gulp { task }) => ("gulp".to_owned(), vec![task]),
+            None if !depends_on.is_empty() => (String::new(), Vec::new()),
+            None => bail!("Missing `type` field in task"),
         };
         // Per VSC docs, only `command`, `args` and `options` support variable substitution.
         let command = replacer.replace(&command);
@@ -70,6 +192,13 @@ impl VsCodeTaskDefinition {
             label: self.label,
             command,
             args,
+            depends_on,
+            depends_order,
+            problem_matchers: self
+                .other_attributes
+                .get("problemMatcher")
+                .map(|matchers| problem_matchers(matchers, replacer))
+                .unwrap_or_default(),
             ..TaskTemplate::default()
         };
         if let Some(options) = self.options {
@@ -121,7 +250,8 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::{
-        TaskTemplate, TaskTemplates, VsCodeTaskFile,
+        DependsOrder, ProblemMatcher, ProblemMatcherDefinition, ProblemPattern, ProblemPatterns,
+        ProblemSeverity, TaskTemplate, TaskTemplates, VsCodeTaskFile,
         vscode_format::{Command, VsCodeTaskDefinition},
     };
 
@@ -224,6 +354,7 @@ mod tests {
                 label: "gulp: tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
@@ -235,18 +366,21 @@ mod tests {
                     "${ZED_WORKTREE_ROOT}/src".to_string(),
                     "--watch".to_string(),
                 ],
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$tsc-watch".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:compiler".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:compiler".to_string()],
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "npm: build:tests".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build:tests:notypecheck".to_string()],
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$tsc".to_string())],
                 ..Default::default()
             },
         ];
@@ -327,32 +461,166 @@ mod tests {
                 label: "Build Extension in Background".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "watch".to_string()],
+                problem_matchers: vec![ProblemMatcher::Custom(ProblemMatcherDefinition {
+                    base: Some("$tsc-watch".to_string()),
+                    file_location: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..Default::default()
+                })],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Extension".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "build".to_string()],
+                problem_matchers: vec![ProblemMatcher::Custom(ProblemMatcherDefinition {
+                    base: Some("$tsc".to_string()),
+                    file_location: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..Default::default()
+                })],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server".to_string(),
                 command: "cargo build --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Build Server (Release)".to_string(),
                 command: "cargo build --release --package rust-analyzer".to_string(),
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
             TaskTemplate {
                 label: "Pretest".to_string(),
                 command: "npm".to_string(),
                 args: vec!["run".to_string(), "pretest".to_string()],
+                problem_matchers: vec![ProblemMatcher::Custom(ProblemMatcherDefinition {
+                    base: Some("$tsc".to_string()),
+                    file_location: Some("${ZED_WORKTREE_ROOT}/editors/code/".to_string()),
+                    ..Default::default()
+                })],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server and Extension".to_string(),
+                depends_on: vec!["Build Server".to_string(), "Build Extension".to_string()],
+                depends_order: DependsOrder::Parallel,
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$rustc".to_string())],
+                ..Default::default()
+            },
+            TaskTemplate {
+                label: "Build Server (Release) and Extension".to_string(),
+                depends_on: vec![
+                    "Build Server (Release)".to_string(),
+                    "Build Extension".to_string(),
+                ],
+                depends_order: DependsOrder::Parallel,
+                problem_matchers: vec![ProblemMatcher::BuiltIn("$rustc".to_string())],
                 ..Default::default()
             },
         ];
         let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
         assert_eq!(tasks.0, expected);
     }
+
+    #[test]
+    fn can_deserialize_tasks_with_dependencies() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    {
+                        "label": "lint",
+                        "type": "shell",
+                        "command": "eslint"
+                    },
+                    {
+                        "label": "check",
+                        "dependsOn": "lint",
+                        "dependsOrder": "sequence",
+                        "type": "shell",
+                        "command": "tsc"
+                    },
+                    {
+                        "label": "invalid",
+                        "dependsOn": [{ "type": "npm", "script": "build" }]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0,
+            vec![
+                TaskTemplate {
+                    label: "lint".to_string(),
+                    command: "eslint".to_string(),
+                    ..Default::default()
+                },
+                TaskTemplate {
+                    label: "check".to_string(),
+                    command: "tsc".to_string(),
+                    depends_on: vec!["lint".to_string()],
+                    depends_order: DependsOrder::Sequence,
+                    ..Default::default()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn can_deserialize_problem_matchers() {
+        let vscode_definitions: VsCodeTaskFile = serde_json_lenient::from_str(
+            r#"{
+                "tasks": [
+                    {
+                        "label": "lint",
+                        "type": "shell",
+                        "command": "lint",
+                        "problemMatcher": [
+                            "$unknown",
+                            "$gcc",
+                            {
+                                "owner": "lint",
+                                "severity": "warning",
+                                "fileLocation": "absolute",
+                                "pattern": [
+                                    { "regexp": "^(.*)$", "file": 1 },
+                                    { "regexp": "^(\\d+): (.*)$", "line": 1, "message": 2, "loop": true }
+                                ]
+                            }
+                        ]
+                    }
+                ]
+            }"#,
+        )
+        .unwrap();
+        let tasks: TaskTemplates = vscode_definitions.try_into().unwrap();
+        assert_eq!(
+            tasks.0[0].problem_matchers,
+            vec![
+                ProblemMatcher::BuiltIn("$gcc".to_string()),
+                ProblemMatcher::Custom(ProblemMatcherDefinition {
+                    source: Some("lint".to_string()),
+                    severity: Some(ProblemSeverity::Warning),
+                    pattern: Some(ProblemPatterns::Multiple(vec![
+                        ProblemPattern {
+                            regexp: "^(.*)$".to_string(),
+                            file: Some(1),
+                            ..Default::default()
+                        },
+                        ProblemPattern {
+                            regexp: "^(\\d+): (.*)$".to_string(),
+                            line: Some(1),
+                            message: Some(2),
+                            repeat: true,
+                            ..Default::default()
+                        },
+                    ])),
+                    ..Default::default()
+                }),
+            ]
+        );
+    }
 }
@@ -454,13 +454,23 @@ impl PickerDelegate for TasksModalDelegate {
             String::new()
         };
 
-        if resolved_task.resolved.command_label != resolved_task.resolved_label {
+        if !resolved_task.resolved.command_label.is_empty()
+            && resolved_task.resolved.command_label != resolved_task.resolved_label
+        {
             if !tooltip_label_text.trim().is_empty() {
                 tooltip_label_text.push('\n');
             }
             tooltip_label_text.push_str(&resolved_task.resolved.command_label);
         }
 
+        if !template.depends_on.is_empty() {
+            if !tooltip_label_text.trim().is_empty() {
+                tooltip_label_text.push('\n');
+            }
+            tooltip_label_text.push_str("Runs after: ");
+            tooltip_label_text.push_str(&template.depends_on.join(", "));
+        }
+
         if template.tags.len() > 0 {
             tooltip_label_text.push('\n');
             tooltip_label_text.push_str(
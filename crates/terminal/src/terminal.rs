@@ -1318,6 +1318,47 @@ impl Terminal {
         term.bounds_to_string(start, end)
     }
 
+    /// Returns the output written after the given position, up to the line the cursor is on,
+    /// which may still be written to, along with the position to read the next output from.
+    pub fn read_output_since(&self, position: &OutputPosition) -> (String, OutputPosition) {
+        let term = self.term.lock_unfair();
+        let grid = term.grid();
+        let topmost_line = grid.topmost_line().0;
+        let end = self.find_logical_line_start(grid, grid.cursor.point.line.0, topmost_line);
+        let row_text = |row: i32| row_to_string(&grid[Line(row)]);
+
+        let mut start = (topmost_line + position.rows as i32).min(end);
+        // Once the scrollback is full, rows are dropped from its top as output is written,
+        // so the last row read may have moved up.
+        if let Some(last_row) = position.last_row.as_ref() {
+            if let Some(row) = (topmost_line..start)
+                .rev()
+                .find(|row| row_text(*row) == *last_row)
+            {
+                start = row + 1;
+            }
+        }
+
+        let mut output = String::new();
+        for row in start..end {
+            let text = row_text(row);
+            let wrapped = grid[Line(row)][Column(grid.columns() - 1)]
+                .flags
+                .contains(Flags::WRAPLINE);
+            if wrapped {
+                output.push_str(&text);
+            } else {
+                output.push_str(text.trim_end());
+                output.push('\n');
+            }
+        }
+        let next_position = OutputPosition {
+            rows: (end - topmost_line) as usize,
+            last_row: (end > topmost_line).then(|| row_text(end - 1)),
+        };
+        (output, next_position)
+    }
+
     pub fn last_n_non_empty_lines(&self, n: usize) -> Vec<String> {
         let term = self.term.clone();
         let terminal = term.lock_unfair();
@@ -1831,6 +1872,15 @@ impl Terminal {
     }
 }
 
+/// How far the output of a terminal was read with [`Terminal::read_output_since`].
+#[derive(Clone, Debug, Default)]
+pub struct OutputPosition {
+    /// The number of rows read, from the top of the scrollback.
+    rows: usize,
+    /// The text of the last row read.
+    last_row: Option<String>,
+}
+
 // Helper function to convert a grid row to a string
 pub fn row_to_string(row: &Row<Cell>) -> String {
     row[..Column(row.len())]
@@ -621,6 +621,142 @@ fn test_history() {
     assert_eq!(buffer.text(), "X12cde6");
 }
 
+#[test]
+fn test_undo_tree() {
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "");
+    buffer.set_group_interval(Duration::from_secs(0));
+
+    buffer.edit([(0..0, "a")]);
+    let transaction_a = buffer.peek_undo_stack().unwrap().transaction_id();
+    buffer.edit([(1..1, "b")]);
+    let transaction_b = buffer.peek_undo_stack().unwrap().transaction_id();
+    buffer.undo();
+
+    // Editing after undoing starts a new branch instead of discarding "b".
+    buffer.edit([(1..1, "c")]);
+    let transaction_c = buffer.peek_undo_stack().unwrap().transaction_id();
+    assert_eq!(buffer.text(), "ac");
+    assert!(buffer.redo().is_none());
+    assert_eq!(
+        buffer
+            .undo_tree()
+            .into_iter()
+            .map(|node| (node.transaction_id, node.parent, node.applied))
+            .collect::<Vec<_>>(),
+        [
+            (transaction_a, None, true),
+            (transaction_b, Some(transaction_a), false),
+            (transaction_c, Some(transaction_a), true),
+        ]
+    );
+    assert_eq!(
+        buffer
+            .transaction_summary(transaction_b)
+            .unwrap()
+            .inserted_text,
+        "b"
+    );
+
+    buffer.travel_to_undo_state(Some(transaction_b));
+    assert_eq!(buffer.text(), "ab");
+    buffer.undo();
+    assert_eq!(buffer.text(), "a");
+    buffer.redo();
+    assert_eq!(buffer.text(), "ab");
+
+    buffer.travel_to_undo_state(None);
+    assert_eq!(buffer.text(), "");
+    // Redoing follows the most recent branch.
+    buffer.redo();
+    buffer.redo();
+    assert_eq!(buffer.text(), "ac");
+
+    // Travelling through time visits the states in the order they were created.
+    buffer.travel_undo_history(-1);
+    assert_eq!(buffer.text(), "ab");
+    buffer.travel_undo_history(-1);
+    assert_eq!(buffer.text(), "a");
+    buffer.redo();
+    assert_eq!(buffer.text(), "ac");
+    buffer.travel_undo_history(-5);
+    assert_eq!(buffer.text(), "");
+    assert!(buffer.travel_undo_history(-1).is_empty());
+    buffer.travel_undo_history(2);
+    assert_eq!(buffer.text(), "ab");
+    buffer.travel_undo_history(1);
+    assert_eq!(buffer.text(), "ac");
+    assert!(buffer.travel_undo_history(1).is_empty());
+}
+
+#[test]
+fn test_undo_tree_forgets_oldest_branches() {
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "");
+    buffer.set_group_interval(Duration::from_secs(0));
+
+    let mut undone_transactions = Vec::new();
+    for _ in 0..MAX_DETACHED_TRANSACTIONS + 10 {
+        buffer.edit([(0..0, "a")]);
+        undone_transactions.push(buffer.peek_undo_stack().unwrap().transaction_id());
+        buffer.undo();
+    }
+    buffer.edit([(0..0, "b")]);
+    let transaction_b = buffer.peek_undo_stack().unwrap().transaction_id();
+
+    let transaction_ids = buffer
+        .undo_tree()
+        .into_iter()
+        .map(|node| node.transaction_id)
+        .collect::<Vec<_>>();
+    assert_eq!(transaction_ids.len(), MAX_DETACHED_TRANSACTIONS + 1);
+    assert_eq!(transaction_ids.last(), Some(&transaction_b));
+    assert!(!transaction_ids.contains(&undone_transactions[9]));
+    assert!(transaction_ids.contains(&undone_transactions[10]));
+
+    buffer.travel_to_undo_state(undone_transactions.last().copied());
+    assert_eq!(buffer.text(), "a");
+}
+
+#[test]
+fn test_serialize_and_restore_undo_history() {
+    let mut buffer = Buffer::new(0, BufferId::new(1).unwrap(), "one two three");
+    buffer.set_group_interval(Duration::from_secs(0));
+
+    buffer.edit([(0..3, "ONE"), (8..13, "3")]);
+    buffer.edit([(4..7, "2")]);
+    buffer.edit([(0..0, "zero ")]);
+    buffer.undo();
+    assert_eq!(buffer.text(), "ONE 2 3");
+
+    let transactions = buffer.serialize_undo_history(usize::MAX);
+    assert_eq!(transactions.len(), 2);
+    assert_eq!(buffer.serialize_undo_history(1), transactions[1..]);
+
+    let mut restored = Buffer::new(0, BufferId::new(2).unwrap(), buffer.text());
+    restored.set_group_interval(Duration::from_secs(0));
+    restored.restore_undo_history(&transactions).unwrap();
+    assert_eq!(restored.text(), "ONE 2 3");
+
+    restored.undo();
+    assert_eq!(restored.text(), "ONE two 3");
+    restored.undo();
+    assert_eq!(restored.text(), "one two three");
+    assert!(restored.undo().is_none());
+    restored.redo();
+    restored.redo();
+    assert_eq!(restored.text(), "ONE 2 3");
+
+    // History recorded for a different text is rejected without editing the buffer.
+    let mut other = Buffer::new(0, BufferId::new(3).unwrap(), "something else");
+    assert!(other.restore_undo_history(&transactions).is_err());
+    assert_eq!(other.text(), "something else");
+    assert!(other.peek_undo_stack().is_none());
+
+    // A captured history isn't affected by later edits.
+    let history = buffer.undo_history_snapshot().unwrap();
+    buffer.edit([(0..0, "zero ")]);
+    assert_eq!(history.serialize(usize::MAX), transactions);
+}
+
 #[test]
 fn test_finalize_last_transaction() {
     let now = Instant::now();
@@ -28,7 +28,7 @@ use std::{
     cmp::{self, Ordering, Reverse},
     fmt::Display,
     future::Future,
-    iter::Iterator,
+    iter::{self, Iterator},
     num::NonZeroU64,
     ops::{self, Deref, Range, Sub},
     str,
@@ -48,6 +48,10 @@ static LINE_SEPARATORS_REGEX: LazyLock<Regex> =
 
 pub type TransactionId = clock::Lamport;
 
+/// How many undone transactions are kept on the branches of the undo tree
+/// other than the current one.
+const MAX_DETACHED_TRANSACTIONS: usize = 1000;
+
 pub struct Buffer {
     snapshot: BufferSnapshot,
     history: History,
@@ -142,11 +146,124 @@ impl HistoryEntry {
     }
 }
 
+/// A transaction from the undo stack expressed as plain text edits, so that it
+/// can outlive the operations that produced it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct SerializedTransaction {
+    pub edits: Vec<SerializedEdit>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SerializedEdit {
+    /// The replaced range, in offsets of the text preceding the transaction.
+    pub old_range: Range<usize>,
+    pub old_text: String,
+    pub new_text: String,
+}
+
+impl SerializedTransaction {
+    fn edits(&self) -> Vec<(Range<usize>, &str)> {
+        self.edits
+            .iter()
+            .map(|edit| (edit.old_range.clone(), edit.new_text.as_str()))
+            .collect()
+    }
+
+    /// Returns the edits reverting this transaction, in offsets of the text
+    /// following it.
+    fn inverse_edits(&self) -> Vec<(Range<usize>, &str)> {
+        let mut delta = 0_isize;
+        self.edits
+            .iter()
+            .map(|edit| {
+                let start = (edit.old_range.start as isize + delta) as usize;
+                delta += edit.new_text.len() as isize - edit.old_range.len() as isize;
+                (start..start + edit.new_text.len(), edit.old_text.as_str())
+            })
+            .collect()
+    }
+}
+
+/// The undo history of a buffer captured by [`Buffer::undo_history_snapshot`].
+#[derive(Clone)]
+pub struct UndoHistorySnapshot {
+    snapshot: BufferSnapshot,
+    history: History,
+    lamport_clock: clock::Lamport,
+}
+
+impl UndoHistorySnapshot {
+    /// Returns up to `max_transactions` of the most recent transactions in the
+    /// undo stack as plain text edits, ordered from the oldest to the newest.
+    ///
+    /// The transactions are undone one by one on a copy of the buffer, which is
+    /// slow for long histories and is best done on a background thread.
+    pub fn serialize(self, max_transactions: usize) -> Vec<SerializedTransaction> {
+        let mut buffer = Buffer {
+            snapshot: self.snapshot,
+            history: self.history,
+            deferred_ops: OperationQueue::new(),
+            deferred_replicas: HashSet::default(),
+            lamport_clock: self.lamport_clock,
+            subscriptions: Default::default(),
+            edit_id_resolvers: Default::default(),
+            wait_for_version_txs: Default::default(),
+        };
+        let mut transactions = Vec::new();
+        while transactions.len() < max_transactions {
+            let before_undo = buffer.snapshot();
+            if buffer.undo().is_none() {
+                break;
+            }
+
+            let edits = buffer
+                .edits_since::<usize>(&before_undo.version)
+                .map(|edit| SerializedEdit {
+                    old_text: buffer.text_for_range(edit.new.clone()).collect(),
+                    new_text: before_undo.text_for_range(edit.old).collect(),
+                    old_range: edit.new,
+                })
+                .collect::<Vec<_>>();
+            if !edits.is_empty() {
+                transactions.push(SerializedTransaction { edits });
+            }
+        }
+        transactions.reverse();
+        transactions
+    }
+}
+
+/// A transaction in the undo history, which forms a tree because making an
+/// edit after undoing starts a new branch instead of discarding the undone
+/// transactions.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UndoTreeNode {
+    pub transaction_id: TransactionId,
+    /// The transaction this one was made after, or `None` if it was made on
+    /// the initial text.
+    pub parent: Option<TransactionId>,
+    /// Whether the transaction is currently applied to the text.
+    pub applied: bool,
+    pub first_edit_at: Instant,
+    pub last_edit_at: Instant,
+}
+
+/// The changes made by a transaction, for displaying it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TransactionSummary {
+    pub inserted_text: String,
+    pub deleted_len: usize,
+}
+
+#[derive(Clone)]
 struct History {
     base_text: Rope,
     operations: TreeMap<clock::Lamport, Operation>,
     undo_stack: Vec<HistoryEntry>,
     redo_stack: Vec<HistoryEntry>,
+    /// Undone transactions on branches of the undo tree other than the current
+    /// one, along with the transaction they were made after.
+    detached: HashMap<TransactionId, (Option<TransactionId>, HistoryEntry)>,
     transaction_depth: usize,
     group_interval: Duration,
 }
@@ -191,6 +308,7 @@ impl History {
             operations: Default::default(),
             undo_stack: Vec::new(),
             redo_stack: Vec::new(),
+            detached: HashMap::default(),
             transaction_depth: 0,
             // Don't group transactions in tests unless we opt in, because it's a footgun.
             #[cfg(any(test, feature = "test-support"))]
@@ -244,7 +362,7 @@ impl History {
                 self.undo_stack.pop();
                 None
             } else {
-                self.redo_stack.clear();
+                self.branch_off_redo_stack();
                 let entry = self.undo_stack.last_mut().unwrap();
                 entry.last_edit_at = now;
                 Some(entry)
@@ -320,7 +438,97 @@ impl History {
             last_edit_at: now,
             suppress_grouping: false,
         });
-        self.redo_stack.clear();
+        self.branch_off_redo_stack();
+    }
+
+    /// Keeps the transactions that were undone before the last transaction was
+    /// pushed as a separate branch of the undo tree.
+    fn branch_off_redo_stack(&mut self) {
+        if self.redo_stack.is_empty() {
+            return;
+        }
+
+        let fork = self.undo_stack.len().checked_sub(2).map(|ix| {
+            // Don't group the new transaction with the one the branch forks off,
+            // so that the state the branch was made on can still be reached.
+            let entry = &mut self.undo_stack[ix];
+            entry.suppress_grouping = true;
+            entry.transaction.id
+        });
+        self.detach_redo_stack(fork);
+        self.forget_oldest_branches();
+    }
+
+    fn detach_redo_stack(&mut self, mut parent: Option<TransactionId>) {
+        for entry in self.redo_stack.drain(..).rev() {
+            let transaction_id = entry.transaction.id;
+            self.detached.insert(transaction_id, (parent, entry));
+            parent = Some(transaction_id);
+        }
+    }
+
+    /// Forgets the transactions at the tips of the oldest branches of the undo
+    /// tree, until there are no more than [`MAX_DETACHED_TRANSACTIONS`] of them.
+    fn forget_oldest_branches(&mut self) {
+        while self.detached.len() > MAX_DETACHED_TRANSACTIONS {
+            let parents = self
+                .detached
+                .values()
+                .filter_map(|(parent, _)| *parent)
+                .collect::<HashSet<_>>();
+            let mut tips = self
+                .detached
+                .keys()
+                .filter(|transaction_id| !parents.contains(transaction_id))
+                .copied()
+                .collect::<Vec<_>>();
+            tips.sort_unstable();
+            let excess = self.detached.len() - MAX_DETACHED_TRANSACTIONS;
+            for transaction_id in tips.into_iter().take(excess) {
+                self.detached.remove(&transaction_id);
+            }
+        }
+    }
+
+    fn undo_tree(&self) -> Vec<UndoTreeNode> {
+        let mut nodes =
+            Vec::with_capacity(self.undo_stack.len() + self.redo_stack.len() + self.detached.len());
+        let node = |entry: &HistoryEntry, parent, applied| UndoTreeNode {
+            transaction_id: entry.transaction.id,
+            parent,
+            applied,
+            first_edit_at: entry.first_edit_at,
+            last_edit_at: entry.last_edit_at,
+        };
+
+        let mut parent = None;
+        for entry in &self.undo_stack {
+            nodes.push(node(entry, parent, true));
+            parent = Some(entry.transaction.id);
+        }
+        for entry in self.redo_stack.iter().rev() {
+            nodes.push(node(entry, parent, false));
+            parent = Some(entry.transaction.id);
+        }
+        for (parent, entry) in self.detached.values() {
+            nodes.push(node(entry, *parent, false));
+        }
+        nodes.sort_unstable_by_key(|node| node.transaction_id);
+
+        // Attach the branches whose fork was forgotten to the root.
+        let transaction_ids = nodes
+            .iter()
+            .map(|node| node.transaction_id)
+            .collect::<HashSet<_>>();
+        for node in &mut nodes {
+            if node
+                .parent
+                .is_some_and(|parent| !transaction_ids.contains(&parent))
+            {
+                node.parent = None;
+            }
+        }
+        nodes
     }
 
     fn push_undo(&mut self, op_id: clock::Lamport) {
@@ -383,7 +591,9 @@ impl History {
         {
             Some(self.redo_stack.remove(entry_ix).transaction)
         } else {
-            None
+            self.detached
+                .remove(&transaction_id)
+                .map(|(_, entry)| entry.transaction)
         }
     }
 
@@ -396,7 +606,8 @@ impl History {
                 self.redo_stack
                     .iter()
                     .rfind(|entry| entry.transaction.id == transaction_id)
-            })?;
+            })
+            .or_else(|| self.detached.get(&transaction_id).map(|(_, entry)| entry))?;
         Some(&entry.transaction)
     }
 
@@ -409,6 +620,11 @@ impl History {
                 self.redo_stack
                     .iter_mut()
                     .rfind(|entry| entry.transaction.id == transaction_id)
+            })
+            .or_else(|| {
+                self.detached
+                    .get_mut(&transaction_id)
+                    .map(|(_, entry)| entry)
             })?;
         Some(&mut entry.transaction)
     }
@@ -1467,6 +1683,205 @@ impl Buffer {
             .collect()
     }
 
+    /// Returns every transaction in the undo history, including the ones on
+    /// abandoned branches, ordered from the oldest to the newest.
+    pub fn undo_tree(&self) -> Vec<UndoTreeNode> {
+        self.history.undo_tree()
+    }
+
+    pub fn transaction_summary(&self, transaction_id: TransactionId) -> Option<TransactionSummary> {
+        let transaction = self.history.transaction(transaction_id)?;
+        let mut summary = TransactionSummary::default();
+        for edit_id in &transaction.edit_ids {
+            if let Some(Operation::Edit(edit)) = self.history.operations.get(edit_id) {
+                for (range, new_text) in edit.ranges.iter().zip(&edit.new_text) {
+                    summary.deleted_len += range.end.0 - range.start.0;
+                    summary.inserted_text.push_str(new_text);
+                }
+            }
+        }
+        Some(summary)
+    }
+
+    /// Undoes and redoes transactions so that the text is in the state right
+    /// after `target` was applied, or in its initial state if `target` is
+    /// `None`. The branch of the undo tree containing `target` becomes the
+    /// current one, so its subsequent transactions can then be redone.
+    pub fn travel_to_undo_state(&mut self, target: Option<TransactionId>) -> Vec<Operation> {
+        if self.history.transaction_depth > 0 {
+            return Vec::new();
+        }
+
+        let nodes = self.history.undo_tree();
+        let parents = nodes
+            .iter()
+            .map(|node| (node.transaction_id, node.parent))
+            .collect::<HashMap<_, _>>();
+        if target.is_some_and(|target| !parents.contains_key(&target)) {
+            return Vec::new();
+        }
+        let mut newest_children = HashMap::<Option<TransactionId>, TransactionId>::default();
+        for node in &nodes {
+            newest_children.insert(node.parent, node.transaction_id);
+        }
+
+        let mut path = Vec::new();
+        let mut next = target;
+        while let Some(transaction_id) = next {
+            path.push(transaction_id);
+            next = parents[&transaction_id];
+        }
+        path.reverse();
+        let target_depth = path.len();
+        let mut last = target;
+        while let Some(child) = newest_children.get(&last) {
+            path.push(*child);
+            last = Some(*child);
+        }
+
+        let common_depth = self
+            .history
+            .undo_stack
+            .iter()
+            .zip(&path)
+            .take_while(|(entry, transaction_id)| entry.transaction.id == **transaction_id)
+            .count()
+            .min(target_depth);
+        let mut operations = Vec::new();
+        while self.history.undo_stack.len() > common_depth {
+            let Some((_, operation)) = self.undo() else {
+                break;
+            };
+            operations.push(operation);
+        }
+
+        let fork = self
+            .history
+            .undo_stack
+            .last()
+            .map(|entry| entry.transaction.id);
+        self.history.detach_redo_stack(fork);
+        for transaction_id in path[common_depth..].iter().rev() {
+            if let Some((_, entry)) = self.history.detached.remove(transaction_id) {
+                self.history.redo_stack.push(entry);
+            }
+        }
+        self.history.forget_oldest_branches();
+        for _ in common_depth..target_depth {
+            let Some((_, operation)) = self.redo() else {
+                break;
+            };
+            operations.push(operation);
+        }
+        operations
+    }
+
+    /// Moves through the states of the undo tree in the order they were
+    /// created, going back in time when `delta` is negative.
+    pub fn travel_undo_history(&mut self, delta: isize) -> Vec<Operation> {
+        let states = iter::once(None)
+            .chain(
+                self.history
+                    .undo_tree()
+                    .into_iter()
+                    .map(|node| Some(node.transaction_id)),
+            )
+            .collect::<Vec<_>>();
+        let current_state = self
+            .history
+            .undo_stack
+            .last()
+            .map(|entry| entry.transaction.id);
+        let Some(current_ix) = states.iter().position(|state| *state == current_state) else {
+            return Vec::new();
+        };
+        let target_ix = current_ix
+            .saturating_add_signed(delta)
+            .min(states.len() - 1);
+        if target_ix == current_ix {
+            return Vec::new();
+        }
+        self.travel_to_undo_state(states[target_ix])
+    }
+
+    /// Returns up to `max_transactions` of the most recent transactions in the
+    /// undo stack as plain text edits, ordered from the oldest to the newest.
+    pub fn serialize_undo_history(&self, max_transactions: usize) -> Vec<SerializedTransaction> {
+        self.undo_history_snapshot()
+            .map_or_else(Vec::new, |history| history.serialize(max_transactions))
+    }
+
+    /// Captures the undo history along with the text it applies to, so that it
+    /// can be serialized on a background thread. Returns `None` during a
+    /// transaction.
+    pub fn undo_history_snapshot(&self) -> Option<UndoHistorySnapshot> {
+        if self.history.transaction_depth > 0 {
+            return None;
+        }
+        Some(UndoHistorySnapshot {
+            snapshot: self.snapshot.clone(),
+            history: self.history.clone(),
+            lamport_clock: self.lamport_clock,
+        })
+    }
+
+    /// Rebuilds the undo stack from transactions returned by
+    /// [`Buffer::serialize_undo_history`] for the current text.
+    ///
+    /// The text is rewound to its state before the oldest transaction and each
+    /// transaction is then replayed, so that the text ends up unchanged but all
+    /// the transactions can be undone. Returns the ids of the replayed
+    /// transactions along with the operations performed.
+    pub fn restore_undo_history(
+        &mut self,
+        transactions: &[SerializedTransaction],
+    ) -> Result<(Vec<TransactionId>, Vec<Operation>)> {
+        anyhow::ensure!(
+            self.history.transaction_depth == 0,
+            "cannot restore undo history during a transaction"
+        );
+
+        let mut text = self.visible_text.clone();
+        for transaction in transactions.iter().rev() {
+            anyhow::ensure!(!transaction.edits.is_empty(), "empty transaction");
+            let inverse_edits = transaction.inverse_edits();
+            for (edit, (range, old_text)) in transaction.edits.iter().zip(inverse_edits).rev() {
+                anyhow::ensure!(
+                    range.end <= text.len()
+                        && text.clip_offset(range.start, Bias::Left) == range.start
+                        && text.clip_offset(range.end, Bias::Left) == range.end,
+                    "invalid range {range:?} in undo history"
+                );
+                anyhow::ensure!(
+                    text.chunks_in_range(range.clone()).collect::<String>() == edit.new_text,
+                    "undo history does not match the buffer's text"
+                );
+                text.replace(range, old_text);
+            }
+        }
+
+        let mut operations = Vec::new();
+        self.finalize_last_transaction();
+        self.start_transaction();
+        for transaction in transactions.iter().rev() {
+            operations.push(self.edit(transaction.inverse_edits()));
+        }
+        if let Some((rewind_transaction_id, _)) = self.end_transaction() {
+            self.forget_transaction(rewind_transaction_id);
+        }
+
+        let mut transaction_ids = Vec::with_capacity(transactions.len());
+        for transaction in transactions {
+            self.start_transaction();
+            operations.push(self.edit(transaction.edits()));
+            if let Some((transaction_id, _)) = self.end_transaction() {
+                transaction_ids.push(transaction_id);
+            }
+            self.finalize_last_transaction();
+        }
+        Ok((transaction_ids, operations))
+    }
+
     fn undo_or_redo(&mut self, transaction: Transaction) -> Operation {
         let mut counts = HashMap::default();
         for edit_id in transaction.edit_ids {
@@ -0,0 +1,367 @@
+//! Reads themes written for other editors, so that they can be placed in the
+//! themes directory like Zed themes.
+
+mod base16;
+mod helix;
+mod textmate;
+
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use gpui::{Hsla, hsla};
+use indexmap::IndexMap;
+
+use crate::{
+    AccentContent, AppearanceContent, HighlightStyleContent, PlayerColorContent,
+    StatusColorsContent, ThemeColorsContent, ThemeFamilyContent, ThemeStyleContent,
+    WindowBackgroundContent, hex_color, try_parse_color,
+};
+
+/// A format that themes can be read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeFormat {
+    /// A Zed theme family, as JSON.
+    Zed,
+    /// A TextMate or Sublime Text `.tmTheme` property list.
+    TextMate,
+    /// A Base16 or Base24 color scheme, as YAML.
+    Base16,
+    /// A Helix theme, as TOML.
+    Helix,
+}
+
+impl ThemeFormat {
+    /// Returns the format of the theme file at the given path, based on its extension.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(Self::Zed),
+            "tmtheme" => Some(Self::TextMate),
+            "yaml" | "yml" => Some(Self::Base16),
+            "toml" => Some(Self::Helix),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a theme in the given format to a theme family.
+///
+/// `name` is used for themes that aren't named in the file itself, like Helix
+/// themes, which are named after their file.
+pub fn import_theme(
+    format: ThemeFormat,
+    contents: &[u8],
+    name: &str,
+) -> Result<ThemeFamilyContent> {
+    match format {
+        ThemeFormat::Zed => {
+            serde_json_lenient::from_slice(contents).context("failed to parse Zed theme")
+        }
+        ThemeFormat::TextMate => {
+            textmate::import(contents, name).context("failed to parse tmTheme")
+        }
+        ThemeFormat::Base16 => {
+            let contents = std::str::from_utf8(contents)?;
+            base16::import(contents, name).context("failed to parse Base16 scheme")
+        }
+        ThemeFormat::Helix => {
+            let contents = std::str::from_utf8(contents)?;
+            helix::import(contents, name).context("failed to parse Helix theme")
+        }
+    }
+}
+
+/// Returns whether a background color calls for a dark theme.
+fn appearance_for_background(background: Hsla) -> AppearanceContent {
+    if background.l < 0.5 {
+        AppearanceContent::Dark
+    } else {
+        AppearanceContent::Light
+    }
+}
+
+/// Mixes `amount` of `color` into `base`.
+fn mix(base: Hsla, color: Hsla, amount: f32) -> Hsla {
+    base.blend(color.opacity(amount))
+}
+
+/// The colors of a theme in a format that only describes a few colors, from
+/// which the UI colors of the Zed theme are derived.
+struct Palette {
+    appearance: AppearanceContent,
+    background: Hsla,
+    foreground: Hsla,
+    /// The background of the panels and bars around the editor.
+    surface: Option<Hsla>,
+    selection: Option<Hsla>,
+    line_highlight: Option<Hsla>,
+    line_number: Option<Hsla>,
+    cursor: Option<Hsla>,
+    /// The color of comments and other secondary text.
+    muted: Option<Hsla>,
+    red: Option<Hsla>,
+    orange: Option<Hsla>,
+    yellow: Option<Hsla>,
+    green: Option<Hsla>,
+    cyan: Option<Hsla>,
+    blue: Option<Hsla>,
+    magenta: Option<Hsla>,
+}
+
+impl Palette {
+    fn new(background: Hsla, foreground: Hsla) -> Self {
+        Self {
+            appearance: appearance_for_background(background),
+            background,
+            foreground,
+            surface: None,
+            selection: None,
+            line_highlight: None,
+            line_number: None,
+            cursor: None,
+            muted: None,
+            red: None,
+            orange: None,
+            yellow: None,
+            green: None,
+            cyan: None,
+            blue: None,
+            magenta: None,
+        }
+    }
+
+    /// Fills in the accent colors that are missing with the colors of the
+    /// given syntax nodes.
+    fn fill_accents_from_syntax(&mut self, syntax: &IndexMap<String, HighlightStyleContent>) {
+        let color = |names: &[&str]| {
+            names.iter().find_map(|name| {
+                let color = syntax.get(*name)?.color.as_ref()?;
+                try_parse_color(color).ok()
+            })
+        };
+        self.muted = self.muted.or_else(|| color(&["comment"]));
+        self.red = self.red.or_else(|| color(&["tag", "variable.special"]));
+        self.orange = self.orange.or_else(|| color(&["number", "constant"]));
+        self.yellow = self.yellow.or_else(|| color(&["type", "attribute"]));
+        self.green = self.green.or_else(|| color(&["string"]));
+        self.cyan = self.cyan.or_else(|| color(&["string.escape", "operator"]));
+        self.blue = self.blue.or_else(|| color(&["function", "title"]));
+        self.magenta = self.magenta.or_else(|| color(&["keyword"]));
+    }
+
+    /// Returns an accent color, or a color of the given hue when the theme
+    /// doesn't have it.
+    fn accent(&self, color: Option<Hsla>, hue: f32) -> Hsla {
+        color.unwrap_or_else(|| {
+            let lightness = match self.appearance {
+                AppearanceContent::Dark => 0.65,
+                AppearanceContent::Light => 0.45,
+            };
+            hsla(hue / 360., 0.6, lightness, 1.)
+        })
+    }
+
+    fn style(&self, syntax: IndexMap<String, HighlightStyleContent>) -> ThemeStyleContent {
+        let background = self.background;
+        let foreground = self.foreground;
+        let shade = |amount: f32| mix(background, foreground, amount);
+        let surface = self.surface.unwrap_or_else(|| shade(0.04));
+        let muted = self.muted.unwrap_or_else(|| shade(0.6));
+        let selection = self.selection.unwrap_or_else(|| shade(0.2));
+        let line_number = self.line_number.unwrap_or_else(|| shade(0.35));
+
+        let red = self.accent(self.red, 0.);
+        let orange = self.accent(self.orange, 30.);
+        let yellow = self.accent(self.yellow, 50.);
+        let green = self.accent(self.green, 120.);
+        let cyan = self.accent(self.cyan, 180.);
+        let blue = self.accent(self.blue, 215.);
+        let magenta = self.accent(self.magenta, 290.);
+        let cursor = self.cursor.unwrap_or(blue);
+
+        let (black, white) = match self.appearance {
+            AppearanceContent::Dark => (shade(0.2), foreground),
+            AppearanceContent::Light => (foreground, shade(0.2)),
+        };
+        let bright = |color: Hsla| Hsla {
+            l: (color.l + 0.1).min(1.),
+            ..color
+        };
+        let dim = |color: Hsla| mix(background, color, 0.7);
+
+        let color = |color: Hsla| Some(hex_color(color));
+        let colors = ThemeColorsContent {
+            border: color(shade(0.15)),
+            border_variant: color(shade(0.08)),
+            border_focused: color(blue),
+            border_selected: color(blue.opacity(0.5)),
+            border_transparent: color(gpui::transparent_black()),
+            border_disabled: color(shade(0.1)),
+            elevated_surface_background: color(surface),
+            surface_background: color(surface),
+            background: color(surface),
+            element_background: color(shade(0.06)),
+            element_hover: color(shade(0.1)),
+            element_active: color(shade(0.14)),
+            element_selected: color(shade(0.14)),
+            element_disabled: color(shade(0.04)),
+            drop_target_background: color(blue.opacity(0.2)),
+            ghost_element_background: color(gpui::transparent_black()),
+            ghost_element_hover: color(shade(0.1)),
+            ghost_element_active: color(shade(0.14)),
+            ghost_element_selected: color(shade(0.14)),
+            ghost_element_disabled: color(shade(0.04)),
+            text: color(foreground),
+            text_muted: color(muted),
+            text_placeholder: color(shade(0.4)),
+            text_disabled: color(shade(0.4)),
+            text_accent: color(blue),
+            icon: color(foreground),
+            icon_muted: color(muted),
+            icon_disabled: color(shade(0.4)),
+            icon_placeholder: color(muted),
+            icon_accent: color(blue),
+            status_bar_background: color(surface),
+            title_bar_background: color(surface),
+            title_bar_inactive_background: color(surface),
+            toolbar_background: color(background),
+            tab_bar_background: color(surface),
+            tab_inactive_background: color(surface),
+            tab_active_background: color(background),
+            search_match_background: color(yellow.opacity(0.3)),
+            panel_background: color(surface),
+            panel_focused_border: color(blue),
+            pane_focused_border: color(blue),
+            scrollbar_thumb_background: color(foreground.opacity(0.15)),
+            scrollbar_thumb_hover_background: color(foreground.opacity(0.25)),
+            scrollbar_thumb_border: color(gpui::transparent_black()),
+            scrollbar_track_background: color(gpui::transparent_black()),
+            scrollbar_track_border: color(shade(0.08)),
+            editor_foreground: color(foreground),
+            editor_background: color(background),
+            editor_gutter_background: color(background),
+            editor_subheader_background: color(surface),
+            editor_active_line_background: color(
+                self.line_highlight.unwrap_or_else(|| shade(0.05)),
+            ),
+            editor_highlighted_line_background: color(shade(0.08)),
+            editor_line_number: color(line_number),
+            editor_active_line_number: color(foreground),
+            editor_invisible: color(shade(0.25)),
+            editor_wrap_guide: color(shade(0.08)),
+            editor_active_wrap_guide: color(shade(0.15)),
+            editor_indent_guide: color(shade(0.08)),
+            editor_indent_guide_active: color(shade(0.2)),
+            editor_document_highlight_read_background: color(selection.opacity(0.5)),
+            editor_document_highlight_write_background: color(selection.opacity(0.7)),
+            terminal_background: color(background),
+            terminal_foreground: color(foreground),
+            terminal_bright_foreground: color(bright(foreground)),
+            terminal_dim_foreground: color(dim(foreground)),
+            terminal_ansi_background: color(background),
+            terminal_ansi_black: color(black),
+            terminal_ansi_bright_black: color(bright(black)),
+            terminal_ansi_dim_black: color(dim(black)),
+            terminal_ansi_red: color(red),
+            terminal_ansi_bright_red: color(bright(red)),
+            terminal_ansi_dim_red: color(dim(red)),
+            terminal_ansi_green: color(green),
+            terminal_ansi_bright_green: color(bright(green)),
+            terminal_ansi_dim_green: color(dim(green)),
+            terminal_ansi_yellow: color(yellow),
+            terminal_ansi_bright_yellow: color(bright(yellow)),
+            terminal_ansi_dim_yellow: color(dim(yellow)),
+            terminal_ansi_blue: color(blue),
+            terminal_ansi_bright_blue: color(bright(blue)),
+            terminal_ansi_dim_blue: color(dim(blue)),
+            terminal_ansi_magenta: color(magenta),
+            terminal_ansi_bright_magenta: color(bright(magenta)),
+            terminal_ansi_dim_magenta: color(dim(magenta)),
+            terminal_ansi_cyan: color(cyan),
+            terminal_ansi_bright_cyan: color(bright(cyan)),
+            terminal_ansi_dim_cyan: color(dim(cyan)),
+            terminal_ansi_white: color(white),
+            terminal_ansi_bright_white: color(bright(white)),
+            terminal_ansi_dim_white: color(dim(white)),
+            link_text_hover: color(blue),
+            version_control_added: color(green),
+            version_control_deleted: color(red),
+            version_control_modified: color(yellow),
+            version_control_renamed: color(blue),
+            version_control_conflict: color(orange),
+            version_control_ignored: color(muted),
+            ..Default::default()
+        };
+
+        let background_of = |color: Hsla| Some(hex_color(color.opacity(0.15)));
+        let border_of = |color: Hsla| Some(hex_color(color.opacity(0.4)));
+        let status = StatusColorsContent {
+            conflict: color(orange),
+            conflict_background: background_of(orange),
+            conflict_border: border_of(orange),
+            created: color(green),
+            created_background: background_of(green),
+            created_border: border_of(green),
+            deleted: color(red),
+            deleted_background: background_of(red),
+            deleted_border: border_of(red),
+            error: color(red),
+            error_background: background_of(red),
+            error_border: border_of(red),
+            hidden: color(muted),
+            hidden_background: background_of(muted),
+            hidden_border: border_of(muted),
+            hint: color(muted),
+            hint_background: background_of(muted),
+            hint_border: border_of(muted),
+            ignored: color(muted),
+            ignored_background: background_of(muted),
+            ignored_border: border_of(muted),
+            info: color(blue),
+            info_background: background_of(blue),
+            info_border: border_of(blue),
+            modified: color(yellow),
+            modified_background: background_of(yellow),
+            modified_border: border_of(yellow),
+            predictive: color(muted),
+            predictive_background: background_of(muted),
+            predictive_border: border_of(muted),
+            renamed: color(blue),
+            renamed_background: background_of(blue),
+            renamed_border: border_of(blue),
+            success: color(green),
+            success_background: background_of(green),
+            success_border: border_of(green),
+            unreachable: color(muted),
+            unreachable_background: background_of(muted),
+            unreachable_border: border_of(muted),
+            warning: color(yellow),
+            warning_background: background_of(yellow),
+            warning_border: border_of(yellow),
+        };
+
+        let accents = [blue, magenta, green, yellow, cyan, red, orange];
+        ThemeStyleContent {
+            window_background_appearance: Some(WindowBackgroundContent::Opaque),
+            accents: accents
+                .iter()
+                .map(|accent| AccentContent(color(*accent)))
+                .collect(),
+            colors,
+            status,
+            players: [cursor, magenta, green, yellow, cyan, red, orange]
+                .into_iter()
+                .map(|player| PlayerColorContent {
+                    cursor: color(player),
+                    background: color(player),
+                    selection: color(if player == cursor {
+                        selection
+                    } else {
+                        player.opacity(0.25)
+                    }),
+                })
+                .collect(),
+            syntax,
+        }
+    }
+}
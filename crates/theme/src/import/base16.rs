@@ -0,0 +1,228 @@
+//! Imports [Base16](https://github.com/tinted-theming/home) and Base24 color
+//! schemes, in both the original format, where the colors are top-level keys
+//! like `base00: "181818"`, and the newer one, where they're nested under
+//! `palette`.
+
+use anyhow::{Context as _, Result};
+use collections::HashMap;
+use gpui::Hsla;
+use indexmap::IndexMap;
+
+use crate::{
+    AppearanceContent, FontStyleContent, FontWeightContent, HighlightStyleContent, ThemeContent,
+    ThemeFamilyContent, hex_color, try_parse_color,
+};
+
+use super::Palette;
+
+/// The Base16 color that each syntax node is drawn with, following the
+/// scheme's styling guidelines.
+const SYNTAX_COLORS: &[(&str, &str)] = &[
+    ("attribute", "base0A"),
+    ("boolean", "base09"),
+    ("comment", "base03"),
+    ("comment.doc", "base03"),
+    ("constant", "base09"),
+    ("constructor", "base0A"),
+    ("embedded", "base0F"),
+    ("emphasis", "base0E"),
+    ("emphasis.strong", "base0E"),
+    ("enum", "base0A"),
+    ("function", "base0D"),
+    ("keyword", "base0E"),
+    ("label", "base0D"),
+    ("link_text", "base0D"),
+    ("link_uri", "base0C"),
+    ("number", "base09"),
+    ("operator", "base05"),
+    ("preproc", "base0E"),
+    ("property", "base08"),
+    ("punctuation", "base05"),
+    ("punctuation.bracket", "base05"),
+    ("punctuation.delimiter", "base05"),
+    ("punctuation.list_marker", "base08"),
+    ("punctuation.special", "base0F"),
+    ("string", "base0B"),
+    ("string.escape", "base0C"),
+    ("string.regex", "base0C"),
+    ("string.special", "base0C"),
+    ("string.special.symbol", "base0B"),
+    ("tag", "base08"),
+    ("text.literal", "base0B"),
+    ("title", "base0D"),
+    ("type", "base0A"),
+    ("variable", "base05"),
+    ("variable.special", "base08"),
+    ("variant", "base0A"),
+];
+
+pub(super) fn import(contents: &str, fallback_name: &str) -> Result<ThemeFamilyContent> {
+    let values = parse_values(contents);
+    let color = |key: &str| -> Result<Hsla> {
+        let value = values
+            .get(key)
+            .with_context(|| format!("missing color {key}"))?;
+        let value = value.strip_prefix('#').unwrap_or(value);
+        try_parse_color(&format!("#{value}")).with_context(|| format!("invalid color {key}"))
+    };
+    let name = values
+        .get("name")
+        .or_else(|| values.get("scheme"))
+        .map_or(fallback_name, String::as_str)
+        .to_string();
+    let author = values.get("author").cloned().unwrap_or_default();
+
+    let mut palette = Palette::new(color("base00")?, color("base05")?);
+    match values.get("variant").map(String::as_str) {
+        Some("dark") => palette.appearance = AppearanceContent::Dark,
+        Some("light") => palette.appearance = AppearanceContent::Light,
+        _ => {}
+    }
+    palette.surface = Some(color("base01")?);
+    palette.line_highlight = Some(color("base01")?);
+    palette.selection = Some(color("base02")?);
+    palette.line_number = Some(color("base03")?);
+    palette.muted = Some(color("base04")?);
+    palette.cursor = Some(color("base05")?);
+    palette.red = Some(color("base08")?);
+    palette.orange = Some(color("base09")?);
+    palette.yellow = Some(color("base0A")?);
+    palette.green = Some(color("base0B")?);
+    palette.cyan = Some(color("base0C")?);
+    palette.blue = Some(color("base0D")?);
+    palette.magenta = Some(color("base0E")?);
+
+    let mut syntax = IndexMap::new();
+    for (name, key) in SYNTAX_COLORS {
+        let mut style = HighlightStyleContent {
+            color: Some(hex_color(color(key)?)),
+            ..Default::default()
+        };
+        match *name {
+            "comment" | "comment.doc" | "emphasis" => {
+                style.font_style = Some(FontStyleContent::Italic)
+            }
+            "emphasis.strong" | "title" => style.font_weight = Some(FontWeightContent::Bold),
+            _ => {}
+        }
+        syntax.insert(name.to_string(), style);
+    }
+
+    let mut style = palette.style(syntax);
+    // Base24 adds bright versions of the accent colors.
+    let bright = |key: &str| color(key).ok().map(hex_color);
+    let colors = &mut style.colors;
+    colors.terminal_ansi_bright_red = bright("base12").or(colors.terminal_ansi_bright_red.take());
+    colors.terminal_ansi_bright_yellow =
+        bright("base13").or(colors.terminal_ansi_bright_yellow.take());
+    colors.terminal_ansi_bright_green =
+        bright("base14").or(colors.terminal_ansi_bright_green.take());
+    colors.terminal_ansi_bright_cyan = bright("base15").or(colors.terminal_ansi_bright_cyan.take());
+    colors.terminal_ansi_bright_blue = bright("base16").or(colors.terminal_ansi_bright_blue.take());
+    colors.terminal_ansi_bright_magenta =
+        bright("base17").or(colors.terminal_ansi_bright_magenta.take());
+
+    Ok(ThemeFamilyContent {
+        name: name.clone(),
+        author,
+        themes: vec![ThemeContent {
+            name,
+            appearance: palette.appearance,
+            style,
+        }],
+    })
+}
+
+/// Reads the `key: value` pairs of a scheme, regardless of how they're
+/// nested. Schemes only use this small subset of YAML.
+fn parse_values(contents: &str) -> HashMap<String, String> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once(':')?;
+            let key = key.trim().trim_matches(|c| c == '"' || c == '\'');
+            if key.is_empty() || key.starts_with('#') {
+                return None;
+            }
+            let value = value.trim();
+            let value = if let Some(quoted) = value.strip_prefix('"') {
+                quoted.split('"').next()?
+            } else if let Some(quoted) = value.strip_prefix('\'') {
+                quoted.split('\'').next()?
+            } else {
+                value.split(" #").next()?.trim()
+            };
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_base16() {
+        let scheme = r##"
+scheme: "Default Dark"
+author: "Chris Kempson (http://chriskempson.com)"
+base00: "181818" # Default background
+base01: "282828"
+base02: "383838"
+base03: "585858"
+base04: "b8b8b8"
+base05: "d8d8d8"
+base06: "e8e8e8"
+base07: "f8f8f8"
+base08: "ab4642"
+base09: "dc9656"
+base0A: "f7ca88"
+base0B: "a1b56c"
+base0C: "86c1b9"
+base0D: "7cafc2"
+base0E: "ba8baf"
+base0F: "a16946"
+"##;
+        let family = import(scheme, "default-dark").unwrap();
+        assert_eq!(family.name, "Default Dark");
+        assert_eq!(family.author, "Chris Kempson (http://chriskempson.com)");
+        let theme = &family.themes[0];
+        assert_eq!(theme.appearance, AppearanceContent::Dark);
+        assert_eq!(
+            theme.style.colors.editor_background.as_deref(),
+            Some("#181818ff")
+        );
+        assert_eq!(
+            theme.style.syntax["keyword"].color.as_deref(),
+            Some("#ba8bafff")
+        );
+    }
+
+    #[test]
+    fn test_import_base24_palette() {
+        let mut scheme = String::from(
+            "system: \"base24\"\nname: \"Example\"\nauthor: \"Someone\"\nvariant: \"light\"\npalette:\n",
+        );
+        for ix in 0..24 {
+            scheme.push_str(&format!(
+                "  base{ix:02X}: \"#{:02x}{:02x}{:02x}\"\n",
+                200,
+                ix * 8,
+                100
+            ));
+        }
+        let family = import(&scheme, "example").unwrap();
+        assert_eq!(family.name, "Example");
+        let theme = &family.themes[0];
+        assert_eq!(theme.appearance, AppearanceContent::Light);
+        assert_eq!(
+            theme.style.colors.terminal_ansi_bright_red.as_deref(),
+            Some("#c89064ff")
+        );
+    }
+
+    #[test]
+    fn test_import_base16_missing_color() {
+        assert!(import("scheme: \"Broken\"\nbase00: \"000000\"\n", "broken").is_err());
+    }
+}
@@ -0,0 +1,200 @@
+//! Imports [Helix](https://docs.helix-editor.com/themes.html) themes.
+
+use anyhow::{Context as _, Result};
+use gpui::Hsla;
+use indexmap::IndexMap;
+
+use crate::{
+    FontStyleContent, FontWeightContent, HighlightStyleContent, ThemeContent, ThemeFamilyContent,
+    hex_color, try_parse_color,
+};
+
+use super::Palette;
+
+/// The Helix scope that each syntax node is drawn with. Scopes that a theme
+/// doesn't style fall back to their parent scope, like in Helix.
+const SYNTAX_SCOPES: &[(&str, &str)] = &[
+    ("attribute", "attribute"),
+    ("boolean", "constant.builtin.boolean"),
+    ("comment", "comment"),
+    ("comment.doc", "comment.block.documentation"),
+    ("constant", "constant"),
+    ("constructor", "constructor"),
+    ("embedded", "markup.raw.block"),
+    ("emphasis", "markup.italic"),
+    ("emphasis.strong", "markup.bold"),
+    ("enum", "type.enum"),
+    ("function", "function"),
+    ("keyword", "keyword"),
+    ("label", "label"),
+    ("link_text", "markup.link.text"),
+    ("link_uri", "markup.link.url"),
+    ("number", "constant.numeric"),
+    ("operator", "operator"),
+    ("preproc", "keyword.directive"),
+    ("property", "variable.other.member"),
+    ("punctuation", "punctuation"),
+    ("punctuation.bracket", "punctuation.bracket"),
+    ("punctuation.delimiter", "punctuation.delimiter"),
+    ("punctuation.list_marker", "markup.list"),
+    ("punctuation.special", "punctuation.special"),
+    ("string", "string"),
+    ("string.escape", "constant.character.escape"),
+    ("string.regex", "string.regexp"),
+    ("string.special", "string.special"),
+    ("string.special.symbol", "string.special.symbol"),
+    ("tag", "tag"),
+    ("text.literal", "markup.raw"),
+    ("title", "markup.heading"),
+    ("type", "type"),
+    ("variable", "variable"),
+    ("variable.special", "variable.builtin"),
+    ("variant", "type.enum.variant"),
+];
+
+/// A style in a Helix theme, which is either a color or a table of colors
+/// and modifiers.
+#[derive(Default)]
+struct HelixStyle {
+    fg: Option<Hsla>,
+    bg: Option<Hsla>,
+    modifiers: Vec<String>,
+}
+
+pub(super) fn import(contents: &str, name: &str) -> Result<ThemeFamilyContent> {
+    let theme: toml::Table = contents.parse()?;
+    let palette_colors = theme.get("palette").and_then(toml::Value::as_table);
+    let resolve_color = |value: &toml::Value| {
+        let value = value.as_str()?;
+        let value = palette_colors
+            .and_then(|palette| palette.get(value)?.as_str())
+            .unwrap_or(value);
+        try_parse_color(value).ok()
+    };
+    let style = |scope: &str| -> Option<HelixStyle> {
+        // Fall back to the parent scopes, so that `keyword.control` is drawn
+        // like `keyword` when it isn't styled itself.
+        let mut scope = scope;
+        let value = loop {
+            if let Some(value) = theme.get(scope) {
+                break value;
+            }
+            scope = &scope[..scope.rfind('.')?];
+        };
+        Some(match value {
+            toml::Value::Table(table) => HelixStyle {
+                fg: table.get("fg").and_then(resolve_color),
+                bg: table.get("bg").and_then(resolve_color),
+                modifiers: table
+                    .get("modifiers")
+                    .and_then(toml::Value::as_array)
+                    .map(|modifiers| {
+                        modifiers
+                            .iter()
+                            .filter_map(|modifier| Some(modifier.as_str()?.to_string()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+            },
+            value => HelixStyle {
+                fg: resolve_color(value),
+                ..Default::default()
+            },
+        })
+    };
+    let fg = |scope: &str| style(scope).and_then(|style| style.fg);
+    let bg = |scope: &str| style(scope).and_then(|style| style.bg);
+
+    let background = bg("ui.background").context("the theme doesn't set ui.background")?;
+    let foreground = fg("ui.text")
+        .or_else(|| fg("ui.background"))
+        .context("the theme doesn't set ui.text")?;
+
+    let mut syntax = IndexMap::new();
+    for (name, scope) in SYNTAX_SCOPES {
+        let Some(style) = style(scope) else {
+            continue;
+        };
+        let has_modifier = |modifier: &str| style.modifiers.iter().any(|m| m == modifier);
+        let style = HighlightStyleContent {
+            color: style.fg.map(hex_color),
+            background_color: style.bg.map(hex_color),
+            font_style: has_modifier("italic").then_some(FontStyleContent::Italic),
+            font_weight: has_modifier("bold").then_some(FontWeightContent::Bold),
+        };
+        if !style.is_empty() {
+            syntax.insert(name.to_string(), style);
+        }
+    }
+
+    let mut palette = Palette::new(background, foreground);
+    palette.surface = bg("ui.statusline").or_else(|| bg("ui.popup"));
+    palette.selection = bg("ui.selection.primary").or_else(|| bg("ui.selection"));
+    palette.line_highlight = bg("ui.cursorline.primary").or_else(|| bg("ui.cursorline"));
+    palette.line_number = fg("ui.linenr");
+    palette.cursor = bg("ui.cursor.primary").or_else(|| bg("ui.cursor"));
+    palette.muted = fg("comment");
+    palette.red = fg("error").or_else(|| fg("diff.minus"));
+    palette.yellow = fg("warning").or_else(|| fg("diff.delta"));
+    palette.green = fg("diff.plus");
+    palette.blue = fg("info").or_else(|| fg("hint"));
+    palette.fill_accents_from_syntax(&syntax);
+
+    Ok(ThemeFamilyContent {
+        name: name.to_string(),
+        author: String::new(),
+        themes: vec![ThemeContent {
+            name: name.to_string(),
+            appearance: palette.appearance,
+            style: palette.style(syntax),
+        }],
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppearanceContent;
+
+    #[test]
+    fn test_import_helix_theme() {
+        let theme = r##"
+"ui.background" = { bg = "base" }
+"ui.text" = "text"
+"ui.selection" = { bg = "#403d52" }
+"comment" = { fg = "muted", modifiers = ["italic"] }
+"keyword" = "pine"
+"constant.numeric" = { fg = "gold", modifiers = ["bold"] }
+"error" = "love"
+
+[palette]
+base = "#faf4ed"
+text = "#575279"
+muted = "#9893a5"
+pine = "#286983"
+gold = "#ea9d34"
+love = "#b4637a"
+"##;
+        let family = import(theme, "rose_pine_dawn").unwrap();
+        assert_eq!(family.name, "rose_pine_dawn");
+        let theme = &family.themes[0];
+        assert_eq!(theme.appearance, AppearanceContent::Light);
+        assert_eq!(
+            theme.style.colors.editor_background.as_deref(),
+            Some("#faf4edff")
+        );
+
+        let syntax = &theme.style.syntax;
+        assert_eq!(syntax["comment"].color.as_deref(), Some("#9893a5ff"));
+        assert_eq!(syntax["comment"].font_style, Some(FontStyleContent::Italic));
+        // `keyword.directive` falls back to `keyword`.
+        assert_eq!(syntax["preproc"].color.as_deref(), Some("#286983ff"));
+        assert_eq!(syntax["number"].font_weight, Some(FontWeightContent::Bold));
+        assert!(!syntax.contains_key("string"));
+    }
+
+    #[test]
+    fn test_import_helix_theme_without_background() {
+        assert!(import("\"ui.text\" = \"#ffffff\"\n", "broken").is_err());
+    }
+}
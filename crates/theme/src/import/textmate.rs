@@ -0,0 +1,310 @@
+//! Imports TextMate and Sublime Text `.tmTheme` files.
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use serde::Deserialize;
+
+use crate::{
+    FontStyleContent, FontWeightContent, HighlightStyleContent, ThemeContent, ThemeFamilyContent,
+    hex_color, try_parse_color,
+};
+
+use super::Palette;
+
+/// The TextMate scopes that each syntax node is matched against, most
+/// specific first.
+const SYNTAX_SCOPES: &[(&str, &[&str])] = &[
+    ("attribute", &["entity.other.attribute-name"]),
+    (
+        "boolean",
+        &["constant.language.boolean", "constant.language"],
+    ),
+    ("comment", &["comment"]),
+    ("comment.doc", &["comment.block.documentation", "comment"]),
+    ("constant", &["constant.other", "constant"]),
+    (
+        "constructor",
+        &["entity.name.function.constructor", "entity.name.class"],
+    ),
+    ("embedded", &["meta.embedded", "source"]),
+    ("emphasis", &["markup.italic"]),
+    ("emphasis.strong", &["markup.bold"]),
+    ("enum", &["entity.name.enum", "entity.name.type"]),
+    ("function", &["entity.name.function", "support.function"]),
+    ("keyword", &["keyword.control", "keyword", "storage"]),
+    ("label", &["entity.name.label"]),
+    ("link_text", &["string.other.link", "markup.underline.link"]),
+    ("link_uri", &["markup.underline.link"]),
+    ("number", &["constant.numeric"]),
+    ("operator", &["keyword.operator"]),
+    ("preproc", &["meta.preprocessor", "keyword.control.import"]),
+    (
+        "property",
+        &[
+            "variable.other.property",
+            "variable.other.member",
+            "support.type.property-name",
+        ],
+    ),
+    ("punctuation", &["punctuation"]),
+    (
+        "punctuation.bracket",
+        &["punctuation.section", "punctuation"],
+    ),
+    (
+        "punctuation.delimiter",
+        &[
+            "punctuation.separator",
+            "punctuation.terminator",
+            "punctuation",
+        ],
+    ),
+    (
+        "punctuation.list_marker",
+        &["punctuation.definition.list", "markup.list"],
+    ),
+    (
+        "punctuation.special",
+        &["punctuation.definition.template-expression", "punctuation"],
+    ),
+    ("string", &["string"]),
+    ("string.escape", &["constant.character.escape"]),
+    ("string.regex", &["string.regexp"]),
+    ("string.special", &["string.other", "constant.other.symbol"]),
+    ("string.special.symbol", &["constant.other.symbol"]),
+    ("tag", &["entity.name.tag"]),
+    ("text.literal", &["markup.raw", "markup.inline.raw"]),
+    ("title", &["markup.heading", "entity.name.section"]),
+    (
+        "type",
+        &["entity.name.type", "support.type", "storage.type"],
+    ),
+    ("variable", &["variable"]),
+    ("variable.special", &["variable.language"]),
+    (
+        "variant",
+        &["variable.other.enummember", "constant.other.enum"],
+    ),
+];
+
+#[derive(Deserialize)]
+struct TmTheme {
+    name: Option<String>,
+    author: Option<String>,
+    settings: Vec<TmThemeRule>,
+}
+
+#[derive(Deserialize)]
+struct TmThemeRule {
+    scope: Option<String>,
+    settings: TmThemeSettings,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TmThemeSettings {
+    foreground: Option<String>,
+    background: Option<String>,
+    font_style: Option<String>,
+    caret: Option<String>,
+    selection: Option<String>,
+    line_highlight: Option<String>,
+    gutter_foreground: Option<String>,
+}
+
+pub(super) fn import(contents: &[u8], fallback_name: &str) -> Result<ThemeFamilyContent> {
+    let theme: TmTheme = plist::from_bytes(contents)?;
+    let name = theme
+        .name
+        .clone()
+        .unwrap_or_else(|| fallback_name.to_string());
+
+    // The rule without a scope holds the colors of the editor itself.
+    let global = theme
+        .settings
+        .iter()
+        .find(|rule| rule.scope.is_none())
+        .map(|rule| &rule.settings);
+    let color = |color: Option<&String>| color.and_then(|color| try_parse_color(color).ok());
+    let background = color(global.and_then(|settings| settings.background.as_ref()));
+    let foreground = color(global.and_then(|settings| settings.foreground.as_ref()));
+    let (Some(background), Some(foreground)) = (background, foreground) else {
+        anyhow::bail!("the theme doesn't set a background and foreground color");
+    };
+
+    let mut syntax = IndexMap::new();
+    for (name, scopes) in SYNTAX_SCOPES {
+        let Some(rule) = scopes
+            .iter()
+            .find_map(|scope| best_matching_rule(&theme.settings, scope))
+        else {
+            continue;
+        };
+        let font_style = rule.settings.font_style.as_deref().unwrap_or_default();
+        let style = HighlightStyleContent {
+            color: color(rule.settings.foreground.as_ref()).map(hex_color),
+            background_color: color(rule.settings.background.as_ref()).map(hex_color),
+            font_style: font_style
+                .contains("italic")
+                .then_some(FontStyleContent::Italic),
+            font_weight: font_style
+                .contains("bold")
+                .then_some(FontWeightContent::Bold),
+        };
+        if !style.is_empty() {
+            syntax.insert(name.to_string(), style);
+        }
+    }
+
+    let mut palette = Palette::new(background, foreground);
+    if let Some(global) = global {
+        palette.selection = color(global.selection.as_ref());
+        palette.line_highlight = color(global.line_highlight.as_ref());
+        palette.line_number = color(global.gutter_foreground.as_ref());
+        palette.cursor = color(global.caret.as_ref());
+    }
+    palette.fill_accents_from_syntax(&syntax);
+
+    Ok(ThemeFamilyContent {
+        name: name.clone(),
+        author: theme.author.unwrap_or_default(),
+        themes: vec![ThemeContent {
+            name,
+            appearance: palette.appearance,
+            style: palette.style(syntax),
+        }],
+    })
+}
+
+/// Finds the rule that applies to the given scope, which is the rule with
+/// the longest selector that the scope starts with, or the last such rule if
+/// there are several, like in TextMate.
+fn best_matching_rule<'a>(rules: &'a [TmThemeRule], scope: &str) -> Option<&'a TmThemeRule> {
+    rules
+        .iter()
+        .filter(|rule| rule.settings.foreground.is_some() || rule.settings.font_style.is_some())
+        .filter_map(|rule| {
+            let selector_len = rule
+                .scope
+                .as_deref()?
+                .split(',')
+                .map(str::trim)
+                .filter(|selector| scope_matches(selector, scope))
+                .map(str::len)
+                .max()?;
+            Some((selector_len, rule))
+        })
+        .max_by_key(|(selector_len, _)| *selector_len)
+        .map(|(_, rule)| rule)
+}
+
+/// Returns whether a selector applies to a scope. Only selectors of a single
+/// scope are supported, so `string.quoted` matches `string.quoted.double`,
+/// but `source.rust string` doesn't match anything.
+fn scope_matches(selector: &str, scope: &str) -> bool {
+    scope
+        .strip_prefix(selector)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('.'))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::AppearanceContent;
+
+    const MONOKAI: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>name</key>
+    <string>Monokai</string>
+    <key>settings</key>
+    <array>
+        <dict>
+            <key>settings</key>
+            <dict>
+                <key>background</key>
+                <string>#272822</string>
+                <key>foreground</key>
+                <string>#F8F8F2</string>
+                <key>caret</key>
+                <string>#F8F8F0</string>
+                <key>selection</key>
+                <string>#49483E</string>
+            </dict>
+        </dict>
+        <dict>
+            <key>name</key>
+            <string>Comment</string>
+            <key>scope</key>
+            <string>comment</string>
+            <key>settings</key>
+            <dict>
+                <key>foreground</key>
+                <string>#75715E</string>
+            </dict>
+        </dict>
+        <dict>
+            <key>name</key>
+            <string>Keyword</string>
+            <key>scope</key>
+            <string>keyword, storage</string>
+            <key>settings</key>
+            <dict>
+                <key>foreground</key>
+                <string>#F92672</string>
+            </dict>
+        </dict>
+        <dict>
+            <key>name</key>
+            <string>Function name</string>
+            <key>scope</key>
+            <string>entity.name.function</string>
+            <key>settings</key>
+            <dict>
+                <key>fontStyle</key>
+                <string>bold italic</string>
+                <key>foreground</key>
+                <string>#A6E22E</string>
+            </dict>
+        </dict>
+    </array>
+</dict>
+</plist>
+"#;
+
+    #[test]
+    fn test_import_tmtheme() {
+        let family = import(MONOKAI.as_bytes(), "monokai").unwrap();
+        assert_eq!(family.name, "Monokai");
+        let theme = &family.themes[0];
+        assert_eq!(theme.appearance, AppearanceContent::Dark);
+        assert_eq!(
+            theme.style.colors.editor_background.as_deref(),
+            Some("#272822ff")
+        );
+
+        let syntax = &theme.style.syntax;
+        assert_eq!(syntax["comment"].color.as_deref(), Some("#75715eff"));
+        assert_eq!(syntax["comment.doc"].color.as_deref(), Some("#75715eff"));
+        // `keyword.operator` falls back to the `keyword` rule.
+        assert_eq!(syntax["operator"].color.as_deref(), Some("#f92672ff"));
+        assert_eq!(
+            syntax["function"].font_style,
+            Some(FontStyleContent::Italic)
+        );
+        assert_eq!(
+            syntax["function"].font_weight,
+            Some(FontWeightContent::Bold)
+        );
+        assert!(!syntax.contains_key("string"));
+    }
+
+    #[test]
+    fn test_scope_matches() {
+        assert!(scope_matches("string", "string"));
+        assert!(scope_matches("string", "string.quoted"));
+        assert!(!scope_matches("string", "stringy"));
+        assert!(!scope_matches("string.quoted", "string"));
+    }
+}
@@ -126,8 +126,12 @@ impl ThemeRegistry {
         }
     }
 
-    #[allow(unused)]
-    fn insert_user_theme_families(&self, families: impl IntoIterator<Item = ThemeFamilyContent>) {
+    /// Refines the given theme families and adds their themes to the registry,
+    /// replacing any themes with the same names.
+    pub fn insert_user_theme_families(
+        &self,
+        families: impl IntoIterator<Item = ThemeFamilyContent>,
+    ) {
         for family in families.into_iter() {
             let refined_family = refine_theme_family(family);
 
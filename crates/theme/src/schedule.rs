@@ -0,0 +1,360 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, NaiveTime, TimeZone, Timelike as _};
+use gpui::{App, Global, Task};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use settings::{Settings as _, SettingsStore};
+
+use crate::{Appearance, ThemeMode, ThemeSettings};
+
+const SECONDS_PER_DAY: u32 = 24 * 60 * 60;
+
+/// The longest time to wait before checking the schedule again, so that a
+/// change of the system clock or a sleeping machine delays a switch by at
+/// most this long.
+const MAX_SCHEDULE_CHECK_INTERVAL: Duration = Duration::from_secs(15 * 60);
+
+/// The Julian date of the Unix epoch.
+const UNIX_EPOCH_JULIAN_DATE: f64 = 2440587.5;
+
+/// The Julian date of the J2000 epoch, which the solar position formulas are
+/// relative to.
+const J2000_JULIAN_DATE: f64 = 2451545.0;
+
+/// A schedule for switching between the light and dark themes of a theme
+/// selection in [`ThemeMode::Scheduled`].
+#[derive(Clone, Debug, Serialize, Deserialize, JsonSchema, PartialEq)]
+#[serde(untagged)]
+pub enum ThemeSchedule {
+    /// Switches to the light theme and to the dark theme at fixed local times.
+    Fixed {
+        /// The local time to switch to the light theme at, like `"07:00"`.
+        #[schemars(with = "String")]
+        light: ScheduleTime,
+        /// The local time to switch to the dark theme at, like `"19:30"`.
+        #[schemars(with = "String")]
+        dark: ScheduleTime,
+    },
+    /// Switches to the light theme at sunrise and to the dark theme at sunset,
+    /// at the given location.
+    ///
+    /// The times are calculated locally, so the location never leaves the
+    /// machine.
+    SunriseSunset {
+        /// The latitude of the location, in degrees north.
+        latitude: f64,
+        /// The longitude of the location, in degrees east.
+        longitude: f64,
+    },
+}
+
+/// A local time of day in a [`ThemeSchedule`], written as `"HH:MM"`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ScheduleTime(NaiveTime);
+
+impl TryFrom<String> for ScheduleTime {
+    type Error = chrono::ParseError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        NaiveTime::parse_from_str(&value, "%H:%M").map(Self)
+    }
+}
+
+impl From<ScheduleTime> for String {
+    fn from(value: ScheduleTime) -> Self {
+        value.0.format("%H:%M").to_string()
+    }
+}
+
+impl ThemeSchedule {
+    /// Returns the appearance that the schedule calls for at the given time,
+    /// and how long it is until the schedule switches to the other one.
+    pub fn appearance_at<Tz: TimeZone>(&self, now: &DateTime<Tz>) -> (Appearance, Duration) {
+        match self {
+            Self::Fixed { light, dark } => {
+                let now = now.time().num_seconds_from_midnight();
+                let light = light.0.num_seconds_from_midnight();
+                let dark = dark.0.num_seconds_from_midnight();
+                let seconds_until =
+                    |time: u32| match (time + SECONDS_PER_DAY - now) % SECONDS_PER_DAY {
+                        0 => SECONDS_PER_DAY,
+                        seconds => seconds,
+                    };
+
+                let is_light = if light <= dark {
+                    light <= now && now < dark
+                } else {
+                    now >= light || now < dark
+                };
+                if is_light {
+                    (
+                        Appearance::Light,
+                        Duration::from_secs(seconds_until(dark).into()),
+                    )
+                } else {
+                    (
+                        Appearance::Dark,
+                        Duration::from_secs(seconds_until(light).into()),
+                    )
+                }
+            }
+            Self::SunriseSunset {
+                latitude,
+                longitude,
+            } => sun_appearance_at(now.timestamp() as f64, *latitude, *longitude),
+        }
+    }
+}
+
+/// The times the sun rises and sets on a day.
+enum SunEvents {
+    RiseAndSet {
+        sunrise: f64,
+        sunset: f64,
+    },
+    /// The sun doesn't set, like during polar summer.
+    AlwaysUp,
+    /// The sun doesn't rise, like during polar winter.
+    AlwaysDown,
+}
+
+/// Returns the appearance at the given Unix timestamp at a location, and how
+/// long it is until the next sunrise or sunset.
+fn sun_appearance_at(now: f64, latitude: f64, longitude: f64) -> (Appearance, Duration) {
+    let day = (now / SECONDS_PER_DAY as f64 + UNIX_EPOCH_JULIAN_DATE - J2000_JULIAN_DATE + 0.0008)
+        .ceil() as i64;
+
+    // The day number is only approximately aligned with the local day, so
+    // look at the surrounding days too.
+    let mut events = Vec::new();
+    let mut polar_appearance = None;
+    for day in day - 2..=day + 1 {
+        match sun_events(day, latitude, longitude) {
+            SunEvents::RiseAndSet { sunrise, sunset } => {
+                events.push((sunrise, Appearance::Light));
+                events.push((sunset, Appearance::Dark));
+            }
+            SunEvents::AlwaysUp => polar_appearance = Some(Appearance::Light),
+            SunEvents::AlwaysDown => polar_appearance = Some(Appearance::Dark),
+        }
+    }
+    events.sort_by(|(a, _), (b, _)| a.total_cmp(b));
+
+    let appearance = events
+        .iter()
+        .rev()
+        .find(|(time, _)| *time <= now)
+        .map(|(_, appearance)| *appearance)
+        .or(polar_appearance)
+        .unwrap_or(Appearance::Dark);
+    let next_switch = events
+        .iter()
+        .find(|(time, _)| *time > now)
+        .map_or(SECONDS_PER_DAY as f64, |(time, _)| time - now);
+
+    (appearance, Duration::from_secs_f64(next_switch))
+}
+
+/// Calculates the sunrise and sunset on the given day, counted from the
+/// J2000 epoch, with the [sunrise equation](https://en.wikipedia.org/wiki/Sunrise_equation).
+fn sun_events(day: i64, latitude: f64, longitude: f64) -> SunEvents {
+    let mean_solar_time = day as f64 - longitude / 360.;
+    let mean_anomaly = (357.5291 + 0.98560028 * mean_solar_time)
+        .rem_euclid(360.)
+        .to_radians();
+    let center = 1.9148 * mean_anomaly.sin()
+        + 0.02 * (2. * mean_anomaly).sin()
+        + 0.0003 * (3. * mean_anomaly).sin();
+    let ecliptic_longitude = (mean_anomaly.to_degrees() + center + 180. + 102.9372)
+        .rem_euclid(360.)
+        .to_radians();
+    let solar_transit = J2000_JULIAN_DATE + mean_solar_time + 0.0053 * mean_anomaly.sin()
+        - 0.0069 * (2. * ecliptic_longitude).sin();
+    let declination = (ecliptic_longitude.sin() * 23.4397_f64.to_radians().sin()).asin();
+
+    let latitude = latitude.to_radians();
+    let hour_angle_cos = ((-0.833_f64).to_radians().sin() - latitude.sin() * declination.sin())
+        / (latitude.cos() * declination.cos());
+    if hour_angle_cos < -1. {
+        return SunEvents::AlwaysUp;
+    } else if hour_angle_cos > 1. {
+        return SunEvents::AlwaysDown;
+    }
+
+    let hour_angle = hour_angle_cos.acos().to_degrees() / 360.;
+    let to_timestamp =
+        |julian_date: f64| (julian_date - UNIX_EPOCH_JULIAN_DATE) * SECONDS_PER_DAY as f64;
+    SunEvents::RiseAndSet {
+        sunrise: to_timestamp(solar_transit - hour_angle),
+        sunset: to_timestamp(solar_transit + hour_angle),
+    }
+}
+
+#[derive(Default)]
+struct GlobalThemeScheduler {
+    schedule: Option<ThemeSchedule>,
+    appearance: Option<Appearance>,
+    _next_check: Option<Task<()>>,
+}
+
+impl Global for GlobalThemeScheduler {}
+
+/// Returns the appearance that the themes of a selection in the given mode
+/// are picked for.
+///
+/// This is the appearance that the theme schedule currently calls for in
+/// [`ThemeMode::Scheduled`], when a schedule is configured, and the system
+/// appearance otherwise.
+pub fn appearance_for_mode(
+    mode: Option<ThemeMode>,
+    system_appearance: Appearance,
+    cx: &App,
+) -> Appearance {
+    if mode != Some(ThemeMode::Scheduled) {
+        return system_appearance;
+    }
+
+    cx.try_global::<GlobalThemeScheduler>()
+        .and_then(|scheduler| scheduler.appearance)
+        .unwrap_or(system_appearance)
+}
+
+pub(crate) fn init(cx: &mut App) {
+    update_scheduled_appearance(cx);
+
+    cx.observe_global::<SettingsStore>(|cx| {
+        let schedule = ThemeSettings::get_global(cx).theme_schedule.clone();
+        if cx.default_global::<GlobalThemeScheduler>().schedule != schedule {
+            update_scheduled_appearance(cx);
+        }
+    })
+    .detach();
+}
+
+/// Checks which appearance the theme schedule calls for, reloading the themes
+/// when it has changed, and checks again when it's due to switch.
+fn update_scheduled_appearance(cx: &mut App) {
+    let schedule = ThemeSettings::get_global(cx).theme_schedule.clone();
+    let (appearance, next_check) = match &schedule {
+        Some(schedule) => {
+            let (appearance, next_switch) = schedule.appearance_at(&Local::now());
+            let next_check = cx.spawn(async move |cx| {
+                cx.background_executor()
+                    .timer(next_switch.min(MAX_SCHEDULE_CHECK_INTERVAL))
+                    .await;
+                cx.update(update_scheduled_appearance).ok();
+            });
+            (Some(appearance), Some(next_check))
+        }
+        None => (None, None),
+    };
+
+    let previous_appearance = cx.default_global::<GlobalThemeScheduler>().appearance;
+    cx.set_global(GlobalThemeScheduler {
+        schedule,
+        appearance,
+        _next_check: next_check,
+    });
+
+    if appearance != previous_appearance {
+        ThemeSettings::reload_current_theme(cx);
+        ThemeSettings::reload_current_icon_theme(cx);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{FixedOffset, Utc};
+
+    fn time(time: &str) -> ScheduleTime {
+        ScheduleTime::try_from(time.to_string()).unwrap()
+    }
+
+    #[test]
+    fn test_fixed_schedule() {
+        let schedule = ThemeSchedule::Fixed {
+            light: time("07:00"),
+            dark: time("19:30"),
+        };
+        let offset = FixedOffset::east_opt(2 * 3600).unwrap();
+        let at = |hour, minute| {
+            offset
+                .with_ymd_and_hms(2024, 6, 21, hour, minute, 0)
+                .unwrap()
+        };
+
+        assert_eq!(
+            schedule.appearance_at(&at(6, 59)),
+            (Appearance::Dark, Duration::from_secs(60))
+        );
+        assert_eq!(
+            schedule.appearance_at(&at(7, 0)),
+            (Appearance::Light, Duration::from_secs(12 * 3600 + 30 * 60))
+        );
+        assert_eq!(
+            schedule.appearance_at(&at(23, 0)),
+            (Appearance::Dark, Duration::from_secs(8 * 3600))
+        );
+
+        // Schedules can also switch to the light theme in the evening.
+        let schedule = ThemeSchedule::Fixed {
+            light: time("20:00"),
+            dark: time("06:00"),
+        };
+        assert_eq!(schedule.appearance_at(&at(23, 0)).0, Appearance::Light);
+        assert_eq!(schedule.appearance_at(&at(12, 0)).0, Appearance::Dark);
+    }
+
+    #[test]
+    fn test_sunrise_sunset_schedule() {
+        // Berlin, where the sun rises at 02:43 UTC and sets at 19:33 UTC on
+        // the summer solstice.
+        let schedule = ThemeSchedule::SunriseSunset {
+            latitude: 52.52,
+            longitude: 13.405,
+        };
+        let at = |hour, minute| Utc.with_ymd_and_hms(2024, 6, 21, hour, minute, 0).unwrap();
+
+        let (appearance, next_switch) = schedule.appearance_at(&at(2, 0));
+        assert_eq!(appearance, Appearance::Dark);
+        assert!(next_switch.as_secs().abs_diff(43 * 60) < 5 * 60);
+
+        let (appearance, next_switch) = schedule.appearance_at(&at(12, 0));
+        assert_eq!(appearance, Appearance::Light);
+        assert!(next_switch.as_secs().abs_diff(7 * 3600 + 33 * 60) < 5 * 60);
+
+        let (appearance, _) = schedule.appearance_at(&at(22, 0));
+        assert_eq!(appearance, Appearance::Dark);
+    }
+
+    #[test]
+    fn test_sunrise_sunset_schedule_during_polar_day() {
+        // Tromsø, where the sun doesn't set in June.
+        let schedule = ThemeSchedule::SunriseSunset {
+            latitude: 69.65,
+            longitude: 18.96,
+        };
+        let midnight = Utc.with_ymd_and_hms(2024, 6, 21, 23, 0, 0).unwrap();
+        assert_eq!(schedule.appearance_at(&midnight).0, Appearance::Light);
+    }
+
+    #[test]
+    fn test_schedule_deserialization() {
+        let schedule: ThemeSchedule =
+            serde_json::from_str(r#"{ "light": "07:00", "dark": "19:30" }"#).unwrap();
+        assert_eq!(
+            schedule,
+            ThemeSchedule::Fixed {
+                light: time("07:00"),
+                dark: time("19:30"),
+            }
+        );
+        assert!(
+            serde_json::from_str::<ThemeSchedule>(r#"{ "light": "7am", "dark": "19:30" }"#)
+                .is_err()
+        );
+    }
+}
@@ -11,7 +11,10 @@ use serde::{Deserialize, Deserializer, Serialize};
 use serde_json::Value;
 use serde_repr::{Deserialize_repr, Serialize_repr};
 
-use crate::{StatusColorsRefinement, ThemeColorsRefinement};
+use crate::{
+    Appearance, StatusColors, StatusColorsRefinement, Theme, ThemeColors, ThemeColorsRefinement,
+    ThemeStyles,
+};
 
 pub(crate) fn try_parse_color(color: &str) -> Result<Hsla> {
     let rgba = gpui::Rgba::try_from(color)?;
@@ -28,6 +31,14 @@ pub(crate) fn try_parse_color(color: &str) -> Result<Hsla> {
     Ok(hsla)
 }
 
+/// Formats a color the way colors are written in theme files, as `#rrggbbaa`.
+pub fn hex_color(color: Hsla) -> String {
+    let rgba = color.to_rgb();
+    let [r, g, b, a] = [rgba.r, rgba.g, rgba.b, rgba.a]
+        .map(|channel| (channel.clamp(0., 1.) * 255.).round() as u8);
+    format!("#{r:02x}{g:02x}{b:02x}{a:02x}")
+}
+
 fn ensure_non_opaque(color: Hsla) -> Hsla {
     const MAXIMUM_OPACITY: f32 = 0.7;
     if color.a <= MAXIMUM_OPACITY {
@@ -56,6 +67,15 @@ pub enum WindowBackgroundContent {
     Blurred,
 }
 
+impl From<Appearance> for AppearanceContent {
+    fn from(value: Appearance) -> Self {
+        match value {
+            Appearance::Light => Self::Light,
+            Appearance::Dark => Self::Dark,
+        }
+    }
+}
+
 impl From<WindowBackgroundContent> for WindowBackgroundAppearance {
     fn from(value: WindowBackgroundContent) -> Self {
         match value {
@@ -66,6 +86,16 @@ impl From<WindowBackgroundContent> for WindowBackgroundAppearance {
     }
 }
 
+impl From<WindowBackgroundAppearance> for WindowBackgroundContent {
+    fn from(value: WindowBackgroundAppearance) -> Self {
+        match value {
+            WindowBackgroundAppearance::Opaque => Self::Opaque,
+            WindowBackgroundAppearance::Transparent => Self::Transparent,
+            WindowBackgroundAppearance::Blurred => Self::Blurred,
+        }
+    }
+}
+
 /// The content of a serialized theme family.
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct ThemeFamilyContent {
@@ -82,6 +112,18 @@ pub struct ThemeContent {
     pub style: ThemeStyleContent,
 }
 
+impl From<&Theme> for ThemeContent {
+    /// Serializes every color of the theme, so that it round-trips through
+    /// [`refine_theme_family`](crate::refine_theme_family) unchanged.
+    fn from(theme: &Theme) -> Self {
+        Self {
+            name: theme.name.to_string(),
+            appearance: theme.appearance.into(),
+            style: ThemeStyleContent::from(&theme.styles),
+        }
+    }
+}
+
 /// The content of a serialized theme.
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(default)]
@@ -146,6 +188,38 @@ impl ThemeStyleContent {
     }
 }
 
+impl From<&ThemeStyles> for ThemeStyleContent {
+    fn from(styles: &ThemeStyles) -> Self {
+        Self {
+            window_background_appearance: Some(styles.window_background_appearance.into()),
+            accents: styles
+                .accents
+                .0
+                .iter()
+                .map(|accent| AccentContent(Some(hex_color(*accent))))
+                .collect(),
+            colors: ThemeColorsContent::from(&styles.colors),
+            status: StatusColorsContent::from(&styles.status),
+            players: styles
+                .player
+                .0
+                .iter()
+                .map(|player| PlayerColorContent {
+                    cursor: Some(hex_color(player.cursor)),
+                    background: Some(hex_color(player.background)),
+                    selection: Some(hex_color(player.selection)),
+                })
+                .collect(),
+            syntax: styles
+                .syntax
+                .highlights
+                .iter()
+                .map(|(name, style)| (name.clone(), HighlightStyleContent::from(style)))
+                .collect(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct ThemeColorsContent {
@@ -1130,6 +1204,146 @@ impl ThemeColorsContent {
     }
 }
 
+impl From<&ThemeColors> for ThemeColorsContent {
+    fn from(colors: &ThemeColors) -> Self {
+        Self {
+            border: Some(hex_color(colors.border)),
+            border_variant: Some(hex_color(colors.border_variant)),
+            border_focused: Some(hex_color(colors.border_focused)),
+            border_selected: Some(hex_color(colors.border_selected)),
+            border_transparent: Some(hex_color(colors.border_transparent)),
+            border_disabled: Some(hex_color(colors.border_disabled)),
+            elevated_surface_background: Some(hex_color(colors.elevated_surface_background)),
+            surface_background: Some(hex_color(colors.surface_background)),
+            background: Some(hex_color(colors.background)),
+            element_background: Some(hex_color(colors.element_background)),
+            element_hover: Some(hex_color(colors.element_hover)),
+            element_active: Some(hex_color(colors.element_active)),
+            element_selected: Some(hex_color(colors.element_selected)),
+            element_disabled: Some(hex_color(colors.element_disabled)),
+            drop_target_background: Some(hex_color(colors.drop_target_background)),
+            ghost_element_background: Some(hex_color(colors.ghost_element_background)),
+            ghost_element_hover: Some(hex_color(colors.ghost_element_hover)),
+            ghost_element_active: Some(hex_color(colors.ghost_element_active)),
+            ghost_element_selected: Some(hex_color(colors.ghost_element_selected)),
+            ghost_element_disabled: Some(hex_color(colors.ghost_element_disabled)),
+            text: Some(hex_color(colors.text)),
+            text_muted: Some(hex_color(colors.text_muted)),
+            text_placeholder: Some(hex_color(colors.text_placeholder)),
+            text_disabled: Some(hex_color(colors.text_disabled)),
+            text_accent: Some(hex_color(colors.text_accent)),
+            icon: Some(hex_color(colors.icon)),
+            icon_muted: Some(hex_color(colors.icon_muted)),
+            icon_disabled: Some(hex_color(colors.icon_disabled)),
+            icon_placeholder: Some(hex_color(colors.icon_placeholder)),
+            icon_accent: Some(hex_color(colors.icon_accent)),
+            debugger_accent: Some(hex_color(colors.debugger_accent)),
+            status_bar_background: Some(hex_color(colors.status_bar_background)),
+            title_bar_background: Some(hex_color(colors.title_bar_background)),
+            title_bar_inactive_background: Some(hex_color(colors.title_bar_inactive_background)),
+            toolbar_background: Some(hex_color(colors.toolbar_background)),
+            tab_bar_background: Some(hex_color(colors.tab_bar_background)),
+            tab_inactive_background: Some(hex_color(colors.tab_inactive_background)),
+            tab_active_background: Some(hex_color(colors.tab_active_background)),
+            search_match_background: Some(hex_color(colors.search_match_background)),
+            panel_background: Some(hex_color(colors.panel_background)),
+            panel_focused_border: Some(hex_color(colors.panel_focused_border)),
+            panel_indent_guide: Some(hex_color(colors.panel_indent_guide)),
+            panel_indent_guide_hover: Some(hex_color(colors.panel_indent_guide_hover)),
+            panel_indent_guide_active: Some(hex_color(colors.panel_indent_guide_active)),
+            pane_focused_border: Some(hex_color(colors.pane_focused_border)),
+            pane_group_border: Some(hex_color(colors.pane_group_border)),
+            scrollbar_thumb_background: Some(hex_color(colors.scrollbar_thumb_background)),
+            scrollbar_thumb_hover_background: Some(hex_color(
+                colors.scrollbar_thumb_hover_background,
+            )),
+            scrollbar_thumb_active_background: Some(hex_color(
+                colors.scrollbar_thumb_active_background,
+            )),
+            scrollbar_thumb_border: Some(hex_color(colors.scrollbar_thumb_border)),
+            scrollbar_track_background: Some(hex_color(colors.scrollbar_track_background)),
+            scrollbar_track_border: Some(hex_color(colors.scrollbar_track_border)),
+            minimap_thumb_background: Some(hex_color(colors.minimap_thumb_background)),
+            minimap_thumb_hover_background: Some(hex_color(colors.minimap_thumb_hover_background)),
+            minimap_thumb_active_background: Some(hex_color(
+                colors.minimap_thumb_active_background,
+            )),
+            minimap_thumb_border: Some(hex_color(colors.minimap_thumb_border)),
+            editor_foreground: Some(hex_color(colors.editor_foreground)),
+            editor_background: Some(hex_color(colors.editor_background)),
+            editor_gutter_background: Some(hex_color(colors.editor_gutter_background)),
+            editor_subheader_background: Some(hex_color(colors.editor_subheader_background)),
+            editor_active_line_background: Some(hex_color(colors.editor_active_line_background)),
+            editor_highlighted_line_background: Some(hex_color(
+                colors.editor_highlighted_line_background,
+            )),
+            editor_debugger_active_line_background: Some(hex_color(
+                colors.editor_debugger_active_line_background,
+            )),
+            editor_line_number: Some(hex_color(colors.editor_line_number)),
+            editor_active_line_number: Some(hex_color(colors.editor_active_line_number)),
+            editor_hover_line_number: Some(hex_color(colors.editor_hover_line_number)),
+            editor_invisible: Some(hex_color(colors.editor_invisible)),
+            editor_wrap_guide: Some(hex_color(colors.editor_wrap_guide)),
+            editor_active_wrap_guide: Some(hex_color(colors.editor_active_wrap_guide)),
+            editor_indent_guide: Some(hex_color(colors.editor_indent_guide)),
+            editor_indent_guide_active: Some(hex_color(colors.editor_indent_guide_active)),
+            editor_document_highlight_read_background: Some(hex_color(
+                colors.editor_document_highlight_read_background,
+            )),
+            editor_document_highlight_write_background: Some(hex_color(
+                colors.editor_document_highlight_write_background,
+            )),
+            editor_document_highlight_bracket_background: Some(hex_color(
+                colors.editor_document_highlight_bracket_background,
+            )),
+            terminal_background: Some(hex_color(colors.terminal_background)),
+            terminal_foreground: Some(hex_color(colors.terminal_foreground)),
+            terminal_ansi_background: Some(hex_color(colors.terminal_ansi_background)),
+            terminal_bright_foreground: Some(hex_color(colors.terminal_bright_foreground)),
+            terminal_dim_foreground: Some(hex_color(colors.terminal_dim_foreground)),
+            terminal_ansi_black: Some(hex_color(colors.terminal_ansi_black)),
+            terminal_ansi_bright_black: Some(hex_color(colors.terminal_ansi_bright_black)),
+            terminal_ansi_dim_black: Some(hex_color(colors.terminal_ansi_dim_black)),
+            terminal_ansi_red: Some(hex_color(colors.terminal_ansi_red)),
+            terminal_ansi_bright_red: Some(hex_color(colors.terminal_ansi_bright_red)),
+            terminal_ansi_dim_red: Some(hex_color(colors.terminal_ansi_dim_red)),
+            terminal_ansi_green: Some(hex_color(colors.terminal_ansi_green)),
+            terminal_ansi_bright_green: Some(hex_color(colors.terminal_ansi_bright_green)),
+            terminal_ansi_dim_green: Some(hex_color(colors.terminal_ansi_dim_green)),
+            terminal_ansi_yellow: Some(hex_color(colors.terminal_ansi_yellow)),
+            terminal_ansi_bright_yellow: Some(hex_color(colors.terminal_ansi_bright_yellow)),
+            terminal_ansi_dim_yellow: Some(hex_color(colors.terminal_ansi_dim_yellow)),
+            terminal_ansi_blue: Some(hex_color(colors.terminal_ansi_blue)),
+            terminal_ansi_bright_blue: Some(hex_color(colors.terminal_ansi_bright_blue)),
+            terminal_ansi_dim_blue: Some(hex_color(colors.terminal_ansi_dim_blue)),
+            terminal_ansi_magenta: Some(hex_color(colors.terminal_ansi_magenta)),
+            terminal_ansi_bright_magenta: Some(hex_color(colors.terminal_ansi_bright_magenta)),
+            terminal_ansi_dim_magenta: Some(hex_color(colors.terminal_ansi_dim_magenta)),
+            terminal_ansi_cyan: Some(hex_color(colors.terminal_ansi_cyan)),
+            terminal_ansi_bright_cyan: Some(hex_color(colors.terminal_ansi_bright_cyan)),
+            terminal_ansi_dim_cyan: Some(hex_color(colors.terminal_ansi_dim_cyan)),
+            terminal_ansi_white: Some(hex_color(colors.terminal_ansi_white)),
+            terminal_ansi_bright_white: Some(hex_color(colors.terminal_ansi_bright_white)),
+            terminal_ansi_dim_white: Some(hex_color(colors.terminal_ansi_dim_white)),
+            link_text_hover: Some(hex_color(colors.link_text_hover)),
+            version_control_added: Some(hex_color(colors.version_control_added)),
+            version_control_deleted: Some(hex_color(colors.version_control_deleted)),
+            version_control_modified: Some(hex_color(colors.version_control_modified)),
+            version_control_renamed: Some(hex_color(colors.version_control_renamed)),
+            version_control_conflict: Some(hex_color(colors.version_control_conflict)),
+            version_control_ignored: Some(hex_color(colors.version_control_ignored)),
+            version_control_conflict_marker_ours: Some(hex_color(
+                colors.version_control_conflict_marker_ours,
+            )),
+            version_control_conflict_marker_theirs: Some(hex_color(
+                colors.version_control_conflict_marker_theirs,
+            )),
+            ..Default::default()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct StatusColorsContent {
@@ -1451,6 +1665,55 @@ impl StatusColorsContent {
     }
 }
 
+impl From<&StatusColors> for StatusColorsContent {
+    fn from(status: &StatusColors) -> Self {
+        Self {
+            conflict: Some(hex_color(status.conflict)),
+            conflict_background: Some(hex_color(status.conflict_background)),
+            conflict_border: Some(hex_color(status.conflict_border)),
+            created: Some(hex_color(status.created)),
+            created_background: Some(hex_color(status.created_background)),
+            created_border: Some(hex_color(status.created_border)),
+            deleted: Some(hex_color(status.deleted)),
+            deleted_background: Some(hex_color(status.deleted_background)),
+            deleted_border: Some(hex_color(status.deleted_border)),
+            error: Some(hex_color(status.error)),
+            error_background: Some(hex_color(status.error_background)),
+            error_border: Some(hex_color(status.error_border)),
+            hidden: Some(hex_color(status.hidden)),
+            hidden_background: Some(hex_color(status.hidden_background)),
+            hidden_border: Some(hex_color(status.hidden_border)),
+            hint: Some(hex_color(status.hint)),
+            hint_background: Some(hex_color(status.hint_background)),
+            hint_border: Some(hex_color(status.hint_border)),
+            ignored: Some(hex_color(status.ignored)),
+            ignored_background: Some(hex_color(status.ignored_background)),
+            ignored_border: Some(hex_color(status.ignored_border)),
+            info: Some(hex_color(status.info)),
+            info_background: Some(hex_color(status.info_background)),
+            info_border: Some(hex_color(status.info_border)),
+            modified: Some(hex_color(status.modified)),
+            modified_background: Some(hex_color(status.modified_background)),
+            modified_border: Some(hex_color(status.modified_border)),
+            predictive: Some(hex_color(status.predictive)),
+            predictive_background: Some(hex_color(status.predictive_background)),
+            predictive_border: Some(hex_color(status.predictive_border)),
+            renamed: Some(hex_color(status.renamed)),
+            renamed_background: Some(hex_color(status.renamed_background)),
+            renamed_border: Some(hex_color(status.renamed_border)),
+            success: Some(hex_color(status.success)),
+            success_background: Some(hex_color(status.success_background)),
+            success_border: Some(hex_color(status.success_border)),
+            unreachable: Some(hex_color(status.unreachable)),
+            unreachable_background: Some(hex_color(status.unreachable_background)),
+            unreachable_border: Some(hex_color(status.unreachable_border)),
+            warning: Some(hex_color(status.warning)),
+            warning_background: Some(hex_color(status.warning_background)),
+            warning_border: Some(hex_color(status.warning_border)),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq)]
 pub struct AccentContent(pub Option<String>);
 
@@ -1479,6 +1742,16 @@ impl From<FontStyleContent> for FontStyle {
     }
 }
 
+impl From<FontStyle> for FontStyleContent {
+    fn from(value: FontStyle) -> Self {
+        match value {
+            FontStyle::Normal => FontStyleContent::Normal,
+            FontStyle::Italic => FontStyleContent::Italic,
+            FontStyle::Oblique => FontStyleContent::Oblique,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, Serialize_repr, Deserialize_repr, PartialEq)]
 #[repr(u16)]
 pub enum FontWeightContent {
@@ -1537,6 +1810,23 @@ impl From<FontWeightContent> for FontWeight {
     }
 }
 
+impl From<FontWeight> for FontWeightContent {
+    /// Rounds the weight to the nearest one that can be written in a theme.
+    fn from(value: FontWeight) -> Self {
+        match (value.0 / 100.).round() as i32 {
+            ..=1 => FontWeightContent::Thin,
+            2 => FontWeightContent::ExtraLight,
+            3 => FontWeightContent::Light,
+            4 => FontWeightContent::Normal,
+            5 => FontWeightContent::Medium,
+            6 => FontWeightContent::Semibold,
+            7 => FontWeightContent::Bold,
+            8 => FontWeightContent::ExtraBold,
+            _ => FontWeightContent::Black,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema, PartialEq)]
 #[serde(default)]
 pub struct HighlightStyleContent {
@@ -1561,6 +1851,17 @@ impl HighlightStyleContent {
     }
 }
 
+impl From<&HighlightStyle> for HighlightStyleContent {
+    fn from(style: &HighlightStyle) -> Self {
+        Self {
+            color: style.color.map(hex_color),
+            background_color: style.background_color.map(hex_color),
+            font_style: style.font_style.map(FontStyleContent::from),
+            font_weight: style.font_weight.map(FontWeightContent::from),
+        }
+    }
+}
+
 fn treat_error_as_none<'de, T, D>(deserializer: D) -> Result<Option<T>, D::Error>
 where
     T: Deserialize<'de>,
@@ -1569,3 +1870,37 @@ where
     let value: Value = Deserialize::deserialize(deserializer)?;
     Ok(T::deserialize(value).ok())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{fallback_themes::zed_default_themes, refine_theme_family};
+
+    #[test]
+    fn test_exported_theme_round_trips() {
+        let theme = &zed_default_themes().themes[0];
+        let exported = ThemeContent::from(theme);
+        let json = serde_json::to_string(&ThemeFamilyContent {
+            name: "Exported".into(),
+            author: "Zed".into(),
+            themes: vec![exported.clone()],
+        })
+        .unwrap();
+
+        let family = refine_theme_family(serde_json::from_str(&json).unwrap());
+        let reexported = ThemeContent::from(&family.themes[0]);
+        assert_eq!(reexported.name, theme.name.as_ref());
+        assert_eq!(
+            reexported.appearance,
+            AppearanceContent::from(theme.appearance)
+        );
+        assert_eq!(reexported.style, exported.style);
+    }
+
+    #[test]
+    fn test_hex_color() {
+        let magenta: Hsla = gpui::rgb(0xff00ff).into();
+        assert_eq!(hex_color(magenta), "#ff00ffff");
+        assert_eq!(hex_color(magenta.opacity(0.5)), "#ff00ff80");
+    }
+}
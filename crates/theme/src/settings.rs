@@ -1,7 +1,7 @@
 use crate::fallback_themes::zed_default_dark;
 use crate::{
     Appearance, DEFAULT_ICON_THEME_NAME, IconTheme, IconThemeNotFoundError, SyntaxTheme, Theme,
-    ThemeNotFoundError, ThemeRegistry, ThemeStyleContent,
+    ThemeNotFoundError, ThemeRegistry, ThemeSchedule, ThemeStyleContent, appearance_for_mode,
 };
 use anyhow::Result;
 use derive_more::{Deref, DerefMut};
@@ -122,6 +122,8 @@ pub struct ThemeSettings {
     ///
     /// Note: This setting is still experimental. See [this tracking issue](https://github.com/zed-industries/zed/issues/18078)
     pub theme_overrides: Option<ThemeStyleContent>,
+    /// The schedule for switching between light and dark themes in [`ThemeMode::Scheduled`].
+    pub theme_schedule: Option<ThemeSchedule>,
     /// The current icon theme selection.
     pub icon_theme_selection: Option<IconThemeSelection>,
     /// The active icon theme.
@@ -148,13 +150,14 @@ impl ThemeSettings {
     /// Reloads the current theme.
     ///
     /// Reads the [`ThemeSettings`] to know which theme should be loaded,
-    /// taking into account the current [`SystemAppearance`].
+    /// taking into account the current [`SystemAppearance`] and theme schedule.
     pub fn reload_current_theme(cx: &mut App) {
         let mut theme_settings = ThemeSettings::get_global(cx).clone();
         let system_appearance = SystemAppearance::global(cx);
 
         if let Some(theme_selection) = theme_settings.theme_selection.clone() {
-            let mut theme_name = theme_selection.theme(*system_appearance);
+            let appearance = appearance_for_mode(theme_selection.mode(), *system_appearance, cx);
+            let mut theme_name = theme_selection.theme(appearance);
 
             // If the selected theme doesn't exist, fall back to a default theme
             // based on the system appearance.
@@ -164,7 +167,7 @@ impl ThemeSettings {
                     log::error!("{err}");
                 }
 
-                theme_name = Self::default_theme(*system_appearance);
+                theme_name = Self::default_theme(appearance);
             };
 
             if let Some(_theme) = theme_settings.switch_theme(theme_name, cx) {
@@ -176,13 +179,15 @@ impl ThemeSettings {
     /// Reloads the current icon theme.
     ///
     /// Reads the [`ThemeSettings`] to know which icon theme should be loaded,
-    /// taking into account the current [`SystemAppearance`].
+    /// taking into account the current [`SystemAppearance`] and theme schedule.
     pub fn reload_current_icon_theme(cx: &mut App) {
         let mut theme_settings = ThemeSettings::get_global(cx).clone();
         let system_appearance = SystemAppearance::global(cx);
 
         if let Some(icon_theme_selection) = theme_settings.icon_theme_selection.clone() {
-            let mut icon_theme_name = icon_theme_selection.icon_theme(*system_appearance);
+            let appearance =
+                appearance_for_mode(icon_theme_selection.mode(), *system_appearance, cx);
+            let mut icon_theme_name = icon_theme_selection.icon_theme(appearance);
 
             // If the selected icon theme doesn't exist, fall back to the default theme.
             let theme_registry = ThemeRegistry::global(cx);
@@ -288,6 +293,8 @@ fn theme_name_ref(_: &mut SchemaGenerator) -> Schema {
 /// `Light` and `Dark` will select their respective themes.
 ///
 /// `System` will select the theme based on the system's appearance.
+///
+/// `Scheduled` will select the theme based on the `theme_schedule` setting.
 #[derive(Debug, PartialEq, Eq, Clone, Copy, Default, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum ThemeMode {
@@ -300,17 +307,24 @@ pub enum ThemeMode {
     /// Use the theme based on the system's appearance.
     #[default]
     System,
+
+    /// Use the `light` theme during the day and the `dark` theme at night,
+    /// following the `theme_schedule` setting.
+    Scheduled,
 }
 
 impl ThemeSelection {
     /// Returns the theme name for the selected [ThemeMode].
-    pub fn theme(&self, system_appearance: Appearance) -> &str {
+    ///
+    /// `appearance` is the appearance that the [ThemeMode] follows, as
+    /// returned by [`appearance_for_mode`].
+    pub fn theme(&self, appearance: Appearance) -> &str {
         match self {
             Self::Static(theme) => theme,
             Self::Dynamic { mode, light, dark } => match mode {
                 ThemeMode::Light => light,
                 ThemeMode::Dark => dark,
-                ThemeMode::System => match system_appearance {
+                ThemeMode::System | ThemeMode::Scheduled => match appearance {
                     Appearance::Light => light,
                     Appearance::Dark => dark,
                 },
@@ -352,14 +366,15 @@ pub enum IconThemeSelection {
 }
 
 impl IconThemeSelection {
-    /// Returns the icon theme name based on the given [`Appearance`].
-    pub fn icon_theme(&self, system_appearance: Appearance) -> &str {
+    /// Returns the icon theme name based on the given [`Appearance`], as
+    /// returned by [`appearance_for_mode`].
+    pub fn icon_theme(&self, appearance: Appearance) -> &str {
         match self {
             Self::Static(theme) => theme,
             Self::Dynamic { mode, light, dark } => match mode {
                 ThemeMode::Light => light,
                 ThemeMode::Dark => dark,
-                ThemeMode::System => match system_appearance {
+                ThemeMode::System | ThemeMode::Scheduled => match appearance {
                     Appearance::Light => light,
                     Appearance::Dark => dark,
                 },
@@ -425,6 +440,10 @@ pub struct ThemeSettingsContent {
     /// The name of the icon theme to use.
     #[serde(default)]
     pub icon_theme: Option<IconThemeSelection>,
+    /// When to switch between the light and dark themes, if the theme mode
+    /// is `scheduled`.
+    #[serde(default)]
+    pub theme_schedule: Option<ThemeSchedule>,
 
     /// UNSTABLE: Expect many elements to be broken.
     ///
@@ -460,7 +479,7 @@ impl ThemeSettingsContent {
                 ThemeSelection::Dynamic { mode, light, dark } => match mode {
                     ThemeMode::Light => light,
                     ThemeMode::Dark => dark,
-                    ThemeMode::System => match appearance {
+                    ThemeMode::System | ThemeMode::Scheduled => match appearance {
                         Appearance::Light => light,
                         Appearance::Dark => dark,
                     },
@@ -481,7 +500,7 @@ impl ThemeSettingsContent {
                 IconThemeSelection::Dynamic { mode, light, dark } => match mode {
                     ThemeMode::Light => light,
                     ThemeMode::Dark => dark,
-                    ThemeMode::System => match appearance {
+                    ThemeMode::System | ThemeMode::Scheduled => match appearance {
                         Appearance::Light => light,
                         Appearance::Dark => dark,
                     },
@@ -684,6 +703,26 @@ impl ThemeSettings {
         }
     }
 
+    fn set_theme_selection(
+        &mut self,
+        selection: &ThemeSelection,
+        themes: &ThemeRegistry,
+        appearance: Appearance,
+    ) {
+        self.theme_selection = Some(selection.clone());
+
+        match themes.get(selection.theme(appearance)) {
+            Ok(theme) => {
+                self.active_theme = theme;
+            }
+            Err(err @ ThemeNotFoundError(_)) => {
+                if themes.extensions_loaded() {
+                    log::error!("{err}");
+                }
+            }
+        }
+    }
+
     /// Switches to the icon theme with the given name, if it exists.
     ///
     /// Returns a `Some` containing the new icon theme if it was successful.
@@ -814,6 +853,8 @@ impl settings::Settings for ThemeSettings {
     fn load(sources: SettingsSources<Self::FileContent>, cx: &mut App) -> Result<Self> {
         let themes = ThemeRegistry::default_global(cx);
         let system_appearance = SystemAppearance::default_global(cx);
+        let appearance_for =
+            |mode: Option<ThemeMode>| appearance_for_mode(mode, *system_appearance, cx);
 
         let defaults = sources.default;
         let mut this = Self {
@@ -843,17 +884,20 @@ impl settings::Settings for ThemeSettings {
             agent_font_size: defaults.agent_font_size.unwrap().into(),
             theme_selection: defaults.theme.clone(),
             active_theme: themes
-                .get(defaults.theme.as_ref().unwrap().theme(*system_appearance))
+                .get(defaults.theme.as_ref().unwrap().theme(appearance_for(
+                    defaults.theme.as_ref().and_then(ThemeSelection::mode),
+                )))
                 .or(themes.get(&zed_default_dark().name))
                 .unwrap(),
             theme_overrides: None,
+            theme_schedule: defaults.theme_schedule.clone(),
             icon_theme_selection: defaults.icon_theme.clone(),
             active_icon_theme: defaults
                 .icon_theme
                 .as_ref()
                 .and_then(|selection| {
                     themes
-                        .get_icon_theme(selection.icon_theme(*system_appearance))
+                        .get_icon_theme(selection.icon_theme(appearance_for(selection.mode())))
                         .ok()
                 })
                 .unwrap_or_else(|| themes.get_icon_theme(DEFAULT_ICON_THEME_NAME).unwrap()),
@@ -898,20 +942,11 @@ impl settings::Settings for ThemeSettings {
             }
 
             if let Some(value) = &value.theme {
-                this.theme_selection = Some(value.clone());
-
-                let theme_name = value.theme(*system_appearance);
+                this.set_theme_selection(value, &themes, appearance_for(value.mode()));
+            }
 
-                match themes.get(theme_name) {
-                    Ok(theme) => {
-                        this.active_theme = theme;
-                    }
-                    Err(err @ ThemeNotFoundError(_)) => {
-                        if themes.extensions_loaded() {
-                            log::error!("{err}");
-                        }
-                    }
-                }
+            if let Some(value) = &value.theme_schedule {
+                this.theme_schedule = Some(value.clone());
             }
 
             this.theme_overrides.clone_from(&value.theme_overrides);
@@ -920,7 +955,7 @@ impl settings::Settings for ThemeSettings {
             if let Some(value) = &value.icon_theme {
                 this.icon_theme_selection = Some(value.clone());
 
-                let icon_theme_name = value.icon_theme(*system_appearance);
+                let icon_theme_name = value.icon_theme(appearance_for(value.mode()));
 
                 match themes.get_icon_theme(icon_theme_name) {
                     Ok(icon_theme) => {
@@ -956,6 +991,15 @@ impl settings::Settings for ThemeSettings {
             this.unnecessary_code_fade = this.unnecessary_code_fade.clamp(0.0, 0.9);
         }
 
+        // Projects can pick a theme for the windows they're open in, which
+        // helps telling projects apart.
+        for value in sources.project {
+            if let Some(value) = &value.theme {
+                this.set_theme_selection(value, &themes, appearance_for(value.mode()));
+                this.apply_theme_overrides();
+            }
+        }
+
         Ok(this)
     }
 
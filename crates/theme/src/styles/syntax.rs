@@ -85,6 +85,33 @@ impl SyntaxTheme {
             highlights: merged_highlights,
         })
     }
+
+    /// Returns this theme's styles for the highlight names of `other`, in the
+    /// same order, so that highlight ids computed for `other` can be used with
+    /// the returned theme.
+    ///
+    /// Names this theme doesn't style fall back to the style of their parent
+    /// name, so `function.method` falls back to `function`.
+    pub fn aligned_to(&self, other: &SyntaxTheme) -> SyntaxTheme {
+        let highlights = other
+            .highlights
+            .iter()
+            .map(|(name, _)| {
+                let mut parent = name.as_str();
+                let style = loop {
+                    if let Some(ix) = self.highlight_id(parent) {
+                        break self.highlights[ix as usize].1;
+                    }
+                    match parent.rfind('.') {
+                        Some(ix) => parent = &parent[..ix],
+                        None => break HighlightStyle::default(),
+                    }
+                };
+                (name.clone(), style)
+            })
+            .collect();
+        Self { highlights }
+    }
 }
 
 #[cfg(test)]
@@ -93,6 +120,25 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn test_syntax_theme_aligned_to() {
+        let theme = SyntaxTheme::new_test([("keyword", gpui::red()), ("function", gpui::green())]);
+        let other = SyntaxTheme::new_test([
+            ("function.method", gpui::black()),
+            ("keyword", gpui::black()),
+            ("string", gpui::black()),
+        ]);
+
+        let aligned = theme.aligned_to(&other);
+        assert_eq!(
+            aligned.highlight_id("keyword"),
+            other.highlight_id("keyword")
+        );
+        assert_eq!(aligned.color("function.method"), gpui::green());
+        assert_eq!(aligned.color("keyword"), gpui::red());
+        assert_eq!(aligned.get("string"), HighlightStyle::default());
+    }
+
     #[test]
     fn test_syntax_theme_merge() {
         // Merging into an empty `SyntaxTheme` keeps all the user-defined styles.
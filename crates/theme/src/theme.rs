@@ -13,11 +13,14 @@ mod fallback_themes;
 mod font_family_cache;
 mod icon_theme;
 mod icon_theme_schema;
+mod import;
 mod registry;
 mod scale;
+mod schedule;
 mod schema;
 mod settings;
 mod styles;
+mod window_theme;
 
 use std::path::Path;
 use std::sync::Arc;
@@ -38,11 +41,14 @@ pub use crate::default_colors::*;
 pub use crate::font_family_cache::*;
 pub use crate::icon_theme::*;
 pub use crate::icon_theme_schema::*;
+pub use crate::import::*;
 pub use crate::registry::*;
 pub use crate::scale::*;
+pub use crate::schedule::*;
 pub use crate::schema::*;
 pub use crate::settings::*;
 pub use crate::styles::*;
+pub use crate::window_theme::set_window_theme;
 
 /// Defines window border radius for platforms that use client side decorations.
 pub const CLIENT_SIDE_DECORATION_ROUNDING: Pixels = px(10.0);
@@ -102,6 +108,7 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
 
     ThemeSettings::register(cx);
     FontFamilyCache::init_global(cx);
+    schedule::init(cx);
 
     let mut prev_buffer_font_size_settings =
         ThemeSettings::get_global(cx).buffer_font_size_settings();
@@ -118,6 +125,8 @@ pub fn init(themes_to_load: LoadThemes, cx: &mut App) {
             prev_ui_font_size_settings = ui_font_size_settings;
             reset_ui_font_size(cx);
         }
+
+        window_theme::realign_window_themes(cx);
     })
     .detach();
 }
@@ -130,7 +139,8 @@ pub trait ActiveTheme {
 
 impl ActiveTheme for App {
     fn theme(&self) -> &Arc<Theme> {
-        &ThemeSettings::get_global(self).active_theme
+        window_theme::window_theme(self)
+            .unwrap_or_else(|| &ThemeSettings::get_global(self).active_theme)
     }
 }
 
@@ -351,8 +361,20 @@ impl Theme {
 
 /// Asynchronously reads the user theme from the specified path.
 pub async fn read_user_theme(theme_path: &Path, fs: Arc<dyn Fs>) -> Result<ThemeFamilyContent> {
-    let reader = fs.open_sync(theme_path).await?;
-    let theme_family: ThemeFamilyContent = serde_json_lenient::from_reader(reader)?;
+    let theme_family: ThemeFamilyContent = match ThemeFormat::from_path(theme_path) {
+        None | Some(ThemeFormat::Zed) => {
+            let reader = fs.open_sync(theme_path).await?;
+            serde_json_lenient::from_reader(reader)?
+        }
+        Some(format) => {
+            let contents = fs.load_bytes(theme_path).await?;
+            let name = theme_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            import_theme(format, &contents, &name)?
+        }
+    };
 
     for theme in &theme_family.themes {
         if theme
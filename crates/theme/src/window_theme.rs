@@ -0,0 +1,88 @@
+use std::sync::Arc;
+
+use collections::HashMap;
+use gpui::{App, Global, WindowId};
+use settings::Settings as _;
+
+use crate::{Theme, ThemeSettings};
+
+struct WindowTheme {
+    /// The theme that was set for the window.
+    theme: Arc<Theme>,
+    /// The theme with its syntax styles aligned to the active theme, which is
+    /// what's used to highlight buffers in every window.
+    aligned: Arc<Theme>,
+}
+
+#[derive(Default)]
+struct GlobalWindowThemes(HashMap<WindowId, WindowTheme>);
+
+impl Global for GlobalWindowThemes {}
+
+/// Shows the given theme in a window instead of the active theme, or goes
+/// back to the active theme when `theme` is `None`.
+pub fn set_window_theme(window_id: WindowId, theme: Option<Arc<Theme>>, cx: &mut App) {
+    let active_theme = ThemeSettings::get_global(cx).active_theme.clone();
+    let window_themes = &mut cx.default_global::<GlobalWindowThemes>().0;
+    let changed = match theme {
+        Some(theme) => {
+            let unchanged = window_themes
+                .get(&window_id)
+                .is_some_and(|window_theme| Arc::ptr_eq(&window_theme.theme, &theme));
+            if !unchanged {
+                let aligned = align_theme(&theme, &active_theme);
+                window_themes.insert(window_id, WindowTheme { theme, aligned });
+            }
+            !unchanged
+        }
+        None => window_themes.remove(&window_id).is_some(),
+    };
+
+    if changed {
+        cx.refresh_windows();
+    }
+}
+
+/// Returns the theme that was set for the window that is being updated, if any.
+pub(crate) fn window_theme(cx: &App) -> Option<&Arc<Theme>> {
+    let window_id = cx.updating_window_id()?;
+    let window_theme = cx.try_global::<GlobalWindowThemes>()?.0.get(&window_id)?;
+    Some(&window_theme.aligned)
+}
+
+/// Realigns the syntax styles of the window themes after the active theme
+/// has changed.
+pub(crate) fn realign_window_themes(cx: &mut App) {
+    let active_theme = ThemeSettings::get_global(cx).active_theme.clone();
+    let Some(window_themes) = cx.try_global::<GlobalWindowThemes>() else {
+        return;
+    };
+    let is_aligned = window_themes.0.values().all(|window_theme| {
+        window_theme.aligned.styles.syntax.highlights.len()
+            == active_theme.styles.syntax.highlights.len()
+            && window_theme
+                .aligned
+                .styles
+                .syntax
+                .highlights
+                .iter()
+                .zip(&active_theme.styles.syntax.highlights)
+                .all(|((a, _), (b, _))| a == b)
+    });
+    if is_aligned {
+        return;
+    }
+
+    for window_theme in cx.global_mut::<GlobalWindowThemes>().0.values_mut() {
+        window_theme.aligned = align_theme(&window_theme.theme, &active_theme);
+    }
+    cx.refresh_windows();
+}
+
+/// Returns the theme with its syntax styles in the order of the active
+/// theme's, as the highlight ids of buffers are computed for the active theme.
+fn align_theme(theme: &Theme, active_theme: &Theme) -> Arc<Theme> {
+    let mut theme = theme.clone();
+    theme.styles.syntax = Arc::new(theme.styles.syntax.aligned_to(&active_theme.styles.syntax));
+    Arc::new(theme)
+}
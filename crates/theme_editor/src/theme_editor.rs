@@ -0,0 +1,1027 @@
+//! An editor for the colors of a theme. Every change is applied to the app
+//! right away, and the result can be exported as a theme family file.
+
+use std::{ops::Range, path::PathBuf, sync::Arc};
+
+use anyhow::{Context as _, Result};
+use editor::{Editor, EditorEvent};
+use fs::Fs;
+use gpui::{
+    App, ClickEvent, Context, Entity, EventEmitter, FocusHandle, Focusable, Hsla,
+    ListSizingBehavior, Render, Stateful, Subscription, UniformListScrollHandle, WeakEntity,
+    Window, actions, uniform_list,
+};
+use serde_json::{Map, Value, json};
+use settings::{Settings, SettingsStore};
+use theme::{
+    AppearanceContent, ThemeContent, ThemeFamilyContent, ThemeRegistry, ThemeSettings,
+    ThemeStyleContent, hex_color,
+};
+use ui::{
+    ListItem, ListItemSpacing, NumericStepper, Tooltip, prelude::*, utils::calculate_contrast_ratio,
+};
+use util::ResultExt as _;
+use workspace::{
+    OpenOptions, OpenThemePreview, Workspace, WorkspaceId,
+    item::{Item, ItemEvent},
+    notifications::DetachAndPromptErr,
+};
+
+actions!(theme_editor, [OpenThemeEditor, ExportTheme]);
+
+const THEME_SCHEMA_URL: &str = "https://zed.dev/schema/themes/v0.2.0.json";
+
+/// Pairs of UI colors where the first is drawn on top of the second, with the
+/// contrast ratio the pair needs to stay legible (4.5:1 for text, 3:1 for
+/// secondary text and icons, following WCAG).
+const CONTRAST_CHECKS: &[(&str, &str, f32)] = &[
+    ("text", "background", 4.5),
+    ("text", "surface.background", 4.5),
+    ("text", "elevated_surface.background", 4.5),
+    ("text", "panel.background", 4.5),
+    ("text", "status_bar.background", 4.5),
+    ("text", "title_bar.background", 4.5),
+    ("text", "tab.active_background", 4.5),
+    ("text.muted", "background", 3.),
+    ("text.muted", "panel.background", 3.),
+    ("text.placeholder", "editor.background", 3.),
+    ("icon", "background", 3.),
+    ("icon.muted", "background", 3.),
+    ("editor.foreground", "editor.background", 4.5),
+    ("editor.line_number", "editor.gutter.background", 3.),
+    ("editor.active_line_number", "editor.gutter.background", 3.),
+    ("terminal.foreground", "terminal.background", 4.5),
+];
+
+/// The contrast that syntax colors and status colors need against the
+/// background they're drawn on.
+const MINIMUM_ACCENT_CONTRAST_RATIO: f32 = 3.;
+
+/// The number of hues to pick from.
+const PICKER_HUES: usize = 24;
+/// The number of saturations and lightnesses of the picked hue to pick from.
+const PICKER_SATURATIONS: usize = 12;
+const PICKER_LIGHTNESSES: usize = 9;
+
+pub fn init(cx: &mut App) {
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &OpenThemeEditor, window, cx| {
+            if let Some(theme_editor) = workspace.item_of_type::<ThemeEditor>(cx) {
+                workspace.activate_item(&theme_editor, true, true, window, cx);
+                return;
+            }
+            let fs = workspace.app_state().fs.clone();
+            let workspace_handle = workspace.weak_handle();
+            let theme_editor = cx.new(|cx| ThemeEditor::new(fs, workspace_handle, window, cx));
+            workspace.add_item_to_active_pane(Box::new(theme_editor), None, true, window, cx);
+        });
+    })
+    .detach();
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ColorSection {
+    /// The `ThemeColors`.
+    Ui,
+    /// The `StatusColors`.
+    Status,
+    /// The colors of syntax nodes.
+    Syntax,
+}
+
+impl ColorSection {
+    fn label(self) -> &'static str {
+        match self {
+            Self::Ui => "UI",
+            Self::Status => "Status",
+            Self::Syntax => "Syntax",
+        }
+    }
+}
+
+struct ColorEntry {
+    section: ColorSection,
+    /// The key of the color in a theme file, like `editor.background`, or the
+    /// name of the syntax node.
+    key: SharedString,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum ColorChannel {
+    Hue,
+    Saturation,
+    Lightness,
+    Alpha,
+}
+
+impl ColorChannel {
+    const ALL: [Self; 4] = [Self::Hue, Self::Saturation, Self::Lightness, Self::Alpha];
+
+    fn label(self) -> &'static str {
+        match self {
+            Self::Hue => "Hue",
+            Self::Saturation => "Saturation",
+            Self::Lightness => "Lightness",
+            Self::Alpha => "Alpha",
+        }
+    }
+
+    fn format(self, color: Hsla) -> String {
+        match self {
+            Self::Hue => format!("{:.0}°", color.h * 360.),
+            Self::Saturation => format!("{:.0}%", color.s * 100.),
+            Self::Lightness => format!("{:.0}%", color.l * 100.),
+            Self::Alpha => format!("{:.0}%", color.a * 100.),
+        }
+    }
+
+    /// Moves the channel by the given number of steps of 5° or 5%.
+    fn adjust(self, color: &mut Hsla, steps: f32) {
+        match self {
+            Self::Hue => color.h = (color.h + steps * 5. / 360.).rem_euclid(1.),
+            Self::Saturation => color.s = (color.s + steps * 0.05).clamp(0., 1.),
+            Self::Lightness => color.l = (color.l + steps * 0.05).clamp(0., 1.),
+            Self::Alpha => color.a = (color.a + steps * 0.05).clamp(0., 1.),
+        }
+    }
+}
+
+pub struct ThemeEditor {
+    fs: Arc<dyn Fs>,
+    workspace: WeakEntity<Workspace>,
+    focus_handle: FocusHandle,
+    name_editor: Entity<Editor>,
+    author_editor: Entity<Editor>,
+    filter_editor: Entity<Editor>,
+    hex_editor: Entity<Editor>,
+    appearance: AppearanceContent,
+    /// The style of the edited theme, as it's written in a theme file.
+    style: Map<String, Value>,
+    /// The style of the theme that the editor was opened with.
+    original_style: Map<String, Value>,
+    entries: Vec<ColorEntry>,
+    /// The indices of the entries that match the filter.
+    matches: Vec<usize>,
+    /// The index of the entry being edited.
+    selected_entry: Option<usize>,
+    /// The hue that the picker offers colors of, which is kept separately
+    /// because gray colors lose their hue.
+    picker_hue: f32,
+    /// The name that the edited theme was last added to the registry with.
+    registered_name: Option<SharedString>,
+    /// The file that the theme was last exported to.
+    exported_path: Option<PathBuf>,
+    /// Whether the theme in the registry is the one that was last exported.
+    exported: bool,
+    scroll_handle: UniformListScrollHandle,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl ThemeEditor {
+    fn new(
+        fs: Arc<dyn Fs>,
+        workspace: WeakEntity<Workspace>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let theme = cx.theme().clone();
+        let content = ThemeContent::from(theme.as_ref());
+        let style = style_map(&content.style);
+
+        let name_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Theme name", cx);
+            editor.set_text(format!("{} (Custom)", theme.name), window, cx);
+            editor
+        });
+        let author_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Author", cx);
+            editor
+        });
+        let filter_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("Filter colors...", cx);
+            editor
+        });
+        let hex_editor = cx.new(|cx| {
+            let mut editor = Editor::single_line(window, cx);
+            editor.set_placeholder_text("#rrggbbaa", cx);
+            editor
+        });
+
+        let subscriptions = vec![
+            cx.subscribe(&name_editor, |this, _, event, cx| {
+                // Only re-register the theme once it's been changed.
+                if let EditorEvent::BufferEdited = event {
+                    if this.registered_name.is_some() {
+                        this.apply_theme(cx);
+                    }
+                }
+            }),
+            cx.subscribe(&filter_editor, |this, _, event, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.update_matches(cx);
+                }
+            }),
+            cx.subscribe(&hex_editor, |this, _, event, cx| {
+                if let EditorEvent::BufferEdited = event {
+                    this.hex_edited(cx);
+                }
+            }),
+            cx.on_release(|this, cx| this.restore_theme(cx)),
+        ];
+
+        let mut this = Self {
+            fs,
+            workspace,
+            focus_handle: cx.focus_handle(),
+            name_editor,
+            author_editor,
+            filter_editor,
+            hex_editor,
+            appearance: content.appearance,
+            entries: color_entries(&content.style),
+            original_style: style.clone(),
+            style,
+            matches: Vec::new(),
+            selected_entry: None,
+            picker_hue: 0.,
+            registered_name: None,
+            exported_path: None,
+            exported: false,
+            scroll_handle: UniformListScrollHandle::new(),
+            _subscriptions: subscriptions,
+        };
+        this.update_matches(cx);
+        this
+    }
+
+    fn theme_name(&self, cx: &App) -> String {
+        self.name_editor.read(cx).text(cx).trim().to_string()
+    }
+
+    /// Returns why the edited theme can't be added to the registry.
+    fn name_error(&self, cx: &App) -> Option<SharedString> {
+        let name = self.theme_name(cx);
+        if name.is_empty() {
+            return Some("The theme needs a name".into());
+        }
+        let is_registered = self
+            .registered_name
+            .as_ref()
+            .is_some_and(|registered_name| registered_name.as_ref() == name);
+        if !is_registered && ThemeRegistry::global(cx).get(&name).is_ok() {
+            return Some(format!("A theme named \"{name}\" already exists").into());
+        }
+        None
+    }
+
+    fn theme_family_json(&self, cx: &App) -> Value {
+        let name = self.theme_name(cx);
+        json!({
+            "$schema": THEME_SCHEMA_URL,
+            "name": name,
+            "author": self.author_editor.read(cx).text(cx).trim(),
+            "themes": [{
+                "name": name,
+                "appearance": self.appearance,
+                "style": self.style,
+            }],
+        })
+    }
+
+    fn theme_family(&self, cx: &App) -> Result<ThemeFamilyContent> {
+        serde_json::from_value(self.theme_family_json(cx))
+            .context("the theme doesn't match the theme schema")
+    }
+
+    fn theme_file_contents(&self, cx: &App) -> Result<String> {
+        if let Some(error) = self.name_error(cx) {
+            anyhow::bail!(error);
+        }
+        self.theme_family(cx)?;
+        Ok(serde_json::to_string_pretty(&self.theme_family_json(cx))?)
+    }
+
+    /// Adds the edited theme to the registry and makes it the active theme.
+    fn apply_theme(&mut self, cx: &mut Context<Self>) {
+        cx.notify();
+        if self.name_error(cx).is_some() {
+            return;
+        }
+        let Some(family) = self.theme_family(cx).log_err() else {
+            return;
+        };
+
+        let name = SharedString::from(family.name.clone());
+        let registry = ThemeRegistry::global(cx);
+        if let Some(previous_name) = self.registered_name.replace(name.clone()) {
+            if previous_name != name {
+                registry.remove_user_themes(&[previous_name]);
+            }
+        }
+        registry.insert_user_theme_families([family]);
+        self.exported = false;
+        let Some(theme) = registry.get(&name).log_err() else {
+            return;
+        };
+
+        SettingsStore::update_global(cx, |store, cx| {
+            let mut theme_settings = store.get::<ThemeSettings>(None).clone();
+            theme_settings.active_theme = theme;
+            theme_settings.apply_theme_overrides();
+            store.override_global(theme_settings);
+            cx.refresh_windows();
+        });
+    }
+
+    /// Removes the edited theme from the registry and goes back to the theme
+    /// from the settings, unless the edited theme was exported.
+    fn restore_theme(&mut self, cx: &mut App) {
+        if self.exported {
+            return;
+        }
+        let Some(registered_name) = self.registered_name.take() else {
+            return;
+        };
+        let registry = ThemeRegistry::global(cx);
+        registry.remove_user_themes(&[registered_name]);
+        match self.exported_path.take() {
+            // The theme was changed after it was exported, so the registry
+            // gets the exported theme back.
+            Some(path) => {
+                let fs = self.fs.clone();
+                cx.spawn(async move |cx| {
+                    registry.load_user_theme(&path, fs).await.log_err();
+                    cx.update(ThemeSettings::reload_current_theme).log_err();
+                })
+                .detach();
+            }
+            None => ThemeSettings::reload_current_theme(cx),
+        }
+    }
+
+    fn export(&mut self, _: &ExportTheme, window: &mut Window, cx: &mut Context<Self>) {
+        let contents = self.theme_file_contents(cx);
+        let path = paths::themes_dir().join(format!("{}.json", file_stem(&self.theme_name(cx))));
+        let fs = self.fs.clone();
+        let workspace = self.workspace.clone();
+        cx.spawn_in(window, async move |this, cx| {
+            fs.create_dir(paths::themes_dir()).await?;
+            fs.atomic_write(path.clone(), contents?).await?;
+            this.update(cx, |this, _| {
+                this.exported_path = Some(path.clone());
+                this.exported = true;
+            })?;
+            workspace
+                .update_in(cx, |workspace, window, cx| {
+                    workspace.open_abs_path(path, OpenOptions::default(), window, cx)
+                })?
+                .await?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to export theme", window, cx, |_, _, _| None);
+    }
+
+    fn update_matches(&mut self, cx: &mut Context<Self>) {
+        let filter = self.filter_editor.read(cx).text(cx).to_lowercase();
+        let filter = filter.trim();
+        self.matches = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| {
+                entry.key.to_lowercase().contains(filter)
+                    || entry.section.label().to_lowercase().contains(filter)
+            })
+            .map(|(ix, _)| ix)
+            .collect();
+        cx.notify();
+    }
+
+    fn color_value(&self, section: ColorSection, key: &str) -> Option<&str> {
+        match section {
+            ColorSection::Ui | ColorSection::Status => self.style.get(key)?.as_str(),
+            ColorSection::Syntax => self.style.get("syntax")?.get(key)?.get("color")?.as_str(),
+        }
+    }
+
+    fn color(&self, section: ColorSection, key: &str) -> Option<Hsla> {
+        parse_color(self.color_value(section, key)?)
+    }
+
+    fn entry_color(&self, entry_ix: usize) -> Option<Hsla> {
+        let entry = self.entries.get(entry_ix)?;
+        self.color(entry.section, &entry.key)
+    }
+
+    /// Changes the color of an entry, returning whether it was different.
+    fn set_entry_value(&mut self, entry_ix: usize, value: Value, cx: &mut Context<Self>) -> bool {
+        let Some(entry) = self.entries.get(entry_ix) else {
+            return false;
+        };
+        let slot = match entry.section {
+            ColorSection::Ui | ColorSection::Status => Some(
+                self.style
+                    .entry(entry.key.to_string())
+                    .or_insert(Value::Null),
+            ),
+            ColorSection::Syntax => self
+                .style
+                .get_mut("syntax")
+                .and_then(|syntax| syntax.get_mut(entry.key.as_ref()))
+                .and_then(Value::as_object_mut)
+                .map(|style| style.entry("color").or_insert(Value::Null)),
+        };
+        let Some(slot) = slot else {
+            return false;
+        };
+        if *slot == value {
+            return false;
+        }
+        *slot = value;
+        self.apply_theme(cx);
+        true
+    }
+
+    fn select_entry(&mut self, entry_ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(entry_ix) else {
+            return;
+        };
+        let value = self
+            .color_value(entry.section, &entry.key)
+            .unwrap_or_default()
+            .to_string();
+        self.selected_entry = Some(entry_ix);
+        if let Some(color) = parse_color(&value) {
+            self.follow_hue(color);
+        }
+        self.hex_editor
+            .update(cx, |editor, cx| editor.set_text(value, window, cx));
+        cx.notify();
+    }
+
+    fn hex_edited(&mut self, cx: &mut Context<Self>) {
+        let Some(entry_ix) = self.selected_entry else {
+            return;
+        };
+        let text = self.hex_editor.read(cx).text(cx);
+        // Wait until the text is a complete color.
+        if let Some(color) = parse_color(text.trim()) {
+            self.follow_hue(color);
+            self.set_entry_value(entry_ix, Value::String(hex_color(color)), cx);
+        }
+    }
+
+    fn follow_hue(&mut self, color: Hsla) {
+        if color.s > 0. {
+            self.picker_hue = color.h;
+        }
+    }
+
+    fn update_selected_color(
+        &mut self,
+        update: impl FnOnce(&mut Hsla),
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(entry_ix) = self.selected_entry else {
+            return;
+        };
+        let Some(mut color) = self.entry_color(entry_ix) else {
+            return;
+        };
+        update(&mut color);
+        self.follow_hue(color);
+        let value = hex_color(color);
+        if self.set_entry_value(entry_ix, Value::String(value.clone()), cx) {
+            self.hex_editor
+                .update(cx, |editor, cx| editor.set_text(value, window, cx));
+        }
+    }
+
+    fn reset_selected_color(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(entry_ix) = self.selected_entry else {
+            return;
+        };
+        let entry = &self.entries[entry_ix];
+        let original_value = match entry.section {
+            ColorSection::Ui | ColorSection::Status => self.original_style.get(entry.key.as_ref()),
+            ColorSection::Syntax => self
+                .original_style
+                .get("syntax")
+                .and_then(|syntax| syntax.get(entry.key.as_ref()))
+                .and_then(|style| style.get("color")),
+        };
+        let original_value = original_value.cloned().unwrap_or(Value::Null);
+        if self.set_entry_value(entry_ix, original_value, cx) {
+            self.select_entry(entry_ix, window, cx);
+        }
+    }
+
+    /// Describes the pairs of colors that include the entry's color and are
+    /// too close to each other.
+    fn contrast_warnings(&self, entry: &ColorEntry) -> Vec<String> {
+        let key = entry.key.as_ref();
+        let mut checks = Vec::new();
+        match entry.section {
+            ColorSection::Ui => {
+                checks.extend(
+                    CONTRAST_CHECKS
+                        .iter()
+                        .filter(|(foreground, background, _)| {
+                            *foreground == key || *background == key
+                        })
+                        .map(|(foreground, background, minimum)| {
+                            (
+                                ColorSection::Ui,
+                                foreground.to_string(),
+                                background.to_string(),
+                                *minimum,
+                            )
+                        }),
+                );
+            }
+            // Status colors are drawn on their own background, like errors
+            // on `error.background`.
+            ColorSection::Status => {
+                let foreground = key.strip_suffix(".background").unwrap_or(key);
+                if !foreground.contains('.') {
+                    checks.push((
+                        ColorSection::Status,
+                        foreground.to_string(),
+                        format!("{foreground}.background"),
+                        MINIMUM_ACCENT_CONTRAST_RATIO,
+                    ));
+                }
+            }
+            ColorSection::Syntax => checks.push((
+                ColorSection::Syntax,
+                key.to_string(),
+                "editor.background".to_string(),
+                MINIMUM_ACCENT_CONTRAST_RATIO,
+            )),
+        }
+
+        let background_section = match entry.section {
+            ColorSection::Status => ColorSection::Status,
+            ColorSection::Ui | ColorSection::Syntax => ColorSection::Ui,
+        };
+        let base = self
+            .color(ColorSection::Ui, "background")
+            .unwrap_or_default();
+        checks
+            .into_iter()
+            .filter_map(|(foreground_section, foreground, background, minimum)| {
+                // Translucent colors are judged by how they look when drawn on
+                // the colors below them.
+                let background_color = base.blend(self.color(background_section, &background)?);
+                let foreground_color =
+                    background_color.blend(self.color(foreground_section, &foreground)?);
+                let ratio = calculate_contrast_ratio(foreground_color, background_color);
+                (ratio < minimum).then(|| {
+                    format!(
+                        "{foreground} on {background} has a contrast of {ratio:.1}:1, \
+                         at least {minimum:.1}:1 is recommended"
+                    )
+                })
+            })
+            .collect()
+    }
+
+    fn render_entries(&self, range: Range<usize>, cx: &mut Context<Self>) -> Vec<ListItem> {
+        range
+            .filter_map(|ix| {
+                let entry_ix = *self.matches.get(ix)?;
+                let entry = self.entries.get(entry_ix)?;
+                let value = self
+                    .color_value(entry.section, &entry.key)
+                    .unwrap_or_default()
+                    .to_string();
+                let warnings = self.contrast_warnings(entry);
+                Some(
+                    ListItem::new(ix)
+                        .spacing(ListItemSpacing::Sparse)
+                        .toggle_state(self.selected_entry == Some(entry_ix))
+                        .start_slot(
+                            render_swatch(self.color(entry.section, &entry.key), cx).size_4(),
+                        )
+                        .child(
+                            h_flex()
+                                .w_full()
+                                .gap_2()
+                                .child(Label::new(entry.key.clone()).size(LabelSize::Small))
+                                .child(
+                                    Label::new(entry.section.label())
+                                        .size(LabelSize::XSmall)
+                                        .color(Color::Muted),
+                                ),
+                        )
+                        .end_slot(
+                            h_flex()
+                                .gap_1()
+                                .when(!warnings.is_empty(), |this| {
+                                    this.child(
+                                        div()
+                                            .id(("contrast-warning", ix))
+                                            .child(
+                                                Icon::new(IconName::Warning)
+                                                    .size(IconSize::Small)
+                                                    .color(Color::Warning),
+                                            )
+                                            .tooltip(Tooltip::text(warnings.join("\n"))),
+                                    )
+                                })
+                                .child(
+                                    Label::new(value).size(LabelSize::Small).color(Color::Muted),
+                                ),
+                        )
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.select_entry(entry_ix, window, cx);
+                        })),
+                )
+            })
+            .collect()
+    }
+
+    fn render_header(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let border_color = cx.theme().colors().border_variant;
+        v_flex()
+            .p_2()
+            .gap_2()
+            .flex_none()
+            .border_b_1()
+            .border_color(border_color)
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .border_1()
+                            .border_color(border_color)
+                            .rounded_sm()
+                            .child(self.name_editor.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .border_1()
+                            .border_color(border_color)
+                            .rounded_sm()
+                            .child(self.author_editor.clone()),
+                    )
+                    .child(
+                        Button::new("preview-theme", "Preview")
+                            .icon(IconName::Eye)
+                            .icon_position(IconPosition::Start)
+                            .on_click(|_, window, cx| {
+                                window.dispatch_action(Box::new(OpenThemePreview), cx)
+                            }),
+                    )
+                    .child(
+                        Button::new("export-theme", "Export")
+                            .icon(IconName::Download)
+                            .icon_position(IconPosition::Start)
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.export(&ExportTheme, window, cx)
+                            })),
+                    ),
+            )
+            .children(
+                self.name_error(cx)
+                    .map(|error| Label::new(error).size(LabelSize::Small).color(Color::Error)),
+            )
+            .child(self.filter_editor.clone())
+    }
+
+    fn render_color_picker(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let picker = v_flex()
+            .w(rems(22.))
+            .h_full()
+            .flex_none()
+            .p_4()
+            .gap_3()
+            .border_l_1()
+            .border_color(cx.theme().colors().border_variant);
+        let Some(entry) = self
+            .selected_entry
+            .and_then(|entry_ix| self.entries.get(entry_ix))
+        else {
+            return picker.child(
+                Label::new("Select a color to edit it")
+                    .size(LabelSize::Small)
+                    .color(Color::Muted),
+            );
+        };
+        let color = self.color(entry.section, &entry.key);
+
+        picker
+            .child(Headline::new(entry.key.clone()).size(HeadlineSize::Small))
+            .child(
+                h_flex()
+                    .gap_2()
+                    .child(render_swatch(color, cx).size_12())
+                    .child(
+                        div()
+                            .flex_1()
+                            .px_2()
+                            .py_1()
+                            .border_1()
+                            .border_color(cx.theme().colors().border_variant)
+                            .rounded_sm()
+                            .child(self.hex_editor.clone()),
+                    ),
+            )
+            .children(color.map(|color| self.render_palette(color, cx)))
+            .children(color.map(|color| {
+                v_flex()
+                    .gap_1()
+                    .children(ColorChannel::ALL.into_iter().map(|channel| {
+                        h_flex()
+                            .justify_between()
+                            .child(Label::new(channel.label()).size(LabelSize::Small))
+                            .child(NumericStepper::new(
+                                channel.label(),
+                                channel.format(color),
+                                cx.listener(move |this, _: &ClickEvent, window, cx| {
+                                    this.update_selected_color(
+                                        |color| channel.adjust(color, -1.),
+                                        window,
+                                        cx,
+                                    )
+                                }),
+                                cx.listener(move |this, _: &ClickEvent, window, cx| {
+                                    this.update_selected_color(
+                                        |color| channel.adjust(color, 1.),
+                                        window,
+                                        cx,
+                                    )
+                                }),
+                            ))
+                    }))
+            }))
+            .children(self.contrast_warnings(entry).into_iter().map(|warning| {
+                h_flex()
+                    .gap_1()
+                    .items_start()
+                    .child(
+                        Icon::new(IconName::Warning)
+                            .size(IconSize::Small)
+                            .color(Color::Warning),
+                    )
+                    .child(Label::new(warning).size(LabelSize::Small))
+            }))
+            .child(
+                Button::new("reset-color", "Reset to Original")
+                    .icon(IconName::RotateCcw)
+                    .icon_position(IconPosition::Start)
+                    .on_click(
+                        cx.listener(|this, _, window, cx| this.reset_selected_color(window, cx)),
+                    ),
+            )
+    }
+
+    /// Renders a strip of hues, and a grid of the saturations and lightnesses
+    /// of the picked hue, which keep the alpha of the color when clicked.
+    fn render_palette(&self, color: Hsla, cx: &Context<Self>) -> impl IntoElement {
+        let picked_hue_ix = (self.picker_hue * PICKER_HUES as f32).round() as usize % PICKER_HUES;
+        let hues = h_flex().children((0..PICKER_HUES).map(|hue_ix| {
+            let hue = hue_ix as f32 / PICKER_HUES as f32;
+            render_palette_swatch(
+                ("picker-hue", hue_ix),
+                gpui::hsla(hue, 1., 0.5, 1.),
+                hue_ix == picked_hue_ix,
+                cx,
+            )
+            .on_click(cx.listener(move |this, _, window, cx| {
+                this.picker_hue = hue;
+                this.update_selected_color(|color| color.h = hue, window, cx);
+                cx.notify();
+            }))
+        }));
+        let shades = (0..PICKER_LIGHTNESSES).map(|lightness_ix| {
+            let lightness = 1. - (lightness_ix + 1) as f32 / (PICKER_LIGHTNESSES + 1) as f32;
+            h_flex().children((0..PICKER_SATURATIONS).map(move |saturation_ix| {
+                let saturation = saturation_ix as f32 / (PICKER_SATURATIONS - 1) as f32;
+                let shade = gpui::hsla(self.picker_hue, saturation, lightness, 1.);
+                let is_picked = hex_color(shade) == hex_color(Hsla { a: 1., ..color });
+                render_palette_swatch(
+                    (
+                        "picker-shade",
+                        lightness_ix * PICKER_SATURATIONS + saturation_ix,
+                    ),
+                    shade,
+                    is_picked,
+                    cx,
+                )
+                .on_click(cx.listener(move |this, _, window, cx| {
+                    this.update_selected_color(
+                        |color| {
+                            *color = Hsla {
+                                a: color.a,
+                                ..shade
+                            }
+                        },
+                        window,
+                        cx,
+                    );
+                }))
+            }))
+        });
+        v_flex()
+            .gap_1()
+            .child(hues)
+            .child(v_flex().children(shades))
+    }
+}
+
+impl EventEmitter<()> for ThemeEditor {}
+
+impl Focusable for ThemeEditor {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl Item for ThemeEditor {
+    type Event = ();
+
+    fn to_item_events(_: &Self::Event, _: impl FnMut(ItemEvent)) {}
+
+    fn tab_content_text(&self, _detail: usize, _cx: &App) -> SharedString {
+        "Theme Editor".into()
+    }
+
+    fn tab_icon(&self, _window: &Window, _cx: &App) -> Option<Icon> {
+        Some(Icon::new(IconName::SwatchBook).color(Color::Muted))
+    }
+
+    fn telemetry_event_text(&self) -> Option<&'static str> {
+        None
+    }
+
+    fn clone_on_split(
+        &self,
+        _workspace_id: Option<WorkspaceId>,
+        _window: &mut Window,
+        _cx: &mut Context<Self>,
+    ) -> Option<Entity<Self>> {
+        None
+    }
+}
+
+impl Render for ThemeEditor {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        h_flex()
+            .key_context("ThemeEditor")
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(Self::export))
+            .size_full()
+            .bg(cx.theme().colors().editor_background)
+            .child(
+                v_flex()
+                    .flex_1()
+                    .h_full()
+                    .child(self.render_header(cx))
+                    .child(
+                        uniform_list(
+                            "theme-editor-colors",
+                            self.matches.len(),
+                            cx.processor(|this, range: Range<usize>, _, cx| {
+                                this.render_entries(range, cx)
+                            }),
+                        )
+                        .with_sizing_behavior(ListSizingBehavior::Infer)
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full()
+                        .p_1(),
+                    ),
+            )
+            .child(self.render_color_picker(cx))
+    }
+}
+
+fn render_swatch(color: Option<Hsla>, cx: &App) -> Div {
+    div()
+        .flex_none()
+        .rounded_sm()
+        .border_1()
+        .border_color(cx.theme().colors().border)
+        .when_some(color, |swatch, color| swatch.bg(color))
+}
+
+fn render_palette_swatch(
+    id: impl Into<ElementId>,
+    color: Hsla,
+    is_picked: bool,
+    cx: &App,
+) -> Stateful<Div> {
+    div()
+        .id(id)
+        .flex_1()
+        .h_4()
+        .bg(color)
+        .border_1()
+        .border_color(if is_picked {
+            cx.theme().colors().text
+        } else {
+            color
+        })
+        .cursor_pointer()
+}
+
+fn parse_color(color: &str) -> Option<Hsla> {
+    gpui::Rgba::try_from(color).ok().map(Hsla::from)
+}
+
+/// Converts a theme style to the JSON written in theme files, leaving out the
+/// colors that aren't set.
+fn style_map(style: &ThemeStyleContent) -> Map<String, Value> {
+    let mut value = serde_json::to_value(style).unwrap_or_default();
+    remove_nulls(&mut value);
+    match value {
+        Value::Object(map) => map,
+        _ => Map::new(),
+    }
+}
+
+fn remove_nulls(value: &mut Value) {
+    if let Value::Object(map) = value {
+        map.retain(|_, value| !value.is_null());
+        map.values_mut().for_each(remove_nulls);
+    }
+}
+
+/// Lists the colors that can be edited, in the order of the theme schema.
+fn color_entries(style: &ThemeStyleContent) -> Vec<ColorEntry> {
+    let keys = |value: serde_json::Result<Value>| -> Vec<SharedString> {
+        match value {
+            Ok(Value::Object(map)) => map
+                .into_iter()
+                .filter(|(_, value)| value.is_string())
+                .map(|(key, _)| key.into())
+                .collect(),
+            _ => Vec::new(),
+        }
+    };
+    let ui = keys(serde_json::to_value(&style.colors));
+    let status = keys(serde_json::to_value(&style.status));
+    let syntax = style
+        .syntax
+        .iter()
+        .filter(|(_, style)| style.color.is_some())
+        .map(|(name, _)| SharedString::from(name.clone()));
+
+    ui.into_iter()
+        .map(|key| ColorEntry {
+            section: ColorSection::Ui,
+            key,
+        })
+        .chain(status.into_iter().map(|key| ColorEntry {
+            section: ColorSection::Status,
+            key,
+        }))
+        .chain(syntax.map(|key| ColorEntry {
+            section: ColorSection::Syntax,
+            key,
+        }))
+        .collect()
+}
+
+/// Turns a theme name into the name of the file it's exported to.
+fn file_stem(theme_name: &str) -> String {
+    theme_name
+        .to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_file_stem() {
+        assert_eq!(file_stem("One Dark (Custom)"), "one-dark-custom");
+        assert_eq!(file_stem("  Gruvbox   Hard "), "gruvbox-hard");
+    }
+
+    #[test]
+    fn test_color_channel_adjust() {
+        let mut color = gpui::hsla(358. / 360., 0.98, 0.5, 1.);
+        ColorChannel::Hue.adjust(&mut color, 1.);
+        assert!((color.h - 3. / 360.).abs() < 1e-4);
+        ColorChannel::Saturation.adjust(&mut color, 1.);
+        assert_eq!(color.s, 1.);
+        ColorChannel::Alpha.adjust(&mut color, -2.);
+        assert!((color.a - 0.9).abs() < 1e-4);
+    }
+}
@@ -4,7 +4,7 @@ mod vscode;
 
 use std::fs::File;
 use std::io::Write;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use anyhow::{Context as _, Result};
 use clap::Parser;
@@ -13,7 +13,7 @@ use log::LevelFilter;
 use serde::Deserialize;
 use simplelog::ColorChoice;
 use simplelog::{TermLogger, TerminalMode};
-use theme::{Appearance, AppearanceContent};
+use theme::{Appearance, AppearanceContent, ThemeFormat};
 
 use crate::vscode::VsCodeTheme;
 use crate::vscode::VsCodeThemeConverter;
@@ -105,7 +105,31 @@ fn main() -> Result<()> {
 
     let theme_file_path = args.theme_path;
 
-    let theme_file = match File::open(&theme_file_path) {
+    // Themes for editors other than VS Code are converted by the theme crate,
+    // which also reads them from the themes directory at runtime.
+    let theme = match ThemeFormat::from_path(&theme_file_path) {
+        Some(format @ (ThemeFormat::TextMate | ThemeFormat::Base16 | ThemeFormat::Helix)) => {
+            let contents = std::fs::read(&theme_file_path)?;
+            let name = theme_file_path
+                .file_stem()
+                .map(|stem| stem.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            let theme_family = theme::import_theme(format, &contents, &name)
+                .context(format!("failed to import theme {theme_file_path:?}"))?;
+            serde_json::to_value(theme_family).unwrap()
+        }
+        Some(ThemeFormat::Zed) | None => import_vscode_theme(&theme_file_path)?,
+    };
+
+    write_theme(theme, args.output)?;
+
+    log::info!("Done!");
+
+    Ok(())
+}
+
+fn import_vscode_theme(theme_file_path: &Path) -> Result<serde_json::Value> {
+    let theme_file = match File::open(theme_file_path) {
         Ok(file) => file,
         Err(err) => {
             log::info!("Failed to open file at path: {:?}", theme_file_path);
@@ -125,21 +149,22 @@ fn main() -> Result<()> {
     let converter = VsCodeThemeConverter::new(vscode_theme, theme_metadata, IndexMap::new());
 
     let theme = converter.convert()?;
-    let mut theme = serde_json::to_value(theme).unwrap();
+    Ok(serde_json::to_value(theme).unwrap())
+}
+
+fn write_theme(mut theme: serde_json::Value, output: Option<PathBuf>) -> Result<()> {
     theme.as_object_mut().unwrap().insert(
         "$schema".to_string(),
         serde_json::Value::String(ZED_THEME_SCHEMA_URL.to_string()),
     );
     let theme_json = serde_json::to_string_pretty(&theme).unwrap();
 
-    if let Some(output) = args.output {
+    if let Some(output) = output {
         let mut file = File::create(output)?;
         file.write_all(theme_json.as_bytes())?;
     } else {
         println!("{}", theme_json);
     }
 
-    log::info!("Done!");
-
     Ok(())
 }
@@ -7,7 +7,7 @@ use gpui::{
 use picker::{Picker, PickerDelegate};
 use settings::{Settings as _, SettingsStore, update_settings_file};
 use std::sync::Arc;
-use theme::{Appearance, IconTheme, ThemeMeta, ThemeRegistry, ThemeSettings};
+use theme::{Appearance, IconTheme, ThemeMeta, ThemeRegistry, ThemeSettings, appearance_for_mode};
 use ui::{ListItem, ListItemSpacing, prelude::*, v_flex};
 use util::ResultExt;
 use workspace::{ModalView, ui::HighlightedLabel};
@@ -175,7 +175,11 @@ impl PickerDelegate for IconThemeSelectorDelegate {
             value = theme_name
         );
 
-        let appearance = Appearance::from(window.appearance());
+        let mode = ThemeSettings::get_global(cx)
+            .icon_theme_selection
+            .as_ref()
+            .and_then(|selection| selection.mode());
+        let appearance = appearance_for_mode(mode, Appearance::from(window.appearance()), cx);
 
         update_settings_file::<ThemeSettings>(self.fs.clone(), cx, move |settings, _| {
             settings.set_icon_theme(theme_name.to_string(), appearance);
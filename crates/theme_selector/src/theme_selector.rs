@@ -9,7 +9,7 @@ use gpui::{
 use picker::{Picker, PickerDelegate};
 use settings::{SettingsStore, update_settings_file};
 use std::sync::Arc;
-use theme::{Appearance, Theme, ThemeMeta, ThemeRegistry, ThemeSettings};
+use theme::{Appearance, Theme, ThemeMeta, ThemeRegistry, ThemeSettings, appearance_for_mode};
 use ui::{ListItem, ListItemSpacing, prelude::*, v_flex};
 use util::ResultExt;
 use workspace::{ModalView, Workspace, ui::HighlightedLabel, with_active_or_new_workspace};
@@ -227,7 +227,11 @@ impl PickerDelegate for ThemeSelectorDelegate {
 
         telemetry::event!("Settings Changed", setting = "theme", value = theme_name);
 
-        let appearance = Appearance::from(window.appearance());
+        let mode = ThemeSettings::get_global(cx)
+            .theme_selection
+            .as_ref()
+            .and_then(|selection| selection.mode());
+        let appearance = appearance_for_mode(mode, Appearance::from(window.appearance()), cx);
 
         update_settings_file::<ThemeSettings>(self.fs.clone(), cx, move |settings, _| {
             settings.set_theme(theme_name.to_string(), appearance);
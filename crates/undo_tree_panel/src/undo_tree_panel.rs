@@ -0,0 +1,518 @@
+mod undo_tree_panel_settings;
+
+use std::{
+    sync::Arc,
+    time::{Duration, Instant},
+};
+
+use anyhow::Context as _;
+use collections::HashMap;
+use db::kvp::KEY_VALUE_STORE;
+use editor::{Editor, EditorEvent};
+use gpui::{
+    Action, App, AsyncWindowContext, Context, Entity, EventEmitter, FocusHandle, Focusable,
+    ListSizingBehavior, Pixels, Render, ScrollStrategy, SharedString, Subscription, Task,
+    UniformListScrollHandle, WeakEntity, Window, actions, uniform_list,
+};
+use language::{TransactionId, UndoTreeNode};
+use menu::{Confirm, SelectFirst, SelectLast, SelectNext, SelectPrevious};
+use project::Fs;
+use serde::{Deserialize, Serialize};
+use settings::Settings;
+use time::{OffsetDateTime, UtcOffset};
+use ui::{ListItem, Tab, prelude::*};
+use util::ResultExt as _;
+use workspace::{
+    Workspace,
+    dock::{DockPosition, Panel, PanelEvent},
+};
+
+pub use undo_tree_panel_settings::UndoTreePanelSettings;
+
+actions!(undo_tree_panel, [ToggleFocus]);
+
+const UNDO_TREE_PANEL_KEY: &str = "UndoTreePanel";
+
+/// The maximum number of characters of inserted text shown for a change.
+const PREVIEW_LEN: usize = 60;
+
+/// How long to wait after an edit before rebuilding the entries.
+const UPDATE_DEBOUNCE: Duration = Duration::from_millis(100);
+
+pub fn init(cx: &mut App) {
+    UndoTreePanelSettings::register(cx);
+
+    cx.observe_new(|workspace: &mut Workspace, _, _| {
+        workspace.register_action(|workspace, _: &ToggleFocus, window, cx| {
+            workspace.toggle_panel_focus::<UndoTreePanel>(window, cx);
+        });
+    })
+    .detach();
+}
+
+/// Shows the undo tree of the active editor's buffer, including the branches
+/// abandoned by editing after undoing, and lets the user go back to any state.
+pub struct UndoTreePanel {
+    fs: Arc<dyn Fs>,
+    focus_handle: FocusHandle,
+    width: Option<Pixels>,
+    active: bool,
+    active_editor: Option<Entity<Editor>>,
+    entries: Vec<UndoTreeEntry>,
+    update_entries_task: Task<()>,
+    selected_ix: Option<usize>,
+    scroll_handle: UniformListScrollHandle,
+    pending_serialization: Task<Option<()>>,
+    editor_subscription: Option<Subscription>,
+    _workspace_subscription: Subscription,
+}
+
+/// A state of the buffer in the undo tree, reached right after applying a
+/// transaction, or the initial state of the buffer.
+struct UndoTreeEntry {
+    state: Option<TransactionId>,
+    /// The position of the state in the order the states were created.
+    sequence: usize,
+    depth: usize,
+    is_current: bool,
+    is_applied: bool,
+    preview: SharedString,
+    edited_at: Option<Instant>,
+}
+
+#[derive(Serialize, Deserialize)]
+struct SerializedUndoTreePanel {
+    width: Option<Pixels>,
+}
+
+impl UndoTreePanel {
+    pub fn load(
+        workspace: WeakEntity<Workspace>,
+        cx: AsyncWindowContext,
+    ) -> Task<anyhow::Result<Entity<Self>>> {
+        cx.spawn(async move |cx| {
+            let serialized_panel = cx
+                .background_spawn(async move { KEY_VALUE_STORE.read_kvp(UNDO_TREE_PANEL_KEY) })
+                .await
+                .context("loading undo tree panel")
+                .log_err()
+                .flatten()
+                .map(|panel| serde_json::from_str::<SerializedUndoTreePanel>(&panel))
+                .transpose()
+                .log_err()
+                .flatten();
+
+            workspace.update_in(cx, |workspace, window, cx| {
+                let panel = Self::new(workspace, window, cx);
+                if let Some(serialized_panel) = serialized_panel {
+                    panel.update(cx, |panel, cx| {
+                        panel.width = serialized_panel.width.map(|width| width.round());
+                        cx.notify();
+                    });
+                }
+                panel
+            })
+        })
+    }
+
+    fn new(
+        workspace: &mut Workspace,
+        window: &mut Window,
+        cx: &mut Context<Workspace>,
+    ) -> Entity<Self> {
+        let fs = workspace.app_state().fs.clone();
+        let workspace_entity = cx.entity();
+        cx.new(|cx| {
+            let workspace_subscription = cx.subscribe_in(
+                &workspace_entity,
+                window,
+                |panel, workspace, event, window, cx| {
+                    if let workspace::Event::ActiveItemChanged = event {
+                        let active_editor = workspace
+                            .read(cx)
+                            .active_item(cx)
+                            .and_then(|item| item.act_as::<Editor>(cx));
+                        panel.set_active_editor(active_editor, window, cx);
+                    }
+                },
+            );
+
+            let mut panel = Self {
+                fs,
+                focus_handle: cx.focus_handle(),
+                width: None,
+                active: false,
+                active_editor: None,
+                entries: Vec::new(),
+                update_entries_task: Task::ready(()),
+                selected_ix: None,
+                scroll_handle: UniformListScrollHandle::new(),
+                pending_serialization: Task::ready(None),
+                editor_subscription: None,
+                _workspace_subscription: workspace_subscription,
+            };
+            let active_editor = workspace
+                .active_item(cx)
+                .and_then(|item| item.act_as::<Editor>(cx));
+            panel.set_active_editor(active_editor, window, cx);
+            panel
+        })
+    }
+
+    fn serialize(&mut self, cx: &mut Context<Self>) {
+        let width = self.width;
+        self.pending_serialization = cx.background_spawn(
+            async move {
+                KEY_VALUE_STORE
+                    .write_kvp(
+                        UNDO_TREE_PANEL_KEY.into(),
+                        serde_json::to_string(&SerializedUndoTreePanel { width })?,
+                    )
+                    .await?;
+                anyhow::Ok(())
+            }
+            .log_err(),
+        );
+    }
+
+    fn set_active_editor(
+        &mut self,
+        editor: Option<Entity<Editor>>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let editor = editor.filter(|editor| {
+            let editor = editor.read(cx);
+            editor.mode().is_full() && editor.buffer().read(cx).is_singleton()
+        });
+        if editor == self.active_editor {
+            return;
+        }
+
+        self.editor_subscription = editor.as_ref().map(|editor| {
+            cx.subscribe_in(editor, window, |panel, _, event, _, cx| match event {
+                EditorEvent::BufferEdited | EditorEvent::Edited { .. } => {
+                    panel.schedule_entries_update(cx)
+                }
+                _ => {}
+            })
+        });
+        self.active_editor = editor;
+        if self.active {
+            self.update_entries(cx);
+        }
+    }
+
+    /// Rebuilds the entries once the edits stop, if the panel is visible.
+    fn schedule_entries_update(&mut self, cx: &mut Context<Self>) {
+        if !self.active {
+            return;
+        }
+        self.update_entries_task = cx.spawn(async move |panel, cx| {
+            cx.background_executor().timer(UPDATE_DEBOUNCE).await;
+            panel.update(cx, |panel, cx| panel.update_entries(cx)).ok();
+        });
+    }
+
+    fn update_entries(&mut self, cx: &mut Context<Self>) {
+        self.update_entries_task = Task::ready(());
+        self.entries.clear();
+        cx.notify();
+        let Some(buffer) = self
+            .active_editor
+            .as_ref()
+            .and_then(|editor| editor.read(cx).buffer().read(cx).as_singleton())
+        else {
+            self.selected_ix = None;
+            return;
+        };
+        let buffer = buffer.read(cx);
+        let nodes = buffer.undo_tree();
+        let current_state = buffer.peek_undo_stack().map(|entry| entry.transaction_id());
+
+        let mut children = HashMap::<Option<TransactionId>, Vec<(usize, &UndoTreeNode)>>::default();
+        for (ix, node) in nodes.iter().enumerate() {
+            children
+                .entry(node.parent)
+                .or_default()
+                .push((ix + 1, node));
+        }
+
+        self.entries.push(UndoTreeEntry {
+            state: None,
+            sequence: 0,
+            depth: 0,
+            is_current: current_state.is_none(),
+            is_applied: true,
+            preview: "Initial text".into(),
+            edited_at: None,
+        });
+        // Changes made after each other stay at the same depth, while every
+        // additional branch is nested under the state it was made on.
+        let mut stack = Vec::new();
+        if let Some(root_children) = children.get(&None) {
+            for (child_ix, child) in root_children.iter().enumerate().rev() {
+                stack.push((child, child_ix.min(1)));
+            }
+        }
+        while let Some(((sequence, node), depth)) = stack.pop() {
+            let preview = buffer
+                .transaction_summary(node.transaction_id)
+                .map(|summary| {
+                    let inserted_text = summary.inserted_text.trim();
+                    if inserted_text.is_empty() {
+                        "Deleted text".into()
+                    } else {
+                        let first_line = inserted_text.lines().next().unwrap_or_default();
+                        util::truncate_and_trailoff(first_line, PREVIEW_LEN).into()
+                    }
+                })
+                .unwrap_or_default();
+            self.entries.push(UndoTreeEntry {
+                state: Some(node.transaction_id),
+                sequence: *sequence,
+                depth,
+                is_current: current_state == Some(node.transaction_id),
+                is_applied: node.applied,
+                preview,
+                edited_at: Some(node.last_edit_at),
+            });
+            if let Some(node_children) = children.get(&Some(node.transaction_id)) {
+                for (child_ix, child) in node_children.iter().enumerate().rev() {
+                    stack.push((child, depth + child_ix.min(1)));
+                }
+            }
+        }
+
+        self.selected_ix = self.entries.iter().position(|entry| entry.is_current);
+        if let Some(selected_ix) = self.selected_ix {
+            self.scroll_handle
+                .scroll_to_item(selected_ix, ScrollStrategy::Center);
+        }
+    }
+
+    fn travel_to(&mut self, ix: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let (Some(entry), Some(editor)) = (self.entries.get(ix), self.active_editor.clone()) else {
+            return;
+        };
+        let state = entry.state;
+        editor.update(cx, |editor, cx| {
+            editor.travel_to_undo_state(state, window, cx)
+        });
+    }
+
+    fn select_next(&mut self, _: &SelectNext, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_ix) = self.selected_ix {
+            if selected_ix + 1 < self.entries.len() {
+                self.select(selected_ix + 1, cx);
+            }
+        }
+    }
+
+    fn select_previous(&mut self, _: &SelectPrevious, _: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_ix) = self.selected_ix {
+            self.select(selected_ix.saturating_sub(1), cx);
+        }
+    }
+
+    fn select_first(&mut self, _: &SelectFirst, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select(0, cx);
+        }
+    }
+
+    fn select_last(&mut self, _: &SelectLast, _: &mut Window, cx: &mut Context<Self>) {
+        if !self.entries.is_empty() {
+            self.select(self.entries.len() - 1, cx);
+        }
+    }
+
+    fn select(&mut self, ix: usize, cx: &mut Context<Self>) {
+        self.selected_ix = Some(ix);
+        self.scroll_handle.scroll_to_item(ix, ScrollStrategy::Top);
+        cx.notify();
+    }
+
+    fn confirm(&mut self, _: &Confirm, window: &mut Window, cx: &mut Context<Self>) {
+        if let Some(selected_ix) = self.selected_ix {
+            self.travel_to(selected_ix, window, cx);
+        }
+    }
+
+    fn render_entry(&self, ix: usize, now: OffsetDateTime, cx: &mut Context<Self>) -> ListItem {
+        let entry = &self.entries[ix];
+        let icon_color = if entry.is_current {
+            Color::Accent
+        } else if entry.is_applied {
+            Color::Default
+        } else {
+            Color::Muted
+        };
+        let label_color = if entry.is_applied {
+            Color::Default
+        } else {
+            Color::Muted
+        };
+        let edited_at = entry.edited_at.map(|edited_at| {
+            time_format::format_localized_timestamp(
+                now - edited_at.elapsed(),
+                now,
+                UtcOffset::current_local_offset().unwrap_or(UtcOffset::UTC),
+                time_format::TimestampFormat::Relative,
+            )
+        });
+
+        ListItem::new(ix)
+            .indent_level(entry.depth)
+            .indent_step_size(px(12.))
+            .spacing(ui::ListItemSpacing::Sparse)
+            .toggle_state(self.selected_ix == Some(ix))
+            .start_slot(
+                Icon::new(IconName::Circle)
+                    .size(IconSize::XSmall)
+                    .color(icon_color),
+            )
+            .child(
+                h_flex()
+                    .w_full()
+                    .gap_2()
+                    .child(
+                        Label::new(entry.sequence.to_string())
+                            .size(LabelSize::Small)
+                            .color(Color::Muted),
+                    )
+                    .child(
+                        Label::new(entry.preview.clone())
+                            .size(LabelSize::Small)
+                            .color(label_color)
+                            .truncate(),
+                    ),
+            )
+            .end_slot::<Label>(edited_at.map(|edited_at| {
+                Label::new(edited_at)
+                    .size(LabelSize::XSmall)
+                    .color(Color::Muted)
+            }))
+            .on_click(cx.listener(move |panel, _, window, cx| {
+                panel.selected_ix = Some(ix);
+                panel.travel_to(ix, window, cx);
+            }))
+    }
+}
+
+impl Panel for UndoTreePanel {
+    fn persistent_name() -> &'static str {
+        "UndoTreePanel"
+    }
+
+    fn position(&self, _: &Window, cx: &App) -> DockPosition {
+        UndoTreePanelSettings::get_global(cx).dock
+    }
+
+    fn position_is_valid(&self, position: DockPosition) -> bool {
+        matches!(position, DockPosition::Left | DockPosition::Right)
+    }
+
+    fn set_position(&mut self, position: DockPosition, _: &mut Window, cx: &mut Context<Self>) {
+        settings::update_settings_file::<UndoTreePanelSettings>(
+            self.fs.clone(),
+            cx,
+            move |settings, _| settings.dock = Some(position),
+        );
+    }
+
+    fn size(&self, _: &Window, cx: &App) -> Pixels {
+        self.width
+            .unwrap_or_else(|| UndoTreePanelSettings::get_global(cx).default_width)
+    }
+
+    fn set_size(&mut self, size: Option<Pixels>, _: &mut Window, cx: &mut Context<Self>) {
+        self.width = size;
+        self.serialize(cx);
+        cx.notify();
+    }
+
+    fn icon(&self, _: &Window, cx: &App) -> Option<IconName> {
+        UndoTreePanelSettings::get_global(cx)
+            .button
+            .then_some(IconName::HistoryRerun)
+    }
+
+    fn icon_tooltip(&self, _: &Window, _: &App) -> Option<&'static str> {
+        Some("Undo Tree Panel")
+    }
+
+    fn toggle_action(&self) -> Box<dyn Action> {
+        Box::new(ToggleFocus)
+    }
+
+    fn activation_priority(&self) -> u32 {
+        10
+    }
+
+    fn set_active(&mut self, active: bool, _: &mut Window, cx: &mut Context<Self>) {
+        let was_active = self.active;
+        self.active = active;
+        if active && !was_active {
+            self.update_entries(cx);
+        }
+    }
+}
+
+impl Focusable for UndoTreePanel {
+    fn focus_handle(&self, _: &App) -> FocusHandle {
+        self.focus_handle.clone()
+    }
+}
+
+impl EventEmitter<PanelEvent> for UndoTreePanel {}
+
+impl Render for UndoTreePanel {
+    fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let now = OffsetDateTime::now_utc();
+        v_flex()
+            .id("undo-tree-panel")
+            .key_context("UndoTreePanel")
+            .track_focus(&self.focus_handle)
+            .size_full()
+            .on_action(cx.listener(Self::select_next))
+            .on_action(cx.listener(Self::select_previous))
+            .on_action(cx.listener(Self::select_first))
+            .on_action(cx.listener(Self::select_last))
+            .on_action(cx.listener(Self::confirm))
+            .child(
+                h_flex()
+                    .px_2()
+                    .h(Tab::container_height(cx))
+                    .flex_none()
+                    .border_b_1()
+                    .border_color(cx.theme().colors().border)
+                    .child(Label::new("Undo Tree")),
+            )
+            .map(|this| {
+                if self.entries.is_empty() {
+                    this.child(
+                        v_flex().p_4().child(
+                            Label::new("Open a file to see its undo history.")
+                                .color(Color::Muted)
+                                .size(LabelSize::Small),
+                        ),
+                    )
+                } else {
+                    this.child(
+                        uniform_list(
+                            "undo-tree-entries",
+                            self.entries.len(),
+                            cx.processor(move |panel, range: std::ops::Range<usize>, _, cx| {
+                                range.map(|ix| panel.render_entry(ix, now, cx)).collect()
+                            }),
+                        )
+                        .with_sizing_behavior(ListSizingBehavior::Infer)
+                        .track_scroll(self.scroll_handle.clone())
+                        .size_full()
+                        .p_1(),
+                    )
+                }
+            })
+    }
+}
@@ -1,15 +1,22 @@
-use anyhow::Result;
+pub(crate) mod arglist;
+mod quickfix;
+
+use anyhow::{Context as _, Result, anyhow};
+use arglist::{FirstArg, LastArg, NextArg, PreviousArg, SetArglist, ShowArglist};
 use collections::{HashMap, HashSet};
 use command_palette_hooks::CommandInterceptResult;
 use editor::{
-    Bias, Editor, ToPoint,
+    Anchor, Bias, Editor, ToPoint,
     actions::{SortLinesCaseInsensitive, SortLinesCaseSensitive},
     display_map::ToDisplayPoint,
     scroll::Autoscroll,
 };
-use gpui::{Action, App, AppContext as _, Context, Global, Window, actions};
+use gpui::{
+    Action, App, AppContext as _, AsyncWindowContext, Context, Entity, Focusable, Global,
+    Keystroke, Task, WeakEntity, Window, actions,
+};
 use itertools::Itertools;
-use language::Point;
+use language::{Buffer, Point};
 use multi_buffer::MultiBufferRow;
 use project::ProjectPath;
 use regex::Regex;
@@ -24,17 +31,17 @@ use std::{
     process::Stdio,
     str::Chars,
     sync::{Arc, OnceLock},
-    time::Instant,
+    time::{Duration, Instant},
 };
 use task::{HideStrategy, RevealStrategy, SpawnInTerminal, TaskId};
 use ui::ActiveTheme;
 use util::ResultExt;
-use workspace::notifications::DetachAndPromptErr;
-use workspace::{Item, SaveIntent, notifications::NotifyResultExt};
+use workspace::notifications::{DetachAndPromptErr, NotifyTaskExt};
+use workspace::{Item, SaveIntent, Workspace, notifications::NotifyResultExt};
 use zed_actions::{OpenDocs, RevealTarget};
 
 use crate::{
-    ToggleMarksView, ToggleRegistersView, Vim,
+    ToggleMarksView, ToggleRegistersView, Vim, VimAddon,
     motion::{EndOfDocument, Motion, MotionKind, StartOfDocument},
     normal::{
         JoinLines,
@@ -186,6 +193,33 @@ struct VimEdit {
     pub filename: String,
 }
 
+/// Plays keystrokes as if they were typed in normal mode, with `:normal`.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct NormalCommand {
+    range: Option<CommandRange>,
+    keystrokes: Vec<Keystroke>,
+}
+
+/// The locations a bulk command runs its command at.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum BulkTargets {
+    /// Every entry of the quickfix list, for `:cdo`.
+    QuickfixEntries,
+    /// The first entry of every file in the quickfix list, for `:cfdo`.
+    QuickfixFiles,
+    /// Every file in the argument list, for `:argdo`.
+    Arglist,
+}
+
+/// Runs an ex command at a list of locations, with `:cdo`, `:cfdo` or `:argdo`.
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+struct BulkCommand {
+    targets: BulkTargets,
+    command: String,
+}
+
 #[derive(Debug)]
 struct WrappedAction(Box<dyn Action>);
 
@@ -517,12 +551,22 @@ pub fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
     });
 
     Vim::action(editor, cx, |vim, action: &OnMatchingLines, window, cx| {
+        action.run(vim, window, cx).detach()
+    });
+
+    Vim::action(editor, cx, |vim, action: &NormalCommand, window, cx| {
+        action.run(vim, window, cx).detach()
+    });
+
+    Vim::action(editor, cx, |vim, action: &BulkCommand, window, cx| {
         action.run(vim, window, cx)
     });
 
     Vim::action(editor, cx, |vim, action: &ShellExec, window, cx| {
         action.run(vim, window, cx)
-    })
+    });
+
+    arglist::register(editor, cx);
 }
 
 #[derive(Default)]
@@ -1001,6 +1045,31 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         VimCommand::new(("bf", "irst"), workspace::ActivateItem(0)),
         VimCommand::new(("br", "ewind"), workspace::ActivateItem(0)),
         VimCommand::new(("bl", "ast"), workspace::ActivateLastItem),
+        VimCommand::new(("ar", "gs"), ShowArglist).args(|_, files| {
+            Some(
+                SetArglist {
+                    files,
+                    append: false,
+                }
+                .boxed_clone(),
+            )
+        }),
+        VimCommand::new(("arga", "dd"), ArgumentRequired).args(|_, files| {
+            Some(
+                SetArglist {
+                    files,
+                    append: true,
+                }
+                .boxed_clone(),
+            )
+        }),
+        VimCommand::new(("argdo", ""), ArgumentRequired).args(bulk_command(BulkTargets::Arglist)),
+        VimCommand::new(("n", "ext"), NextArg { count: 1 }).range(next_arg),
+        VimCommand::new(("N", "ext"), PreviousArg { count: 1 }).range(previous_arg),
+        VimCommand::new(("prev", "ious"), PreviousArg { count: 1 }).range(previous_arg),
+        VimCommand::new(("fir", "st"), FirstArg),
+        VimCommand::new(("rew", "ind"), FirstArg),
+        VimCommand::new(("la", "st"), LastArg),
         VimCommand::str(("buffers", ""), "tab_switcher::ToggleAll"),
         VimCommand::str(("ls", ""), "tab_switcher::ToggleAll"),
         VimCommand::new(("new", ""), workspace::NewFileSplitHorizontal),
@@ -1040,13 +1109,24 @@ fn generate_commands(_: &App) -> Vec<VimCommand> {
         VimCommand::str(("cl", "ist"), "diagnostics::Deploy"),
         VimCommand::new(("cc", ""), editor::actions::Hover),
         VimCommand::new(("ll", ""), editor::actions::Hover),
-        VimCommand::new(("cn", "ext"), editor::actions::GoToDiagnostic).range(wrap_count),
-        VimCommand::new(("cp", "revious"), editor::actions::GoToPreviousDiagnostic)
-            .range(wrap_count),
-        VimCommand::new(("cN", "ext"), editor::actions::GoToPreviousDiagnostic).range(wrap_count),
+        VimCommand::new(("cn", "ext"), workspace::quickfix::Next).range(wrap_count),
+        VimCommand::new(("cp", "revious"), workspace::quickfix::Previous).range(wrap_count),
+        VimCommand::new(("cN", "ext"), workspace::quickfix::Previous).range(wrap_count),
+        VimCommand::new(("cfir", "st"), workspace::quickfix::First),
+        VimCommand::new(("cr", "ewind"), workspace::quickfix::First),
+        VimCommand::new(("cla", "st"), workspace::quickfix::Last),
+        VimCommand::new(("col", "der"), workspace::quickfix::Older),
+        VimCommand::new(("cnew", "er"), workspace::quickfix::Newer),
+        VimCommand::str(("cope", "n"), "quickfix_panel::ToggleFocus"),
+        VimCommand::str(("cw", "indow"), "quickfix_panel::ToggleFocus"),
+        VimCommand::new(("ln", "ext"), editor::actions::GoToDiagnostic).range(wrap_count),
         VimCommand::new(("lp", "revious"), editor::actions::GoToPreviousDiagnostic)
             .range(wrap_count),
         VimCommand::new(("lN", "ext"), editor::actions::GoToPreviousDiagnostic).range(wrap_count),
+        VimCommand::new(("cdo", ""), ArgumentRequired)
+            .args(bulk_command(BulkTargets::QuickfixEntries)),
+        VimCommand::new(("cfdo", ""), ArgumentRequired)
+            .args(bulk_command(BulkTargets::QuickfixFiles)),
         VimCommand::new(("j", "oin"), JoinLines).range(select_range),
         VimCommand::new(("fo", "ld"), editor::actions::FoldSelectedRanges).range(act_on_range),
         VimCommand::new(("foldo", "pen"), editor::actions::UnfoldLines)
@@ -1146,6 +1226,30 @@ fn wrap_count(action: Box<dyn Action>, range: &CommandRange) -> Option<Box<dyn A
     })
 }
 
+fn next_arg(_: Box<dyn Action>, range: &CommandRange) -> Option<Box<dyn Action>> {
+    Some(
+        NextArg {
+            count: range.as_count()?,
+        }
+        .boxed_clone(),
+    )
+}
+
+fn previous_arg(_: Box<dyn Action>, range: &CommandRange) -> Option<Box<dyn Action>> {
+    Some(
+        PreviousArg {
+            count: range.as_count()?,
+        }
+        .boxed_clone(),
+    )
+}
+
+fn bulk_command(
+    targets: BulkTargets,
+) -> impl Fn(Box<dyn Action>, String) -> Option<Box<dyn Action>> + Send + Sync + 'static {
+    move |_, command| Some(BulkCommand { targets, command }.boxed_clone())
+}
+
 pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptResult> {
     // NOTE: We also need to support passing arguments to commands like :w
     // (ideally with filename autocompletion).
@@ -1226,6 +1330,8 @@ pub fn command_interceptor(mut input: &str, cx: &App) -> Vec<CommandInterceptRes
         } else {
             None
         }
+    } else if query.starts_with("norm") {
+        NormalCommand::parse(query, range.clone())
     } else if query.contains('!') {
         ShellExec::parse(query, range.clone())
     } else {
@@ -1346,21 +1452,21 @@ impl OnMatchingLines {
         })
     }
 
-    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) -> Task<()> {
         let result = vim.update_editor(window, cx, |vim, editor, window, cx| {
             self.range.buffer_range(vim, editor, window, cx)
         });
 
         let range = match result {
-            None => return,
+            None => return Task::ready(()),
             Some(e @ Err(_)) => {
                 let Some(workspace) = vim.workspace(window) else {
-                    return;
+                    return Task::ready(());
                 };
                 workspace.update(cx, |workspace, cx| {
                     e.notify_err(workspace, cx);
                 });
-                return;
+                return Task::ready(());
             }
             Some(Ok(result)) => result,
         };
@@ -1372,12 +1478,12 @@ impl OnMatchingLines {
             Ok(regex) => vec![(regex, !self.invert)],
             e @ Err(_) => {
                 let Some(workspace) = vim.workspace(window) else {
-                    return;
+                    return Task::ready(());
                 };
                 workspace.update(cx, |workspace, cx| {
                     e.notify_err(workspace, cx);
                 });
-                return;
+                return Task::ready(());
             }
         };
         while let Some(inner) = action
@@ -1411,6 +1517,7 @@ impl OnMatchingLines {
             });
         };
 
+        let vim_entity = cx.entity().downgrade();
         vim.update_editor(window, cx, |_, editor, window, cx| {
             let snapshot = editor.snapshot(window, cx);
             let mut row = range.start.0;
@@ -1420,10 +1527,10 @@ impl OnMatchingLines {
                     .buffer_snapshot
                     .clip_point(Point::new(range.end.0 + 1, 0), Bias::Left);
             cx.spawn_in(window, async move |editor, cx| {
-                let new_selections = cx
+                let matching_rows = cx
                     .background_spawn(async move {
                         let mut line = String::new();
-                        let mut new_selections = Vec::new();
+                        let mut matching_rows = Vec::new();
                         let chunks = snapshot
                             .buffer_snapshot
                             .text_for_range(point_range)
@@ -1435,8 +1542,7 @@ impl OnMatchingLines {
                                     if regexes.iter().all(|(regex, should_match)| {
                                         regex.is_match(&line) == *should_match
                                     }) {
-                                        new_selections
-                                            .push(Point::new(row, 0).to_display_point(&snapshot))
+                                        matching_rows.push(row)
                                     }
                                     row += 1;
                                     line.clear();
@@ -1445,18 +1551,44 @@ impl OnMatchingLines {
                             }
                         }
 
-                        new_selections
+                        matching_rows
                     })
                     .await;
 
-                if new_selections.is_empty() {
+                if matching_rows.is_empty() {
                     return;
                 }
+
+                // `:normal` plays its keystrokes asynchronously, so it is given
+                // the matching lines rather than a cursor on each of them.
+                if let Some(normal) = action.as_any().downcast_ref::<NormalCommand>() {
+                    let Ok(lines) = editor.update(cx, |editor, cx| {
+                        let snapshot = editor.buffer().read(cx).snapshot(cx);
+                        matching_rows
+                            .iter()
+                            .map(|row| snapshot.anchor_before(Point::new(*row, 0)))
+                            .collect::<Vec<_>>()
+                    }) else {
+                        return;
+                    };
+                    if let Ok(task) = vim_entity.update_in(cx, |vim, window, cx| {
+                        normal.run_on_lines(Some(lines), vim, window, cx)
+                    }) {
+                        task.await;
+                    }
+                    return;
+                }
+
                 editor
                     .update_in(cx, |editor, window, cx| {
                         editor.start_transaction_at(Instant::now(), window, cx);
                         editor.change_selections(None, window, cx, |s| {
-                            s.replace_cursors_with(|_| new_selections);
+                            s.replace_cursors_with(|snapshot| {
+                                matching_rows
+                                    .iter()
+                                    .map(|row| Point::new(*row, 0).to_display_point(snapshot))
+                                    .collect()
+                            });
                         });
                         window.dispatch_action(action, cx);
                         cx.defer_in(window, move |editor, window, cx| {
@@ -1469,8 +1601,350 @@ impl OnMatchingLines {
                     })
                     .ok();
             })
-            .detach();
+        })
+        .unwrap_or_else(|| Task::ready(()))
+    }
+}
+
+impl NormalCommand {
+    fn parse(query: &str, range: Option<CommandRange>) -> Option<Box<dyn Action>> {
+        let rest = query.strip_prefix("norm")?;
+        let rest = rest
+            .strip_prefix("al")
+            .or_else(|| rest.strip_prefix('a'))
+            .unwrap_or(rest);
+        // Zed can't bypass the keymap, so `:normal!` behaves like `:normal`.
+        let rest = rest.strip_prefix('!').unwrap_or(rest);
+        if rest.is_empty() {
+            return Some(ArgumentRequired.boxed_clone());
+        }
+        let keystrokes = parse_keystrokes(rest.strip_prefix(' ')?.trim_start())?;
+        Some(Self { range, keystrokes }.boxed_clone())
+    }
+
+    /// Plays the keystrokes on every line of the range, or once at the
+    /// current selections if there is no range.
+    pub fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) -> Task<()> {
+        let Some(range) = self.range.as_ref() else {
+            return self.run_on_lines(None, vim, window, cx);
+        };
+        let result = vim.update_editor(window, cx, |vim, editor, window, cx| {
+            let range = range.buffer_range(vim, editor, window, cx)?;
+            let snapshot = editor.buffer().read(cx).snapshot(cx);
+            anyhow::Ok(
+                (range.start.0..=range.end.0)
+                    .map(|row| snapshot.anchor_before(Point::new(row, 0)))
+                    .collect::<Vec<_>>(),
+            )
         });
+        match result {
+            None => Task::ready(()),
+            Some(Ok(lines)) => self.run_on_lines(Some(lines), vim, window, cx),
+            Some(e @ Err(_)) => {
+                if let Some(workspace) = vim.workspace(window) {
+                    workspace.update(cx, |workspace, cx| {
+                        e.notify_err(workspace, cx);
+                    });
+                }
+                Task::ready(())
+            }
+        }
+    }
+
+    /// Plays the keystrokes with the cursor at the start of each of the given
+    /// lines in turn, grouping all the edits they make into one transaction.
+    fn run_on_lines(
+        &self,
+        lines: Option<Vec<Anchor>>,
+        vim: &mut Vim,
+        window: &mut Window,
+        cx: &mut Context<Vim>,
+    ) -> Task<()> {
+        let Some(editor) = vim.editor() else {
+            return Task::ready(());
+        };
+        vim.switch_mode(Mode::Normal, false, window, cx);
+        editor.update(cx, |editor, cx| {
+            window.focus(&editor.focus_handle(cx));
+            editor.start_transaction_at(Instant::now(), window, cx);
+        });
+
+        let keystrokes = self.keystrokes.clone();
+        let lines = match lines {
+            Some(lines) => lines.into_iter().map(Some).collect(),
+            None => vec![None],
+        };
+        cx.spawn_in(window, async move |vim, cx| {
+            for line in lines {
+                if let Some(line) = line {
+                    let moved = editor.update_in(cx, |editor, window, cx| {
+                        editor.change_selections(None, window, cx, |s| {
+                            s.select_anchor_ranges([line..line])
+                        })
+                    });
+                    if moved.is_err() {
+                        break;
+                    }
+                }
+                if play_keystrokes(&vim, &keystrokes, cx).log_err().is_none() {
+                    break;
+                }
+            }
+            editor
+                .update(cx, |editor, cx| {
+                    editor.end_transaction_at(Instant::now(), cx)
+                })
+                .ok();
+        })
+    }
+}
+
+/// Dispatches the keystrokes to the focused editor one at a time, so that
+/// the editor's key context is up to date for each of them.
+fn play_keystrokes(
+    vim: &WeakEntity<Vim>,
+    keystrokes: &[Keystroke],
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    for keystroke in keystrokes {
+        cx.update(|window, cx| {
+            window.dispatch_keystroke(keystroke.clone(), cx);
+        })?;
+    }
+    // Like in vim, an unfinished command is aborted as if escape was typed.
+    let unfinished = vim.read_with(cx, |vim, _| {
+        vim.mode != Mode::Normal || vim.active_operator().is_some()
+    })?;
+    if unfinished {
+        let escape = Keystroke::parse("escape")?;
+        cx.update(|window, cx| {
+            window.dispatch_keystroke(escape, cx);
+        })?;
+    }
+    Ok(())
+}
+
+/// Parses the keys given to `:normal`. Keys that can't be typed in the command
+/// palette can be given in vim's key notation, like `<Esc>` or `<C-w>`.
+fn parse_keystrokes(keys: &str) -> Option<Vec<Keystroke>> {
+    let mut keystrokes = Vec::new();
+    let mut rest = keys;
+    while let Some(c) = rest.chars().next() {
+        if c == '<' {
+            if let Some((keystroke, after)) = rest[1..]
+                .split_once('>')
+                .and_then(|(notation, after)| Some((parse_key_notation(notation)?, after)))
+            {
+                keystrokes.push(keystroke);
+                rest = after;
+                continue;
+            }
+        }
+        let key = if c == ' ' {
+            "space".to_string()
+        } else {
+            c.to_string()
+        };
+        keystrokes.push(Keystroke::parse(&key).ok()?);
+        rest = &rest[c.len_utf8()..];
+    }
+    Some(keystrokes)
+}
+
+fn parse_key_notation(notation: &str) -> Option<Keystroke> {
+    let notation = notation.to_ascii_lowercase();
+    let mut modifiers = String::new();
+    let mut key = notation.as_str();
+    while let Some((modifier, rest)) = key.split_once('-').filter(|(_, rest)| !rest.is_empty()) {
+        modifiers.push_str(match modifier {
+            "c" => "ctrl-",
+            "s" => "shift-",
+            "a" | "m" => "alt-",
+            "d" => "cmd-",
+            _ => return None,
+        });
+        key = rest;
+    }
+    let key = match key {
+        "esc" => "escape",
+        "cr" | "return" => "enter",
+        "bs" => "backspace",
+        "del" => "delete",
+        "lt" => "<",
+        "bar" => "|",
+        "bslash" => "\\",
+        "enter" | "tab" | "space" | "up" | "down" | "left" | "right" | "home" | "end"
+        | "pageup" | "pagedown" => key,
+        key if key.chars().count() == 1 => key,
+        _ => return None,
+    };
+    Keystroke::parse(&format!("{modifiers}{key}")).ok()
+}
+
+impl BulkCommand {
+    fn run(&self, vim: &mut Vim, window: &mut Window, cx: &mut Context<Vim>) {
+        let Some(workspace) = vim.workspace(window) else {
+            return;
+        };
+        let Some(action) = command_interceptor(&self.command, cx)
+            .into_iter()
+            .next()
+            .map(|result| result.action)
+        else {
+            let error = Err::<(), _>(anyhow!("Not an editor command: {}", self.command));
+            workspace.update(cx, |workspace, cx| {
+                error.notify_err(workspace, cx);
+            });
+            return;
+        };
+
+        let targets = match self.targets {
+            BulkTargets::QuickfixEntries | BulkTargets::QuickfixFiles => {
+                quickfix::quickfix_entries(&workspace, cx)
+            }
+            BulkTargets::Arglist => arglist::arglist_targets(&workspace, cx),
+        };
+        let one_per_buffer = self.targets == BulkTargets::QuickfixFiles;
+        cx.spawn_in(window, async move |_, cx| {
+            let mut targets = targets.await?;
+            if one_per_buffer {
+                targets.dedup_by_key(|target| target.buffer.entity_id());
+            }
+            anyhow::ensure!(!targets.is_empty(), "No locations to run the command at");
+
+            let mut current_editor = None;
+            let result = run_at_targets(&workspace, action, targets, &mut current_editor, cx).await;
+            if let Some(editor) = current_editor {
+                editor.update(cx, |editor, cx| {
+                    editor.end_transaction_at(Instant::now(), cx)
+                })?;
+            }
+            result
+        })
+        .detach_and_notify_err(window, cx);
+    }
+}
+
+/// A location a bulk command runs its command at.
+pub(crate) struct CommandTarget {
+    buffer: Entity<Buffer>,
+    /// Where to place the cursor before running the command, if anywhere.
+    position: Option<text::Anchor>,
+}
+
+/// Opens each target in the active pane and runs the action there, waiting for
+/// it to finish before moving on. The edits made in each buffer are grouped
+/// into one transaction, which is left open in `current_editor` for the last.
+async fn run_at_targets(
+    workspace: &Entity<Workspace>,
+    action: Box<dyn Action>,
+    targets: Vec<CommandTarget>,
+    current_editor: &mut Option<Entity<Editor>>,
+    cx: &mut AsyncWindowContext,
+) -> Result<()> {
+    for target in targets {
+        let editor = workspace.update_in(cx, |workspace, window, cx| {
+            let pane = workspace.active_pane().clone();
+            workspace.open_project_item::<Editor>(
+                pane,
+                target.buffer.clone(),
+                true,
+                true,
+                window,
+                cx,
+            )
+        })?;
+        if current_editor.as_ref() != Some(&editor) {
+            if let Some(previous_editor) = current_editor.replace(editor.clone()) {
+                previous_editor.update(cx, |editor, cx| {
+                    editor.end_transaction_at(Instant::now(), cx)
+                })?;
+            }
+            editor.update_in(cx, |editor, window, cx| {
+                editor.start_transaction_at(Instant::now(), window, cx)
+            })?;
+        }
+
+        let vim = editor
+            .update_in(cx, |editor, window, cx| {
+                if let Some(position) = target.position {
+                    let point = target
+                        .buffer
+                        .read(cx)
+                        .summary_for_anchor::<Point>(&position);
+                    editor.change_selections(Some(Autoscroll::center()), window, cx, |s| {
+                        s.select_ranges([point..point])
+                    });
+                }
+                editor.addon::<VimAddon>().map(|addon| addon.entity.clone())
+            })?
+            .context("vim mode is not enabled")?;
+        vim.update_in(cx, |vim, window, cx| {
+            vim.run_command(action.boxed_clone(), window, cx)
+        })?
+        .await;
+    }
+    Ok(())
+}
+
+impl Vim {
+    /// Runs an ex command in this vim's editor. The returned task resolves
+    /// once the command is done editing, so that commands can run in sequence.
+    fn run_command(
+        &mut self,
+        action: Box<dyn Action>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        if let Some(action) = action.as_any().downcast_ref::<NormalCommand>() {
+            action.run(self, window, cx)
+        } else if let Some(action) = action.as_any().downcast_ref::<ReplaceCommand>() {
+            self.replace_in_range(action, window, cx)
+        } else if let Some(action) = action.as_any().downcast_ref::<OnMatchingLines>() {
+            action.run(self, window, cx)
+        } else {
+            self.dispatch_to_editor(action, window, cx)
+        }
+    }
+
+    /// Dispatches an action to this vim's editor, at its current selections.
+    /// Actions go to the elements of the last drawn frame, which doesn't
+    /// include an editor that was opened since, so this waits for the editor
+    /// to be drawn first.
+    fn dispatch_to_editor(
+        &mut self,
+        action: Box<dyn Action>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
+        const DRAW_POLL_INTERVAL: Duration = Duration::from_millis(5);
+        const MAX_DRAW_POLLS: usize = 200;
+
+        let (Some(editor), Some(pane)) = (self.editor(), self.pane(window, cx)) else {
+            return Task::ready(());
+        };
+        let editor_focus = editor.focus_handle(cx);
+        let pane_focus = pane.focus_handle(cx);
+        window.focus(&editor_focus);
+        window.refresh();
+        cx.spawn_in(window, async move |_, cx| {
+            for _ in 0..MAX_DRAW_POLLS {
+                let Ok(dispatched) = cx.update(|window, cx| {
+                    let is_drawn = pane_focus.contains(&editor_focus, window);
+                    if is_drawn {
+                        editor_focus.dispatch_action(action.as_ref(), window, cx);
+                    }
+                    is_drawn
+                }) else {
+                    return;
+                };
+                if dispatched {
+                    return;
+                }
+                cx.background_executor().timer(DRAW_POLL_INTERVAL).await;
+            }
+            log::error!("editor wasn't drawn to run {}", action.name());
+        })
     }
 }
 
@@ -1677,6 +2151,7 @@ impl ShellExec {
                     show_summary: false,
                     show_command: false,
                     show_rerun: false,
+                    problem_matchers: Vec::new(),
                 };
 
                 let task_status = workspace.spawn_in_terminal(spawn_in_terminal, window, cx);
@@ -1829,10 +2304,11 @@ mod test {
         test::{NeovimBackedTestContext, VimTestContext},
     };
     use editor::Editor;
-    use gpui::{Context, TestAppContext};
+    use gpui::{Context, SharedString, TestAppContext};
     use indoc::indoc;
+    use project::ProjectPath;
     use util::path;
-    use workspace::Workspace;
+    use workspace::{Workspace, quickfix::QuickfixEntry};
 
     #[gpui::test]
     async fn test_command_basics(cx: &mut TestAppContext) {
@@ -2208,4 +2684,196 @@ mod test {
         });
         assert!(mark.is_none())
     }
+
+    #[gpui::test]
+    async fn test_command_normal(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        cx.set_state(
+            indoc! {"
+            ˇa
+            b
+            c"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": % n o r m space A ; enter");
+        cx.assert_state(
+            indoc! {"
+            a;
+            b;
+            cˇ;"},
+            Mode::Normal,
+        );
+
+        // All the lines are changed in a single transaction.
+        cx.simulate_keystrokes("u");
+        cx.update_editor(|editor, _, cx| assert_eq!(editor.text(cx), "a\nb\nc"));
+
+        cx.simulate_keystrokes(": 2 , 3 n o r m a l space A x < e s c > I y enter");
+        cx.assert_state(
+            indoc! {"
+            a
+            ybx
+            ˇycx"},
+            Mode::Normal,
+        );
+
+        cx.set_state(
+            indoc! {"
+            ˇa
+            b
+            a"},
+            Mode::Normal,
+        );
+        cx.simulate_keystrokes(": g / a / n o r m space I - enter");
+        cx.assert_state(
+            indoc! {"
+            -a
+            b
+            ˇ-a"},
+            Mode::Normal,
+        );
+    }
+
+    #[gpui::test]
+    async fn test_command_arglist(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(path!("/root/dir/file2.rs"), b"two".to_vec())
+            .await;
+        fs.as_fake()
+            .insert_file(path!("/root/dir/file3.rs"), b"three".to_vec())
+            .await;
+
+        cx.simulate_keystrokes(": a r g s space d i r / f i l e [ 2 3 ] . r s enter");
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/file2.rs"), "two", cx);
+        });
+
+        cx.simulate_keystrokes(": n e x t enter");
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/file3.rs"), "three", cx);
+        });
+
+        cx.simulate_keystrokes(": n e x t enter");
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/file3.rs"), "three", cx);
+        });
+
+        cx.simulate_keystrokes(": p r e v enter");
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/file2.rs"), "two", cx);
+        });
+
+        cx.simulate_keystrokes(": a r g d o space n o r m space A ; enter");
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/file3.rs"), "three;", cx);
+            let mut texts = workspace
+                .items_of_type::<Editor>(cx)
+                .map(|editor| editor.read(cx).text(cx))
+                .collect::<Vec<_>>();
+            texts.sort();
+            assert_eq!(texts, ["", "three;", "two;"]);
+        });
+    }
+    #[gpui::test]
+    async fn test_command_cdo_cfdo(cx: &mut TestAppContext) {
+        let mut cx = VimTestContext::new(cx, true).await;
+
+        let fs = cx.workspace(|workspace, _, cx| workspace.project().read(cx).fs().clone());
+        fs.as_fake()
+            .insert_file(
+                path!("/root/dir/file2.rs"),
+                b"one\ntwo one\nthree\n".to_vec(),
+            )
+            .await;
+        fs.as_fake()
+            .insert_file(path!("/root/dir/file3.rs"), b"one one\nfour\n".to_vec())
+            .await;
+        cx.run_until_parked();
+
+        cx.workspace(|workspace, _, cx| {
+            let worktree_id = workspace
+                .project()
+                .read(cx)
+                .worktrees(cx)
+                .next()
+                .unwrap()
+                .read(cx)
+                .id();
+            let entry = |path: &str, row, column| QuickfixEntry {
+                path: ProjectPath {
+                    worktree_id,
+                    path: Path::new(path).into(),
+                },
+                row,
+                column,
+                text: SharedString::default(),
+            };
+            workspace.quickfix().update(cx, |quickfix, cx| {
+                quickfix.set_list(
+                    "matches",
+                    vec![
+                        entry("dir/file2.rs", 0, 0),
+                        entry("dir/file2.rs", 1, 4),
+                        entry("dir/file3.rs", 0, 4),
+                    ],
+                    cx,
+                )
+            });
+        });
+        let texts = |cx: &mut VimTestContext| {
+            cx.workspace(|workspace, _, cx| {
+                let mut texts = workspace
+                    .items_of_type::<Editor>(cx)
+                    .map(|editor| editor.read(cx).text(cx))
+                    .collect::<Vec<_>>();
+                texts.sort();
+                texts
+            })
+        };
+
+        // `:cdo` runs at every entry, and its edits are undone per file.
+        cx.simulate_keystrokes(": c d o space s / o n e / 1 / enter");
+        cx.run_until_parked();
+        assert_eq!(texts(&mut cx), ["", "1\ntwo 1\nthree\n", "1 one\nfour\n"]);
+        cx.workspace(|workspace, _, cx| {
+            assert_active_item(workspace, path!("/root/dir/file3.rs"), "1 one\nfour\n", cx);
+        });
+        cx.simulate_keystrokes("u");
+        assert_eq!(texts(&mut cx), ["", "1\ntwo 1\nthree\n", "one one\nfour\n"]);
+        cx.simulate_keystrokes(": c f i r s t enter");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("u");
+        assert_eq!(
+            texts(&mut cx),
+            ["", "one\ntwo one\nthree\n", "one one\nfour\n"]
+        );
+
+        // `:cfdo` runs once per file, at the file's first entry.
+        cx.simulate_keystrokes(": c f d o space n o r m space A ; enter");
+        cx.run_until_parked();
+        assert_eq!(
+            texts(&mut cx),
+            ["", "one one;\nfour\n", "one;\ntwo one\nthree\n"]
+        );
+        cx.simulate_keystrokes("u");
+        cx.simulate_keystrokes(": c f i r s t enter");
+        cx.run_until_parked();
+        cx.simulate_keystrokes("u");
+        assert_eq!(
+            texts(&mut cx),
+            ["", "one\ntwo one\nthree\n", "one one\nfour\n"]
+        );
+
+        // Other actions are dispatched to each file's editor, at its entry.
+        cx.simulate_keystrokes(": c f d o space j enter");
+        cx.run_until_parked();
+        assert_eq!(
+            texts(&mut cx),
+            ["", "one one four\n", "one two one\nthree\n"]
+        );
+    }
 }
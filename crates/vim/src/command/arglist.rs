@@ -0,0 +1,223 @@
+use std::path::Path;
+
+use anyhow::{Context as _, Result};
+use editor::Editor;
+use futures::future;
+use gpui::{Action, App, Context, Entity, Task, Window, actions};
+use project::{Project, ProjectPath};
+use util::paths::PathMatcher;
+use workspace::{Toast, Workspace, notifications::NotificationId, notifications::NotifyResultExt};
+
+use super::CommandTarget;
+use crate::Vim;
+
+/// The files `:next`, `:previous` and `:argdo` work through, set with `:args`.
+#[derive(Default)]
+pub(crate) struct Arglist {
+    paths: Vec<ProjectPath>,
+    ix: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct SetArglist {
+    pub(crate) files: String,
+    pub(crate) append: bool,
+}
+
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct NextArg {
+    pub(crate) count: u32,
+}
+
+#[derive(Clone, Debug, PartialEq, Action)]
+#[action(namespace = vim, no_json, no_register)]
+pub(crate) struct PreviousArg {
+    pub(crate) count: u32,
+}
+
+actions!(vim, [ShowArglist, FirstArg, LastArg]);
+
+pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
+    Vim::action(editor, cx, |vim, action: &SetArglist, window, cx| {
+        let Some(workspace) = vim.workspace(window) else {
+            return;
+        };
+        let paths = resolve_files(workspace.read(cx).project(), &action.files, cx);
+        if action.append {
+            let arglist = Vim::globals(cx)
+                .arglists
+                .entry(workspace.entity_id())
+                .or_default();
+            arglist.paths.extend(paths);
+        } else {
+            Vim::globals(cx)
+                .arglists
+                .insert(workspace.entity_id(), Arglist { paths, ix: 0 });
+            vim.go_to_arg(|_| Ok(0), window, cx);
+        }
+    });
+
+    Vim::action(editor, cx, |vim, _: &ShowArglist, window, cx| {
+        let Some(workspace) = vim.workspace(window) else {
+            return;
+        };
+        let message = match Vim::globals(cx).arglists.get(&workspace.entity_id()) {
+            Some(arglist) if !arglist.paths.is_empty() => arglist
+                .paths
+                .iter()
+                .enumerate()
+                .map(|(ix, path)| {
+                    let path = path.path.to_string_lossy();
+                    if ix == arglist.ix {
+                        format!("[{path}]")
+                    } else {
+                        path.into_owned()
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join(" "),
+            _ => "The argument list is empty".to_string(),
+        };
+        workspace.update(cx, |workspace, cx| {
+            workspace.show_toast(
+                Toast::new(NotificationId::unique::<ShowArglist>(), message),
+                cx,
+            )
+        });
+    });
+
+    Vim::action(editor, cx, |vim, action: &NextArg, window, cx| {
+        let count = action.count as usize;
+        vim.go_to_arg(
+            |arglist| {
+                let ix = arglist.ix + count;
+                anyhow::ensure!(ix < arglist.paths.len(), "Cannot go beyond last file");
+                Ok(ix)
+            },
+            window,
+            cx,
+        )
+    });
+
+    Vim::action(editor, cx, |vim, action: &PreviousArg, window, cx| {
+        let count = action.count as usize;
+        vim.go_to_arg(
+            |arglist| {
+                arglist
+                    .ix
+                    .checked_sub(count)
+                    .context("Cannot go before first file")
+            },
+            window,
+            cx,
+        )
+    });
+
+    Vim::action(editor, cx, |vim, _: &FirstArg, window, cx| {
+        vim.go_to_arg(|_| Ok(0), window, cx)
+    });
+
+    Vim::action(editor, cx, |vim, _: &LastArg, window, cx| {
+        vim.go_to_arg(
+            |arglist| Ok(arglist.paths.len().saturating_sub(1)),
+            window,
+            cx,
+        )
+    });
+}
+
+impl Vim {
+    /// Opens the file of the argument list at the index picked by `target`.
+    fn go_to_arg(
+        &mut self,
+        target: impl FnOnce(&Arglist) -> Result<usize>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let Some(workspace) = self.workspace(window) else {
+            return;
+        };
+        let arglist = Vim::globals(cx)
+            .arglists
+            .entry(workspace.entity_id())
+            .or_default();
+        let path = if arglist.paths.is_empty() {
+            Err(anyhow::anyhow!("The argument list is empty"))
+        } else {
+            target(arglist).map(|ix| {
+                arglist.ix = ix;
+                arglist.paths[ix].clone()
+            })
+        };
+        workspace.update(cx, |workspace, cx| {
+            if let Some(path) = path.notify_err(workspace, cx) {
+                workspace
+                    .open_path(path, None, true, window, cx)
+                    .detach_and_log_err(cx);
+            }
+        });
+    }
+}
+
+/// Opens the buffers of the files in the argument list, for `:argdo`.
+pub(crate) fn arglist_targets(
+    workspace: &Entity<Workspace>,
+    cx: &mut App,
+) -> Task<Result<Vec<CommandTarget>>> {
+    let project = workspace.read(cx).project().clone();
+    let paths = Vim::globals(cx)
+        .arglists
+        .get(&workspace.entity_id())
+        .map(|arglist| arglist.paths.clone())
+        .unwrap_or_default();
+    let open_buffers = paths
+        .into_iter()
+        .map(|path| project.update(cx, |project, cx| project.open_buffer(path, cx)))
+        .collect::<Vec<_>>();
+    cx.spawn(async move |_| {
+        let buffers = future::try_join_all(open_buffers).await?;
+        Ok(buffers
+            .into_iter()
+            .map(|buffer| CommandTarget {
+                buffer,
+                position: None,
+            })
+            .collect())
+    })
+}
+
+/// Resolves the files given to `:args`. Each is either a glob matched against
+/// the files of the project, or a path relative to one of its worktrees.
+fn resolve_files(project: &Entity<Project>, files: &str, cx: &App) -> Vec<ProjectPath> {
+    let project = project.read(cx);
+    let mut paths = Vec::new();
+    for file in files.split_whitespace() {
+        if file.contains(['*', '?', '[']) {
+            let Ok(matcher) = PathMatcher::new([file]) else {
+                continue;
+            };
+            for worktree in project.visible_worktrees(cx) {
+                let worktree = worktree.read(cx);
+                paths.extend(
+                    worktree
+                        .files(false, 0)
+                        .filter(|entry| matcher.is_match(&entry.path))
+                        .map(|entry| ProjectPath {
+                            worktree_id: worktree.id(),
+                            path: entry.path.clone(),
+                        }),
+                );
+            }
+        } else if let Some(path) = project.find_project_path(Path::new(file), cx) {
+            paths.push(path);
+        } else if let Some(worktree) = project.visible_worktrees(cx).next() {
+            paths.push(ProjectPath {
+                worktree_id: worktree.read(cx).id(),
+                path: Path::new(file).into(),
+            });
+        }
+    }
+    paths
+}
@@ -0,0 +1,160 @@
+use anyhow::Result;
+use futures::future;
+use gpui::{App, AsyncApp, Entity, Task, WeakEntity};
+use itertools::Itertools as _;
+use language::{Bias, Buffer, DiagnosticSeverity, Point};
+use project::ProjectPath;
+use search::ProjectSearchView;
+use workspace::{Toast, Workspace, notifications::NotificationId};
+
+use super::CommandTarget;
+
+/// Returns the entries of the quickfix list, which `:cdo` and `:cfdo` work
+/// through: the workspace's current quickfix list if it has entries, the
+/// matches of the project search if one is open, or the project's errors and
+/// warnings otherwise.
+pub(crate) fn quickfix_entries(
+    workspace: &Entity<Workspace>,
+    cx: &mut App,
+) -> Task<Result<Vec<CommandTarget>>> {
+    let weak_workspace = workspace.downgrade();
+    let workspace = workspace.read(cx);
+    let project = workspace.project().clone();
+    let list_entries = workspace
+        .quickfix()
+        .read(cx)
+        .current_list()
+        .map(|list| list.entries.clone())
+        .filter(|entries| !entries.is_empty());
+    let project_search = workspace
+        .active_item_as::<ProjectSearchView>(cx)
+        .or_else(|| workspace.items_of_type::<ProjectSearchView>(cx).next());
+
+    if let Some(list_entries) = list_entries {
+        let open_buffers = list_entries
+            .iter()
+            .map(|entry| {
+                let open_buffer = project.update(cx, |project, cx| {
+                    project.open_buffer(entry.path.clone(), cx)
+                });
+                (entry.path.clone(), open_buffer)
+            })
+            .collect::<Vec<_>>();
+        return cx.spawn(async move |cx| {
+            let buffers = open_entry_buffers(weak_workspace, open_buffers, cx).await;
+            list_entries
+                .into_iter()
+                .zip(buffers)
+                .filter_map(|(entry, buffer)| Some((entry, buffer?)))
+                .map(|(entry, buffer)| {
+                    let position = buffer.read_with(cx, |buffer, _| {
+                        let point =
+                            buffer.clip_point(Point::new(entry.row, entry.column), Bias::Left);
+                        buffer.anchor_before(point)
+                    })?;
+                    Ok(CommandTarget {
+                        buffer,
+                        position: Some(position),
+                    })
+                })
+                .collect()
+        });
+    }
+
+    if let Some(project_search) = project_search {
+        let project = project.read(cx);
+        let entries = project_search
+            .read(cx)
+            .get_matches(cx)
+            .into_iter()
+            .filter_map(|range| {
+                let buffer = project.buffer_for_id(range.start.buffer_id?, cx)?;
+                Some(CommandTarget {
+                    buffer,
+                    position: Some(range.start.text_anchor),
+                })
+            })
+            .collect();
+        return Task::ready(Ok(entries));
+    }
+
+    let paths = project
+        .read(cx)
+        .diagnostic_summaries(false, cx)
+        .filter(|(_, _, summary)| summary.error_count + summary.warning_count > 0)
+        .map(|(path, _, _)| path)
+        .unique()
+        .collect::<Vec<_>>();
+    let open_buffers = paths
+        .into_iter()
+        .map(|path| {
+            let open_buffer =
+                project.update(cx, |project, cx| project.open_buffer(path.clone(), cx));
+            (path, open_buffer)
+        })
+        .collect::<Vec<_>>();
+    cx.spawn(async move |cx| {
+        let mut entries = Vec::new();
+        let buffers = open_entry_buffers(weak_workspace, open_buffers, cx).await;
+        for buffer in buffers.into_iter().flatten() {
+            let snapshot = buffer.read_with(cx, |buffer, _| buffer.snapshot())?;
+            for (_, group) in snapshot.diagnostic_groups(None) {
+                let primary = &group.entries[group.primary_ix];
+                if primary.diagnostic.severity <= DiagnosticSeverity::WARNING {
+                    entries.push(CommandTarget {
+                        buffer: buffer.clone(),
+                        position: Some(primary.range.start),
+                    });
+                }
+            }
+        }
+        Ok(entries)
+    })
+}
+
+/// Marks the notification listing the files that the quickfix entries are in
+/// but which couldn't be opened.
+struct SkippedQuickfixFiles;
+
+/// Waits for the buffers of the quickfix entries to open, in the order they
+/// were given. A file that can't be opened, e.g. because it was deleted since
+/// the list was made, is skipped and reported rather than failing the command.
+async fn open_entry_buffers(
+    workspace: WeakEntity<Workspace>,
+    open_buffers: Vec<(ProjectPath, Task<Result<Entity<Buffer>>>)>,
+    cx: &mut AsyncApp,
+) -> Vec<Option<Entity<Buffer>>> {
+    let (paths, open_buffers): (Vec<_>, Vec<_>) = open_buffers.into_iter().unzip();
+    let results = future::join_all(open_buffers).await;
+
+    let mut skipped = Vec::new();
+    let buffers = paths
+        .into_iter()
+        .zip(results)
+        .map(|(path, result)| match result {
+            Ok(buffer) => Some(buffer),
+            Err(error) => {
+                log::error!("failed to open quickfix entry {:?}: {error:#}", path.path);
+                skipped.push(path.path.display().to_string());
+                None
+            }
+        })
+        .collect();
+
+    let skipped = skipped.into_iter().unique().collect::<Vec<_>>();
+    if !skipped.is_empty() {
+        let message = format!(
+            "Skipped files that could not be opened: {}",
+            skipped.join(", ")
+        );
+        workspace
+            .update(cx, |workspace, cx| {
+                workspace.show_toast(
+                    Toast::new(NotificationId::unique::<SkippedQuickfixFiles>(), message),
+                    cx,
+                )
+            })
+            .ok();
+    }
+    buffers
+}
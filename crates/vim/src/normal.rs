@@ -64,6 +64,8 @@ actions!(
         ShowLocation,
         Undo,
         Redo,
+        GoToOlderUndoState,
+        GoToNewerUndoState,
     ]
 );
 
@@ -168,6 +170,20 @@ pub(crate) fn register(editor: &mut Editor, cx: &mut Context<Vim>) {
             }
         });
     });
+    Vim::action(editor, cx, |vim, _: &GoToOlderUndoState, window, cx| {
+        let times = Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        vim.update_editor(window, cx, |_, editor, window, cx| {
+            editor.travel_undo_history(-(times.unwrap_or(1) as isize), window, cx);
+        });
+    });
+    Vim::action(editor, cx, |vim, _: &GoToNewerUndoState, window, cx| {
+        let times = Vim::take_count(cx);
+        Vim::take_forced_motion(cx);
+        vim.update_editor(window, cx, |_, editor, window, cx| {
+            editor.travel_undo_history(times.unwrap_or(1) as isize, window, cx);
+        });
+    });
 
     repeat::register(editor, cx);
     scroll::register(editor, cx);
@@ -1,12 +1,12 @@
 use editor::{Editor, EditorSettings};
-use gpui::{Action, Context, Window, actions};
+use gpui::{Action, Context, Task, Window, actions};
 use language::Point;
 use schemars::JsonSchema;
 use search::{BufferSearchBar, SearchOptions, buffer_search};
 use serde_derive::Deserialize;
 use settings::Settings;
 use std::{iter::Peekable, str::Chars};
-use util::serde::default_true;
+use util::{ResultExt as _, serde::default_true};
 use workspace::{notifications::NotifyResultExt, searchable::Direction};
 
 use crate::{
@@ -432,13 +432,24 @@ impl Vim {
         window: &mut Window,
         cx: &mut Context<Self>,
     ) {
+        self.replace_in_range(action, window, cx).detach();
+    }
+
+    /// Replaces the matches in the command's range, returning a task that
+    /// resolves once the replacements have been made.
+    pub(crate) fn replace_in_range(
+        &mut self,
+        action: &ReplaceCommand,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Task<()> {
         let replacement = action.replacement.clone();
         let Some(((pane, workspace), editor)) = self
             .pane(window, cx)
             .zip(self.workspace(window))
             .zip(self.editor())
         else {
-            return;
+            return Task::ready(());
         };
         if let Some(result) = self.update_editor(window, cx, |vim, editor, window, cx| {
             let range = action.range.buffer_range(vim, editor, window, cx)?;
@@ -458,7 +469,7 @@ impl Vim {
             let mut options = SearchOptions::REGEX;
 
             let Some(search_bar) = pane.toolbar().read(cx).item_of_type::<BufferSearchBar>() else {
-                return;
+                return Task::ready(());
             };
             let search = search_bar.update(cx, |search_bar, cx| {
                 if !search_bar.show(window, cx) {
@@ -487,9 +498,11 @@ impl Vim {
                 search_bar.set_replacement(Some(&replacement.replacement), cx);
                 Some(search_bar.search(&search, Some(options), window, cx))
             });
-            let Some(search) = search else { return };
+            let Some(search) = search else {
+                return Task::ready(());
+            };
             let search_bar = search_bar.downgrade();
-            cx.spawn_in(window, async move |_, cx| {
+            let replace = cx.spawn_in(window, async move |_, cx| {
                 search.await?;
                 search_bar.update_in(cx, |search_bar, window, cx| {
                     search_bar.select_last_match(window, cx);
@@ -515,8 +528,10 @@ impl Vim {
                     search_bar.set_search_options(options, cx);
                 })?;
                 anyhow::Ok(())
+            });
+            cx.spawn(async move |_, _| {
+                replace.await.log_err();
             })
-            .detach_and_log_err(cx);
         })
     }
 }
@@ -1,4 +1,4 @@
-use crate::command::command_interceptor;
+use crate::command::{arglist::Arglist, command_interceptor};
 use crate::motion::MotionKind;
 use crate::normal::repeat::Replayer;
 use crate::surrounds::SurroundsType;
@@ -224,6 +224,7 @@ pub struct VimGlobals {
     pub focused_vim: Option<WeakEntity<Vim>>,
 
     pub marks: HashMap<EntityId, Entity<MarksState>>,
+    pub arglists: HashMap<EntityId, Arglist>,
 }
 
 pub struct MarksState {
@@ -746,6 +747,7 @@ impl VimGlobals {
         cx.observe_release(&cx.entity(), move |_, _, cx| {
             Vim::update_globals(cx, |globals, _| {
                 globals.marks.remove(&entity_id);
+                globals.arglists.remove(&entity_id);
             })
         })
         .detach();
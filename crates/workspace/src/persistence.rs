@@ -13,7 +13,9 @@ use client::DevServerProjectId;
 use db::{define_connection, query, sqlez::connection::Connection, sqlez_macros::sql};
 use gpui::{Axis, Bounds, Task, WindowBounds, WindowId, point, size};
 use itertools::Itertools;
-use project::debugger::breakpoint_store::{BreakpointState, SourceBreakpoint};
+use project::debugger::breakpoint_store::{
+    BreakpointState, DataBreakpoint, DataBreakpointAccessType, SourceBreakpoint,
+};
 
 use language::{LanguageName, Toolchain};
 use project::WorktreeId;
@@ -186,6 +188,23 @@ impl Column for BreakpointStateWrapper<'_> {
     }
 }
 
+fn access_type_to_db(access_type: &DataBreakpointAccessType) -> &'static str {
+    match access_type {
+        DataBreakpointAccessType::Read => "read",
+        DataBreakpointAccessType::Write => "write",
+        DataBreakpointAccessType::ReadWrite => "readWrite",
+    }
+}
+
+fn access_type_from_db(access_type: &str) -> Option<DataBreakpointAccessType> {
+    match access_type {
+        "read" => Some(DataBreakpointAccessType::Read),
+        "write" => Some(DataBreakpointAccessType::Write),
+        "readWrite" => Some(DataBreakpointAccessType::ReadWrite),
+        _ => None,
+    }
+}
+
 /// This struct is used to implement traits on Vec<breakpoint>
 #[derive(Debug)]
 #[allow(dead_code)]
@@ -542,6 +561,20 @@ define_connection! {
         ALTER TABLE breakpoints ADD COLUMN condition TEXT;
         ALTER TABLE breakpoints ADD COLUMN hit_condition TEXT;
     ),
+    sql!(
+        CREATE TABLE data_breakpoints (
+            workspace_id INTEGER NOT NULL,
+            data_id TEXT NOT NULL,
+            description TEXT NOT NULL,
+            access_type TEXT,
+            condition TEXT,
+            hit_condition TEXT,
+            state INTEGER DEFAULT(0) NOT NULL,
+            FOREIGN KEY(workspace_id) REFERENCES workspaces(workspace_id)
+            ON DELETE CASCADE
+            ON UPDATE CASCADE
+        );
+    ),
     ];
 }
 
@@ -630,6 +663,7 @@ impl WorkspaceDb {
             docks,
             session_id: None,
             breakpoints: self.breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             window_id,
         })
     }
@@ -684,6 +718,7 @@ impl WorkspaceDb {
             window_bounds,
             centered_layout: centered_layout.unwrap_or(false),
             breakpoints: self.breakpoints(workspace_id),
+            data_breakpoints: self.data_breakpoints(workspace_id),
             display,
             docks,
             session_id: None,
@@ -737,6 +772,48 @@ impl WorkspaceDb {
         }
     }
 
+    fn data_breakpoints(&self, workspace_id: WorkspaceId) -> Vec<DataBreakpoint> {
+        let data_breakpoints: Result<
+            Vec<(
+                String,
+                String,
+                Option<String>,
+                Option<String>,
+                Option<String>,
+                BreakpointStateWrapper,
+            )>,
+        > = self
+            .select_bound(sql! {
+                SELECT data_id, description, access_type, condition, hit_condition, state
+                FROM data_breakpoints
+                WHERE workspace_id = ?
+            })
+            .and_then(|mut prepared_statement| (prepared_statement)(workspace_id));
+
+        match data_breakpoints {
+            Ok(data_breakpoints) => data_breakpoints
+                .into_iter()
+                .map(
+                    |(data_id, description, access_type, condition, hit_condition, state)| {
+                        DataBreakpoint {
+                            data_id: data_id.into(),
+                            description: description.into(),
+                            access_type: access_type.as_deref().and_then(access_type_from_db),
+                            condition: condition.map(Arc::from),
+                            hit_condition: hit_condition.map(Arc::from),
+                            state: state.0.into_owned(),
+                            can_persist: true,
+                        }
+                    },
+                )
+                .collect(),
+            Err(msg) => {
+                log::error!("Data breakpoints query failed with msg: {msg}");
+                Default::default()
+            }
+        }
+    }
+
     /// Saves a workspace using the worktree roots. Will garbage collect any workspaces
     /// that used this workspace previously
     pub(crate) async fn save_workspace(&self, workspace: SerializedWorkspace) {
@@ -779,6 +856,27 @@ impl WorkspaceDb {
 
                 }
 
+                conn.exec_bound(sql!(DELETE FROM data_breakpoints WHERE workspace_id = ?1))?(workspace.id).context("Clearing old data breakpoints")?;
+
+                for bp in workspace.data_breakpoints {
+                    let state = BreakpointStateWrapper::from(bp.state);
+                    if let Err(err) = conn.exec_bound(sql!(
+                        INSERT INTO data_breakpoints (workspace_id, data_id, description, access_type, condition, hit_condition, state)
+                        VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7);))?
+
+                    ((
+                        workspace.id,
+                        bp.data_id.as_ref(),
+                        bp.description.as_ref(),
+                        bp.access_type.as_ref().map(access_type_to_db),
+                        bp.condition,
+                        bp.hit_condition,
+                        state,
+                    )) {
+                        log::error!("{err}");
+                    }
+                }
+
 
                 match workspace.location {
                     SerializedWorkspaceLocation::Local(local_paths, local_paths_order) => {
@@ -1570,6 +1668,7 @@ mod tests {
                 );
                 map
             },
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1649,6 +1748,56 @@ mod tests {
         assert_eq!(loaded_breakpoints[4].path, Arc::from(path));
     }
 
+    #[gpui::test]
+    async fn test_data_breakpoints() {
+        zlog::init_test();
+
+        let db = WorkspaceDb::open_test_db("test_data_breakpoints").await;
+        let id = db.next_id().await.unwrap();
+
+        let watchpoint = DataBreakpoint {
+            data_id: "0x7ffee4b8".into(),
+            description: "counter".into(),
+            access_type: Some(DataBreakpointAccessType::Write),
+            condition: Some("counter > 10".into()),
+            hit_condition: Some("3".into()),
+            state: BreakpointState::Enabled,
+            can_persist: true,
+        };
+        let disabled_watchpoint = DataBreakpoint {
+            data_id: "items".into(),
+            description: "items".into(),
+            access_type: None,
+            condition: None,
+            hit_condition: None,
+            state: BreakpointState::Disabled,
+            can_persist: true,
+        };
+
+        let mut workspace = SerializedWorkspace {
+            id,
+            location: SerializedWorkspaceLocation::from_local_paths(["/tmp"]),
+            center_group: Default::default(),
+            window_bounds: Default::default(),
+            display: Default::default(),
+            docks: Default::default(),
+            centered_layout: false,
+            breakpoints: Default::default(),
+            data_breakpoints: vec![watchpoint.clone(), disabled_watchpoint.clone()],
+            session_id: None,
+            window_id: None,
+        };
+
+        db.save_workspace(workspace.clone()).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert_eq!(loaded.data_breakpoints, [watchpoint, disabled_watchpoint]);
+
+        workspace.data_breakpoints.clear();
+        db.save_workspace(workspace).await;
+        let loaded = db.workspace_for_roots(&["/tmp"]).unwrap();
+        assert!(loaded.data_breakpoints.is_empty());
+    }
+
     #[gpui::test]
     async fn test_remove_last_breakpoint() {
         zlog::init_test();
@@ -1689,6 +1838,7 @@ mod tests {
                 );
                 map
             },
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1815,6 +1965,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1828,6 +1979,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -1933,6 +2085,7 @@ mod tests {
             center_group,
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1968,6 +2121,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -1984,6 +2138,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: Some(2),
         };
@@ -2024,6 +2179,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2064,6 +2220,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(10),
         };
@@ -2077,6 +2234,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-1".to_owned()),
             window_id: Some(20),
         };
@@ -2090,6 +2248,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(30),
         };
@@ -2103,6 +2262,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: None,
             window_id: None,
         };
@@ -2121,6 +2281,7 @@ mod tests {
             docks: Default::default(),
             centered_layout: false,
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             session_id: Some("session-id-2".to_owned()),
             window_id: Some(50),
         };
@@ -2134,6 +2295,7 @@ mod tests {
             center_group: Default::default(),
             window_bounds: Default::default(),
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             display: Default::default(),
             docks: Default::default(),
             centered_layout: false,
@@ -2192,6 +2354,7 @@ mod tests {
             display: Default::default(),
             docks: Default::default(),
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             centered_layout: false,
             session_id: None,
             window_id: None,
@@ -2240,6 +2403,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
@@ -2333,6 +2497,7 @@ mod tests {
             centered_layout: false,
             session_id: Some("one-session".to_owned()),
             breakpoints: Default::default(),
+            data_breakpoints: Default::default(),
             window_id: Some(window_id),
         })
         .collect::<Vec<_>>();
@@ -0,0 +1,287 @@
+use std::{
+    collections::VecDeque,
+    path::{Path, PathBuf},
+};
+
+use gpui::{Context, SharedString, actions};
+use project::ProjectPath;
+
+actions!(
+    quickfix,
+    [Next, Previous, First, Last, Older, Newer, Export, Import]
+);
+
+/// The number of quickfix lists kept in the history, like in Vim.
+const MAX_LISTS: usize = 10;
+
+/// A location in the project, together with a message describing it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuickfixEntry {
+    pub path: ProjectPath,
+    /// The zero-based row of the location.
+    pub row: u32,
+    /// The zero-based column of the location.
+    pub column: u32,
+    pub text: SharedString,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuickfixList {
+    pub title: SharedString,
+    pub entries: Vec<QuickfixEntry>,
+    /// The entry that was navigated to last.
+    pub selected: Option<usize>,
+}
+
+/// The quickfix lists of a workspace. Any source of locations, such as the
+/// project search, the diagnostics or a task, can make a new list, and
+/// the older lists are kept around to go back to.
+#[derive(Default)]
+pub struct Quickfix {
+    lists: VecDeque<QuickfixList>,
+    current: usize,
+}
+
+impl Quickfix {
+    /// Makes a new quickfix list the current one. Like in Vim, the lists
+    /// newer than the current one are dropped.
+    pub fn set_list(
+        &mut self,
+        title: impl Into<SharedString>,
+        entries: Vec<QuickfixEntry>,
+        cx: &mut Context<Self>,
+    ) {
+        if !self.lists.is_empty() {
+            self.lists.truncate(self.current + 1);
+        }
+        self.lists.push_back(QuickfixList {
+            title: title.into(),
+            entries,
+            selected: None,
+        });
+        if self.lists.len() > MAX_LISTS {
+            self.lists.pop_front();
+        }
+        self.current = self.lists.len() - 1;
+        cx.notify();
+    }
+
+    pub fn lists(&self) -> impl Iterator<Item = &QuickfixList> {
+        self.lists.iter()
+    }
+
+    pub fn current_list_ix(&self) -> Option<usize> {
+        (!self.lists.is_empty()).then_some(self.current)
+    }
+
+    pub fn current_list(&self) -> Option<&QuickfixList> {
+        self.lists.get(self.current)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.current_list()
+            .is_none_or(|list| list.entries.is_empty())
+    }
+
+    pub fn select_list(&mut self, ix: usize, cx: &mut Context<Self>) -> bool {
+        if ix >= self.lists.len() || ix == self.current {
+            return false;
+        }
+        self.current = ix;
+        cx.notify();
+        true
+    }
+
+    pub fn older(&mut self, cx: &mut Context<Self>) -> bool {
+        self.current > 0 && self.select_list(self.current - 1, cx)
+    }
+
+    pub fn newer(&mut self, cx: &mut Context<Self>) -> bool {
+        self.select_list(self.current + 1, cx)
+    }
+
+    /// Selects an entry of the current list, returning it so the caller can
+    /// go to its location.
+    pub fn select(&mut self, ix: usize, cx: &mut Context<Self>) -> Option<QuickfixEntry> {
+        let list = self.lists.get_mut(self.current)?;
+        let entry = list.entries.get(ix)?.clone();
+        list.selected = Some(ix);
+        cx.notify();
+        Some(entry)
+    }
+
+    pub fn select_next(&mut self, cx: &mut Context<Self>) -> Option<QuickfixEntry> {
+        let ix = self
+            .current_list()?
+            .selected
+            .map_or(0, |selected| selected + 1);
+        self.select(ix, cx)
+    }
+
+    pub fn select_previous(&mut self, cx: &mut Context<Self>) -> Option<QuickfixEntry> {
+        let ix = self.current_list()?.selected?.checked_sub(1)?;
+        self.select(ix, cx)
+    }
+
+    pub fn select_first(&mut self, cx: &mut Context<Self>) -> Option<QuickfixEntry> {
+        self.select(0, cx)
+    }
+
+    pub fn select_last(&mut self, cx: &mut Context<Self>) -> Option<QuickfixEntry> {
+        let ix = self.current_list()?.entries.len().checked_sub(1)?;
+        self.select(ix, cx)
+    }
+}
+
+/// A line of a quickfix file, in the `path:line:column: text` format that
+/// compilers, `grep -n` and Vim's `:cfile` use. Lines and columns start at 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct QuickfixFileLine {
+    pub path: PathBuf,
+    pub row: u32,
+    pub column: u32,
+    pub text: String,
+}
+
+pub fn format_quickfix_line(path: &Path, row: u32, column: u32, text: &str) -> String {
+    let text = text.lines().next().unwrap_or_default();
+    format!("{}:{}:{}: {}", path.display(), row + 1, column + 1, text)
+}
+
+/// Parses a line of a quickfix file, where the column and the text are
+/// optional. The returned row and column are zero-based.
+pub fn parse_quickfix_line(line: &str) -> Option<QuickfixFileLine> {
+    fn split_number(text: &str) -> Option<(u32, &str)> {
+        let end = text
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(text.len());
+        let rest = &text[end..];
+        if !rest.is_empty() && !rest.starts_with(':') {
+            return None;
+        }
+        Some((text[..end].parse().ok()?, rest))
+    }
+
+    // Paths can contain colons themselves, such as Windows drive letters, so
+    // the path ends at the first colon that is followed by a line number.
+    for (ix, _) in line.match_indices(':') {
+        let path = &line[..ix];
+        if path.is_empty() {
+            continue;
+        }
+        let Some((row, rest)) = split_number(&line[ix + 1..]) else {
+            continue;
+        };
+        let (column, rest) = match rest.strip_prefix(':').and_then(split_number) {
+            Some((column, rest)) => (column, rest),
+            None => (1, rest),
+        };
+        let text = rest.strip_prefix(':').unwrap_or(rest).trim();
+        return Some(QuickfixFileLine {
+            path: PathBuf::from(path),
+            row: row.saturating_sub(1),
+            column: column.saturating_sub(1),
+            text: text.to_string(),
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use gpui::{AppContext as _, TestAppContext};
+    use project::WorktreeId;
+
+    use super::*;
+
+    fn entry(path: &str, row: u32) -> QuickfixEntry {
+        QuickfixEntry {
+            path: ProjectPath {
+                worktree_id: WorktreeId::from_usize(1),
+                path: Arc::from(Path::new(path)),
+            },
+            row,
+            column: 0,
+            text: SharedString::default(),
+        }
+    }
+
+    #[gpui::test]
+    fn test_quickfix_history(cx: &mut TestAppContext) {
+        let quickfix = cx.new(|_| Quickfix::default());
+        quickfix.update(cx, |quickfix, cx| {
+            assert!(quickfix.is_empty());
+            assert_eq!(quickfix.select_next(cx), None);
+
+            quickfix.set_list("first", vec![entry("a.rs", 1), entry("b.rs", 2)], cx);
+            assert_eq!(quickfix.select_next(cx), Some(entry("a.rs", 1)));
+            assert_eq!(quickfix.select_next(cx), Some(entry("b.rs", 2)));
+            assert_eq!(quickfix.select_next(cx), None);
+            assert_eq!(quickfix.select_previous(cx), Some(entry("a.rs", 1)));
+            assert_eq!(quickfix.select_previous(cx), None);
+            assert_eq!(quickfix.select_last(cx), Some(entry("b.rs", 2)));
+
+            quickfix.set_list("second", vec![entry("c.rs", 3)], cx);
+            quickfix.set_list("third", vec![entry("d.rs", 4)], cx);
+            assert_eq!(quickfix.current_list().unwrap().title, "third");
+            assert!(quickfix.older(cx));
+            assert!(quickfix.older(cx));
+            assert!(!quickfix.older(cx));
+            assert_eq!(quickfix.current_list().unwrap().title, "first");
+            // The selection of each list is kept when switching between them.
+            assert_eq!(quickfix.current_list().unwrap().selected, Some(1));
+
+            // A new list replaces the ones newer than the current one.
+            quickfix.set_list("fourth", vec![], cx);
+            let titles = quickfix
+                .lists()
+                .map(|list| list.title.clone())
+                .collect::<Vec<_>>();
+            assert_eq!(titles, ["first", "fourth"]);
+            assert!(!quickfix.newer(cx));
+            assert!(quickfix.is_empty());
+
+            for ix in 0..MAX_LISTS {
+                quickfix.set_list(format!("list {ix}"), vec![], cx);
+            }
+            assert_eq!(quickfix.lists().count(), MAX_LISTS);
+            assert_eq!(quickfix.lists().next().unwrap().title, "list 0");
+        });
+    }
+
+    #[test]
+    fn test_quickfix_lines() {
+        let line = format_quickfix_line(Path::new("src/main.rs"), 9, 4, "unused variable\nmore");
+        assert_eq!(line, "src/main.rs:10:5: unused variable");
+        assert_eq!(
+            parse_quickfix_line(&line),
+            Some(QuickfixFileLine {
+                path: PathBuf::from("src/main.rs"),
+                row: 9,
+                column: 4,
+                text: "unused variable".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_quickfix_line("C:\\src\\lib.rs:3: a: b"),
+            Some(QuickfixFileLine {
+                path: PathBuf::from("C:\\src\\lib.rs"),
+                row: 2,
+                column: 0,
+                text: "a: b".to_string(),
+            })
+        );
+        assert_eq!(
+            parse_quickfix_line("lib.rs:7"),
+            Some(QuickfixFileLine {
+                path: PathBuf::from("lib.rs"),
+                row: 6,
+                column: 0,
+                text: String::new(),
+            })
+        );
+        assert_eq!(parse_quickfix_line("warning: something"), None);
+    }
+}
@@ -39,6 +39,8 @@ pub struct SearchOptions {
     pub case: bool,
     pub word: bool,
     pub regex: bool,
+    /// Specifies whether the item can be searched by syntax structure.
+    pub structural: bool,
     /// Specifies whether the  supports search & replace.
     pub replacement: bool,
     pub selection: bool,
@@ -53,6 +55,7 @@ pub trait SearchableItem: Item + EventEmitter<SearchEvent> {
             case: true,
             word: true,
             regex: true,
+            structural: false,
             replacement: true,
             selection: true,
             find_in_results: false,
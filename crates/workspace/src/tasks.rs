@@ -1,14 +1,22 @@
-use std::process::ExitStatus;
+use std::{cell::RefCell, path::Path, process::ExitStatus, rc::Rc};
 
 use anyhow::Result;
-use gpui::{AppContext, Context, Entity, Task};
+use collections::HashMap;
+use futures::future::{self, FutureExt as _, Shared};
+use gpui::{AppContext, AsyncWindowContext, Context, Entity, Task, WeakEntity};
 use language::Buffer;
-use project::TaskSourceKind;
+use project::{TaskGraph, TaskSourceKind};
 use remote::ConnectionState;
-use task::{DebugScenario, ResolvedTask, SpawnInTerminal, TaskContext, TaskTemplate};
+use task::{
+    DebugScenario, DependencyFailurePolicy, DependsOrder, ResolvedTask, SpawnInTerminal,
+    TaskContext, TaskId, TaskTemplate, VariableName,
+};
 use ui::Window;
 
-use crate::Workspace;
+use crate::{
+    Toast, Workspace,
+    notifications::{DetachAndPromptErr, NotificationId},
+};
 
 impl Workspace {
     pub fn schedule_task(
@@ -65,12 +73,17 @@ impl Workspace {
                     project.task_store().read(cx).task_inventory().cloned()
                 {
                     task_inventory.update(cx, |inventory, _| {
-                        inventory.task_scheduled(task_source_kind, resolved_task);
+                        inventory.task_scheduled(task_source_kind.clone(), resolved_task.clone());
                     })
                 }
             });
         }
 
+        if resolved_task.has_dependencies() {
+            self.schedule_task_graph(task_source_kind, resolved_task, window, cx);
+            return;
+        }
+
         if let Some(terminal_provider) = self.terminal_provider.as_ref() {
             let task_status = terminal_provider.spawn(spawn_in_terminal, window, cx);
             cx.background_spawn(async move {
@@ -90,6 +103,48 @@ impl Workspace {
         }
     }
 
+    /// Runs the tasks the task depends on, in the order and with the failure policy it specifies, then the task itself.
+    /// Every task in the graph runs at most once, even if several tasks depend on it.
+    fn schedule_task_graph(
+        &mut self,
+        task_source_kind: TaskSourceKind,
+        resolved_task: ResolvedTask,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let project = self.project.read(cx);
+        let Some(inventory) = project.task_store().read(cx).task_inventory().cloned() else {
+            return;
+        };
+        let worktree = match &task_source_kind {
+            TaskSourceKind::Worktree { id, .. } => Some(*id),
+            _ => resolved_task
+                .task_context()
+                .task_variables
+                .get(&VariableName::WorktreeRoot)
+                .and_then(|root| project.find_worktree(Path::new(root), cx))
+                .map(|(worktree, _)| worktree.read(cx).id()),
+        };
+        let label = resolved_task.resolved_label.clone();
+        let graph =
+            inventory
+                .read(cx)
+                .resolve_task_graph(task_source_kind, resolved_task, worktree, cx);
+        cx.spawn_in(window, async move |workspace, cx| {
+            let graph = graph.await?;
+            let steps = Rc::new(RefCell::new(Vec::new()));
+            let succeeded =
+                run_task_graph(graph, workspace.clone(), Rc::default(), steps.clone(), cx).await;
+            let summary = task_graph_summary(&label, succeeded, &steps.borrow());
+            let toast = Toast::new(NotificationId::unique::<TaskGraph>(), summary);
+            workspace.update(cx, |workspace, cx| {
+                workspace.show_toast(if succeeded { toast.autohide() } else { toast }, cx)
+            })?;
+            anyhow::Ok(())
+        })
+        .detach_and_prompt_err("Failed to run task", window, cx, |_, _, _| None);
+    }
+
     pub fn start_debug_session(
         &mut self,
         scenario: DebugScenario,
@@ -116,3 +171,433 @@ impl Workspace {
         }
     }
 }
+
+/// The outcome of a single task of a task graph.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum TaskStepStatus {
+    Succeeded,
+    Failed,
+    Skipped,
+}
+
+impl std::fmt::Display for TaskStepStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Succeeded => write!(f, "succeeded"),
+            Self::Failed => write!(f, "failed"),
+            Self::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+/// Describes how the task graph of the task with the given label went, with the status of each of its steps.
+fn task_graph_summary(label: &str, succeeded: bool, steps: &[(String, TaskStepStatus)]) -> String {
+    let outcome = if succeeded { "succeeded" } else { "failed" };
+    let mut summary = format!("Task `{label}` {outcome}");
+    for (step, status) in steps {
+        summary.push_str(&format!("\n{status}: {step}"));
+    }
+    summary
+}
+
+type TaskRuns = Rc<RefCell<HashMap<TaskId, Shared<Task<bool>>>>>;
+type TaskSteps = Rc<RefCell<Vec<(String, TaskStepStatus)>>>;
+
+/// Starts running the task graph, reusing the run of any task already started by another part of the graph.
+fn run_task_graph(
+    graph: TaskGraph,
+    workspace: WeakEntity<Workspace>,
+    runs: TaskRuns,
+    steps: TaskSteps,
+    cx: &mut AsyncWindowContext,
+) -> Shared<Task<bool>> {
+    if let Some(run) = runs.borrow().get(&graph.task.id) {
+        return run.clone();
+    }
+
+    let id = graph.task.id.clone();
+    let run = cx
+        .spawn({
+            let runs = runs.clone();
+            async move |cx| {
+                let template = graph.task.original_task();
+                let abort_on_failure =
+                    template.on_dependency_failure == DependencyFailurePolicy::Abort;
+                let dependencies_succeeded = match template.depends_order {
+                    DependsOrder::Sequence => {
+                        let mut succeeded = true;
+                        for dependency in graph.dependencies {
+                            if !succeeded && abort_on_failure {
+                                break;
+                            }
+                            succeeded &= run_task_graph(
+                                dependency,
+                                workspace.clone(),
+                                runs.clone(),
+                                steps.clone(),
+                                cx,
+                            )
+                            .await;
+                        }
+                        succeeded
+                    }
+                    DependsOrder::Parallel => {
+                        let dependencies = graph
+                            .dependencies
+                            .into_iter()
+                            .map(|dependency| {
+                                run_task_graph(
+                                    dependency,
+                                    workspace.clone(),
+                                    runs.clone(),
+                                    steps.clone(),
+                                    cx,
+                                )
+                            })
+                            .collect::<Vec<_>>();
+                        future::join_all(dependencies)
+                            .await
+                            .into_iter()
+                            .all(|succeeded| succeeded)
+                    }
+                };
+
+                let label = graph.task.resolved_label.clone();
+                let status = if !dependencies_succeeded && abort_on_failure {
+                    TaskStepStatus::Skipped
+                } else if graph.task.resolved.command.is_empty() {
+                    if dependencies_succeeded {
+                        TaskStepStatus::Succeeded
+                    } else {
+                        TaskStepStatus::Failed
+                    }
+                } else {
+                    let task_status = workspace.update_in(cx, |workspace, window, cx| {
+                        workspace.spawn_in_terminal(graph.task.resolved.clone(), window, cx)
+                    });
+                    let succeeded = match task_status {
+                        Ok(task_status) => match task_status.await {
+                            Some(Ok(status)) => status.success(),
+                            Some(Err(e)) => {
+                                log::error!("Task `{label}` spawn failed: {e}");
+                                false
+                            }
+                            None => false,
+                        },
+                        Err(_) => false,
+                    };
+                    if succeeded && dependencies_succeeded {
+                        TaskStepStatus::Succeeded
+                    } else {
+                        TaskStepStatus::Failed
+                    }
+                };
+                log::debug!("Task `{label}` {status}");
+                steps.borrow_mut().push((label, status));
+                status == TaskStepStatus::Succeeded
+            }
+        })
+        .shared();
+    runs.borrow_mut().insert(id, run.clone());
+    run
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+    use futures::channel::oneshot;
+    use gpui::{App, TestAppContext, VisualTestContext};
+    use project::{FakeFs, Project};
+
+    use super::*;
+    use crate::{TerminalProvider, tests::init_test};
+
+    /// The tasks spawned in the terminal, which keep running until the test finishes them.
+    #[derive(Default)]
+    struct FakeTerminals {
+        started: Vec<String>,
+        running: HashMap<String, oneshot::Sender<bool>>,
+    }
+
+    struct FakeTerminalProvider(Rc<RefCell<FakeTerminals>>);
+
+    impl TerminalProvider for FakeTerminalProvider {
+        fn spawn(
+            &self,
+            task: SpawnInTerminal,
+            _: &mut Window,
+            cx: &mut App,
+        ) -> Task<Option<Result<ExitStatus>>> {
+            let (tx, rx) = oneshot::channel();
+            let mut terminals = self.0.borrow_mut();
+            terminals.started.push(task.full_label.clone());
+            terminals.running.insert(task.full_label, tx);
+            cx.background_spawn(async move {
+                match rx.await {
+                    Ok(true) => Some(Ok(ExitStatus::default())),
+                    Ok(false) => Some(Err(anyhow!("task failed"))),
+                    Err(_) => None,
+                }
+            })
+        }
+    }
+
+    fn task_graph(template: TaskTemplate, dependencies: Vec<TaskGraph>) -> TaskGraph {
+        let template = TaskTemplate {
+            depends_on: dependencies
+                .iter()
+                .map(|dependency| dependency.task.resolved_label.clone())
+                .collect(),
+            ..template
+        };
+        TaskGraph {
+            task_source_kind: TaskSourceKind::UserInput,
+            task: template
+                .resolve_task("test", &TaskContext::default())
+                .unwrap(),
+            dependencies,
+        }
+    }
+
+    fn command(label: &str) -> TaskTemplate {
+        TaskTemplate {
+            label: label.to_string(),
+            command: format!("echo {label}"),
+            ..TaskTemplate::default()
+        }
+    }
+
+    struct TaskGraphTest {
+        terminals: Rc<RefCell<FakeTerminals>>,
+        steps: TaskSteps,
+        run: Task<bool>,
+    }
+
+    impl TaskGraphTest {
+        async fn start(
+            graph: TaskGraph,
+            cx: &mut TestAppContext,
+        ) -> (Self, &mut VisualTestContext) {
+            let fs = FakeFs::new(cx.executor());
+            let project = Project::test(fs, [], cx).await;
+            let (workspace, cx) =
+                cx.add_window_view(|window, cx| Workspace::test_new(project, window, cx));
+            let terminals = Rc::new(RefCell::new(FakeTerminals::default()));
+            let steps = TaskSteps::default();
+            let run = workspace.update_in(cx, |workspace, window, cx| {
+                workspace.set_terminal_provider(FakeTerminalProvider(terminals.clone()));
+                let steps = steps.clone();
+                cx.spawn_in(window, async move |workspace, cx| {
+                    run_task_graph(graph, workspace, Rc::default(), steps, cx).await
+                })
+            });
+            cx.run_until_parked();
+            (
+                Self {
+                    terminals,
+                    steps,
+                    run,
+                },
+                cx,
+            )
+        }
+
+        /// The labels of the tasks started so far, in the order they were started.
+        fn started(&self) -> Vec<String> {
+            self.terminals.borrow().started.clone()
+        }
+
+        fn finish(&self, label: &str, succeeded: bool, cx: &mut VisualTestContext) {
+            let task = self.terminals.borrow_mut().running.remove(label);
+            task.unwrap_or_else(|| panic!("task `{label}` is not running"))
+                .send(succeeded)
+                .unwrap();
+            cx.run_until_parked();
+        }
+
+        fn steps(&self) -> Vec<(String, TaskStepStatus)> {
+            self.steps.borrow().clone()
+        }
+    }
+
+    fn sorted(mut labels: Vec<String>) -> Vec<String> {
+        labels.sort();
+        labels
+    }
+
+    fn steps(steps: &[(&str, TaskStepStatus)]) -> Vec<(String, TaskStepStatus)> {
+        steps
+            .iter()
+            .map(|(label, status)| (label.to_string(), *status))
+            .collect()
+    }
+
+    #[gpui::test]
+    async fn test_sequential_dependencies(cx: &mut TestAppContext) {
+        init_test(cx);
+        let graph = task_graph(
+            command("deploy"),
+            vec![
+                task_graph(command("build"), Vec::new()),
+                task_graph(command("test"), Vec::new()),
+            ],
+        );
+        let (test, cx) = TaskGraphTest::start(graph, cx).await;
+
+        assert_eq!(test.started(), ["build"]);
+        test.finish("build", true, cx);
+        assert_eq!(test.started(), ["build", "test"]);
+        test.finish("test", true, cx);
+        assert_eq!(test.started(), ["build", "test", "deploy"]);
+        test.finish("deploy", true, cx);
+
+        assert!(test.run.await);
+        assert_eq!(
+            test.steps(),
+            steps(&[
+                ("build", TaskStepStatus::Succeeded),
+                ("test", TaskStepStatus::Succeeded),
+                ("deploy", TaskStepStatus::Succeeded),
+            ])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_parallel_dependencies(cx: &mut TestAppContext) {
+        init_test(cx);
+        let graph = task_graph(
+            TaskTemplate {
+                depends_order: DependsOrder::Parallel,
+                ..command("deploy")
+            },
+            vec![
+                task_graph(command("build"), Vec::new()),
+                task_graph(command("test"), Vec::new()),
+            ],
+        );
+        let (test, cx) = TaskGraphTest::start(graph, cx).await;
+
+        assert_eq!(sorted(test.started()), ["build", "test"]);
+        test.finish("test", true, cx);
+        assert_eq!(sorted(test.started()), ["build", "test"]);
+        test.finish("build", true, cx);
+        assert_eq!(test.started()[2..], ["deploy"]);
+        test.finish("deploy", true, cx);
+
+        assert!(test.run.await);
+        assert_eq!(
+            test.steps(),
+            steps(&[
+                ("test", TaskStepStatus::Succeeded),
+                ("build", TaskStepStatus::Succeeded),
+                ("deploy", TaskStepStatus::Succeeded),
+            ])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_dependency_failure_aborts(cx: &mut TestAppContext) {
+        init_test(cx);
+        let graph = task_graph(
+            command("deploy"),
+            vec![
+                task_graph(command("build"), Vec::new()),
+                task_graph(command("test"), Vec::new()),
+            ],
+        );
+        let (test, cx) = TaskGraphTest::start(graph, cx).await;
+
+        test.finish("build", false, cx);
+        assert!(!test.run.await);
+        assert_eq!(test.started(), ["build"]);
+        assert_eq!(
+            test.steps(),
+            steps(&[
+                ("build", TaskStepStatus::Failed),
+                ("deploy", TaskStepStatus::Skipped),
+            ])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_dependency_failure_continues(cx: &mut TestAppContext) {
+        init_test(cx);
+        let graph = task_graph(
+            TaskTemplate {
+                on_dependency_failure: DependencyFailurePolicy::Continue,
+                ..command("deploy")
+            },
+            vec![
+                task_graph(command("build"), Vec::new()),
+                task_graph(command("test"), Vec::new()),
+            ],
+        );
+        let (test, cx) = TaskGraphTest::start(graph, cx).await;
+
+        test.finish("build", false, cx);
+        test.finish("test", true, cx);
+        test.finish("deploy", true, cx);
+        assert!(!test.run.await);
+        assert_eq!(test.started(), ["build", "test", "deploy"]);
+        assert_eq!(
+            test.steps(),
+            steps(&[
+                ("build", TaskStepStatus::Failed),
+                ("test", TaskStepStatus::Succeeded),
+                ("deploy", TaskStepStatus::Failed),
+            ])
+        );
+    }
+
+    #[gpui::test]
+    async fn test_shared_dependency_runs_once(cx: &mut TestAppContext) {
+        init_test(cx);
+        let graph = task_graph(
+            TaskTemplate {
+                depends_order: DependsOrder::Parallel,
+                ..command("deploy")
+            },
+            vec![
+                task_graph(
+                    command("client"),
+                    vec![task_graph(command("build"), Vec::new())],
+                ),
+                task_graph(
+                    command("server"),
+                    vec![task_graph(command("build"), Vec::new())],
+                ),
+            ],
+        );
+        let (test, cx) = TaskGraphTest::start(graph, cx).await;
+
+        assert_eq!(test.started(), ["build"]);
+        test.finish("build", true, cx);
+        assert_eq!(sorted(test.started()), ["build", "client", "server"]);
+        test.finish("client", true, cx);
+        test.finish("server", true, cx);
+        test.finish("deploy", true, cx);
+
+        assert!(test.run.await);
+        assert_eq!(
+            test.steps(),
+            steps(&[
+                ("build", TaskStepStatus::Succeeded),
+                ("client", TaskStepStatus::Succeeded),
+                ("server", TaskStepStatus::Succeeded),
+                ("deploy", TaskStepStatus::Succeeded),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_task_graph_summary() {
+        let steps = steps(&[
+            ("build", TaskStepStatus::Succeeded),
+            ("deploy", TaskStepStatus::Succeeded),
+        ]);
+        assert_eq!(
+            task_graph_summary("deploy", true, &steps),
+            "Task `deploy` succeeded\nsucceeded: build\nsucceeded: deploy"
+        );
+    }
+}
@@ -6,6 +6,7 @@ pub mod notifications;
 pub mod pane;
 pub mod pane_group;
 mod persistence;
+pub mod quickfix;
 pub mod searchable;
 pub mod shared_screen;
 mod status_bar;
@@ -15,6 +16,7 @@ mod toast_layer;
 mod toolbar;
 mod workspace_settings;
 
+pub use theme_preview::OpenThemePreview;
 pub use toast_layer::{ToastAction, ToastLayer, ToastView};
 
 use anyhow::{Context as _, Result, anyhow};
@@ -71,11 +73,12 @@ use project::{
     DirectoryLister, Project, ProjectEntryId, ProjectPath, ResolvedPath, Worktree, WorktreeId,
     debugger::{breakpoint_store::BreakpointStoreEvent, session::ThreadStatus},
 };
+use quickfix::Quickfix;
 use remote::{SshClientDelegate, SshConnectionOptions, ssh_session::ConnectionIdentifier};
 use schemars::JsonSchema;
 use serde::Deserialize;
 use session::AppSession;
-use settings::Settings;
+use settings::{Settings, SettingsLocation, SettingsStore};
 use shared_screen::SharedScreen;
 use sqlez::{
     bindable::{Bind, Column, StaticColumnCount},
@@ -97,7 +100,9 @@ use std::{
     time::Duration,
 };
 use task::{DebugScenario, SpawnInTerminal, TaskContext};
-use theme::{ActiveTheme, SystemAppearance, ThemeSettings};
+use theme::{
+    ActiveTheme, SystemAppearance, Theme, ThemeRegistry, ThemeSettings, appearance_for_mode,
+};
 pub use toolbar::{Toolbar, ToolbarItemEvent, ToolbarItemLocation, ToolbarItemView};
 pub use ui;
 use ui::{Window, prelude::*};
@@ -951,6 +956,7 @@ pub struct Workspace {
     notifications: Notifications,
     suppressed_notifications: HashSet<NotificationId>,
     project: Entity<Project>,
+    quickfix: Entity<Quickfix>,
     follower_states: HashMap<CollaboratorId, FollowerState>,
     last_leaders_by_pane: HashMap<WeakEntity<Pane>, CollaboratorId>,
     window_edited: bool,
@@ -1021,6 +1027,7 @@ impl Workspace {
 
                 project::Event::WorktreeRemoved(_) | project::Event::WorktreeAdded(_) => {
                     this.update_window_title(window, cx);
+                    this.update_window_theme(window, cx);
                     this.serialize_workspace(window, cx);
                     // This event could be triggered by `AddFolderToProject` or `RemoveFromProject`.
                     // So we need to update the history.
@@ -1098,7 +1105,8 @@ impl Workspace {
             window,
             |workspace, _, event, window, cx| match event {
                 BreakpointStoreEvent::BreakpointsUpdated(_, _)
-                | BreakpointStoreEvent::BreakpointsCleared(_) => {
+                | BreakpointStoreEvent::BreakpointsCleared(_)
+                | BreakpointStoreEvent::DataBreakpointsUpdated => {
                     workspace.serialize_workspace(window, cx);
                 }
                 BreakpointStoreEvent::SetDebugLine | BreakpointStoreEvent::ClearDebugLines => {}
@@ -1239,7 +1247,11 @@ impl Workspace {
                 ThemeSettings::reload_current_theme(cx);
                 ThemeSettings::reload_current_icon_theme(cx);
             }),
+            cx.observe_global_in::<SettingsStore>(window, |this, window, cx| {
+                this.update_window_theme(window, cx);
+            }),
             cx.on_release(move |this, cx| {
+                theme::set_window_theme(window_handle.window_id(), None, cx);
                 this.app_state.workspace_store.update(cx, move |store, _| {
                     store.workspaces.remove(&window_handle.clone());
                 })
@@ -1248,6 +1260,7 @@ impl Workspace {
 
         cx.defer_in(window, |this, window, cx| {
             this.update_window_title(window, cx);
+            this.update_window_theme(window, cx);
             this.show_initial_notifications(cx);
         });
         Workspace {
@@ -1272,6 +1285,7 @@ impl Workspace {
             bottom_dock_layout,
             right_dock,
             project: project.clone(),
+            quickfix: cx.new(|_| Quickfix::default()),
             follower_states: Default::default(),
             last_leaders_by_pane: Default::default(),
             dispatching_keystrokes: Default::default(),
@@ -1567,6 +1581,10 @@ impl Workspace {
         &self.project
     }
 
+    pub fn quickfix(&self) -> &Entity<Quickfix> {
+        &self.quickfix
+    }
+
     pub fn recently_activated_items(&self, cx: &App) -> HashMap<EntityId, usize> {
         let mut history: HashMap<EntityId, usize> = HashMap::default();
 
@@ -4224,6 +4242,33 @@ impl Workspace {
         window.set_window_title(&title);
     }
 
+    /// Shows the theme picked in the project's settings in this window, when
+    /// it differs from the theme picked in the user's settings.
+    fn update_window_theme(&mut self, window: &mut Window, cx: &mut App) {
+        let theme = self.project_theme(cx);
+        theme::set_window_theme(window.window_handle().window_id(), theme, cx);
+    }
+
+    fn project_theme(&self, cx: &App) -> Option<Arc<Theme>> {
+        let worktree = self.project.read(cx).visible_worktrees(cx).next()?;
+        let settings_location = SettingsLocation {
+            worktree_id: worktree.read(cx).id(),
+            path: Path::new(""),
+        };
+        let theme_selection = ThemeSettings::get(Some(settings_location), cx)
+            .theme_selection
+            .as_ref()?;
+        if Some(theme_selection) == ThemeSettings::get_global(cx).theme_selection.as_ref() {
+            return None;
+        }
+
+        let appearance =
+            appearance_for_mode(theme_selection.mode(), SystemAppearance::global(cx).0, cx);
+        ThemeRegistry::global(cx)
+            .get(theme_selection.theme(appearance))
+            .log_err()
+    }
+
     fn update_window_edited(&mut self, window: &mut Window, cx: &mut App) {
         let is_edited = !self.project.read(cx).is_disconnected(cx) && !self.dirty_items.is_empty();
         if is_edited != self.window_edited {
@@ -5074,6 +5119,12 @@ impl Workspace {
                     .read(cx)
                     .all_source_breakpoints(cx)
             });
+            let data_breakpoints = self
+                .project
+                .read(cx)
+                .breakpoint_store()
+                .read(cx)
+                .persistent_data_breakpoints();
 
             let center_group = build_serialized_pane_group(&self.center.root, window, cx);
             let docks = build_serialized_docks(self, window, cx);
@@ -5088,6 +5139,7 @@ impl Workspace {
                 centered_layout: self.centered_layout,
                 session_id: self.session_id.clone(),
                 breakpoints,
+                data_breakpoints,
                 window_id: Some(window.window_handle().window_id().as_u64()),
             };
 
@@ -5259,6 +5311,10 @@ impl Workspace {
                     project
                         .breakpoint_store()
                         .update(cx, |breakpoint_store, cx| {
+                            breakpoint_store.with_serialized_data_breakpoints(
+                                serialized_workspace.data_breakpoints,
+                                cx,
+                            );
                             breakpoint_store
                                 .with_serialized_breakpoints(serialized_workspace.breakpoints, cx)
                         })
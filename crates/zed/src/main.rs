@@ -39,7 +39,7 @@ use std::{
 };
 use theme::{
     ActiveTheme, IconThemeNotFoundError, SystemAppearance, ThemeNotFoundError, ThemeRegistry,
-    ThemeSettings,
+    ThemeSettings, appearance_for_mode,
 };
 use util::{ConnectionResult, ResultExt, TryFutureExt, maybe};
 use uuid::Uuid;
@@ -564,9 +564,12 @@ pub fn main() {
         project_symbols::init(cx);
         project_panel::init(cx);
         outline_panel::init(cx);
+        undo_tree_panel::init(cx);
+        quickfix_panel::init(cx);
         tasks_ui::init(cx);
         snippets_ui::init(cx);
         channel::init(&app_state.client.clone(), app_state.user_store.clone(), cx);
+        semantic_index::init(cx.http_client(), cx);
         search::init(cx);
         vim::init(cx);
         terminal_view::init(cx);
@@ -574,6 +577,7 @@ pub fn main() {
         language_selector::init(cx);
         toolchain_selector::init(cx);
         theme_selector::init(cx);
+        theme_editor::init(cx);
         language_tools::init(cx);
         call::init(app_state.client.clone(), app_state.user_store.clone(), cx);
         notifications::init(app_state.client.clone(), app_state.user_store.clone(), cx);
@@ -747,6 +751,7 @@ fn handle_open_request(request: OpenRequest, app_state: Arc<AppState>, cx: &mut
             let (_window, results) = open_paths_with_positions(
                 &paths_with_position,
                 &request.diff_paths,
+                &[],
                 app_state,
                 workspace::OpenOptions::default(),
                 &mut cx,
@@ -1142,7 +1147,8 @@ fn eager_load_active_theme_and_icon_theme(fs: Arc<dyn Fs>, cx: &App) {
     let appearance = SystemAppearance::global(cx).0;
 
     if let Some(theme_selection) = theme_settings.theme_selection.as_ref() {
-        let theme_name = theme_selection.theme(appearance);
+        let theme_name =
+            theme_selection.theme(appearance_for_mode(theme_selection.mode(), appearance, cx));
         if matches!(theme_registry.get(theme_name), Err(ThemeNotFoundError(_))) {
             if let Some(theme_path) = extension_store.read(cx).path_to_extension_theme(theme_name) {
                 cx.spawn({
@@ -1162,7 +1168,11 @@ fn eager_load_active_theme_and_icon_theme(fs: Arc<dyn Fs>, cx: &App) {
     }
 
     if let Some(icon_theme_selection) = theme_settings.icon_theme_selection.as_ref() {
-        let icon_theme_name = icon_theme_selection.icon_theme(appearance);
+        let icon_theme_name = icon_theme_selection.icon_theme(appearance_for_mode(
+            icon_theme_selection.mode(),
+            appearance,
+            cx,
+        ));
         if matches!(
             theme_registry.get_icon_theme(icon_theme_name),
             Err(IconThemeNotFoundError(_))
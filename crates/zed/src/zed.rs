@@ -43,6 +43,7 @@ use project::{DirectoryLister, ProjectItem};
 use project_panel::ProjectPanel;
 use prompt_store::PromptBuilder;
 use quick_action_bar::QuickActionBar;
+use quickfix_panel::QuickfixPanel;
 use recent_projects::open_ssh_project;
 use release_channel::{AppCommitSha, ReleaseChannel};
 use rope::Rope;
@@ -58,6 +59,7 @@ use std::{borrow::Cow, path::Path, sync::Arc};
 use terminal_view::terminal_panel::{self, TerminalPanel};
 use theme::{ActiveTheme, ThemeSettings};
 use ui::{PopoverMenuHandle, prelude::*};
+use undo_tree_panel::UndoTreePanel;
 use util::markdown::MarkdownString;
 use util::{ResultExt, asset_str};
 use uuid::Uuid;
@@ -472,6 +474,8 @@ fn initialize_panels(
             workspace_handle.clone(),
             cx.clone(),
         );
+        let undo_tree_panel = UndoTreePanel::load(workspace_handle.clone(), cx.clone());
+        let quickfix_panel = QuickfixPanel::load(workspace_handle.clone(), cx.clone());
         let debug_panel = DebugPanel::load(workspace_handle.clone(), cx);
 
         let (
@@ -482,6 +486,8 @@ fn initialize_panels(
             channels_panel,
             chat_panel,
             notification_panel,
+            undo_tree_panel,
+            quickfix_panel,
             debug_panel,
         ) = futures::try_join!(
             project_panel,
@@ -491,6 +497,8 @@ fn initialize_panels(
             channels_panel,
             chat_panel,
             notification_panel,
+            undo_tree_panel,
+            quickfix_panel,
             debug_panel,
         )?;
 
@@ -502,6 +510,8 @@ fn initialize_panels(
             workspace.add_panel(channels_panel, window, cx);
             workspace.add_panel(chat_panel, window, cx);
             workspace.add_panel(notification_panel, window, cx);
+            workspace.add_panel(undo_tree_panel, window, cx);
+            workspace.add_panel(quickfix_panel, window, cx);
             workspace.add_panel(debug_panel, window, cx);
         })?;
 
@@ -933,11 +943,13 @@ fn initialize_pane(
             let breadcrumbs = cx.new(|_| Breadcrumbs::new());
             toolbar.add_item(breadcrumbs, window, cx);
             let buffer_search_bar = cx.new(|cx| {
-                search::BufferSearchBar::new(
+                let mut buffer_search_bar = search::BufferSearchBar::new(
                     Some(workspace.project().read(cx).languages().clone()),
                     window,
                     cx,
-                )
+                );
+                buffer_search_bar.set_workspace_id(workspace.database_id(), cx);
+                buffer_search_bar
             });
             toolbar.add_item(buffer_search_bar.clone(), window, cx);
             let proposed_change_bar = cx.new(|_| ProposedChangesEditorToolbar::new());
@@ -4352,6 +4364,8 @@ mod tests {
                 "project_search",
                 "project_symbols",
                 "projects",
+                "quickfix",
+                "quickfix_panel",
                 "repl",
                 "rules_library",
                 "search",
@@ -4364,6 +4378,7 @@ mod tests {
                 "theme_selector",
                 "toast",
                 "toolchain",
+                "undo_tree_panel",
                 "variable_list",
                 "vim",
                 "welcome",
@@ -4466,6 +4481,8 @@ mod tests {
             git_ui::init(cx);
             project_panel::init(cx);
             outline_panel::init(cx);
+            undo_tree_panel::init(cx);
+            quickfix_panel::init(cx);
             terminal_view::init(cx);
             copilot::copilot_chat::init(
                 app_state.fs.clone(),
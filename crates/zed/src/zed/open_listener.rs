@@ -13,8 +13,9 @@ use futures::channel::{mpsc, oneshot};
 use futures::future::join_all;
 use futures::{FutureExt, SinkExt, StreamExt};
 use git_ui::diff_view::DiffView;
-use gpui::{App, AsyncApp, Global, WindowHandle};
-use language::Point;
+use git_ui::merge_view::MergeView;
+use gpui::{App, AppContext as _, AsyncApp, Entity, Global, WindowHandle};
+use language::{Buffer, BufferEvent, Point, Rope};
 use recent_projects::{SshSettings, open_ssh_project};
 use remote::SshConnectionOptions;
 use settings::Settings;
@@ -215,6 +216,7 @@ fn connect_to_cli(
 pub async fn open_paths_with_positions(
     path_positions: &[PathWithPosition],
     diff_paths: &[[String; 2]],
+    merge_paths: &[[String; 4]],
     app_state: Arc<AppState>,
     open_options: workspace::OpenOptions,
     cx: &mut AsyncApp,
@@ -255,6 +257,30 @@ pub async fn open_paths_with_positions(
         }
     }
 
+    for merge_paths in merge_paths {
+        let [base_path, local_path, remote_path, merged_path] =
+            merge_paths.each_ref().map(PathBuf::from);
+        match workspace.update(cx, |workspace, window, cx| {
+            MergeView::open(
+                base_path,
+                local_path,
+                remote_path,
+                merged_path,
+                workspace,
+                window,
+                cx,
+            )
+        }) {
+            Ok(merge_view) => items.push(Some(
+                merge_view
+                    .await
+                    .map(|merge_view| Box::new(merge_view) as Box<dyn ItemHandle>)
+                    .with_context(|| format!("error merging into {:?}", merge_paths[3])),
+            )),
+            Err(error) => items.push(Some(Err(error))),
+        }
+    }
+
     for (item, path) in items.iter_mut().zip(&paths) {
         if let Some(Err(error)) = item {
             *error = anyhow!("error opening {path:?}: {error}");
@@ -291,6 +317,9 @@ pub async fn handle_cli_connection(
                 urls,
                 paths,
                 diff_paths,
+                merge_paths,
+                stdin,
+                language,
                 wait,
                 open_new_workspace,
                 env,
@@ -317,9 +346,27 @@ pub async fn handle_cli_connection(
                     return;
                 }
 
+                // The piped text follows the request, and is forwarded to the
+                // buffer that shows it as it arrives.
+                let stdin = stdin.then(|| {
+                    let (stdin_tx, stdin_rx) = mpsc::unbounded();
+                    cx.background_spawn(async move {
+                        while let Some(CliRequest::StdinChunk { text }) = requests.next().await {
+                            if stdin_tx.unbounded_send(text).is_err() {
+                                break;
+                            }
+                        }
+                    })
+                    .detach();
+                    stdin_rx
+                });
+
                 let open_workspace_result = open_workspaces(
                     paths,
                     diff_paths,
+                    merge_paths,
+                    stdin,
+                    language,
                     open_new_workspace,
                     &responses,
                     wait,
@@ -332,6 +379,9 @@ pub async fn handle_cli_connection(
                 let status = if open_workspace_result.is_err() { 1 } else { 0 };
                 responses.send(CliResponse::Exit { status }).log_err();
             }
+            CliRequest::StdinChunk { .. } | CliRequest::StdinEnd => {
+                log::error!("received stdin from the cli before an open request");
+            }
         }
     }
 }
@@ -339,6 +389,9 @@ pub async fn handle_cli_connection(
 async fn open_workspaces(
     paths: Vec<String>,
     diff_paths: Vec<[String; 2]>,
+    merge_paths: Vec<[String; 4]>,
+    mut stdin: Option<UnboundedReceiver<String>>,
+    language: Option<String>,
     open_new_workspace: Option<bool>,
     responses: &IpcSender<CliResponse>,
     wait: bool,
@@ -346,7 +399,9 @@ async fn open_workspaces(
     env: Option<collections::HashMap<String, String>>,
     cx: &mut AsyncApp,
 ) -> Result<()> {
-    let grouped_locations = if paths.is_empty() && diff_paths.is_empty() {
+    let nothing_to_open =
+        paths.is_empty() && diff_paths.is_empty() && merge_paths.is_empty() && stdin.is_none();
+    let grouped_locations = if nothing_to_open {
         // If no paths are provided, restore from previous workspaces unless a new workspace is requested with -n
         if open_new_workspace == Some(true) {
             Vec::new()
@@ -396,6 +451,9 @@ async fn open_workspaces(
                     let workspace_failed_to_open = open_local_workspace(
                         workspace_paths,
                         diff_paths.clone(),
+                        merge_paths.clone(),
+                        stdin.take(),
+                        language.clone(),
                         open_new_workspace,
                         wait,
                         responses,
@@ -410,6 +468,16 @@ async fn open_workspaces(
                     }
                 }
                 SerializedWorkspaceLocation::Ssh(ssh) => {
+                    if stdin.take().is_some() {
+                        responses
+                            .send(CliResponse::Stderr {
+                                message: "reading stdin is not supported in remote projects"
+                                    .to_string(),
+                            })
+                            .log_err();
+                        errored = true;
+                        continue;
+                    }
                     let app_state = app_state.clone();
                     let connection_options = cx.update(|cx| {
                         SshSettings::get_global(cx)
@@ -446,6 +514,9 @@ async fn open_workspaces(
 async fn open_local_workspace(
     workspace_paths: Vec<String>,
     diff_paths: Vec<[String; 2]>,
+    merge_paths: Vec<[String; 4]>,
+    stdin: Option<UnboundedReceiver<String>>,
+    language: Option<String>,
     open_new_workspace: Option<bool>,
     wait: bool,
     responses: &IpcSender<CliResponse>,
@@ -460,6 +531,7 @@ async fn open_local_workspace(
     match open_paths_with_positions(
         &paths_with_position,
         &diff_paths,
+        &merge_paths,
         app_state.clone(),
         workspace::OpenOptions {
             open_new_workspace,
@@ -472,11 +544,49 @@ async fn open_local_workspace(
     {
         Ok((workspace, items)) => {
             let mut item_release_futures = Vec::new();
+            let mut merge_results = Vec::new();
+            let mut stdin_result = None;
+
+            if let Some(stdin) = stdin {
+                match open_stdin_buffer(workspace, stdin, language, cx).await {
+                    Ok(buffer) => {
+                        // The buffer can be shown by several editors, such as
+                        // when its tab is split, so the text is written back
+                        // once all of them are closed.
+                        let (tx, rx) = oneshot::channel();
+                        cx.update(|cx| {
+                            cx.observe_release(&buffer, move |buffer, _| {
+                                let _ = tx.send(buffer.text());
+                            })
+                            .detach();
+                            if wait {
+                                keep_buffer_saved(&buffer, cx);
+                            }
+                        })
+                        .log_err();
+                        stdin_result = Some(rx);
+                    }
+                    Err(error) => {
+                        responses
+                            .send(CliResponse::Stderr {
+                                message: format!("error opening stdin: {error}"),
+                            })
+                            .log_err();
+                        errored = true;
+                    }
+                }
+            }
 
             for item in items {
                 match item {
                     Some(Ok(item)) => {
                         cx.update(|cx| {
+                            if let Some(merge_view) = item.downcast::<MergeView>() {
+                                merge_results.push(
+                                    merge_view
+                                        .update(cx, |merge_view, _| merge_view.merge_result()),
+                                );
+                            }
                             let released = oneshot::channel();
                             item.on_release(
                                 cx,
@@ -504,7 +614,11 @@ async fn open_local_workspace(
             if wait {
                 let background = cx.background_executor().clone();
                 let wait = async move {
-                    if paths_with_position.is_empty() && diff_paths.is_empty() {
+                    if paths_with_position.is_empty()
+                        && diff_paths.is_empty()
+                        && merge_paths.is_empty()
+                        && stdin_result.is_none()
+                    {
                         let (done_tx, done_rx) = oneshot::channel();
                         let _subscription = workspace.update(cx, |_, _, cx| {
                             cx.on_release(move |_, _| {
@@ -515,6 +629,29 @@ async fn open_local_workspace(
                     } else {
                         let _ = futures::future::try_join_all(item_release_futures).await;
                     };
+
+                    // Write the edited text back, so that Zed can be used as a
+                    // filter in a pipeline.
+                    if let Some(stdin_result) = stdin_result {
+                        if let Ok(text) = stdin_result.await {
+                            responses.send(CliResponse::Output { text }).log_err();
+                        }
+                    }
+
+                    // Report merges that weren't resolved, so that the tool
+                    // that invoked the merge doesn't consider them successful.
+                    let mut merge_failed = false;
+                    for merge_result in merge_results {
+                        let message = match merge_result.await {
+                            Ok(Ok(true)) => continue,
+                            Ok(Ok(false)) => "merge conflicts were not resolved".to_string(),
+                            Ok(Err(error)) => format!("error writing merge result: {error}"),
+                            Err(_) => "merge view was closed unexpectedly".to_string(),
+                        };
+                        responses.send(CliResponse::Stderr { message }).log_err();
+                        merge_failed = true;
+                    }
+                    merge_failed
                 }
                 .fuse();
 
@@ -525,7 +662,10 @@ async fn open_local_workspace(
                     // waiting for files or workspaces to close.
                     let mut timer = background.timer(Duration::from_secs(1)).fuse();
                     futures::select_biased! {
-                        _ = wait => break,
+                        merge_failed = wait => {
+                            errored |= merge_failed;
+                            break;
+                        }
                         _ = timer => {
                             if responses.send(CliResponse::Ping).is_err() {
                                 break;
@@ -547,6 +687,72 @@ async fn open_local_workspace(
     errored
 }
 
+/// Opens the text piped to the CLI in an untitled buffer, which keeps
+/// receiving the text as it is written to stdin.
+async fn open_stdin_buffer(
+    workspace: WindowHandle<Workspace>,
+    mut stdin: UnboundedReceiver<String>,
+    language: Option<String>,
+    cx: &mut AsyncApp,
+) -> Result<Entity<Buffer>> {
+    // The language is detected from the text that is available right away.
+    let text = stdin.next().await.unwrap_or_default();
+    let languages = workspace.update(cx, |workspace, _, cx| {
+        workspace.project().read(cx).languages().clone()
+    })?;
+    let language = match language {
+        Some(language) => Some(
+            languages
+                .language_for_name_or_extension(&language)
+                .await
+                .with_context(|| format!("unknown language {language:?}"))?,
+        ),
+        None => languages
+            .language_for_content(&Rope::from(text.as_str()))
+            .await
+            .ok(),
+    };
+
+    let buffer = workspace.update(cx, |workspace, window, cx| {
+        let project = workspace.project().clone();
+        let buffer = project.update(cx, |project, cx| {
+            project.create_local_buffer(&text, language, cx)
+        });
+        let editor = cx.new(|cx| Editor::for_buffer(buffer.clone(), Some(project), window, cx));
+        workspace.add_item_to_active_pane(Box::new(editor), None, true, window, cx);
+        buffer
+    })?;
+
+    let weak_buffer = buffer.downgrade();
+    cx.spawn(async move |cx| {
+        while let Some(text) = stdin.next().await {
+            let appended = weak_buffer.update(cx, |buffer, cx| {
+                let end = buffer.len();
+                buffer.edit([(end..end, text)], None, cx);
+            });
+            if appended.is_err() {
+                break;
+            }
+        }
+    })
+    .detach();
+
+    Ok(buffer)
+}
+
+/// Marks the buffer as saved whenever it is edited, for buffers whose text is
+/// written back when they are closed, so that closing them doesn't prompt to
+/// save them.
+fn keep_buffer_saved(buffer: &Entity<Buffer>, cx: &mut App) {
+    buffer.update(cx, |buffer, cx| buffer.did_save(buffer.version(), None, cx));
+    cx.subscribe(buffer, |buffer, event, cx| {
+        if let BufferEvent::Edited = event {
+            buffer.update(cx, |buffer, cx| buffer.did_save(buffer.version(), None, cx));
+        }
+    })
+    .detach();
+}
+
 pub async fn derive_paths_with_position(
     fs: &dyn Fs,
     path_strings: impl IntoIterator<Item = impl AsRef<str>>,
@@ -606,6 +812,7 @@ mod tests {
                 port_forwards: None,
                 nickname: None,
                 upload_binary_over_ssh: false,
+                socket: None,
             }
         );
         assert_eq!(request.open_paths, vec!["/"]);
@@ -722,6 +929,131 @@ mod tests {
             .unwrap();
     }
 
+    #[gpui::test]
+    async fn test_open_workspace_with_stdin(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(path!("/root"), json!({ "dir1": {} }))
+            .await;
+
+        let (response_tx, _) = ipc::channel::<CliResponse>().unwrap();
+        let (stdin_tx, stdin_rx) = mpsc::unbounded();
+        stdin_tx.unbounded_send("piped text\n".to_owned()).unwrap();
+        let errored = cx
+            .spawn({
+                let app_state = app_state.clone();
+                |mut cx| async move {
+                    open_local_workspace(
+                        vec![path!("/root/dir1").to_owned()],
+                        vec![],
+                        vec![],
+                        Some(stdin_rx),
+                        None,
+                        None,
+                        false,
+                        &response_tx,
+                        None,
+                        &app_state,
+                        &mut cx,
+                    )
+                    .await
+                }
+            })
+            .await;
+        assert!(!errored);
+
+        assert_eq!(cx.windows().len(), 1);
+        let workspace = cx.windows()[0].downcast::<Workspace>().unwrap();
+        workspace
+            .update(cx, |workspace, _, cx| {
+                let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+                let buffer = editor.read(cx).buffer().read(cx).as_singleton().unwrap();
+                assert_eq!(buffer.read(cx).text(), "piped text\n");
+                assert!(buffer.read(cx).file().is_none());
+            })
+            .unwrap();
+
+        // Text written to stdin later is appended to the buffer.
+        stdin_tx.unbounded_send("more text\n".to_owned()).unwrap();
+        cx.run_until_parked();
+        workspace
+            .update(cx, |workspace, _, cx| {
+                let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+                let buffer = editor.read(cx).buffer().read(cx).as_singleton().unwrap();
+                assert_eq!(buffer.read(cx).text(), "piped text\nmore text\n");
+            })
+            .unwrap();
+    }
+
+    #[gpui::test]
+    async fn test_open_stdin_and_wait(cx: &mut TestAppContext) {
+        let app_state = init_test(cx);
+
+        app_state
+            .fs
+            .as_fake()
+            .insert_tree(path!("/root"), json!({ "dir1": {} }))
+            .await;
+
+        let (response_tx, response_rx) = ipc::channel::<CliResponse>().unwrap();
+        let (stdin_tx, stdin_rx) = mpsc::unbounded();
+        stdin_tx.unbounded_send("piped text\n".to_owned()).unwrap();
+        drop(stdin_tx);
+        let open = cx.spawn({
+            let app_state = app_state.clone();
+            |mut cx| async move {
+                open_local_workspace(
+                    vec![path!("/root/dir1").to_owned()],
+                    vec![],
+                    vec![],
+                    Some(stdin_rx),
+                    None,
+                    None,
+                    true,
+                    &response_tx,
+                    None,
+                    &app_state,
+                    &mut cx,
+                )
+                .await
+            }
+        });
+        cx.run_until_parked();
+
+        let workspace = cx.windows()[0].downcast::<Workspace>().unwrap();
+        let close = workspace
+            .update(cx, |workspace, window, cx| {
+                let editor = workspace.active_item_as::<Editor>(cx).unwrap();
+                let buffer = editor.read(cx).buffer().read(cx).as_singleton().unwrap();
+                buffer.update(cx, |buffer, cx| {
+                    let end = buffer.len();
+                    buffer.edit([(end..end, "edited text\n")], None, cx);
+                });
+                workspace.active_pane().update(cx, |pane, cx| {
+                    pane.close_active_item(&Default::default(), window, cx)
+                })
+            })
+            .unwrap();
+        cx.run_until_parked();
+        assert!(
+            !cx.has_pending_prompt(),
+            "closing the stdin buffer shouldn't prompt to save it"
+        );
+        close.await.unwrap();
+        assert!(!open.await);
+
+        let mut output = Vec::new();
+        while let Ok(response) = response_rx.try_recv() {
+            if let CliResponse::Output { text } = response {
+                output.push(text);
+            }
+        }
+        assert_eq!(output, ["piped text\nedited text\n"]);
+    }
+
     async fn open_workspace_file(
         path: &str,
         open_new_workspace: Option<bool>,
@@ -737,6 +1069,9 @@ mod tests {
                 open_local_workspace(
                     workspace_paths,
                     vec![],
+                    vec![],
+                    None,
+                    None,
                     open_new_workspace,
                     false,
                     &response_tx,
@@ -110,6 +110,8 @@ impl Render for QuickActionBar {
             editor_value.edit_predictions_enabled_at_cursor(cx);
         let supports_minimap = editor_value.supports_minimap(cx);
         let minimap_enabled = supports_minimap && editor_value.minimap().is_some();
+        let supports_sticky_scroll = editor_value.mode().is_full() && editor.is_singleton(cx);
+        let sticky_scroll_enabled = editor_value.sticky_scroll_enabled(cx);
         let has_available_code_actions = editor_value.has_available_code_actions();
         let code_action_enabled = editor_value.code_actions_enabled_for_toolbar(cx);
         let focus_handle = editor_value.focus_handle(cx);
@@ -406,6 +408,23 @@ impl Render for QuickActionBar {
                                 },)
                             }
 
+                            if supports_sticky_scroll {
+                                menu = menu.toggleable_entry("Sticky Scroll", sticky_scroll_enabled, IconPosition::Start, Some(editor::actions::ToggleStickyScroll.boxed_clone()), {
+                                    let editor = editor.clone();
+                                    move |window, cx| {
+                                        editor
+                                            .update(cx, |editor, cx| {
+                                                editor.toggle_sticky_scroll(
+                                                    &editor::actions::ToggleStickyScroll,
+                                                    window,
+                                                    cx,
+                                                );
+                                            })
+                                            .ok();
+                                    }
+                                },)
+                            }
+
                             if has_edit_prediction_provider {
                                 let mut inline_completion_entry = ContextMenuEntry::new("Edit Predictions")
                                     .toggleable(IconPosition::Start, edit_predictions_enabled_at_cursor && show_edit_predictions)
@@ -150,6 +150,9 @@ fn send_args_to_instance(args: &Args) -> anyhow::Result<()> {
             paths,
             urls,
             diff_paths,
+            merge_paths: Vec::new(),
+            stdin: false,
+            language: None,
             wait: false,
             open_new_workspace: None,
             env: None,
@@ -171,6 +174,7 @@ fn send_args_to_instance(args: &Args) -> anyhow::Result<()> {
                     CliResponse::Ping => {}
                     CliResponse::Stdout { message } => log::info!("{message}"),
                     CliResponse::Stderr { message } => log::error!("{message}"),
+                    CliResponse::Output { text } => log::info!("{text}"),
                     CliResponse::Exit { status } => {
                         exit_status.lock().replace(status);
                         return Ok(());